@@ -559,7 +559,49 @@ alter table USERS
 add constraint uk_users_user_id
 unique (user_id);
 
+alter table USERS
+add column payday TINYINT NOT NULL DEFAULT 25 COMMENT '급여일 (1~31, 주말/공휴일이면 직전 영업일)';
+
+
 
 
 
+CREATE TABLE CONSUME_BUDGET
+(
+  budget_seq              BIGINT    auto_increment   NOT NULL COMMENT '예산 고유번호',
+  user_seq                BIGINT       NOT NULL COMMENT '예산을 등록한 유저 식별번호',
+  budget_scope            VARCHAR(20)  NOT NULL COMMENT '예산 범위 (ROOM: telegram 방, GROUP: 집계 그룹)',
+  scope_seq               BIGINT       NOT NULL COMMENT '범위 식별번호 (room_seq 또는 agg_group_seq)',
+  consume_keyword_type_id BIGINT       NOT NULL DEFAULT 0 COMMENT '소비 타입 식별번호 (0: 전체 예산)',
+  budget_money            BIGINT       NOT NULL COMMENT '급여 주기당 예산 금액',
+  created_at              DATETIME     NOT NULL COMMENT '생성 시각',
+  updated_at              DATETIME     NULL     COMMENT '수정 시각',
+  created_by              VARCHAR(100) NOT NULL COMMENT '생성자',
+  updated_by              VARCHAR(100) NULL     COMMENT '수정자',
+  PRIMARY KEY (budget_seq)
+) ENGINE=InnoDB COMMENT '소비 예산';
+
+alter table CONSUME_BUDGET
+add constraint uk_consume_budget_scope_type
+unique (budget_scope, scope_seq, consume_keyword_type_id);
+
+CREATE TABLE SPENT_DETAIL_OUTBOX
+(
+  outbox_seq    BIGINT    auto_increment   NOT NULL COMMENT '아웃박스 고유번호',
+  spent_idx     BIGINT       NOT NULL COMMENT '변경된 지출내역 식별번호 (삭제된 지출도 남기므로 FK 없음)',
+  indexing_type CHAR(1)      NOT NULL COMMENT '색인 구분 (I: 등록, U: 수정, D: 삭제)',
+  reg_at        DATETIME     NOT NULL COMMENT '지출내역 변경 시각',
+  is_sent       TINYINT      NOT NULL DEFAULT 0 COMMENT 'Kafka 발행 여부',
+  attempt_cnt   INT          NOT NULL DEFAULT 0 COMMENT 'Kafka 발행 실패 횟수',
+  last_error    VARCHAR(1000) NULL    COMMENT '마지막 발행 실패 사유',
+  sent_at       DATETIME     NULL     COMMENT 'Kafka 발행 시각',
+  created_at    DATETIME     NOT NULL COMMENT '생성 시각',
+  created_by    VARCHAR(100) NOT NULL COMMENT '생성자',
+  PRIMARY KEY (outbox_seq)
+) ENGINE=InnoDB COMMENT '지출내역 변경 이벤트 아웃박스 - SPENT_DETAIL 과 같은 트랜잭션에서 기록';
+
+create index idx_spent_detail_outbox_pending on SPENT_DETAIL_OUTBOX (is_sent, outbox_seq);
+
+alter table CRYPTO
+add column price_updated_at DATETIME NULL COMMENT '시세 갱신 시각 (스케줄러가 crypto_price 를 갱신할 때만 기록)';
 
@@ -11,7 +11,7 @@ pub use std::{
 
 pub use tokio::task;
 
-pub use log::{error, info};
+pub use log::{error, info, warn};
 
 pub use flexi_logger::{Age, Cleanup, Criterion, FileSpec, Logger, Naming, Record};
 
@@ -33,8 +33,9 @@ pub use elasticsearch::{
     auth::Credentials as EsCredentials,
     http::response::Response,
     http::transport::{ConnectionPool, MultiNodeConnectionPool, Transport, TransportBuilder},
+    http::StatusCode,
     http::Url,
-    DeleteParts, Elasticsearch, SearchParts,
+    DeleteParts, Elasticsearch, IndexParts, SearchParts,
 };
 
 pub use anyhow::{anyhow, Result};
@@ -55,8 +56,10 @@ pub use regex::Regex;
 pub use num_format::{Locale, ToFormattedString};
 
 pub use rdkafka::{
+    consumer::{CommitMode, Consumer, StreamConsumer},
+    message::{BorrowedMessage, Message as KafkaMessage},
     producer::{FutureProducer, FutureRecord},
-    ClientConfig,
+    ClientConfig, Offset, TopicPartitionList,
 };
 
 pub use async_trait::async_trait;
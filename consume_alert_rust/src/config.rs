@@ -9,7 +9,7 @@ use crate::common::*;
 #[allow(dead_code)]
 pub struct AppConfig {
     /// Telegram bot token list (parsed from comma-separated BOT_TOKENS env var).
-    /// The application spawns one independent polling loop per token.
+    /// The application runs one Dispatcher per token, fed by polling or by the shared webhook listener.
     pub bot_tokens: Vec<String>,
     /// User ID
     pub user_id: String,
@@ -35,6 +35,84 @@ pub struct AppConfig {
     pub redis_user_id_key: String,
 
     pub redis_room_group_key: String,
+    /// Number of days before a cancellation (승인취소) notification in which the original payment is searched
+    pub cancel_match_window_days: i64,
+    /// Minutes a parsed payment time may lie ahead of the Telegram message date before the alert is rejected
+    pub spent_at_future_tolerance_mins: i64,
+    /// Days a parsed payment time may lie behind the Telegram message date before the user is warned
+    pub spent_at_stale_days: i64,
+    /// Classifier asked first for spending categories: "elastic" (default) or "mysql"; the other one is the fallback
+    pub consume_classifier_primary: String,
+    /// Seconds after which the offline classifier reloads its MySQL keyword snapshot
+    pub consume_keyword_refresh_secs: i64,
+    /// Budget usage percentages that trigger a Telegram warning (parsed from comma-separated BUDGET_ALERT_THRESHOLDS, default "50,80,100")
+    pub budget_alert_thresholds: Vec<i64>,
+    /// Whether this process also consumes PRODUCE_TOPIC and indexes SPENT_DETAIL changes into Elasticsearch
+    pub indexing_consumer_enabled: bool,
+    /// Kafka consumer group id of the indexing consumer
+    pub indexing_consumer_group_id: String,
+    /// Dead-letter topic receiving events that could not be indexed (default "<PRODUCE_TOPIC>-dlq")
+    pub indexing_dlq_topic: String,
+    /// Attempts made to index a single event before it is sent to the dead-letter topic
+    pub indexing_max_attempts: u32,
+    /// Milliseconds the outbox relay waits before polling SPENT_DETAIL_OUTBOX again when idle or after a failure
+    pub outbox_relay_interval_ms: u64,
+    /// Maximum number of pending outbox rows the relay publishes per poll
+    pub outbox_relay_batch_size: u64,
+    /// Milliseconds a report query may wait for Elasticsearch before it is answered from MySQL instead
+    pub es_read_timeout_ms: u64,
+    /// Chart renderer: "python" (default, external GRAPH_API_URL service) or "native" (in-process)
+    pub graph_renderer: String,
+    /// Font file used by the native chart renderer; it must contain Hangul glyphs
+    pub chart_font_path: String,
+    /// Attempts made to render a single graph before the failure is reported to the user
+    pub graph_retry_max_attempts: u32,
+    /// Milliseconds before the first graph retry; the delay doubles after every failed attempt
+    pub graph_retry_base_delay_ms: u64,
+    /// Maximum number of Telegram commands processed at the same time across all bots
+    pub command_worker_limit: usize,
+    /// Seconds a SIGTERM/Ctrl-C shutdown waits for in-flight commands before the process exits
+    pub shutdown_timeout_secs: u64,
+    /// How Telegram updates are received: "polling" (default) or "webhook"
+    pub telegram_update_mode: String,
+    /// Address the embedded webhook listener binds to; every bot shares this one port
+    pub webhook_listen_addr: std::net::SocketAddr,
+    /// Public base URL Telegram posts updates to; each bot is served under `<base>/<bot id>`
+    pub webhook_public_url: Option<String>,
+    /// Per-bot webhook secret tokens (comma-separated WEBHOOK_SECRET_TOKENS, same order as BOT_TOKENS)
+    pub webhook_secret_tokens: Vec<String>,
+    /// PEM certificate chain for serving the webhook over HTTPS; plain HTTP when unset (e.g. behind a reverse proxy)
+    pub webhook_tls_cert_path: Option<String>,
+    /// PKCS#8 PEM private key matching WEBHOOK_TLS_CERT_PATH
+    pub webhook_tls_key_path: Option<String>,
+    /// SMTP relay host used for report mails (e.g. smtp.gmail.com)
+    pub smtp_server: Option<String>,
+    /// SMTP login, also used as the sender address
+    pub smtp_id: Option<String>,
+    /// SMTP password or app password
+    pub smtp_pw: Option<String>,
+    /// KST schedule of the monthly report mails (REPORT_MAIL_CRON, e.g. "0 0 9 1 * *"); disabled when unset
+    pub report_mail_schedule: Option<cron::Schedule>,
+    /// Number of merchants listed in the top merchants section of a report mail
+    pub report_mail_top_merchant_cnt: usize,
+    /// KST schedule of the daily asset snapshot (ASSET_SNAPSHOT_CRON, e.g. "0 50 23 * * *"); disabled when unset
+    pub asset_snapshot_schedule: Option<cron::Schedule>,
+    /// KST schedule of the stock price refresh (STOCK_PRICE_CRON, e.g. "0 */10 * * * *"); only open markets are refreshed, disabled when unset
+    pub stock_price_schedule: Option<cron::Schedule>,
+    /// Base URL of the Korea Investment Securities Open API
+    pub kis_api_url: String,
+    /// KIS Open API app key
+    pub kis_app_key: Option<String>,
+    /// KIS Open API app secret
+    pub kis_app_secret: Option<String>,
+    /// Milliseconds between two KIS quote requests, keeping the refresh under the API rate limit
+    pub kis_request_interval_ms: u64,
+    /// KST schedule of the crypto price refresh (CRYPTO_PRICE_CRON, e.g. "0 */5 * * * *"); disabled when unset
+    pub crypto_price_schedule: Option<cron::Schedule>,
+    /// Base URL of the Upbit Open API used to quote KRW crypto prices
+    pub upbit_api_url: String,
+    /// Age in minutes after which a crypto price is flagged as stale in the `my` output
+    pub crypto_price_stale_mins: i64,
 }
 
 /// Global static instance of AppConfig
@@ -59,6 +137,9 @@ impl AppConfig {
     pub fn initialize() -> Result<(), String> {
         dotenv::dotenv().ok();
 
+        let produce_topic: String = env::var("PRODUCE_TOPIC")
+            .map_err(|_| "PRODUCE_TOPIC not found in environment".to_string())?;
+
         let config: AppConfig = AppConfig {
             bot_tokens: env::var("BOT_TOKENS")
                 .map_err(|_| "BOT_TOKENS not found in environment".to_string())?
@@ -68,8 +149,7 @@ impl AppConfig {
                 .collect(),
             user_id: env::var("USER_ID")
                 .map_err(|_| "USER_ID not found in environment".to_string())?,
-            produce_topic: env::var("PRODUCE_TOPIC")
-                .map_err(|_| "PRODUCE_TOPIC not found in environment".to_string())?,
+            produce_topic: produce_topic.clone(),
             kafka_brokers: env::var("KAFKA_BROKERS")
                 .map_err(|_| "KAFKA_BROKERS not found in environment".to_string())?,
             mysql_host: env::var("MY_SQL_HOST").unwrap_or_else(|_| "localhost:3306".to_string()),
@@ -87,13 +167,276 @@ impl AppConfig {
                 .map_err(|_| "REDIS_USER_ID_KEY not found in environment".to_string())?,
             redis_room_group_key: env::var("REDIS_ROOM_GROUP_KEY")
                 .map_err(|_| "REDIS_ROOM_GROUP_KEY not found in environment".to_string())?,
+            cancel_match_window_days: env::var("CANCEL_MATCH_WINDOW_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse::<i64>()
+                .map_err(|_| "CANCEL_MATCH_WINDOW_DAYS must be an integer".to_string())?,
+            spent_at_future_tolerance_mins: env::var("SPENT_AT_FUTURE_TOLERANCE_MINS")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse::<i64>()
+                .map_err(|_| "SPENT_AT_FUTURE_TOLERANCE_MINS must be an integer".to_string())?,
+            spent_at_stale_days: env::var("SPENT_AT_STALE_DAYS")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse::<i64>()
+                .map_err(|_| "SPENT_AT_STALE_DAYS must be an integer".to_string())?,
+            consume_classifier_primary: match env::var("CONSUME_CLASSIFIER_PRIMARY")
+                .unwrap_or_else(|_| "elastic".to_string())
+                .to_lowercase()
+                .as_str()
+            {
+                primary @ ("elastic" | "mysql") => primary.to_string(),
+                other => {
+                    return Err(format!(
+                        "CONSUME_CLASSIFIER_PRIMARY must be 'elastic' or 'mysql': {}",
+                        other
+                    ))
+                }
+            },
+            consume_keyword_refresh_secs: env::var("CONSUME_KEYWORD_REFRESH_SECS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse::<i64>()
+                .map_err(|_| "CONSUME_KEYWORD_REFRESH_SECS must be an integer".to_string())?,
+            budget_alert_thresholds: env::var("BUDGET_ALERT_THRESHOLDS")
+                .unwrap_or_else(|_| "50,80,100".to_string())
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(|s| {
+                    s.parse::<i64>()
+                        .ok()
+                        .filter(|threshold| *threshold > 0)
+                        .ok_or_else(|| {
+                            format!("BUDGET_ALERT_THRESHOLDS must be positive integers: {}", s)
+                        })
+                })
+                .collect::<Result<Vec<i64>, String>>()?,
+            indexing_consumer_enabled: env::var("INDEXING_CONSUMER_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse::<bool>()
+                .map_err(|_| "INDEXING_CONSUMER_ENABLED must be 'true' or 'false'".to_string())?,
+            indexing_consumer_group_id: env::var("INDEXING_CONSUMER_GROUP_ID")
+                .unwrap_or_else(|_| "consume-alert-indexer".to_string()),
+            indexing_dlq_topic: env::var("INDEXING_DLQ_TOPIC")
+                .unwrap_or_else(|_| format!("{}-dlq", produce_topic)),
+            indexing_max_attempts: env::var("INDEXING_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse::<u32>()
+                .ok()
+                .filter(|attempts| *attempts > 0)
+                .ok_or_else(|| "INDEXING_MAX_ATTEMPTS must be a positive integer".to_string())?,
+            outbox_relay_interval_ms: env::var("OUTBOX_RELAY_INTERVAL_MS")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse::<u64>()
+                .map_err(|_| "OUTBOX_RELAY_INTERVAL_MS must be an integer".to_string())?,
+            outbox_relay_batch_size: env::var("OUTBOX_RELAY_BATCH_SIZE")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse::<u64>()
+                .ok()
+                .filter(|batch_size| *batch_size > 0)
+                .ok_or_else(|| "OUTBOX_RELAY_BATCH_SIZE must be a positive integer".to_string())?,
+            es_read_timeout_ms: env::var("ES_READ_TIMEOUT_MS")
+                .unwrap_or_else(|_| "5000".to_string())
+                .parse::<u64>()
+                .ok()
+                .filter(|timeout_ms| *timeout_ms > 0)
+                .ok_or_else(|| "ES_READ_TIMEOUT_MS must be a positive integer".to_string())?,
+            graph_renderer: match env::var("GRAPH_RENDERER")
+                .unwrap_or_else(|_| "python".to_string())
+                .to_lowercase()
+                .as_str()
+            {
+                renderer @ ("python" | "native") => renderer.to_string(),
+                other => {
+                    return Err(format!(
+                        "GRAPH_RENDERER must be 'python' or 'native': {}",
+                        other
+                    ))
+                }
+            },
+            chart_font_path: env::var("CHART_FONT_PATH")
+                .unwrap_or_else(|_| "./data/fonts/BMDOHYEON_ttf.ttf".to_string()),
+            graph_retry_max_attempts: env::var("GRAPH_RETRY_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse::<u32>()
+                .ok()
+                .filter(|attempts| *attempts > 0)
+                .ok_or_else(|| "GRAPH_RETRY_MAX_ATTEMPTS must be a positive integer".to_string())?,
+            graph_retry_base_delay_ms: env::var("GRAPH_RETRY_BASE_DELAY_MS")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse::<u64>()
+                .map_err(|_| "GRAPH_RETRY_BASE_DELAY_MS must be an integer".to_string())?,
+            command_worker_limit: env::var("COMMAND_WORKER_LIMIT")
+                .unwrap_or_else(|_| "8".to_string())
+                .parse::<usize>()
+                .ok()
+                .filter(|limit| *limit > 0)
+                .ok_or_else(|| "COMMAND_WORKER_LIMIT must be a positive integer".to_string())?,
+            shutdown_timeout_secs: env::var("SHUTDOWN_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse::<u64>()
+                .map_err(|_| "SHUTDOWN_TIMEOUT_SECS must be an integer".to_string())?,
+            telegram_update_mode: match env::var("TELEGRAM_UPDATE_MODE")
+                .unwrap_or_else(|_| "polling".to_string())
+                .to_lowercase()
+                .as_str()
+            {
+                mode @ ("polling" | "webhook") => mode.to_string(),
+                other => {
+                    return Err(format!(
+                        "TELEGRAM_UPDATE_MODE must be 'polling' or 'webhook': {}",
+                        other
+                    ))
+                }
+            },
+            webhook_listen_addr: env::var("WEBHOOK_LISTEN_ADDR")
+                .unwrap_or_else(|_| "0.0.0.0:8443".to_string())
+                .parse::<std::net::SocketAddr>()
+                .map_err(|_| {
+                    "WEBHOOK_LISTEN_ADDR must be a socket address such as 0.0.0.0:8443".to_string()
+                })?,
+            webhook_public_url: env::var("WEBHOOK_PUBLIC_URL")
+                .ok()
+                .map(|url| url.trim().trim_end_matches('/').to_string())
+                .filter(|url| !url.is_empty()),
+            webhook_secret_tokens: env::var("WEBHOOK_SECRET_TOKENS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            webhook_tls_cert_path: env::var("WEBHOOK_TLS_CERT_PATH").ok(),
+            webhook_tls_key_path: env::var("WEBHOOK_TLS_KEY_PATH").ok(),
+            smtp_server: env::var("SMTP_SERVER").ok(),
+            smtp_id: env::var("SMTP_ID").ok(),
+            smtp_pw: env::var("SMTP_PW").ok(),
+            report_mail_schedule: Self::parse_cron_schedule("REPORT_MAIL_CRON", "0 0 9 1 * *")?,
+            report_mail_top_merchant_cnt: env::var("REPORT_MAIL_TOP_MERCHANT_CNT")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse::<usize>()
+                .map_err(|_| "REPORT_MAIL_TOP_MERCHANT_CNT must be an integer".to_string())?,
+            asset_snapshot_schedule: Self::parse_cron_schedule(
+                "ASSET_SNAPSHOT_CRON",
+                "0 50 23 * * *",
+            )?,
+            stock_price_schedule: Self::parse_cron_schedule("STOCK_PRICE_CRON", "0 */10 * * * *")?,
+            kis_api_url: env::var("KIS_API_URL")
+                .unwrap_or_else(|_| "https://openapi.koreainvestment.com:9443".to_string()),
+            kis_app_key: env::var("KIS_APP_KEY").ok(),
+            kis_app_secret: env::var("KIS_APP_SECRET").ok(),
+            kis_request_interval_ms: env::var("KIS_REQUEST_INTERVAL_MS")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse::<u64>()
+                .map_err(|_| "KIS_REQUEST_INTERVAL_MS must be an integer".to_string())?,
+            crypto_price_schedule: Self::parse_cron_schedule("CRYPTO_PRICE_CRON", "0 */5 * * * *")?,
+            upbit_api_url: env::var("UPBIT_API_URL")
+                .unwrap_or_else(|_| "https://api.upbit.com".to_string()),
+            crypto_price_stale_mins: env::var("CRYPTO_PRICE_STALE_MINS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse::<i64>()
+                .map_err(|_| "CRYPTO_PRICE_STALE_MINS must be an integer".to_string())?,
         };
 
+        if config.telegram_update_mode == "webhook" {
+            config.validate_webhook()?;
+        }
+
+        if config.report_mail_schedule.is_some()
+            && (config.smtp_server.is_none()
+                || config.smtp_id.is_none()
+                || config.smtp_pw.is_none())
+        {
+            return Err(
+                "SMTP_SERVER, SMTP_ID and SMTP_PW are required when REPORT_MAIL_CRON is set"
+                    .to_string(),
+            );
+        }
+
+        if config.stock_price_schedule.is_some()
+            && (config.kis_app_key.is_none() || config.kis_app_secret.is_none())
+        {
+            return Err(
+                "KIS_APP_KEY and KIS_APP_SECRET are required when STOCK_PRICE_CRON is set"
+                    .to_string(),
+            );
+        }
+
         APP_CONFIG
             .set(config)
             .map_err(|_| "AppConfig already initialized".to_string())
     }
 
+    /// Reads an optional cron schedule (with a seconds field) from an environment variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `env_nm` - Name of the environment variable
+    /// * `example` - Valid expression quoted in the error message
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` when the variable is unset or blank.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error message if the expression cannot be parsed.
+    fn parse_cron_schedule(env_nm: &str, example: &str) -> Result<Option<cron::Schedule>, String> {
+        env::var(env_nm)
+            .ok()
+            .filter(|cron_expr| !cron_expr.trim().is_empty())
+            .map(|cron_expr| {
+                cron::Schedule::from_str(cron_expr.trim()).map_err(|e| {
+                    format!(
+                        "{} must be a cron expression with seconds such as '{}': {}",
+                        env_nm, example, e
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// Checks the settings that webhook mode depends on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error message if the public URL is missing, the secret tokens do not pair up
+    /// with BOT_TOKENS or use characters Telegram rejects, or only half of the TLS pair is set.
+    fn validate_webhook(&self) -> Result<(), String> {
+        if self.webhook_public_url.is_none() {
+            return Err(
+                "WEBHOOK_PUBLIC_URL is required when TELEGRAM_UPDATE_MODE=webhook".to_string(),
+            );
+        }
+
+        if self.webhook_secret_tokens.len() != self.bot_tokens.len() {
+            return Err(format!(
+                "WEBHOOK_SECRET_TOKENS must list one secret per bot token: expected {}, found {}",
+                self.bot_tokens.len(),
+                self.webhook_secret_tokens.len()
+            ));
+        }
+
+        /* Telegram accepts 1-256 characters of A-Z, a-z, 0-9, '_' and '-'. */
+        if self.webhook_secret_tokens.iter().any(|secret| {
+            secret.len() > 256
+                || !secret
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        }) {
+            return Err(
+                "WEBHOOK_SECRET_TOKENS entries must be 1-256 characters of A-Z, a-z, 0-9, '_' or '-'"
+                    .to_string(),
+            );
+        }
+
+        if self.webhook_tls_cert_path.is_some() != self.webhook_tls_key_path.is_some() {
+            return Err(
+                "WEBHOOK_TLS_CERT_PATH and WEBHOOK_TLS_KEY_PATH must be set together".to_string(),
+            );
+        }
+
+        Ok(())
+    }
+
     /// Get a reference to the global configuration
     ///
     /// # Panics
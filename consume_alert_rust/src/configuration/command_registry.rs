@@ -0,0 +1,385 @@
+use crate::enums::{command_id::*, command_scope::*};
+
+/// Declarative description of one Telegram command.
+///
+/// The registry drives command dispatch, the usage hints sent on invalid input, the `/help`
+/// output, and the command menu published with `setMyCommands`.
+#[derive(Debug)]
+pub struct CommandSpec {
+    /// Handler the command is dispatched to
+    pub command_id: CommandId,
+    /// Name typed by the user and published to Telegram
+    pub name: &'static str,
+    /// Alternative names accepted for the command; they are not published to Telegram
+    pub aliases: &'static [&'static str],
+    /// Argument grammar shown after the name, e.g. `<name>:<amount>`
+    pub arg_grammar: &'static str,
+    /// One-line description used in the overview and the Telegram command menu
+    pub summary: &'static str,
+    /// Complete example invocations
+    pub examples: &'static [&'static str],
+    /// Whether the command works on the caller's room or its aggregation group
+    pub scope: CommandScope,
+}
+
+pub static COMMAND_REGISTRY: &[CommandSpec] = &[
+    CommandSpec {
+        command_id: CommandId::Help,
+        name: "help",
+        aliases: &["start"],
+        arg_grammar: "[command]",
+        summary: "Show the available commands or how to use one of them",
+        examples: &["/help", "/help cm"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::Consumption,
+        name: "c",
+        aliases: &[],
+        arg_grammar: "<name>:<amount>",
+        summary: "Record a spending paid with the default payment method",
+        examples: &["c snack:15000"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionBudget,
+        name: "cb",
+        aliases: &[],
+        arg_grammar: "[g] [type:]<amount>",
+        summary: "List, set or remove (amount 0) salary-period budgets; g targets the group",
+        examples: &[
+            "cb",
+            "cb 1500000",
+            "cb 식비:400000",
+            "cb g 3000000",
+            "cb g 식비:0",
+        ],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::DeleteRecentConsumption,
+        name: "cd",
+        aliases: &[],
+        arg_grammar: "",
+        summary: "Delete the most recently recorded spending of the room",
+        examples: &["cd"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::ModifyConsumption,
+        name: "ce",
+        aliases: &[],
+        arg_grammar: "<id> <type|name|money|at> <value>",
+        summary: "Correct the category, name, amount or time of a recorded spending",
+        examples: &[
+            "ce 2n9c type 식비",
+            "ce 2n9c name 스타벅스",
+            "ce 2n9c money 12000",
+            "ce 2n9c at 2026.10.18 12:30",
+        ],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::InstallmentBalance,
+        name: "ci",
+        aliases: &[],
+        arg_grammar: "",
+        summary: "Show the remaining installment balance per card",
+        examples: &["ci"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionPerMon,
+        name: "cm",
+        aliases: &[],
+        arg_grammar: "[YYYY.MM] [-t]",
+        summary: "Monthly consumption report (current month by default)",
+        examples: &["cm", "cm 2023.07", "cm 2023.07 -t"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionPerTerm,
+        name: "ctr",
+        aliases: &[],
+        arg_grammar: "<YYYY.MM.DD-YYYY.MM.DD> [-t]",
+        summary: "Consumption report for a custom date range",
+        examples: &["ctr 2023.07.07-2023.08.01"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionPerDay,
+        name: "ct",
+        aliases: &[],
+        arg_grammar: "[YYYY.MM.DD] [-t]",
+        summary: "Daily consumption report (today by default)",
+        examples: &["ct", "ct 2023.11.11"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionPerSalary,
+        name: "cs",
+        aliases: &[],
+        arg_grammar: "[YYYY.MM] [-t]",
+        summary: "Consumption report for a salary period (current period by default)",
+        examples: &["cs", "cs 2023.11"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionPerWeek,
+        name: "cw",
+        aliases: &[],
+        arg_grammar: "[-t]",
+        summary: "Consumption report for the current week (Mon-Sun)",
+        examples: &["cw"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionPerYear,
+        name: "cy",
+        aliases: &[],
+        arg_grammar: "[YYYY] [-t]",
+        summary: "Yearly consumption report (current year by default)",
+        examples: &["cy", "cy 2023"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionPerSalaryGroup,
+        name: "gs",
+        aliases: &[],
+        arg_grammar: "[YYYY.MM] [-t]",
+        summary: "Salary-period consumption report of the group",
+        examples: &["gs", "gs 2023.11"],
+        scope: CommandScope::Group,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionPerMonGroup,
+        name: "gm",
+        aliases: &[],
+        arg_grammar: "[YYYY.MM] [-t]",
+        summary: "Monthly consumption report of the group",
+        examples: &["gm", "gm 2023.07"],
+        scope: CommandScope::Group,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionPerDayGroup,
+        name: "gt",
+        aliases: &[],
+        arg_grammar: "[YYYY.MM.DD] [-t]",
+        summary: "Daily consumption report of the group",
+        examples: &["gt", "gt 2023.11.11"],
+        scope: CommandScope::Group,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionPerWeekGroup,
+        name: "gw",
+        aliases: &[],
+        arg_grammar: "[-t]",
+        summary: "Consumption report of the group for the current week (Mon-Sun)",
+        examples: &["gw"],
+        scope: CommandScope::Group,
+    },
+    CommandSpec {
+        command_id: CommandId::ConsumptionPerYearGroup,
+        name: "gy",
+        aliases: &[],
+        arg_grammar: "[YYYY] [-t]",
+        summary: "Yearly consumption report of the group",
+        examples: &["gy", "gy 2023"],
+        scope: CommandScope::Group,
+    },
+    CommandSpec {
+        command_id: CommandId::EarnedDetailByWon,
+        name: "ew",
+        aliases: &[],
+        arg_grammar: "<name>:<amount>",
+        summary: "Record an income in Korean won",
+        examples: &["ew salary:5000000"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::EarnedDetailByDollar,
+        name: "ed",
+        aliases: &[],
+        arg_grammar: "<name>:<amount>",
+        summary: "Record an income in US dollars",
+        examples: &["ed salary:1500.50"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::Payday,
+        name: "pd",
+        aliases: &[],
+        arg_grammar: "[day]",
+        summary: "Show or change the payday that starts each salary period",
+        examples: &["pd", "pd 25"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::ShowAllAsset,
+        name: "my",
+        aliases: &[],
+        arg_grammar: "[-t]",
+        summary: "Show every registered asset and the total net worth",
+        examples: &["my", "my -t"],
+        scope: CommandScope::Room,
+    },
+    CommandSpec {
+        command_id: CommandId::NetWorthHistory,
+        name: "mh",
+        aliases: &[],
+        arg_grammar: "[months] [-t]",
+        summary: "Chart the monthly net worth per asset class from the daily snapshots",
+        examples: &["mh", "mh 6", "mh 24 -t"],
+        scope: CommandScope::Room,
+    },
+];
+
+/// Rewrites a Telegram bot command such as `/cm@my_bot 2023.07` to the plain form `cm 2023.07`.
+///
+/// Commands picked from the Telegram menu carry a leading `/` and, in group chats, the bot's
+/// username; both are dropped so every handler parses the same text. Other input is returned
+/// unchanged.
+pub fn normalize_command_text(input_text: &str) -> String {
+    let Some(command_text) = input_text.strip_prefix('/') else {
+        return input_text.to_string();
+    };
+
+    let (command_token, rest) = command_text
+        .split_once(char::is_whitespace)
+        .map(|(command_token, rest)| (command_token, Some(rest)))
+        .unwrap_or((command_text, None));
+    let command_nm: &str = command_token.split('@').next().unwrap_or_default();
+
+    match rest {
+        Some(rest) => format!("{} {}", command_nm, rest),
+        None => command_nm.to_string(),
+    }
+}
+
+/// Finds the command registered under `command_nm` or one of its aliases.
+///
+/// # Arguments
+///
+/// * `command_nm` - First token of the input with any leading `/` already removed
+///
+/// # Returns
+///
+/// Returns the matching `CommandSpec`, or `None` when the input is not a command.
+pub fn find_command_spec(command_nm: &str) -> Option<&'static CommandSpec> {
+    COMMAND_REGISTRY
+        .iter()
+        .find(|spec| spec.name == command_nm || spec.aliases.contains(&command_nm))
+}
+
+/// Returns the registry entry of `command_id`.
+///
+/// # Panics
+///
+/// Panics if `command_id` was added to `CommandId` without a registry entry.
+pub fn command_spec(command_id: CommandId) -> &'static CommandSpec {
+    COMMAND_REGISTRY
+        .iter()
+        .find(|spec| spec.command_id == command_id)
+        .unwrap_or_else(|| panic!("[command_spec] {:?} is not registered", command_id))
+}
+
+/// Builds the `/help` overview listing every command with its argument grammar.
+pub fn help_overview() -> String {
+    let mut help_msg: String = String::from("Available commands (/help <command> for details)\n");
+
+    for spec in COMMAND_REGISTRY {
+        help_msg.push_str(&format!(
+            "\n{} [{}]\n  {}",
+            spec.usage(),
+            spec.scope.scope_nm(),
+            spec.summary
+        ));
+    }
+
+    help_msg
+}
+
+impl CommandSpec {
+    /// Returns the command name followed by its argument grammar, e.g. `c <name>:<amount>`.
+    pub fn usage(&self) -> String {
+        if self.arg_grammar.is_empty() {
+            self.name.to_string()
+        } else {
+            format!("{} {}", self.name, self.arg_grammar)
+        }
+    }
+
+    /// Returns the example line appended to error messages, e.g. `EX) cm | cm 2023.07`.
+    pub fn examples_line(&self) -> String {
+        format!("EX) {}", self.examples.join(" | "))
+    }
+
+    /// Builds the `/help <command>` text of this command.
+    pub fn help_detail(&self) -> String {
+        let mut help_msg: String = format!(
+            "{}\n{}\nScope: {}",
+            self.usage(),
+            self.summary,
+            self.scope.scope_nm()
+        );
+
+        if !self.aliases.is_empty() {
+            help_msg.push_str(&format!("\nAliases: {}", self.aliases.join(", ")));
+        }
+
+        help_msg.push('\n');
+        help_msg.push_str(&self.examples_line());
+        help_msg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_names_are_unique_and_valid_telegram_commands() {
+        let mut seen_nms: Vec<&str> = Vec::new();
+
+        for spec in COMMAND_REGISTRY {
+            for nm in std::iter::once(&spec.name).chain(spec.aliases.iter()) {
+                assert!(!seen_nms.contains(nm), "duplicate command name: {}", nm);
+                assert!(
+                    nm.chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+                    "invalid Telegram command name: {}",
+                    nm
+                );
+                seen_nms.push(nm);
+            }
+
+            /* setMyCommands rejects descriptions shorter than 3 or longer than 256 characters. */
+            assert!((3..=256).contains(&spec.summary.chars().count()));
+            assert!(!spec.examples.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_every_command_id_resolves_by_name_and_alias() {
+        for spec in COMMAND_REGISTRY {
+            assert_eq!(command_spec(spec.command_id).name, spec.name);
+            assert_eq!(
+                find_command_spec(spec.name).unwrap().command_id,
+                spec.command_id
+            );
+        }
+
+        assert_eq!(
+            find_command_spec("start").unwrap().command_id,
+            CommandId::Help
+        );
+        assert!(find_command_spec("unknown").is_none());
+    }
+
+    #[test]
+    fn test_normalize_command_text_strips_slash_and_bot_username() {
+        assert_eq!(normalize_command_text("/cm@my_bot 2023.07"), "cm 2023.07");
+        assert_eq!(normalize_command_text("/help"), "help");
+        assert_eq!(normalize_command_text("c snack:15000"), "c snack:15000");
+    }
+}
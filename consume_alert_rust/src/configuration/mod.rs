@@ -1 +1,2 @@
+pub mod command_registry;
 pub mod elasitc_index_name;
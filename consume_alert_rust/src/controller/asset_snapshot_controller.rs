@@ -0,0 +1,166 @@
+use crate::common::*;
+
+use crate::service_traits::mysql_query_service::*;
+
+use crate::models::{currency_asset_holdings::*, user_current_asset_snapshot::*};
+
+use crate::utils_modules::{schedule_utils::*, time_utils::*};
+
+use crate::AppConfig;
+
+/// Wait before a failed tick stores the snapshots that are still missing for the day.
+const ASSET_SNAPSHOT_RETRY_DELAY: Duration = Duration::from_secs(10 * 60);
+
+#[derive(new)]
+pub struct AssetSnapshotController<M: MysqlQueryService> {
+    mysql_query_service: Arc<M>,
+}
+
+impl<M> AssetSnapshotController<M>
+where
+    M: MysqlQueryService + Sync + Send,
+{
+    /// Stores the asset snapshot of every user on every `ASSET_SNAPSHOT_CRON` tick forever.
+    ///
+    /// Each run is recorded under the KST calendar date it actually ran on. Users without a
+    /// snapshot for today get one at startup, so a day whose tick was missed while the process
+    /// was down is still covered, and again `ASSET_SNAPSHOT_RETRY_DELAY` after a tick that
+    /// failed. Returns immediately when no schedule is configured.
+    pub async fn main_call_function(&self) {
+        let Some(asset_snapshot_schedule) = AppConfig::get_global().asset_snapshot_schedule()
+        else {
+            return;
+        };
+
+        info!("[AssetSnapshotController::main_call_function] Asset snapshot scheduler started");
+
+        if let Err(e) = self.take_missing_snapshots().await {
+            error!("[AssetSnapshotController::main_call_function] {:#}", e);
+        }
+
+        run_on_schedule(asset_snapshot_schedule, "ASSET_SNAPSHOT_CRON", |_| async {
+            if let Err(e) = self.take_snapshots().await {
+                error!("[AssetSnapshotController::main_call_function] {:#}", e);
+
+                tokio::time::sleep(ASSET_SNAPSHOT_RETRY_DELAY).await;
+                self.take_missing_snapshots().await?;
+            }
+
+            Ok(())
+        })
+        .await;
+    }
+
+    /// Aggregates the current assets of every user the same way `my` does and stores them as
+    /// today's snapshot (KST), one row per user and currency.
+    ///
+    /// The holdings are read as they are now, so only today can be stored; missed days cannot
+    /// be backfilled. Rows already stored for today are replaced, so a run can be repeated.
+    /// A failure of one user is logged and does not stop the snapshots of the other users.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of users whose snapshot was stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the users cannot be listed or the snapshot of any user failed.
+    pub async fn take_snapshots(&self) -> anyhow::Result<usize> {
+        let user_seqs: Vec<i64> = self.mysql_query_service.find_user_seqs().await?;
+
+        self.take_user_snapshots(&user_seqs, find_current_kor_naivedate())
+            .await
+    }
+
+    /// Stores today's snapshot (KST) of the users that have none yet, leaving stored rows as
+    /// they are.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of users whose snapshot was stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the users or their snapshots cannot be listed, or the snapshot of
+    /// any user failed.
+    async fn take_missing_snapshots(&self) -> anyhow::Result<usize> {
+        let snapshot_date: DateTime<Utc> = find_current_kor_naivedate();
+        let mut missing_user_seqs: Vec<i64> = Vec::new();
+
+        for user_seq in self.mysql_query_service.find_user_seqs().await? {
+            let snapshots: Vec<UserCurrentAssetSnapshot> = self
+                .mysql_query_service
+                .find_user_current_asset_snapshots(user_seq, snapshot_date)
+                .await?;
+
+            if snapshots.is_empty() {
+                missing_user_seqs.push(user_seq);
+            }
+        }
+
+        self.take_user_snapshots(&missing_user_seqs, snapshot_date)
+            .await
+    }
+
+    /// Stores the snapshot of `snapshot_date` for each of `user_seqs`.
+    async fn take_user_snapshots(
+        &self,
+        user_seqs: &[i64],
+        snapshot_date: DateTime<Utc>,
+    ) -> anyhow::Result<usize> {
+        let mut stored_cnt: usize = 0;
+
+        for user_seq in user_seqs {
+            match self.take_user_snapshot(*user_seq, snapshot_date).await {
+                Ok(()) => stored_cnt += 1,
+                Err(e) => error!(
+                    "[AssetSnapshotController::take_user_snapshots] Failed to store the snapshot of USER-{}: {:#}",
+                    user_seq, e
+                ),
+            }
+        }
+
+        info!(
+            "[AssetSnapshotController::take_user_snapshots] Stored the {} snapshot of {} user(s)",
+            snapshot_date.format("%Y-%m-%d"),
+            stored_cnt
+        );
+
+        if stored_cnt < user_seqs.len() {
+            return Err(anyhow!(
+                "[AssetSnapshotController::take_user_snapshots] {} of {} snapshot(s) of {} failed",
+                user_seqs.len() - stored_cnt,
+                user_seqs.len(),
+                snapshot_date.format("%Y-%m-%d")
+            ));
+        }
+
+        Ok(stored_cnt)
+    }
+
+    /// Aggregates the holdings of one user per currency and replaces that user's rows of the day.
+    async fn take_user_snapshot(
+        &self,
+        user_seq: i64,
+        snapshot_date: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        let mut snapshots: Vec<UserCurrentAssetSnapshot> = Vec::new();
+
+        for currency_code in ASSET_CURRENCY_CODES {
+            let holdings: CurrencyAssetHoldings = self
+                .mysql_query_service
+                .find_currency_asset_holdings(user_seq, currency_code)
+                .await?;
+
+            snapshots.push(holdings.to_user_current_asset_snapshot(user_seq, snapshot_date));
+        }
+
+        self.mysql_query_service
+            .replace_user_current_asset_snapshots_with_transaction(
+                user_seq,
+                snapshot_date,
+                &snapshots,
+            )
+            .await
+    }
+}
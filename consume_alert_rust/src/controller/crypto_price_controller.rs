@@ -0,0 +1,227 @@
+use crate::common::*;
+use std::collections::BTreeMap;
+
+use crate::service_traits::{crypto_quote_provider::*, mysql_query_service::*};
+
+use crate::models::crypto::*;
+
+use crate::utils_modules::schedule_utils::*;
+
+use crate::AppConfig;
+
+#[derive(new)]
+pub struct CryptoPriceController<M: MysqlQueryService> {
+    mysql_query_service: Arc<M>,
+    crypto_quote_providers: Vec<Arc<dyn CryptoQuoteProvider>>,
+}
+
+impl<M> CryptoPriceController<M>
+where
+    M: MysqlQueryService + Sync + Send,
+{
+    /// Refreshes `CRYPTO.crypto_price` on every `CRYPTO_PRICE_CRON` tick forever.
+    ///
+    /// Returns immediately when no schedule is configured.
+    pub async fn main_call_function(&self) {
+        let Some(crypto_price_schedule) = AppConfig::get_global().crypto_price_schedule() else {
+            return;
+        };
+
+        info!(
+            "[CryptoPriceController::main_call_function] Crypto price scheduler started with the {:?} provider(s)",
+            self.crypto_quote_providers
+                .iter()
+                .map(|provider| provider.provider_nm())
+                .collect::<Vec<&str>>()
+        );
+
+        run_on_schedule(crypto_price_schedule, "CRYPTO_PRICE_CRON", |_| {
+            self.refresh_crypto_prices()
+        })
+        .await;
+    }
+
+    /// Quotes every crypto in `CRYPTO` with the provider of its currency and writes the prices
+    /// to `CRYPTO.crypto_price`, one transaction per currency. `CRYPTO.price_updated_at` records
+    /// when each price was last refreshed, so editing a row by hand does not make its price
+    /// look fresh.
+    ///
+    /// Cryptos that cannot be quoted keep their previous price and refresh time, so they show
+    /// up as stale in the `my` command. A failure of one currency is logged and does not stop
+    /// the other currencies.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of CRYPTO rows updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cryptos cannot be listed.
+    async fn refresh_crypto_prices(&self) -> anyhow::Result<u64> {
+        let cryptos: Vec<Crypto> = self.mysql_query_service.find_cryptos().await?;
+
+        let currency_prices: Vec<(String, Vec<(String, Decimal)>)> =
+            find_currency_quote_prices(&self.crypto_quote_providers, &cryptos).await;
+
+        let mut updated_cnt: u64 = 0;
+
+        for (currency_code, crypto_prices) in currency_prices {
+            match self
+                .mysql_query_service
+                .modify_crypto_prices_with_transaction(&currency_code, &crypto_prices)
+                .await
+            {
+                Ok(currency_updated_cnt) => {
+                    info!(
+                        "[CryptoPriceController::refresh_crypto_prices] {}: updated {} row(s)",
+                        currency_code, currency_updated_cnt
+                    );
+                    updated_cnt += currency_updated_cnt;
+                }
+                Err(e) => error!(
+                    "[CryptoPriceController::refresh_crypto_prices] Failed to store {} prices: {:#}",
+                    currency_code, e
+                ),
+            }
+        }
+
+        Ok(updated_cnt)
+    }
+}
+
+/// Groups the cryptos by currency and quotes each group with the provider of that currency.
+///
+/// # Arguments
+///
+/// * `crypto_quote_providers` - Providers to choose from, matched by `currency_code`
+/// * `cryptos` - Cryptos to quote
+///
+/// # Returns
+///
+/// Returns `(currency_code, [(api_symbol, price)])` for every currency that has at least one
+/// quoted price. Currencies without a provider, failed requests and symbols missing from the
+/// answer are logged and left out.
+async fn find_currency_quote_prices(
+    crypto_quote_providers: &[Arc<dyn CryptoQuoteProvider>],
+    cryptos: &[Crypto],
+) -> Vec<(String, Vec<(String, Decimal)>)> {
+    let mut api_symbols_by_currency: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+
+    for crypto in cryptos {
+        let api_symbols: &mut Vec<String> = api_symbols_by_currency
+            .entry(crypto.currency_code.as_str())
+            .or_default();
+
+        if !api_symbols.contains(&crypto.api_symbol) {
+            api_symbols.push(crypto.api_symbol.clone());
+        }
+    }
+
+    let mut currency_prices: Vec<(String, Vec<(String, Decimal)>)> = Vec::new();
+
+    for (currency_code, api_symbols) in api_symbols_by_currency {
+        let Some(crypto_quote_provider) = crypto_quote_providers
+            .iter()
+            .find(|provider| provider.currency_code() == currency_code)
+        else {
+            warn!(
+                "[CryptoPriceController::find_currency_quote_prices] No quote provider for {}, skipped {:?}",
+                currency_code, api_symbols
+            );
+            continue;
+        };
+
+        let quote_prices: HashMap<String, Decimal> = match crypto_quote_provider
+            .find_quote_prices(&api_symbols)
+            .await
+        {
+            Ok(quote_prices) => quote_prices,
+            Err(e) => {
+                warn!(
+                    "[CryptoPriceController::find_currency_quote_prices] '{}' quote of {} failed: {:#}",
+                    crypto_quote_provider.provider_nm(),
+                    currency_code,
+                    e
+                );
+                continue;
+            }
+        };
+
+        let crypto_prices: Vec<(String, Decimal)> = api_symbols
+            .into_iter()
+            .filter_map(|api_symbol| match quote_prices.get(&api_symbol) {
+                Some(quote_price) => Some((api_symbol, *quote_price)),
+                None => {
+                    warn!(
+                        "[CryptoPriceController::find_currency_quote_prices] '{}' has no {} quote for {}",
+                        crypto_quote_provider.provider_nm(),
+                        currency_code,
+                        api_symbol
+                    );
+                    None
+                }
+            })
+            .collect();
+
+        if !crypto_prices.is_empty() {
+            currency_prices.push((currency_code.to_string(), crypto_prices));
+        }
+    }
+
+    currency_prices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::services::crypto_quote_provider_impl::*;
+
+    fn crypto(crypto_seq: i64, api_symbol: &str, currency_code: &str) -> Crypto {
+        Crypto {
+            crypto_seq,
+            crypto_name: api_symbol.to_string(),
+            crypto_price: Decimal::ZERO,
+            api_symbol: api_symbol.to_string(),
+            currency_code: currency_code.to_string(),
+            created_at: Utc::now(),
+            updated_at: None,
+            created_by: "system".to_string(),
+            updated_by: None,
+            price_updated_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_currency_quote_prices() {
+        let crypto_quote_providers: Vec<Arc<dyn CryptoQuoteProvider>> =
+            vec![Arc::new(MockCryptoQuoteProvider::new(
+                "KRW",
+                HashMap::from([
+                    ("BTC".to_string(), Decimal::new(95120000, 0)),
+                    ("ETH".to_string(), Decimal::new(4870000, 0)),
+                    ("SOL".to_string(), Decimal::new(281000, 0)),
+                ]),
+            ))];
+        let cryptos: Vec<Crypto> = vec![
+            crypto(1, "BTC", "KRW"),
+            crypto(2, "ETH", "KRW"),
+            crypto(3, "DOGE", "KRW"),
+            crypto(4, "SOL", "USD"),
+        ];
+
+        let currency_prices: Vec<(String, Vec<(String, Decimal)>)> =
+            find_currency_quote_prices(&crypto_quote_providers, &cryptos).await;
+
+        assert_eq!(
+            currency_prices,
+            vec![(
+                "KRW".to_string(),
+                vec![
+                    ("BTC".to_string(), Decimal::new(95120000, 0)),
+                    ("ETH".to_string(), Decimal::new(4870000, 0)),
+                ]
+            )]
+        );
+    }
+}
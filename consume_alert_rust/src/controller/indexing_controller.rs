@@ -0,0 +1,193 @@
+use crate::common::*;
+
+use crate::service_traits::{
+    consumer_service::*, elastic_query_service::*, mysql_query_service::*, producer_service::*,
+};
+
+use crate::models::{
+    kafka_consumed_message::*, spent_detail_indexing::*, spent_detail_indexing_failure::*,
+    spent_detail_to_kafka::*,
+};
+
+use crate::enums::indexing_type::*;
+
+use crate::AppConfig;
+
+/* Base delay between indexing attempts; the n-th retry waits n times this long. */
+const INDEXING_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/* Delay before Kafka receive or dead-letter publishing is tried again. */
+const CONSUMER_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(new)]
+pub struct IndexingController<
+    E: ElasticQueryService,
+    M: MysqlQueryService,
+    KC: ConsumerService,
+    KP: ProducerService,
+> {
+    elastic_query_service: Arc<E>,
+    mysql_query_service: Arc<M>,
+    consumer_service: KC,
+    producer_service: Arc<KP>,
+}
+
+impl<E, M, KC, KP> IndexingController<E, M, KC, KP>
+where
+    E: ElasticQueryService + Sync + Send,
+    M: MysqlQueryService + Sync + Send,
+    KC: ConsumerService + Sync + Send,
+    KP: ProducerService + Sync + Send,
+{
+    /// Consumes `SpentDetailToKafka` events forever and mirrors each SPENT_DETAIL change into
+    /// the `CONSUME_DETAIL` index.
+    ///
+    /// Offsets are committed only after an event has been indexed or handed to the
+    /// dead-letter topic, so every event is handled at least once.
+    pub async fn main_call_function(&self) {
+        info!("[IndexingController::main_call_function] Indexing consumer started");
+
+        loop {
+            let message: KafkaConsumedMessage = match self.consumer_service.find_message().await {
+                Ok(message) => message,
+                Err(e) => {
+                    error!("[IndexingController::main_call_function] {:#}", e);
+                    tokio::time::sleep(CONSUMER_RETRY_DELAY).await;
+                    continue;
+                }
+            };
+
+            self.handle_message(&message).await;
+
+            if let Err(e) = self.consumer_service.commit_message(&message) {
+                error!("[IndexingController::main_call_function] {:#}", e);
+            }
+        }
+    }
+
+    /// Indexes a single event, retrying up to `INDEXING_MAX_ATTEMPTS` times before sending it
+    /// to the dead-letter topic. Events whose payload cannot be parsed are dead-lettered at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The received Kafka message
+    async fn handle_message(&self, message: &KafkaConsumedMessage) {
+        let spent_detail_event: SpentDetailToKafka = match message.to_object() {
+            Ok(spent_detail_event) => spent_detail_event,
+            Err(e) => {
+                self.input_dead_letter(message, 1, &e).await;
+                return;
+            }
+        };
+
+        let max_attempts: u32 = *AppConfig::get_global().indexing_max_attempts();
+
+        for attempt in 1..=max_attempts {
+            match self.index_spent_detail(&spent_detail_event).await {
+                Ok(()) => return,
+                Err(e) if attempt == max_attempts => {
+                    self.input_dead_letter(message, attempt, &e).await;
+                }
+                Err(e) => {
+                    error!(
+                        "[IndexingController::handle_message] Attempt {}/{} failed for spent_idx={}: {:#}",
+                        attempt, max_attempts, spent_detail_event.spent_idx, e
+                    );
+                    tokio::time::sleep(INDEXING_RETRY_DELAY * attempt).await;
+                }
+            }
+        }
+    }
+
+    /// Applies one SPENT_DETAIL change to Elasticsearch.
+    ///
+    /// Inserts and updates re-read the denormalised row from MySQL and overwrite the document.
+    /// A row that no longer exists or is excluded from indexing (`should_index = 0`) is removed
+    /// from the index, as are deleted rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `spent_detail_event` - The change event produced for the SPENT_DETAIL row
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event type is unknown, or any MySQL or Elasticsearch step fails.
+    async fn index_spent_detail(
+        &self,
+        spent_detail_event: &SpentDetailToKafka,
+    ) -> anyhow::Result<()> {
+        let spent_idx: i64 = spent_detail_event.spent_idx;
+
+        match spent_detail_event.to_indexing_type()? {
+            IndexingType::Insert | IndexingType::Update => {
+                let spent_detail_indexing: Option<SpentDetailIndexing> = self
+                    .mysql_query_service
+                    .find_spent_detail_indexing(spent_idx)
+                    .await?;
+
+                match spent_detail_indexing {
+                    Some(spent_detail_indexing) => {
+                        self.elastic_query_service
+                            .input_spent_detail_indexing(&spent_detail_indexing)
+                            .await
+                    }
+                    None => {
+                        self.elastic_query_service
+                            .delete_spent_detail_indexing(spent_idx)
+                            .await
+                    }
+                }
+            }
+            IndexingType::Delete => {
+                self.elastic_query_service
+                    .delete_spent_detail_indexing(spent_idx)
+                    .await
+            }
+        }
+    }
+
+    /// Publishes a failed event to the dead-letter topic, retrying until the broker accepts it
+    /// so the source offset is never committed for an event that was neither indexed nor kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The Kafka message that could not be indexed
+    /// * `attempts` - Number of indexing attempts made
+    /// * `cause` - The last indexing error
+    async fn input_dead_letter(
+        &self,
+        message: &KafkaConsumedMessage,
+        attempts: u32,
+        cause: &anyhow::Error,
+    ) {
+        let dlq_topic: &str = AppConfig::get_global().indexing_dlq_topic();
+
+        error!(
+            "[IndexingController::input_dead_letter] Sending {}/{}/{} to {}: {:#}",
+            message.topic(),
+            message.partition(),
+            message.offset(),
+            dlq_topic,
+            cause
+        );
+
+        let indexing_failure: SpentDetailIndexingFailure = SpentDetailIndexingFailure::new(
+            message.topic().to_string(),
+            *message.partition(),
+            *message.offset(),
+            message.payload().clone(),
+            format!("{:#}", cause),
+            attempts,
+            Utc::now(),
+        );
+
+        while let Err(e) = self
+            .producer_service
+            .input_object_to_topic(dlq_topic, &indexing_failure, message.key().as_deref())
+            .await
+        {
+            error!("[IndexingController::input_dead_letter] {:#}", e);
+            tokio::time::sleep(CONSUMER_RETRY_DELAY).await;
+        }
+    }
+}
@@ -1,22 +1,35 @@
 use crate::common::*;
 
 use crate::service_traits::{
-    cache_service::*, elastic_query_service::*, graph_api_service::*, mysql_query_service::*,
-    process_service::*, producer_service::*, redis_service::*, telebot_service::*,
+    cache_service::*, consume_classifier::*, elastic_query_service::*, graph_api_service::*,
+    mysql_query_service::*, process_service::*, redis_service::*, telebot_service::*,
 };
 
 use crate::models::{
-    asset_resp::*, assets::*, cash_asset::*, crypto_resp::*, deposit_asset::*, earned_detail::*,
-    per_datetime::*, saving_asset::*, stock_pie_data::*, stock_resp::*,
+    asset_resp::*, assets::*, crypto_resp::*, currency_asset_holdings::*, earned_detail::*,
+    net_worth_history::*, per_datetime::*, stock_pie_data::*, stock_resp::*,
+    user_current_asset_snapshot::*,
 };
 
 use crate::dtos::{
     StockPieDataDto
 };
 
-use crate::utils_modules::{currency_utils::*, io_utils::*, numeric_utils::*, time_utils::*};
+use crate::utils_modules::{
+    command_tokenizer::*, currency_utils::*, numeric_utils::*, time_utils::*,
+};
+
+use crate::enums::{command_arg_error::*, command_id::*};
+
+use crate::AppConfig;
 
-use super::MainController;
+use super::{MainController, GRAPH_FAILURE_NOTICE};
+
+/// Months `mh` covers when no count is given.
+const DEFAULT_NET_WORTH_MONTHS: i32 = 12;
+
+/// Largest month count `mh` accepts.
+const MAX_NET_WORTH_MONTHS: i32 = 60;
 
 #[derive(Clone, Copy)]
 struct ExchangeRates {
@@ -110,6 +123,21 @@ fn build_asset_message(
     msg
 }
 
+/// Lists the cryptos whose price has not been refreshed within `CRYPTO_PRICE_STALE_MINS`.
+fn build_stale_crypto_message(stale_cryptos: &[CryptoResp]) -> String {
+    let mut msg: String = String::from("\n[시세 지연 경고]\n");
+
+    for crypto in stale_cryptos {
+        msg.push_str(&format!(
+            "*  {} : {} 이후 갱신 없음\n",
+            crypto.crypto_name(),
+            to_kst_datetime_format(*crypto.price_updated_at(), "%Y-%m-%d %H:%M"),
+        ));
+    }
+
+    msg
+}
+
 fn build_stock_message(
     stock_resp_details: &[StockRespDetail],
     total_stock_amount_krw: Decimal,
@@ -170,10 +198,10 @@ impl<
         M: MysqlQueryService,
         T: TelebotService,
         P: ProcessService,
-        KP: ProducerService,
         R: RedisService,
         C: CacheService,
-    > MainController<G, E, M, T, P, KP, R, C>
+        CL: ConsumeClassifier,
+    > MainController<G, E, M, T, P, R, C, CL>
 {
     /// Saves an earned-detail record entered in Korean won (`ew name:amount`).
     ///
@@ -198,19 +226,19 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(":");
-
-        if args.len() != 2 {
-            self.tele_bot_service
-                .input_message_confirm(
-                    "There is a problem with the parameter you entered. Please check again.\nEX) ew salary:5000000",
-                )
-                .await?;
+        let Some((earned_name, earned_money)) = self
+            .parse_command_args(CommandId::EarnedDetailByWon, |command_line| {
+                let key_value: KeyValueArg = command_line.key_value_arg()?;
+                let amount: i64 = key_value.parse_value("amount")?;
+                Ok((key_value.key, amount))
+            })
+            .await?
+        else {
             return Err(anyhow!(
                 "[main_controller::command_earend_detail] Invalid parameter format: {}",
                 self.tele_bot_service.get_input_text()
             ));
-        }
+        };
 
         let user_seq: i64 = self
             .resolve_user_seq(telegram_token, telegram_user_id)
@@ -220,22 +248,6 @@ impl<
             .resolve_telegram_room_seq(user_seq, telegram_token, telegram_user_id)
             .await?;
 
-        let earned_name: String = args[0].clone();
-        let earned_money: i64 = match find_parsed_value_from_vector(&args, 1) {
-            Ok(cash) => cash,
-            Err(e) => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "The second parameter must be numeric.\nEX) ew salary:5000000",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[main_controller::command_earend_detail] Non-numeric cash parameter: {:#}",
-                    e
-                ));
-            }
-        };
-
         let usd_amount: f64 = krw_to_usd(earned_money)
             .await
             .inspect_err(|e| {
@@ -314,19 +326,19 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(":");
-
-        if args.len() != 2 {
-            self.tele_bot_service
-                .input_message_confirm(
-                    "There is a problem with the parameter you entered. Please check again.\nEX) ed salary:1500.50",
-                )
-                .await?;
+        let Some((earned_name, usd_amount)) = self
+            .parse_command_args(CommandId::EarnedDetailByDollar, |command_line| {
+                let key_value: KeyValueArg = command_line.key_value_arg()?;
+                let amount: f64 = key_value.parse_value("amount")?;
+                Ok((key_value.key, amount))
+            })
+            .await?
+        else {
             return Err(anyhow!(
                 "[main_controller::command_earend_detail_by_dollor] Invalid parameter format: {}",
                 self.tele_bot_service.get_input_text()
             ));
-        }
+        };
 
         let user_seq: i64 = self
             .resolve_user_seq(telegram_token, telegram_user_id)
@@ -336,22 +348,6 @@ impl<
             .resolve_telegram_room_seq(user_seq, telegram_token, telegram_user_id)
             .await?;
 
-        let earned_name: String = args[0].clone();
-        let usd_amount: f64 = match find_parsed_value_from_vector::<f64>(&args, 1) {
-            Ok(cash) => cash,
-            Err(e) => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "The second parameter must be numeric.\nEX) ed salary:1500.50",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[main_controller::command_earend_detail_by_dollor] Non-numeric cash parameter: {:#}",
-                    e
-                ));
-            }
-        };
-
         let earned_money: i64 = usd_to_krw(usd_amount)
             .await
             .inspect_err(|e| {
@@ -413,45 +409,33 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
+        let Some(year_month) = self
+            .parse_command_args(
+                CommandId::ConsumptionPerMon,
+                CommandLine::optional_year_month_arg,
+            )
+            .await?
+        else {
+            return Err(anyhow!(
+                "[command_consumption_per_mon] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
+        };
 
-        let _permon_datetime: PerDatetime = match args.len() {
-            1 => {
+        let _permon_datetime: PerDatetime = match year_month {
+            None => {
                 let date_start: DateTime<Utc> = find_current_kor_naivedate_first_date()?;
                 let date_end: DateTime<Utc> = find_lastday_naivedate(date_start)?;
 
                 self.process_service
                     .find_nmonth_to_current_date(date_start, date_end, -1)?
             }
-            2 if args
-                .get(1)
-                .is_some_and(|d| is_valid_date_format(d, r"^\d{4}\.\d{2}$").unwrap_or(false)) =>
-            {
-                let parts: Vec<&str> = args[1].split('.').collect();
-                let year: i32 = parts
-                    .first()
-                    .ok_or_else(|| anyhow!("[command_consumption_per_mon] Missing year"))?
-                    .parse()?;
-                let month: u32 = parts
-                    .get(1)
-                    .ok_or_else(|| anyhow!("[command_consumption_per_mon] Missing month"))?
-                    .parse()?;
+            Some(YearMonthArg { year, month }) => {
                 let date_start: DateTime<Utc> = find_naivedate(year, month, 1)?;
                 let date_end: DateTime<Utc> = find_lastday_naivedate(date_start)?;
                 self.process_service
                     .find_nmonth_to_current_date(date_start, date_end, -1)?
             }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "Invalid date format. Please use format YYYY.MM like em 2023.07 or em",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_mon] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
         };
 
         let user_seq: i64 = self
@@ -465,15 +449,35 @@ impl<
         Ok(())
     }
 
+    /// Shows every registered asset and the total net worth of the caller (`my [-t]`).
+    ///
+    /// The asset summary and the stock portfolio are always sent as text first. The asset and
+    /// stock pie charts follow unless `-t` is given; a chart that cannot be rendered is replaced
+    /// by a short notice instead of failing the command.
+    ///
+    /// # Arguments
+    ///
+    /// * `telegram_token` - Telegram bot token used to resolve the caller
+    /// * `telegram_user_id` - Telegram user id used to resolve the caller
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` after the summaries and the available charts are sent to Telegram.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameters are invalid, the caller is unauthorised, or any
+    /// lookup or Telegram step fails.
     pub(super) async fn command_show_all_asset(
         &self,
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
-
-        match args.len() {
-            1 => {
+        match self
+            .parse_command_args(CommandId::ShowAllAsset, CommandLine::no_args)
+            .await?
+        {
+            Some(()) => {
                 let user_seq: i64 = self
                     .resolve_user_seq(telegram_token, telegram_user_id)
                     .await?;
@@ -498,16 +502,21 @@ impl<
                 let mut total_stock_amount_krw: Decimal = Decimal::ZERO;
                 //let mut total_stock_amount_usd: Decimal = Decimal::ZERO;
 
-                for currency_code in &["KRW", "USD"] {
-                    let is_krw: bool = *currency_code == "KRW";
+                let price_checked_at: DateTime<Utc> = Utc::now();
+                let crypto_price_stale_after: chrono::Duration =
+                    chrono::Duration::minutes(*AppConfig::get_global().crypto_price_stale_mins());
+                let mut stale_cryptos: Vec<CryptoResp> = Vec::new();
+
+                for currency_code in ASSET_CURRENCY_CODES {
+                    let is_krw: bool = currency_code == "KRW";
 
-                    let deposits: Vec<DepositAsset> = self
+                    let holdings: CurrencyAssetHoldings = self
                         .mysql_query_service
-                        .find_deposit_asset(user_seq, currency_code)
+                        .find_currency_asset_holdings(user_seq, currency_code)
                         .await
-                        .inspect_err(|e| error!("[command_show_all_asset] deposits: {:#}", e))?;
+                        .inspect_err(|e| error!("[command_show_all_asset] holdings: {:#}", e))?;
 
-                    for d in &deposits {
+                    for d in holdings.deposit_assets() {
                         push_asset(
                             &mut asset_map,
                             &mut totals,
@@ -519,13 +528,7 @@ impl<
                         );
                     }
 
-                    let savings: Vec<SavingAsset> = self
-                        .mysql_query_service
-                        .find_saving_asset(user_seq, currency_code)
-                        .await
-                        .inspect_err(|e| error!("[command_show_all_asset] savings: {:#}", e))?;
-
-                    for s in &savings {
+                    for s in holdings.saving_assets() {
                         push_asset(
                             &mut asset_map,
                             &mut totals,
@@ -537,14 +540,8 @@ impl<
                         );
                     }
 
-                    let stock_resps: Vec<StockResp> = self
-                        .mysql_query_service
-                        .find_stock_response(user_seq, currency_code)
-                        .await
-                        .inspect_err(|e| error!("[command_show_all_asset] stocks: {:#}", e))?;
-
-                    for s in &stock_resps {
-                        let stock_amount: Decimal = s.stock_price * Decimal::from(*s.stock_cnt());
+                    for s in holdings.stock_resps() {
+                        let stock_amount: Decimal = s.stock_amount();
                         push_asset(
                             &mut asset_map,
                             &mut totals,
@@ -565,13 +562,7 @@ impl<
                         stock_list.push(s.clone());
                     }
 
-                    let cryptos: Vec<CryptoResp> = self
-                        .mysql_query_service
-                        .find_crypto_response(user_seq, currency_code)
-                        .await
-                        .inspect_err(|e| error!("[command_show_all_asset] cryptos: {:#}", e))?;
-
-                    for c in &cryptos {
+                    for c in holdings.crypto_resps() {
                         push_asset(
                             &mut asset_map,
                             &mut totals,
@@ -581,14 +572,13 @@ impl<
                             is_krw,
                             rates,
                         );
+
+                        if c.is_price_stale(price_checked_at, crypto_price_stale_after) {
+                            stale_cryptos.push(c.clone());
+                        }
                     }
 
-                    let cashes: Vec<CashAsset> = self
-                        .mysql_query_service
-                        .find_cash_asset(user_seq, currency_code)
-                        .await
-                        .inspect_err(|e| error!("[command_show_all_asset] cashes: {:#}", e))?;
-                    for c in &cashes {
+                    for c in holdings.cash_assets() {
                         push_asset(
                             &mut asset_map,
                             &mut totals,
@@ -601,7 +591,11 @@ impl<
                     }
                 }
 
-                let msg: String = build_asset_message(&asset_map, &totals, rates);
+                let mut msg: String = build_asset_message(&asset_map, &totals, rates);
+
+                if !stale_cryptos.is_empty() {
+                    msg.push_str(&build_stale_crypto_message(&stale_cryptos));
+                }
 
                 self.tele_bot_service
                     .input_message_confirm(&msg)
@@ -610,30 +604,6 @@ impl<
                         error!("[command_show_all_asset] Failed to send message: {:#}", e)
                     })?;
 
-                let total_asset_amount_krw: Decimal = totals.krw + (totals.usd * usd_to_krw);
-                let assets: Assets = Assets::new(total_asset_amount_krw, asset_map);
-                
-                let pie_image_bytes: Vec<u8> = self
-                    .graph_api_service
-                    .find_python_matplot_asset_pie(assets)
-                    .await
-                    .inspect_err(|e| {
-                        error!(
-                            "[command_show_all_asset] Failed to get asset pie image: {:#}",
-                            e
-                        )
-                    })?;
-
-                self.tele_bot_service
-                    .input_photo_from_bytes(pie_image_bytes, "asset_pie.png")
-                    .await
-                    .inspect_err(|e| {
-                        error!(
-                            "[command_show_all_asset] Failed to send asset pie image: {:#}",
-                            e
-                        )
-                    })?;
-                
                 /* 이걸 기준으로 봐야함!! */
                 let stock_resp_details: Vec<StockRespDetail> = stock_list
                     .iter()
@@ -668,7 +638,39 @@ impl<
                             e
                         )
                     })?;
-                
+
+                if self.is_text_only_requested() {
+                    return Ok(());
+                }
+
+                let total_asset_amount_krw: Decimal = totals.krw + (totals.usd * usd_to_krw);
+                let assets: Assets = Assets::new(total_asset_amount_krw, asset_map);
+                let mut is_graph_failed: bool = false;
+
+                match self
+                    .graph_api_service
+                    .find_python_matplot_asset_pie(assets)
+                    .await
+                {
+                    Ok(pie_image_bytes) => self
+                        .tele_bot_service
+                        .input_photo_from_bytes(pie_image_bytes, "asset_pie.png")
+                        .await
+                        .inspect_err(|e| {
+                            error!(
+                                "[command_show_all_asset] Failed to send asset pie image: {:#}",
+                                e
+                            )
+                        })?,
+                    Err(e) => {
+                        error!(
+                            "[command_show_all_asset] Failed to get asset pie image: {:#}",
+                            e
+                        );
+                        is_graph_failed = true;
+                    }
+                }
+
                 let etc_threshold: Decimal = Decimal::new(3, 2);
                 let mut stock_pie_data_dtos: Vec<StockPieDataDto> = Vec::new();
                 let mut etc_amount_krw: Decimal = Decimal::ZERO;
@@ -690,32 +692,44 @@ impl<
                         stock_amount_krw: etc_amount_krw,
                     });
                 }
-                
+
                 let stock_pie_data: StockPieData = StockPieData::new(
                     stock_pie_data_dtos.iter().map(|s| s.stock_alias().to_string()).collect(),
                     stock_pie_data_dtos.iter().map(|s| *s.stock_amount_krw()).collect(),
                     total_stock_amount_krw,
                 );
 
-                let stock_pie_bytes: Vec<u8> = self
+                match self
                     .graph_api_service
                     .find_python_matplot_stock_pie(stock_pie_data)
                     .await
-                    .inspect_err(|e| {
-                        error!("[command_show_all_asset] Failed to get stock pie image: {:#}", e)
-                    })?;
+                {
+                    Ok(stock_pie_bytes) => self
+                        .tele_bot_service
+                        .input_photo_from_bytes(stock_pie_bytes, "stock_pie.png")
+                        .await
+                        .inspect_err(|e| {
+                            error!(
+                                "[command_show_all_asset] Failed to send stock pie image: {:#}",
+                                e
+                            )
+                        })?,
+                    Err(e) => {
+                        error!(
+                            "[command_show_all_asset] Failed to get stock pie image: {:#}",
+                            e
+                        );
+                        is_graph_failed = true;
+                    }
+                }
 
-                self.tele_bot_service
-                    .input_photo_from_bytes(stock_pie_bytes, "stock_pie.png")
-                    .await
-                    .inspect_err(|e| {
-                        error!("[command_show_all_asset] Failed to send stock pie image: {:#}", e)
-                    })?;
+                if is_graph_failed {
+                    self.tele_bot_service
+                        .input_message_confirm(GRAPH_FAILURE_NOTICE)
+                        .await?;
+                }
             }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm("Invalid date format. Please use format `my`")
-                    .await?;
+            None => {
                 return Err(anyhow!(
                     "[command_show_all_asset] Invalid parameter: {:?}",
                     self.tele_bot_service.get_input_text()
@@ -725,4 +739,119 @@ impl<
 
         Ok(())
     }
+
+    /// Shows how the caller's net worth changed month by month (`mh [months] [-t]`).
+    ///
+    /// Reads the daily asset snapshots of the last `months` KST calendar months (default 12,
+    /// `1`~`60`) and sends the latest total with its month-over-month change, biggest movers
+    /// and growth rates, followed by a stacked area chart per asset class. With `-t`, or when
+    /// the chart cannot be rendered, a text table of every month is sent instead of the chart.
+    /// USD snapshot rows are converted at the current exchange rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `telegram_token` - Telegram bot token used to resolve the caller
+    /// * `telegram_user_id` - Telegram user id used to resolve the caller
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` after the summary and the chart or table are sent to Telegram,
+    /// or after an invalid request is reported to the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller is unauthorised, or any MySQL or Telegram step fails.
+    pub(super) async fn command_net_worth_history(
+        &self,
+        telegram_token: &str,
+        telegram_user_id: &str,
+    ) -> anyhow::Result<()> {
+        let Some(months) = self
+            .parse_command_args(CommandId::NetWorthHistory, |command_line| {
+                match command_line.args() {
+                    [] => Ok(DEFAULT_NET_WORTH_MONTHS),
+                    [months] => {
+                        let months: i32 = parse_number(months.text(), "months")?;
+                        if !(1..=MAX_NET_WORTH_MONTHS).contains(&months) {
+                            return Err(CommandArgError::InvalidArg {
+                                arg_nm: "months",
+                                value: months.to_string(),
+                            });
+                        }
+                        Ok(months)
+                    }
+                    [_, unexpected_args @ ..] => Err(CommandArgError::UnexpectedArgs(
+                        unexpected_args
+                            .iter()
+                            .map(|arg| arg.text().to_string())
+                            .collect(),
+                    )),
+                }
+            })
+            .await?
+        else {
+            return Ok(());
+        };
+
+        let user_seq: i64 = self
+            .resolve_user_seq(telegram_token, telegram_user_id)
+            .await?;
+
+        /* Snapshot days are KST calendar dates tagged as UTC, so the bound uses the same form. */
+        let current_month_first: DateTime<Utc> = find_current_kor_naivedate()
+            .with_day(1)
+            .ok_or_else(|| anyhow!("[command_net_worth_history] Invalid current month"))?;
+        let aggregated_from: DateTime<Utc> =
+            find_add_month_from_naivedate(current_month_first, 1 - months)?;
+
+        let snapshots: Vec<UserCurrentAssetSnapshot> = self
+            .mysql_query_service
+            .find_user_current_asset_snapshots(user_seq, aggregated_from)
+            .await?;
+
+        let usd_to_krw: Decimal =
+            fetch_exchange_rate(self.mysql_query_service.as_ref(), "USD", "KRW").await?;
+
+        let net_worth_history: NetWorthHistory = self
+            .process_service
+            .find_net_worth_history(&snapshots, usd_to_krw);
+
+        self.tele_bot_service
+            .input_message_confirm(&net_worth_history.to_summary_string())
+            .await?;
+
+        if net_worth_history.points().is_empty() {
+            return Ok(());
+        }
+
+        if self.is_text_only_requested() {
+            self.tele_bot_service
+                .input_message_confirm(&net_worth_history.to_table_string())
+                .await?;
+            return Ok(());
+        }
+
+        match self
+            .graph_api_service
+            .find_python_matplot_net_worth_history(&net_worth_history)
+            .await
+        {
+            Ok(graph_bytes) => {
+                self.tele_bot_service
+                    .input_photo_from_bytes(graph_bytes, "net_worth_history.png")
+                    .await?
+            }
+            Err(e) => {
+                warn!(
+                    "[command_net_worth_history] Graph rendering failed, sending the table: {:#}",
+                    e
+                );
+                self.tele_bot_service
+                    .input_message_confirm(&net_worth_history.to_table_string())
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
 }
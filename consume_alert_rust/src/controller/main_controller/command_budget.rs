@@ -0,0 +1,410 @@
+use crate::common::*;
+
+use crate::service_traits::{
+    cache_service::*, consume_classifier::*, elastic_query_service::*, graph_api_service::*,
+    mysql_query_service::*, process_service::*, redis_service::*, telebot_service::*,
+};
+
+use crate::models::{consume_budget::*, spent_detail::*};
+
+use crate::configuration::command_registry::*;
+use crate::enums::{budget_scope::*, command_arg_error::*, command_id::*};
+
+use crate::utils_modules::{command_tokenizer::*, time_utils::*};
+
+use crate::AppConfig;
+
+use super::MainController;
+
+impl<
+        G: GraphApiService,
+        E: ElasticQueryService,
+        M: MysqlQueryService,
+        T: TelebotService,
+        P: ProcessService,
+        R: RedisService,
+        C: CacheService,
+        CL: ConsumeClassifier,
+    > MainController<G, E, M, T, P, R, C, CL>
+{
+    /// Registers, removes, or lists salary-period budgets (`cb [g] [type:]amount`).
+    ///
+    /// Without arguments, lists every budget of the caller's room and aggregation group with
+    /// its usage in the current salary period. With an amount, stores the overall budget, or
+    /// the budget of one `consume_keyword_type` when given as `type:amount`. A leading `g`
+    /// applies the budget to the caller's aggregation group instead of the room.
+    /// An amount of `0` removes the budget.
+    ///
+    /// # Arguments
+    ///
+    /// * `telegram_token` - Telegram bot token used to resolve the caller
+    /// * `telegram_user_id` - Telegram user id used to resolve the caller, room and group
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` after the budget change or the budget list is sent to Telegram,
+    /// or after an invalid request is reported to the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller is unauthorised, or any MySQL or Telegram step fails.
+    pub(super) async fn command_consumption_budget(
+        &self,
+        telegram_token: &str,
+        telegram_user_id: &str,
+    ) -> anyhow::Result<()> {
+        let Some(budget_arg) = self
+            .parse_command_args(CommandId::ConsumptionBudget, |command_line| {
+                let (budget_scope, budget_args) = match command_line.args() {
+                    [] => return Ok(None),
+                    [scope, budget_args @ ..] if scope.text() == "g" => {
+                        (BudgetScope::Group, budget_args)
+                    }
+                    budget_args => (BudgetScope::Room, budget_args),
+                };
+
+                let budget: &CommandToken = match budget_args {
+                    [budget] => budget,
+                    [] => return Err(CommandArgError::MissingArg("[type:]amount")),
+                    [_, unexpected_args @ ..] => {
+                        return Err(CommandArgError::UnexpectedArgs(
+                            unexpected_args
+                                .iter()
+                                .map(|arg| arg.text().to_string())
+                                .collect(),
+                        ))
+                    }
+                };
+
+                let (consume_keyword_type, budget_money) = match budget.key_value() {
+                    Some((consume_keyword_type, budget_money)) => {
+                        (Some(consume_keyword_type.trim().to_string()), budget_money)
+                    }
+                    None => (None, budget.text()),
+                };
+
+                let budget_money: i64 = parse_number(budget_money, "amount")?;
+                if budget_money < 0 {
+                    return Err(CommandArgError::InvalidArg {
+                        arg_nm: "amount",
+                        value: budget_money.to_string(),
+                    });
+                }
+
+                Ok(Some((budget_scope, consume_keyword_type, budget_money)))
+            })
+            .await?
+        else {
+            return Ok(());
+        };
+
+        let user_seq: i64 = self
+            .resolve_user_seq(telegram_token, telegram_user_id)
+            .await?;
+
+        let Some((budget_scope, consume_keyword_type, budget_money)) = budget_arg else {
+            return self
+                .show_consumption_budgets(user_seq, telegram_token, telegram_user_id)
+                .await;
+        };
+        let consume_keyword_type: Option<&str> = consume_keyword_type.as_deref();
+
+        let consume_keyword_type_id: i64 = match consume_keyword_type {
+            None => OVERALL_BUDGET_TYPE_ID,
+            Some(consume_keyword_type) => match self
+                .mysql_query_service
+                .find_consume_keyword_type_id(consume_keyword_type)
+                .await?
+            {
+                Some(consume_keyword_type_id) => consume_keyword_type_id,
+                None => {
+                    self.tele_bot_service
+                        .input_message_confirm(&format!(
+                            "Unknown consumption type: {}",
+                            consume_keyword_type
+                        ))
+                        .await?;
+                    return Ok(());
+                }
+            },
+        };
+
+        let scope_seq: i64 = match budget_scope {
+            BudgetScope::Room => {
+                self.resolve_telegram_room_seq(user_seq, telegram_token, telegram_user_id)
+                    .await?
+            }
+            BudgetScope::Group => {
+                self.resolve_telegram_group_seq(user_seq, telegram_token, telegram_user_id)
+                    .await?
+            }
+        };
+
+        let consume_keyword_type_nm: &str = consume_keyword_type.unwrap_or(OVERALL_BUDGET_TYPE_NM);
+
+        if budget_money == 0 {
+            self.mysql_query_service
+                .delete_consume_budget_with_transaction(
+                    budget_scope,
+                    scope_seq,
+                    consume_keyword_type_id,
+                )
+                .await
+                .inspect_err(|e| {
+                    error!("[main_controller::command_consumption_budget] Failed to delete budget: {:#}", e);
+                })?;
+
+            self.tele_bot_service
+                .input_message_confirm(&format!(
+                    "[예산 삭제]\n소비타입: \"{}\"",
+                    consume_keyword_type_nm
+                ))
+                .await?;
+
+            return Ok(());
+        }
+
+        self.mysql_query_service
+            .upsert_consume_budget_with_transaction(&ConsumeBudget::new(
+                user_seq,
+                budget_scope,
+                scope_seq,
+                consume_keyword_type_id,
+                budget_money,
+            ))
+            .await
+            .inspect_err(|e| {
+                error!(
+                    "[main_controller::command_consumption_budget] Failed to store budget: {:#}",
+                    e
+                );
+            })?;
+
+        self.tele_bot_service
+            .input_message_confirm(&format!(
+                "[예산 등록]\n소비타입: \"{}\"\n예산: \"{}원\"",
+                consume_keyword_type_nm,
+                budget_money.to_formatted_string(&Locale::en)
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Sends the budgets of the caller's room and group with their usage in the current salary period.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_seq` - Internal user sequence of the caller
+    /// * `telegram_token` - Telegram bot token used to resolve the room and group
+    /// * `telegram_user_id` - Telegram user id used to resolve the room and group
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller has no room, or any MySQL or Telegram step fails.
+    async fn show_consumption_budgets(
+        &self,
+        user_seq: i64,
+        telegram_token: &str,
+        telegram_user_id: &str,
+    ) -> anyhow::Result<()> {
+        let (period_start, period_end) = self
+            .resolve_salary_period(user_seq, find_current_kor_naivedate())
+            .await?;
+
+        let mut sections: Vec<String> = Vec::new();
+
+        for (budget_scope, scope_seq) in self
+            .find_budget_scopes(user_seq, telegram_token, telegram_user_id)
+            .await?
+        {
+            let budget_usages: Vec<BudgetUsage> = self
+                .find_budget_usages(budget_scope, scope_seq, period_start, period_end)
+                .await?;
+
+            if budget_usages.is_empty() {
+                continue;
+            }
+
+            let usage_lines: Vec<String> = budget_usages
+                .iter()
+                .map(BudgetUsage::to_telegram_string)
+                .collect();
+
+            sections.push(format!(
+                "[{}]\n{}",
+                budget_scope.scope_nm(),
+                usage_lines.join("\n")
+            ));
+        }
+
+        let telegram_message: String = if sections.is_empty() {
+            format!(
+                "No budgets registered.\n{}",
+                command_spec(CommandId::ConsumptionBudget).examples_line()
+            )
+        } else {
+            format!(
+                "[예산 현황] {} ~ {}\n{}",
+                period_start.format("%Y-%m-%d"),
+                (period_end - chrono::Duration::days(1)).format("%Y-%m-%d"),
+                sections.join("\n\n")
+            )
+        };
+
+        self.tele_bot_service
+            .input_message_confirm(&telegram_message)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Warns on Telegram when a newly recorded spending pushes a budget across an alert threshold.
+    ///
+    /// Only spending inside the current salary period is checked. Room budgets and, when the
+    /// room belongs to an aggregation group, group budgets are evaluated; overall budgets and
+    /// the budget of the spending's own category are compared before and after the spending
+    /// against `budget_alert_thresholds`.
+    ///
+    /// # Arguments
+    ///
+    /// * `spent_detail` - The spending that has just been stored
+    /// * `telegram_token` - Telegram bot token used to resolve the group
+    /// * `telegram_user_id` - Telegram user id used to resolve the group
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the period cannot be computed, or any MySQL or Telegram step fails.
+    pub(super) async fn notify_budget_thresholds(
+        &self,
+        spent_detail: &SpentDetail,
+        telegram_token: &str,
+        telegram_user_id: &str,
+    ) -> anyhow::Result<()> {
+        let (period_start, period_end) = self
+            .resolve_salary_period(*spent_detail.user_seq(), find_current_kor_naivedate())
+            .await?;
+
+        let spent_date: DateTime<Utc> = spent_detail
+            .spent_at()
+            .with_timezone(&Seoul)
+            .date_naive()
+            .and_time(NaiveTime::MIN)
+            .and_utc();
+
+        if spent_date < period_start || spent_date >= period_end {
+            return Ok(());
+        }
+
+        let app_config: &AppConfig = AppConfig::get_global();
+
+        for (budget_scope, scope_seq) in self
+            .find_budget_scopes(*spent_detail.user_seq(), telegram_token, telegram_user_id)
+            .await?
+        {
+            let budget_usages: Vec<BudgetUsage> = self
+                .find_budget_usages(budget_scope, scope_seq, period_start, period_end)
+                .await?;
+
+            for budget_usage in budget_usages.iter().filter(|budget_usage| {
+                *budget_usage.consume_keyword_type_id() == OVERALL_BUDGET_TYPE_ID
+                    || budget_usage.consume_keyword_type_id()
+                        == spent_detail.consume_keyword_type_id()
+            }) {
+                let spent_after: i64 = *budget_usage.spent_money();
+                let spent_before: i64 = spent_after - spent_detail.spent_money();
+
+                if let Some(threshold) = self.process_service.find_crossed_budget_threshold(
+                    *budget_usage.budget_money(),
+                    spent_before,
+                    spent_after,
+                    app_config.budget_alert_thresholds(),
+                ) {
+                    self.tele_bot_service
+                        .input_message_confirm(
+                            &budget_usage.to_telegram_string_to_alert(threshold, period_end),
+                        )
+                        .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the budget scopes of the caller: the room, followed by its group when it has one.
+    async fn find_budget_scopes(
+        &self,
+        user_seq: i64,
+        telegram_token: &str,
+        telegram_user_id: &str,
+    ) -> anyhow::Result<Vec<(BudgetScope, i64)>> {
+        let room_seq: i64 = self
+            .resolve_telegram_room_seq(user_seq, telegram_token, telegram_user_id)
+            .await?;
+
+        let mut budget_scopes: Vec<(BudgetScope, i64)> = vec![(BudgetScope::Room, room_seq)];
+
+        if let Some(group_seq) = self
+            .cache_service
+            .find_telegram_group_seq(user_seq, telegram_token, telegram_user_id)
+            .await?
+        {
+            budget_scopes.push((BudgetScope::Group, group_seq));
+        }
+
+        Ok(budget_scopes)
+    }
+
+    /// Loads the budgets of one scope together with their usage between `period_start` and `period_end`.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget_scope` - Room or group scope
+    /// * `scope_seq` - `room_seq` or `agg_group_seq` of the scope
+    /// * `period_start` - Inclusive period start (KST calendar date tagged as UTC)
+    /// * `period_end` - Exclusive period end (KST calendar date tagged as UTC)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a period boundary cannot be converted or a MySQL query fails.
+    async fn find_budget_usages(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<BudgetUsage>> {
+        let consume_budgets: Vec<ConsumeBudgetWithInfo> = self
+            .mysql_query_service
+            .find_consume_budgets(budget_scope, scope_seq)
+            .await?;
+
+        if consume_budgets.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        /* SPENT_DETAIL.spent_at is stored in UTC, so KST midnights are converted first. */
+        let spent_at_from: DateTime<Utc> = find_kst_datetime(
+            period_start.year(),
+            period_start.month(),
+            period_start.day(),
+        )?
+        .with_timezone(&Utc);
+        let spent_at_to: DateTime<Utc> =
+            find_kst_datetime(period_end.year(), period_end.month(), period_end.day())?
+                .with_timezone(&Utc);
+
+        let spent_money_by_types: Vec<SpentMoneyByType> = self
+            .mysql_query_service
+            .find_spent_money_by_type(budget_scope, scope_seq, spent_at_from, spent_at_to)
+            .await?;
+
+        Ok(consume_budgets
+            .iter()
+            .map(|consume_budget| {
+                consume_budget.to_budget_usage(budget_scope, &spent_money_by_types)
+            })
+            .collect())
+    }
+}
@@ -1,16 +1,22 @@
 use crate::common::*;
 
 use crate::service_traits::{
-    cache_service::*, elastic_query_service::*, graph_api_service::*, mysql_query_service::*,
-    process_service::*, producer_service::*, redis_service::*, telebot_service::*,
+    cache_service::*, consume_classifier::*, elastic_query_service::*, graph_api_service::*,
+    mysql_query_service::*, process_service::*, redis_service::*, telebot_service::*,
 };
 
 use crate::models::{
-    consume_index_prodt_type::*, spent_detail::*, spent_detail_to_kafka::*,
-    spent_detail_with_info::*, user_payment_methods::*,
+    consume_index_prodt_type::*, installment_balance::*, spent_detail::*,
+    spent_detail_by_card_approval::*, spent_detail_by_installment::*,
+    spent_detail_with_info::*, spent_group_info::*, user_payment_methods::*,
 };
 
-use crate::utils_modules::io_utils::*;
+use crate::enums::{
+    card_approval_type::*, command_arg_error::*, command_id::*, spent_at_status::*,
+    spent_detail_edit::*,
+};
+
+use crate::utils_modules::{command_tokenizer::*, numeric_utils::*};
 
 use crate::views::spent_detail_view::SpentDetailView;
 
@@ -24,17 +30,18 @@ impl<
         M: MysqlQueryService,
         T: TelebotService,
         P: ProcessService,
-        KP: ProducerService,
         R: RedisService,
         C: CacheService,
-    > MainController<G, E, M, T, P, KP, R, C>
+        CL: ConsumeClassifier,
+    > MainController<G, E, M, T, P, R, C, CL>
 {
     /// Records a manual consumption entry from the `c` command (`c item:amount`).
     ///
     /// Validates the command format and amount, resolves the caller and room,
     /// classifies the spending item, loads the user's default payment method,
-    /// persists the entry to MySQL, publishes an insert event to Kafka,
+    /// persists the entry to MySQL together with its outbox event for Kafka,
     /// and sends a formatted confirmation message to Telegram.
+    /// Budgets whose alert thresholds are crossed by the entry are reported afterwards.
     ///
     /// # Arguments
     ///
@@ -49,25 +56,25 @@ impl<
     ///
     /// Returns an error if the parameter format is invalid, the amount is not numeric,
     /// the caller is unauthorised, no default payment method exists,
-    /// or any downstream lookup, persistence, Kafka, or Telegram step fails.
+    /// or any downstream lookup, persistence, or Telegram step fails.
     pub(super) async fn command_consumption(
         &self,
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(":");
-
-        if args.len() != 2 {
-            self.tele_bot_service
-                .input_message_confirm(
-                    "There is a problem with the parameter you entered. Please check again.\nEX) c snack:15000",
-                )
-                .await?;
+        let Some((spent_name, spent_money)) = self
+            .parse_command_args(CommandId::Consumption, |command_line| {
+                let key_value: KeyValueArg = command_line.key_value_arg()?;
+                let amount: i64 = key_value.parse_value("amount")?;
+                Ok((key_value.key, amount))
+            })
+            .await?
+        else {
             return Err(anyhow!(
                 "[main_controller::command_consumption] Invalid parameter format: {}",
                 self.tele_bot_service.get_input_text()
             ));
-        }
+        };
 
         let user_seq: i64 = self
             .resolve_user_seq(telegram_token, telegram_user_id)
@@ -77,24 +84,8 @@ impl<
             .resolve_telegram_room_seq(user_seq, telegram_token, telegram_user_id)
             .await?;
 
-        let spent_name: String = args[0].clone();
-        let spent_money: i64 = match find_parsed_value_from_vector(&args, 1) {
-            Ok(cash) => cash,
-            Err(e) => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "The second parameter must be numeric.\nEX) c snack:15000",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[main_controller::command_consumption] Non-numeric cash parameter: {:#}",
-                    e
-                ));
-            }
-        };
-
         let spent_type: ConsumingIndexProdtType = self
-            .resolve_spend_type(&spent_name)
+            .resolve_spend_type(&spent_name, user_seq)
             .await
             .inspect_err(|e| {
                 error!(
@@ -155,31 +146,8 @@ impl<
                 );
             })?;
 
-        let utc_now: DateTime<Utc> = Utc::now();
-
-        let produce_payload: SpentDetailToKafka =
-            SpentDetailToKafka::new(spent_idx, String::from("I"), utc_now);
-
-        let partition_key: String = spent_idx.to_string();
-        let app_config: &AppConfig = AppConfig::get_global();
-        let produce_topic: &str = &app_config.produce_topic;
-
-        self.producer_service
-            .input_object_to_topic(
-                produce_topic,
-                &produce_payload,
-                Some(partition_key.as_str()),
-            )
-            .await
-            .inspect_err(|e| {
-                error!(
-                    "[main_controller::command_consumption] Failed to produce Kafka message: {:#}",
-                    e
-                );
-            })?;
-
         self.tele_bot_service
-            .input_message_confirm(&spent_detail_view.to_telegram_string())
+            .input_message_confirm(&spent_detail_view.to_telegram_string_with_short_id(spent_idx))
             .await
             .inspect_err(|e| {
                 error!(
@@ -188,6 +156,16 @@ impl<
                 );
             })?;
 
+        if let Err(e) = self
+            .notify_budget_thresholds(&spent_detail, telegram_token, telegram_user_id)
+            .await
+        {
+            error!(
+                "[main_controller::command_consumption] Failed to check budgets: {:#}",
+                e
+            );
+        }
+
         Ok(())
     }
 
@@ -195,8 +173,13 @@ impl<
     ///
     /// Removes bracketed metadata fragments such as `[...]`, drops blank lines, resolves the
     /// caller and room, lets `process_service` infer the structured spending data,
-    /// classifies the primary spending name, persists the entry to MySQL,
-    /// publishes an insert event to Kafka, and sends a confirmation to Telegram.
+    /// classifies the primary spending name, persists the entry to MySQL together with its
+    /// outbox event for Kafka, and sends a confirmation to Telegram.
+    /// Cancellation notifications cancel the matching original payment instead; when no
+    /// original can be found the refund is recorded as negative spending.
+    /// The year of the payment is inferred from the Telegram message date; alerts dated in the
+    /// future are rejected and unusual payment times are flagged to the user.
+    /// Budgets whose alert thresholds are crossed by the payment are reported afterwards.
     /// Returns early with `Ok(())` when no usable lines remain after preprocessing.
     ///
     /// # Arguments
@@ -212,7 +195,7 @@ impl<
     ///
     /// Returns an error if preprocessing fails, the caller is unauthorised,
     /// payment methods cannot be loaded, the text cannot be converted into a valid entry,
-    /// or any downstream persistence, Kafka, or Telegram step fails.
+    /// the payment time lies in the future, or any downstream persistence or Telegram step fails.
     pub async fn command_consumption_auto(
         &self,
         telegram_token: &str,
@@ -220,26 +203,12 @@ impl<
     ) -> anyhow::Result<()> {
         let args: String = self.tele_bot_service.get_input_text();
 
-        // let bracket_re: Regex = Regex::new(r"\[.*?\]\n?").map_err(|e| {
-        //     anyhow!(
-        //         "[main_controller::command_consumption_auto] Bad regex: {:?}",
-        //         e
-        //     )
-        // })?;
-
-        let bracket_re: Regex = Regex::new(r"\.*?\\n?").map_err(|e| {
-            anyhow!(
-                "[main_controller::command_consumption_auto] Bad regex: {:?}",
-                e
-            )
-        })?;
-
-        let lines: Vec<String> = bracket_re
-            .replace_all(&args, "")
-            .split('\n')
-            .map(|s| s.replace("[", "").replace("]", "").trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
+        let lines: Vec<String> = self
+            .process_service
+            .to_consume_notification_lines(&args)
+            .inspect_err(|e| {
+                error!("[main_controller::command_consumption_auto] {:#}", e);
+            })?;
 
         if lines.is_empty() {
             return Ok(());
@@ -261,17 +230,51 @@ impl<
                 error!("[main_controller::command_consumption_auto] Failed to get user payment methods: {:#}", e);
             })?;
 
-        let mut spent_detail: SpentDetail = self
+        let spent_detail_by_approval: SpentDetailByCardApproval = self
+            .process_service
+            .modify_by_consume_filter(
+                &lines,
+                user_seq,
+                room_seq,
+                user_payment_methods,
+                self.tele_bot_service.get_message_date(),
+            )
+            .inspect_err(|e| {
+                error!("[main_controller::command_consumption_auto] {:#}", e);
+            })?;
+
+        let app_config: &AppConfig = AppConfig::get_global();
+
+        let spent_at_status: SpentAtStatus = self
             .process_service
-            .modify_by_consume_filter(&lines, user_seq, room_seq, user_payment_methods)
+            .find_spent_at_status(
+                *spent_detail_by_approval.spent_detail().spent_at(),
+                self.tele_bot_service.get_message_date(),
+                *app_config.spent_at_future_tolerance_mins(),
+                *app_config.spent_at_stale_days(),
+            )
             .inspect_err(|e| {
                 error!("[main_controller::command_consumption_auto] {:#}", e);
             })?;
 
+        let is_cancellation: bool =
+            *spent_detail_by_approval.approval_type() == CardApprovalType::Cancellation;
+
+        let mut spent_detail: SpentDetail = spent_detail_by_approval.spent_detail().clone();
+
+        if is_cancellation {
+            if self.apply_consumption_cancellation(&spent_detail).await? {
+                return Ok(());
+            }
+
+            /* No original payment could be matched: record the refund as negative spending. */
+            spent_detail.set_spent_money(-spent_detail.spent_money());
+        }
+
         let primary_name: String = spent_detail.spent_name().to_string();
 
         let spent_type: ConsumingIndexProdtType = self
-            .resolve_spend_type(&primary_name)
+            .resolve_spend_type(&primary_name, user_seq)
             .await
             .inspect_err(|e| {
                 error!("[main_controller::command_consumption_auto] Failed to resolve spend type: {:#}", e);
@@ -288,6 +291,30 @@ impl<
                 );
             })?;
 
+        if let Some(warning) =
+            spent_detail_view.to_telegram_string_to_spent_at_warning(spent_at_status)
+        {
+            self.tele_bot_service
+                .input_message_confirm(&warning)
+                .await
+                .inspect_err(|e| {
+                    error!("[main_controller::command_consumption_auto] Failed to send Telegram message: {:#}", e);
+                })?;
+        }
+
+        let installment: i64 = *spent_detail_by_approval.installment();
+
+        if !is_cancellation && installment > 0 {
+            return self
+                .input_installment_consumption(
+                    SpentDetailByInstallment::new(installment, spent_detail),
+                    &spent_detail_view,
+                    telegram_token,
+                    telegram_user_id,
+                )
+                .await;
+        }
+
         let spent_idx: i64 = self
             .mysql_query_service
             .input_prodt_detail_with_transaction(&spent_detail)
@@ -299,38 +326,359 @@ impl<
                 );
             })?;
 
-        let utc_now: DateTime<Utc> = Utc::now();
+        let telegram_message: String = if is_cancellation {
+            spent_detail_view.to_telegram_string_to_refund(spent_idx)
+        } else {
+            spent_detail_view.to_telegram_string_with_short_id(spent_idx)
+        };
 
-        let produce_payload: SpentDetailToKafka =
-            SpentDetailToKafka::new(spent_idx, String::from("I"), utc_now);
+        self.tele_bot_service
+            .input_message_confirm(&telegram_message)
+            .await
+            .inspect_err(|e| {
+                error!("[main_controller::command_consumption_auto] Failed to send Telegram message: {:#}", e);
+            })?;
 
-        let partition_key: String = spent_idx.to_string();
+        if let Err(e) = self
+            .notify_budget_thresholds(&spent_detail, telegram_token, telegram_user_id)
+            .await
+        {
+            error!(
+                "[main_controller::command_consumption_auto] Failed to check budgets: {:#}",
+                e
+            );
+        }
 
-        let app_config: &AppConfig = AppConfig::get_global();
-        let produce_topic: &str = app_config.produce_topic();
+        Ok(())
+    }
 
-        self.producer_service
-            .input_object_to_topic(produce_topic, &produce_payload, Some(partition_key.as_str()))
+    /// Persists an installment purchase as one `SPENT_DETAIL` row per month.
+    ///
+    /// Lets `process_service` split the purchase into monthly rows, inserts a `SPENT_GROUP_INFO`
+    /// row with status `INSTALLMENT` and the monthly rows linked by its `spent_group_id` in a
+    /// single transaction together with an outbox event for every row, and confirms on Telegram
+    /// with the short id of every monthly row.
+    ///
+    /// # Arguments
+    ///
+    /// * `spent_detail_by_installment` - The classified purchase and its number of months
+    /// * `spent_detail_view` - View of the whole purchase used for the confirmation message
+    /// * `telegram_token` - Telegram bot token used to resolve the group for budget alerts
+    /// * `telegram_user_id` - Telegram user id used to resolve the group for budget alerts
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` after all monthly rows are saved and the confirmation is sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if splitting, any MySQL insert, or the Telegram step fails.
+    async fn input_installment_consumption(
+        &self,
+        spent_detail_by_installment: SpentDetailByInstallment,
+        spent_detail_view: &SpentDetailView,
+        telegram_token: &str,
+        telegram_user_id: &str,
+    ) -> anyhow::Result<()> {
+        let spent_detail: &SpentDetail = spent_detail_by_installment.spent_detail();
+        let installment: i64 = *spent_detail_by_installment.installment();
+
+        let monthly_details: Vec<SpentDetail> = self
+            .process_service
+            .find_spent_detail_installment_process(&spent_detail_by_installment)
+            .inspect_err(|e| {
+                error!("[main_controller::input_installment_consumption] Failed to split installment: {:#}", e);
+            })?;
+
+        let spent_group_info: SpentGroupInfo = SpentGroupInfo::new(
+            format!(
+                "installment-{}-{}-{}",
+                spent_detail.user_seq(),
+                spent_detail.payment_method_id(),
+                Utc::now().timestamp_micros()
+            ),
+            INSTALLMENT_GROUP_STATUS.to_string(),
+        );
+
+        let spent_idxs: Vec<i64> = self
+            .mysql_query_service
+            .input_spent_group_details_with_transaction(&spent_group_info, &monthly_details)
             .await
             .inspect_err(|e| {
-                error!("[main_controller::command_consumption_auto] Failed to produce Kafka message: {:#}", e);
+                error!("[main_controller::input_installment_consumption] Failed to insert to MySQL: {:#}", e);
             })?;
 
+        let monthly_money: i64 = monthly_details
+            .last()
+            .map(|detail| *detail.spent_money())
+            .unwrap_or_default();
+
         self.tele_bot_service
-            .input_message_confirm(&spent_detail_view.to_telegram_string())
+            .input_message_confirm(
+                &spent_detail_view.to_telegram_string_to_installment(
+                    installment,
+                    monthly_money,
+                    &spent_idxs,
+                ),
+            )
             .await
             .inspect_err(|e| {
-                error!("[main_controller::command_consumption_auto] Failed to send Telegram message: {:#}", e);
+                error!("[main_controller::input_installment_consumption] Failed to send Telegram message: {:#}", e);
+            })?;
+
+        /* Only the monthly row that falls in the current salary period counts against budgets. */
+        for detail in &monthly_details {
+            if let Err(e) = self
+                .notify_budget_thresholds(detail, telegram_token, telegram_user_id)
+                .await
+            {
+                error!("[main_controller::input_installment_consumption] Failed to check budgets: {:#}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists the remaining installment balance per card for the caller (`ci`).
+    ///
+    /// Loads every future monthly installment row of the caller, aggregates them per card and
+    /// per plan through `process_service`, and sends one summary message to Telegram.
+    ///
+    /// # Arguments
+    ///
+    /// * `telegram_token` - Telegram bot token used to resolve the caller
+    /// * `telegram_user_id` - Telegram user id used to resolve the caller
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` after the summary is sent, or after an early no-op exit when nothing remains.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameter format is invalid, the caller is unauthorised,
+    /// or the MySQL lookup or Telegram step fails.
+    pub(super) async fn command_installment_balance(
+        &self,
+        telegram_token: &str,
+        telegram_user_id: &str,
+    ) -> anyhow::Result<()> {
+        if self
+            .parse_command_args(CommandId::InstallmentBalance, CommandLine::no_args)
+            .await?
+            .is_none()
+        {
+            return Ok(());
+        }
+
+        let user_seq: i64 = self
+            .resolve_user_seq(telegram_token, telegram_user_id)
+            .await?;
+
+        let installment_remains: Vec<InstallmentRemain> = self
+            .mysql_query_service
+            .find_remaining_installments(user_seq, Utc::now())
+            .await
+            .inspect_err(|e| {
+                error!("[main_controller::command_installment_balance] Failed to find installments: {:#}", e);
+            })?;
+
+        let installment_balances: Vec<InstallmentBalance> = self
+            .process_service
+            .find_installment_balance_by_card(&installment_remains);
+
+        if installment_balances.is_empty() {
+            self.tele_bot_service
+                .input_message_confirm("No remaining installments.")
+                .await?;
+            return Ok(());
+        }
+
+        let total_money: i64 = installment_balances
+            .iter()
+            .map(|balance| *balance.remaining_money())
+            .sum();
+
+        let card_messages: Vec<String> = installment_balances
+            .iter()
+            .map(|balance| balance.to_telegram_string())
+            .collect();
+
+        self.tele_bot_service
+            .input_message_confirm(&format!(
+                "[남은 할부금 합계: {}원]\n\n{}",
+                total_money.to_formatted_string(&Locale::en),
+                card_messages.join("\n\n")
+            ))
+            .await
+            .inspect_err(|e| {
+                error!("[main_controller::command_installment_balance] Failed to send Telegram message: {:#}", e);
             })?;
 
         Ok(())
     }
 
+    /// Marks the original spending referenced by a cancellation (승인취소) notification as cancelled.
+    ///
+    /// Looks for an active row with the same user, card and amount whose `spent_at` lies within
+    /// `CANCEL_MATCH_WINDOW_DAYS` before the cancellation, lets `process_service` pick the one with
+    /// the same merchant, flags it with `should_index = 0` and records a delete event in the outbox so
+    /// the Elasticsearch totals drop the payment, and notifies Telegram. When no single row matches,
+    /// the cancellation is matched against installment plans instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `cancellation` - Spending parsed from the cancellation notification (positive amount)
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(true)` if an original payment was found and cancelled, `Ok(false)` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the candidate lookup, the MySQL update, or the Telegram step fails.
+    async fn apply_consumption_cancellation(
+        &self,
+        cancellation: &SpentDetail,
+    ) -> anyhow::Result<bool> {
+        let app_config: &AppConfig = AppConfig::get_global();
+
+        let spent_at_to: DateTime<Utc> = cancellation.spent_at().with_timezone(&Utc);
+        let spent_at_from: DateTime<Utc> =
+            spent_at_to - chrono::Duration::days(*app_config.cancel_match_window_days());
+
+        let candidates: Vec<SpentDetailWithInfo> = self
+            .mysql_query_service
+            .find_cancellable_spent_details(
+                *cancellation.user_seq(),
+                *cancellation.payment_method_id(),
+                *cancellation.spent_money(),
+                spent_at_from,
+                spent_at_to,
+            )
+            .await
+            .inspect_err(|e| {
+                error!("[main_controller::apply_consumption_cancellation] Failed to find cancellation candidates: {:#}", e);
+            })?;
+
+        let original: SpentDetailWithInfo = match self
+            .process_service
+            .find_cancellation_target(cancellation, &candidates)
+        {
+            Some(original) => original,
+            None => {
+                return self
+                    .apply_installment_cancellation(cancellation, spent_at_from, spent_at_to)
+                    .await;
+            }
+        };
+
+        let spent_idx: i64 = original.spent_idx;
+
+        self.mysql_query_service
+            .modify_spent_detail_cancelled_with_transaction(spent_idx)
+            .await
+            .inspect_err(|e| {
+                error!("[main_controller::apply_consumption_cancellation] Failed to cancel SPENT_DETAIL-{}: {:#}", spent_idx, e);
+            })?;
+
+        self.tele_bot_service
+            .input_message_confirm(&original.to_spent_detail_view().to_telegram_string_to_cancel())
+            .await
+            .inspect_err(|e| {
+                error!("[main_controller::apply_consumption_cancellation] Failed to send Telegram message: {:#}", e);
+            })?;
+
+        Ok(true)
+    }
+
+    /// Cancels the installment plan referenced by a cancellation (승인취소) notification.
+    ///
+    /// The monthly rows of the card's installment plans are folded back into their purchases
+    /// through `process_service`; a purchase made within the match window with the cancelled
+    /// amount and the same merchant has all of its monthly rows cancelled in one transaction,
+    /// with a delete event in the outbox for every row, and Telegram is notified.
+    ///
+    /// # Arguments
+    ///
+    /// * `cancellation` - Spending parsed from the cancellation notification (positive amount)
+    /// * `spent_at_from` - Earliest purchase time the cancellation may refer to
+    /// * `spent_at_to` - Latest purchase time the cancellation may refer to
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(true)` if an installment plan was found and cancelled, `Ok(false)` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the candidate lookup, the MySQL update, or the Telegram step fails.
+    async fn apply_installment_cancellation(
+        &self,
+        cancellation: &SpentDetail,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let installment_details: Vec<SpentDetailWithInfo> = self
+            .mysql_query_service
+            .find_cancellable_installment_details(
+                *cancellation.user_seq(),
+                *cancellation.payment_method_id(),
+                spent_at_from,
+                spent_at_to,
+            )
+            .await
+            .inspect_err(|e| {
+                error!("[main_controller::apply_installment_cancellation] Failed to find installment candidates: {:#}", e);
+            })?;
+
+        /* Only plans purchased inside the window; later monthly rows of older plans do not count. */
+        let purchases: Vec<SpentDetailWithInfo> = self
+            .process_service
+            .find_installment_purchases(&installment_details)
+            .into_iter()
+            .filter(|purchase| {
+                purchase.spent_at >= spent_at_from.naive_utc()
+                    && purchase.spent_at <= spent_at_to.naive_utc()
+            })
+            .collect();
+
+        let original: SpentDetailWithInfo = match self
+            .process_service
+            .find_cancellation_target(cancellation, &purchases)
+        {
+            Some(original) => original,
+            None => {
+                info!(
+                    "[main_controller::apply_installment_cancellation] No original payment found for cancellation: {:?}",
+                    cancellation
+                );
+                return Ok(false);
+            }
+        };
+
+        let spent_group_id: i64 = original.spent_group_id;
+
+        self.mysql_query_service
+            .modify_spent_group_cancelled_with_transaction(spent_group_id)
+            .await
+            .inspect_err(|e| {
+                error!("[main_controller::apply_installment_cancellation] Failed to cancel SPENT_GROUP_INFO-{}: {:#}", spent_group_id, e);
+            })?;
+
+        self.tele_bot_service
+            .input_message_confirm(&original.to_spent_detail_view().to_telegram_string_to_cancel())
+            .await
+            .inspect_err(|e| {
+                error!("[main_controller::apply_installment_cancellation] Failed to send Telegram message: {:#}", e);
+            })?;
+
+        Ok(true)
+    }
+
     /// Deletes the most recently recorded consumption entry for the caller's room (`cd`).
     ///
     /// Validates that no extra arguments were supplied, resolves the caller and room,
-    /// loads the latest spending record from MySQL, removes it inside a transaction,
-    /// sends a deletion confirmation to Telegram, and publishes a delete event to Kafka.
+    /// loads the latest spending record from MySQL, removes it inside a transaction that also
+    /// records a delete event in the outbox, and sends a deletion confirmation to Telegram.
     /// Returns early with `Ok(())` if the command format is wrong or there is no record to delete.
     /// If the delete transaction itself fails, the error is logged and the function still returns `Ok(())`.
     ///
@@ -346,21 +694,18 @@ impl<
     /// # Errors
     ///
     /// Returns an error if authorisation or latest-record lookup fails,
-    /// or if any Telegram/Kafka notification step fails.
+    /// or if the Telegram notification step fails.
     /// Delete transaction failures are logged and not propagated.
     pub(super) async fn command_delete_recent_consumption(
         &self,
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
-
-        if args.len() != 1 {
-            self.tele_bot_service
-                .input_message_confirm(
-                    "There is a problem with the parameter you entered. Please check again.\nEX) cd",
-                )
-                .await?;
+        if self
+            .parse_command_args(CommandId::DeleteRecentConsumption, CommandLine::no_args)
+            .await?
+            .is_none()
+        {
             return Ok(());
         }
 
@@ -404,29 +749,210 @@ impl<
                 self.tele_bot_service
                     .input_message_confirm(&spent_detail_view.to_telegram_string_to_delete())
                     .await?;
+            }
+            Err(e) => {
+                error!("[command_delete_recent_consumption] Failed delete SPENT_DETAIL information-{}: {:#}", spent_idx, e)
+            }
+        }
 
-                let utc_now: DateTime<Utc> = Utc::now();
+        Ok(())
+    }
+
+    /// Corrects the category, name, amount, or time of a recorded spending (`ce <id> <field> <value>`).
+    ///
+    /// The spending is addressed by the short id shown in its confirmation message. Only active
+    /// spending owned by the caller can be modified; cancelled or otherwise non-indexed rows
+    /// (`should_index = 0`) are reported as not found so they stay out of the index. A category
+    /// correction is also stored as a per-user keyword so the merchant is classified correctly
+    /// next time. The change is written to `SPENT_DETAIL` with an outbox update event so
+    /// Elasticsearch is re-indexed, and the modified spending is sent back to Telegram.
+    ///
+    /// # Arguments
+    ///
+    /// * `telegram_token` - Telegram bot token used to resolve the caller
+    /// * `telegram_user_id` - Telegram user id used to resolve the caller
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` after the spending is modified, or after an invalid request is reported to the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if authorisation, the MySQL lookup or update, or any Telegram step fails.
+    pub(super) async fn command_modify_consumption(
+        &self,
+        telegram_token: &str,
+        telegram_user_id: &str,
+    ) -> anyhow::Result<()> {
+        let Some((spent_idx, field, value)) = self
+            .parse_command_args(CommandId::ModifyConsumption, |command_line| {
+                let args: &[CommandToken] = command_line.args();
+
+                let short_id: &str = args
+                    .first()
+                    .map(CommandToken::text)
+                    .ok_or(CommandArgError::MissingArg("<id>"))?;
+                let spent_idx: i64 =
+                    from_short_id(short_id).ok_or_else(|| CommandArgError::InvalidArg {
+                        arg_nm: "id",
+                        value: short_id.to_string(),
+                    })?;
+                let field: String = args
+                    .get(1)
+                    .map(|field| field.text().to_string())
+                    .ok_or(CommandArgError::MissingArg("<type|name|money|at>"))?;
 
-                let produce_payload: SpentDetailToKafka =
-                    SpentDetailToKafka::new(spent_idx, String::from("D"), utc_now);
+                if args.len() < 3 {
+                    return Err(CommandArgError::MissingArg("<value>"));
+                }
 
-                let partition_key: String = spent_idx.to_string();
+                Ok((spent_idx, field, command_line.joined_args_from(2)))
+            })
+            .await?
+        else {
+            return Ok(());
+        };
 
-                let app_config: &AppConfig = AppConfig::get_global();
-                let produce_topic: &str = app_config.produce_topic();
+        let user_seq: i64 = self
+            .resolve_user_seq(telegram_token, telegram_user_id)
+            .await?;
 
-                self.producer_service
-                    .input_object_to_topic(produce_topic, &produce_payload, Some(partition_key.as_str()))
-                    .await
-                    .inspect_err(|e| {
-                        error!("[main_controller::command_consumption_auto] Failed to produce Kafka message: {:#}", e);
-                    })?;
+        let original_spent_detail: SpentDetailWithInfo = match self
+            .mysql_query_service
+            .find_spent_detail_with_info(spent_idx)
+            .await?
+        {
+            Some(spent_detail) if spent_detail.user_seq == user_seq => spent_detail,
+            _ => {
+                self.tele_bot_service
+                    .input_message_confirm("No expenses found for the given id.")
+                    .await?;
+                return Ok(());
             }
-            Err(e) => {
-                error!("[command_delete_recent_consumption] Failed delete SPENT_DETAIL information-{}: {:#}", spent_idx, e)
+        };
+
+        let spent_detail_edit: SpentDetailEdit =
+            match self.to_spent_detail_edit(&field, &value).await? {
+                Some(spent_detail_edit) => spent_detail_edit,
+                None => {
+                    self.reply_command_arg_error(
+                        CommandId::ModifyConsumption,
+                        &CommandArgError::InvalidArg {
+                            arg_nm: "change",
+                            value: format!("{} {}", field, value),
+                        },
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            };
+
+        self.mysql_query_service
+            .modify_spent_detail_with_transaction(spent_idx, &spent_detail_edit)
+            .await
+            .inspect_err(|e| {
+                error!(
+                    "[main_controller::command_modify_consumption] Failed to update SPENT_DETAIL-{}: {:#}",
+                    spent_idx, e
+                );
+            })?;
+
+        info!(
+            "[command_modify_consumption] spent_idx={} modified: {:?} (user_seq={})",
+            spent_idx, spent_detail_edit, user_seq
+        );
+
+        /* Teach the classifier so the same merchant lands in the corrected category next time. */
+        if let SpentDetailEdit::ConsumeKeywordType {
+            consume_keyword_type_id,
+            consume_keyword_type,
+        } = &spent_detail_edit
+        {
+            if let Err(e) = self
+                .elastic_query_service
+                .input_learned_consume_keyword(
+                    user_seq,
+                    &original_spent_detail.spent_name,
+                    *consume_keyword_type_id,
+                    consume_keyword_type,
+                )
+                .await
+            {
+                error!(
+                    "[main_controller::command_modify_consumption] Failed to learn keyword '{}': {:#}",
+                    original_spent_detail.spent_name, e
+                );
             }
         }
 
+        let modified_spent_detail: SpentDetailWithInfo = self
+            .mysql_query_service
+            .find_spent_detail_with_info(spent_idx)
+            .await?
+            .ok_or_else(|| {
+                anyhow!(
+                    "[main_controller::command_modify_consumption] SPENT_DETAIL-{} disappeared after the update",
+                    spent_idx
+                )
+            })?;
+
+        self.tele_bot_service
+            .input_message_confirm(
+                &modified_spent_detail
+                    .to_spent_detail_view()
+                    .to_telegram_string_to_modify(spent_idx),
+            )
+            .await
+            .inspect_err(|e| {
+                error!("[main_controller::command_modify_consumption] Failed to send Telegram message: {:#}", e);
+            })?;
+
         Ok(())
     }
+
+    /// Converts the `<field> <value>` part of the `ce` command into a `SpentDetailEdit`.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - One of `type`, `name`, `money`, `at`
+    /// * `value` - New value : ex) "식비", "스타벅스", "12,000", "2026.10.18 12:30"
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(None)` when the field is unknown or the value is invalid (e.g. unknown category).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the category lookup fails.
+    async fn to_spent_detail_edit(
+        &self,
+        field: &str,
+        value: &str,
+    ) -> anyhow::Result<Option<SpentDetailEdit>> {
+        let spent_detail_edit: Option<SpentDetailEdit> = match field {
+            "type" => self
+                .mysql_query_service
+                .find_consume_keyword_type_id(value)
+                .await?
+                .map(
+                    |consume_keyword_type_id| SpentDetailEdit::ConsumeKeywordType {
+                        consume_keyword_type_id,
+                        consume_keyword_type: value.to_string(),
+                    },
+                ),
+            "name" => Some(SpentDetailEdit::SpentName(value.to_string())),
+            "money" => value
+                .replace(",", "")
+                .parse::<i64>()
+                .ok()
+                .map(SpentDetailEdit::SpentMoney),
+            "at" => NaiveDateTime::parse_from_str(value, "%Y.%m.%d %H:%M")
+                .ok()
+                .and_then(|spent_at| Seoul.from_local_datetime(&spent_at).single())
+                .map(|spent_at| SpentDetailEdit::SpentAt(spent_at.fixed_offset())),
+            _ => None,
+        };
+
+        Ok(spent_detail_edit)
+    }
 }
@@ -0,0 +1,61 @@
+use crate::service_traits::{
+    cache_service::*, consume_classifier::*, elastic_query_service::*, graph_api_service::*,
+    mysql_query_service::*, process_service::*, redis_service::*, telebot_service::*,
+};
+
+use crate::configuration::command_registry::*;
+
+use super::MainController;
+
+impl<
+        G: GraphApiService,
+        E: ElasticQueryService,
+        M: MysqlQueryService,
+        T: TelebotService,
+        P: ProcessService,
+        R: RedisService,
+        C: CacheService,
+        CL: ConsumeClassifier,
+    > MainController<G, E, M, T, P, R, C, CL>
+{
+    /// Sends the command overview or the usage of one command (`/help [command]`).
+    ///
+    /// Both texts are generated from `COMMAND_REGISTRY`, so they always match what the
+    /// dispatcher accepts. Unknown command names are answered with the overview.
+    ///
+    /// # Arguments
+    ///
+    /// * `telegram_token` - Telegram bot token used to resolve the caller
+    /// * `telegram_user_id` - Telegram user id used to resolve the caller
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` after the help text is sent to Telegram.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller is unauthorised or the Telegram message fails.
+    pub(super) async fn command_help(
+        &self,
+        telegram_token: &str,
+        telegram_user_id: &str,
+    ) -> anyhow::Result<()> {
+        self.resolve_user_seq(telegram_token, telegram_user_id)
+            .await?;
+
+        let input_text: String = self.tele_bot_service.get_input_text();
+        let help_msg: String = match input_text.split_whitespace().nth(1) {
+            Some(command_nm) => match find_command_spec(command_nm.trim_start_matches('/')) {
+                Some(command_spec) => command_spec.help_detail(),
+                None => format!("Unknown command: {}\n\n{}", command_nm, help_overview()),
+            },
+            None => help_overview(),
+        };
+
+        self.tele_bot_service
+            .input_message_confirm(&help_msg)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use crate::common::*;
+
+use crate::service_traits::{
+    cache_service::*, consume_classifier::*, elastic_query_service::*, graph_api_service::*,
+    mysql_query_service::*, process_service::*, redis_service::*, telebot_service::*,
+};
+
+use crate::utils_modules::{command_tokenizer::*, time_utils::*};
+
+use crate::enums::{command_arg_error::*, command_id::*};
+
+use super::MainController;
+
+impl<
+        G: GraphApiService,
+        E: ElasticQueryService,
+        M: MysqlQueryService,
+        T: TelebotService,
+        P: ProcessService,
+        R: RedisService,
+        C: CacheService,
+        CL: ConsumeClassifier,
+    > MainController<G, E, M, T, P, R, C, CL>
+{
+    /// Shows or changes the caller's payday (`pd [day]`).
+    ///
+    /// Without arguments, shows the configured payday and the current salary period.
+    /// With a day of the month (`1`~`31`), stores it as the new payday. Paydays that fall on a
+    /// weekend or holiday are moved to the previous business day when periods are computed,
+    /// and days past the end of a short month are clamped to its last day.
+    ///
+    /// # Arguments
+    ///
+    /// * `telegram_token` - Telegram bot token used to resolve the caller
+    /// * `telegram_user_id` - Telegram user id used to resolve the caller
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` after the payday or the change result is sent to Telegram,
+    /// or after an invalid request is reported to the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller is unauthorised, or any MySQL or Telegram step fails.
+    pub(super) async fn command_payday(
+        &self,
+        telegram_token: &str,
+        telegram_user_id: &str,
+    ) -> anyhow::Result<()> {
+        let Some(new_payday) = self
+            .parse_command_args(CommandId::Payday, |command_line| {
+                match command_line.args() {
+                    [] => Ok(None),
+                    [payday] => {
+                        let payday: u32 = parse_number(payday.text(), "payday")?;
+                        if !(1..=31).contains(&payday) {
+                            return Err(CommandArgError::InvalidArg {
+                                arg_nm: "payday",
+                                value: payday.to_string(),
+                            });
+                        }
+                        Ok(Some(payday))
+                    }
+                    [_, unexpected_args @ ..] => Err(CommandArgError::UnexpectedArgs(
+                        unexpected_args
+                            .iter()
+                            .map(|arg| arg.text().to_string())
+                            .collect(),
+                    )),
+                }
+            })
+            .await?
+        else {
+            return Ok(());
+        };
+
+        let user_seq: i64 = self
+            .resolve_user_seq(telegram_token, telegram_user_id)
+            .await?;
+
+        if let Some(new_payday) = new_payday {
+            self.mysql_query_service
+                .modify_user_payday_with_transaction(user_seq, new_payday)
+                .await?;
+        }
+
+        let payday: u32 = self
+            .mysql_query_service
+            .find_user_payday(user_seq)
+            .await?
+            .ok_or_else(|| anyhow!("[command_payday] Payday not found: user_seq={}", user_seq))?;
+
+        let (period_start, period_end) = self
+            .resolve_salary_period(user_seq, find_current_kor_naivedate())
+            .await?;
+
+        self.tele_bot_service
+            .input_message_confirm(&format!(
+                "급여일: 매월 {}일 (주말/공휴일이면 직전 영업일)\n현재 급여 기간: {} ~ {}",
+                payday,
+                period_start.format("%Y-%m-%d"),
+                (period_end - chrono::Duration::days(1)).format("%Y-%m-%d")
+            ))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::common::*;
 use crate::service_traits::{
-    cache_service::*, elastic_query_service::*, graph_api_service::*, mysql_query_service::*,
-    process_service::*, producer_service::*, redis_service::*, telebot_service::*,
+    cache_service::*, consume_classifier::*, elastic_query_service::*, graph_api_service::*,
+    mysql_query_service::*, process_service::*, redis_service::*, telebot_service::*,
 };
 
 use crate::dtos::*;
@@ -11,9 +11,11 @@ use crate::models::{
     to_python_graph_line::*,
 };
 
-use crate::enums::range_operator::*;
+use crate::enums::{budget_scope::*, range_operator::*};
 
-use super::MainController;
+use crate::AppConfig;
+
+use super::{MainController, GRAPH_FAILURE_NOTICE};
 
 impl<
         G: GraphApiService,
@@ -21,13 +23,155 @@ impl<
         M: MysqlQueryService,
         T: TelebotService,
         P: ProcessService,
-        KP: ProducerService,
         R: RedisService,
         C: CacheService,
-    > MainController<G, E, M, T, P, KP, R, C>
+        CL: ConsumeClassifier,
+    > MainController<G, E, M, T, P, R, C, CL>
 {
-    /// Fetches consumption data for the given period from Elasticsearch, renders graphs
-    /// via the Python API, and sends all results to the Telegram chat room.
+    /// Awaits an Elasticsearch range aggregation and answers it from `SPENT_DETAIL` instead when
+    /// the cluster errors or does not reply within `ES_READ_TIMEOUT_MS`.
+    ///
+    /// # Arguments
+    ///
+    /// * `es_query` - The pending Elasticsearch aggregation
+    /// * `budget_scope` - Whether `scope_seq` is a `room_seq` or an `agg_group_seq`
+    /// * `scope_seq` - Room or aggregation group the query filters on
+    /// * `range` - `(start_date, end_date, start_op, end_op)` of the Elasticsearch query
+    ///
+    /// # Returns
+    ///
+    /// Returns the matched spending (sorted by `spent_at`) and the sum of their amounts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if the MySQL fallback fails as well.
+    async fn find_spent_details_with_fallback<F>(
+        &self,
+        es_query: F,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+        range: (DateTime<Utc>, DateTime<Utc>, RangeOperator, RangeOperator),
+    ) -> anyhow::Result<AggResultSet<SpentDetailByEs>>
+    where
+        F: std::future::Future<Output = anyhow::Result<AggResultSet<SpentDetailByEs>>>,
+    {
+        let (start_date, end_date, start_op, end_op) = range;
+
+        let es_read_timeout: Duration =
+            Duration::from_millis(*AppConfig::get_global().es_read_timeout_ms());
+
+        match tokio::time::timeout(es_read_timeout, es_query).await {
+            Ok(Ok(spent_detail_info)) => return Ok(spent_detail_info),
+            Ok(Err(e)) => warn!(
+                "[find_spent_details_with_fallback] Elasticsearch failed, reading from MySQL ({:?} {}): {:#}",
+                budget_scope, scope_seq, e
+            ),
+            Err(_) => warn!(
+                "[find_spent_details_with_fallback] Elasticsearch timed out after {:?}, reading from MySQL ({:?} {})",
+                es_read_timeout, budget_scope, scope_seq
+            ),
+        }
+
+        self.mysql_query_service
+            .find_spent_details_by_range(
+                budget_scope,
+                scope_seq,
+                start_date,
+                end_date,
+                start_op,
+                end_op,
+            )
+            .await
+    }
+
+    /// Runs a room-scoped aggregation through [`Self::find_spent_details_with_fallback`].
+    async fn find_room_spent_details_with_fallback(
+        &self,
+        dto: EsRangeRoomSeqQueryDto,
+    ) -> anyhow::Result<AggResultSet<SpentDetailByEs>> {
+        let (room_seq, range) = (
+            dto.room_seq,
+            (dto.start_date, dto.end_date, dto.start_op, dto.end_op),
+        );
+
+        self.find_spent_details_with_fallback(
+            self.elastic_query_service
+                .find_info_filter_roomseq_orderby_aggs_range(dto),
+            BudgetScope::Room,
+            room_seq,
+            range,
+        )
+        .await
+    }
+
+    /// Runs a group-scoped aggregation through [`Self::find_spent_details_with_fallback`],
+    /// filtering on `agg_group_seq`.
+    async fn find_group_spent_details_with_fallback(
+        &self,
+        dto: EsRangeGroupSeqQueryDto,
+    ) -> anyhow::Result<AggResultSet<SpentDetailByEs>> {
+        let (group_seq, range) = (
+            dto.group_seq,
+            (dto.start_date, dto.end_date, dto.start_op, dto.end_op),
+        );
+
+        self.find_spent_details_with_fallback(
+            self.elastic_query_service
+                .find_info_filter_groupseq_orderby_aggs_range(dto),
+            BudgetScope::Group,
+            group_seq,
+            range,
+        )
+        .await
+    }
+
+    /// Renders the cumulative line graph and the category pie for a report.
+    ///
+    /// # Arguments
+    ///
+    /// * `cur_python_graph_info` - Cumulative spending of the requested period
+    /// * `versus_python_graph_info` - Cumulative spending of the comparison period
+    /// * `circle_graph` - Category shares of the requested period
+    ///
+    /// # Returns
+    ///
+    /// Returns both images, line graph first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either graph cannot be rendered after the renderer's retries.
+    async fn find_consume_graph_imgs(
+        &self,
+        cur_python_graph_info: &ToPythonGraphLine,
+        versus_python_graph_info: &ToPythonGraphLine,
+        circle_graph: &ToPythonGraphCircle,
+    ) -> anyhow::Result<Vec<FileInfo>> {
+        let consume_detail_graph: Vec<u8> = self
+            .graph_api_service
+            .find_python_matplot_consume_detail_double(
+                cur_python_graph_info,
+                versus_python_graph_info,
+            )
+            .await?;
+
+        let circle_graph_img: Vec<u8> = self
+            .graph_api_service
+            .find_python_matplot_consume_type(circle_graph)
+            .await?;
+
+        Ok(vec![
+            FileInfo::new(String::from("consume_detail"), consume_detail_graph),
+            FileInfo::new(String::from("consume_type"), circle_graph_img),
+        ])
+    }
+
+    /// Fetches consumption data for the given period from Elasticsearch (or MySQL when it is
+    /// unavailable) and sends the detail list and category summary to the Telegram chat room,
+    /// followed by the graphs.
+    ///
+    /// The text is always sent before any graph is rendered. A graph failure is reported with a
+    /// short notice instead of failing the command, and graphs are skipped entirely when the
+    /// command carries the text-only switch (`-t`).
     ///
     /// # Arguments
     ///
@@ -36,15 +180,16 @@ impl<
     /// * `start_op` - Range operator applied to the start of the date range
     /// * `end_op` - Range operator applied to the end of the date range
     /// * `room_seq` - The Telegram room sequence number to scope the query
-    /// * `detail_yn` - When `true`, also sends the per-item detail message before the graphs
+    /// * `detail_yn` - When `true`, also sends the per-item detail message before the summary
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` after all messages and images have been sent and temp files deleted.
+    /// Returns `Ok(())` after the messages, and the images or the graph failure notice, are sent.
     ///
     /// # Errors
     ///
-    /// Returns an error if the Elasticsearch query, graph API call, or Telegram send fails.
+    /// Returns an error if both Elasticsearch and its MySQL fallback fail, or if a Telegram send
+    /// fails.
     pub(super) async fn common_process_python_double(
         &self,
         dto: CommonProcessPythonDoubleDto,
@@ -63,8 +208,7 @@ impl<
         ) = match (room_seq, group_seq) {
             (Some(rs), _) => {
                 let cur: AggResultSet<SpentDetailByEs> = self
-                    .elastic_query_service
-                    .find_info_filter_roomseq_orderby_aggs_range(EsRangeRoomSeqQueryDto {
+                    .find_room_spent_details_with_fallback(EsRangeRoomSeqQueryDto {
                         index_name: index_name.to_string(),
                         range_field: "spent_at".to_string(),
                         start_date: permon_datetime.date_start,
@@ -78,8 +222,7 @@ impl<
                     })
                     .await?;
                 let versus: AggResultSet<SpentDetailByEs> = self
-                    .elastic_query_service
-                    .find_info_filter_roomseq_orderby_aggs_range(EsRangeRoomSeqQueryDto {
+                    .find_room_spent_details_with_fallback(EsRangeRoomSeqQueryDto {
                         index_name: index_name.to_string(),
                         range_field: "spent_at".to_string(),
                         start_date: permon_datetime.n_date_start,
@@ -96,8 +239,7 @@ impl<
             }
             (None, Some(gs)) => {
                 let cur: AggResultSet<SpentDetailByEs> = self
-                    .elastic_query_service
-                    .find_info_filter_groupseq_orderby_aggs_range(EsRangeGroupSeqQueryDto {
+                    .find_group_spent_details_with_fallback(EsRangeGroupSeqQueryDto {
                         index_name: index_name.to_string(),
                         range_field: "spent_at".to_string(),
                         start_date: permon_datetime.date_start,
@@ -111,8 +253,7 @@ impl<
                     })
                     .await?;
                 let versus: AggResultSet<SpentDetailByEs> = self
-                    .elastic_query_service
-                    .find_info_filter_groupseq_orderby_aggs_range(EsRangeGroupSeqQueryDto {
+                    .find_group_spent_details_with_fallback(EsRangeGroupSeqQueryDto {
                         index_name: index_name.to_string(),
                         range_field: "spent_at".to_string(),
                         start_date: permon_datetime.n_date_start,
@@ -208,45 +349,51 @@ impl<
                 .await?;
         }
 
-        let consume_detail_graph: Vec<u8> = self
-            .graph_api_service
-            .find_python_matplot_consume_detail_double(
-                &cur_python_graph_info,
-                &versus_python_graph_info,
-            )
-            .await?;
-        let consume_detail_graph_img: FileInfo =
-            FileInfo::new(String::from("consume_detail"), consume_detail_graph);
-
         let consume_result_by_type: Vec<ConsumeResultByType> = self
             .process_service
             .find_consumption_result_by_category(&spent_detail_info_kst)?;
-        let circle_graph: ToPythonGraphCircle = self
-            .process_service
-            .to_python_graph_circle_by_consume_type(
+
+        self.tele_bot_service
+            .input_message_consume_info_by_typelist(
                 &consume_result_by_type,
-                *spent_detail_info_kst.agg_result(),
                 permon_datetime.date_start,
                 permon_datetime.date_end,
-            )?;
-        let circle_graph_img: Vec<u8> = self
-            .graph_api_service
-            .find_python_matplot_consume_type(&circle_graph)
+                *spent_detail_info_kst.agg_result(),
+            )
             .await?;
-        let circle_img: FileInfo = FileInfo::new(String::from("consume_type"), circle_graph_img);
-
-        let img_files: Vec<FileInfo> = vec![consume_detail_graph_img, circle_img];
 
-        self.tele_bot_service.input_photo_confirm(img_files).await?;
+        if self.is_text_only_requested() {
+            return Ok(());
+        }
 
-        self.tele_bot_service
-            .input_message_consume_info_by_typelist(
+        let circle_graph: ToPythonGraphCircle = self
+            .process_service
+            .to_python_graph_circle_by_consume_type(
                 &consume_result_by_type,
+                *spent_detail_info_kst.agg_result(),
                 permon_datetime.date_start,
                 permon_datetime.date_end,
-                *spent_detail_info_kst.agg_result(),
+            )?;
+
+        match self
+            .find_consume_graph_imgs(
+                &cur_python_graph_info,
+                &versus_python_graph_info,
+                &circle_graph,
             )
-            .await?;
+            .await
+        {
+            Ok(img_files) => self.tele_bot_service.input_photo_confirm(img_files).await?,
+            Err(e) => {
+                error!(
+                    "[common_process_python_double] Graph rendering failed: {:#}",
+                    e
+                );
+                self.tele_bot_service
+                    .input_message_confirm(GRAPH_FAILURE_NOTICE)
+                    .await?;
+            }
+        }
 
         Ok(())
     }
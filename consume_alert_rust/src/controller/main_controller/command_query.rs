@@ -1,16 +1,16 @@
 use crate::common::*;
 
 use crate::service_traits::{
-    cache_service::*, elastic_query_service::*, graph_api_service::*, mysql_query_service::*,
-    process_service::*, producer_service::*, redis_service::*, telebot_service::*,
+    cache_service::*, consume_classifier::*, elastic_query_service::*, graph_api_service::*,
+    mysql_query_service::*, process_service::*, redis_service::*, telebot_service::*,
 };
 
 use crate::models::per_datetime::*;
 
 use crate::configuration::elasitc_index_name::*;
 use crate::dtos::*;
-use crate::enums::range_operator::*;
-use crate::utils_modules::time_utils::*;
+use crate::enums::{command_id::*, range_operator::*};
+use crate::utils_modules::{command_tokenizer::*, time_utils::*};
 
 use super::MainController;
 
@@ -20,10 +20,10 @@ impl<
         M: MysqlQueryService,
         T: TelebotService,
         P: ProcessService,
-        KP: ProducerService,
         R: RedisService,
         C: CacheService,
-    > MainController<G, E, M, T, P, KP, R, C>
+        CL: ConsumeClassifier,
+    > MainController<G, E, M, T, P, R, C, CL>
 {
     /// Shows the monthly consumption summary for the caller's room (`cm [YYYY.MM]`).
     ///
@@ -47,30 +47,31 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
+        let Some(year_month) = self
+            .parse_command_args(
+                CommandId::ConsumptionPerMon,
+                CommandLine::optional_year_month_arg,
+            )
+            .await?
+        else {
+            return Err(anyhow!(
+                "[command_consumption_per_mon] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
+        };
 
-        let permon_datetime: PerDatetime = match args.len() {
-            1 => {
+        let permon_datetime: PerDatetime = match year_month {
+            None => {
                 let date_start: DateTime<Utc> = find_current_kor_naivedate_first_date()?;
                 let date_end: DateTime<Utc> = find_lastday_naivedate(date_start)?;
 
                 self.process_service
                     .find_nmonth_to_current_date(date_start, date_end, -1)?
             }
-            2 if args
-                .get(1)
-                .is_some_and(|d| is_valid_date_format(d, r"^\d{4}\.\d{2}$").unwrap_or(false)) =>
-            {
-                let parts: Vec<&str> = args[1].split('.').collect();
-                let kor_year: i32 = parts
-                    .first()
-                    .ok_or_else(|| anyhow!("[command_consumption_per_mon] Missing year"))?
-                    .parse()?;
-                let kor_month: u32 = parts
-                    .get(1)
-                    .ok_or_else(|| anyhow!("[command_consumption_per_mon] Missing month"))?
-                    .parse()?;
-
+            Some(YearMonthArg {
+                year: kor_year,
+                month: kor_month,
+            }) => {
                 // KST month start: YYYY-MM-01 00:00:00 KST
                 let kor_date_start: DateTime<chrono_tz::Tz> =
                     find_kst_datetime(kor_year, kor_month, 1)?;
@@ -102,17 +103,6 @@ impl<
                 self.process_service
                     .find_nmonth_to_current_date(date_start, date_end, -1)?
             }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "Invalid date format. Please use format YYYY.MM like cm 2023.07",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_mon] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
         };
 
         let user_seq: i64 = self
@@ -158,59 +148,22 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
-
-        let permon_datetime = match args.len() {
-            2 if args.get(1).is_some_and(|d| {
-                is_valid_date_format(d, r"^\d{4}\.\d{2}\.\d{2}-\d{4}\.\d{2}\.\d{2}$")
-                    .unwrap_or(false)
-            }) =>
-            {
-                let parts: Vec<&str> = args[1].split('-').collect();
-                let start_date: DateTime<Utc> =
-                    to_utc_datetime(parts[0], "%Y.%m.%d").inspect_err(|e| {
-                        error!(
-                            "[command_consumption_per_term] Invalid start date format: {:#}",
-                            e
-                        )
-                    })?;
-                let end_date: DateTime<Utc> =
-                    to_utc_datetime(parts[1], "%Y.%m.%d").inspect_err(|e| {
-                        error!(
-                            "[command_consumption_per_term] Invalid end date format: {:#}",
-                            e
-                        )
-                    })?;
-
-                if start_date > end_date {
-                    self.tele_bot_service
-                        .input_message_confirm(
-                            "Invalid date range. The start date must be earlier than or equal to the end date.\nEX) ctr 2023.07.07-2023.08.01",
-                        )
-                        .await?;
-                    return Err(anyhow!(
-                        "[command_consumption_per_term] Invalid date range: start_date({}) > end_date({})",
-                        start_date.format("%Y.%m.%d"),
-                        end_date.format("%Y.%m.%d")
-                    ));
-                }
-
-                self.process_service
-                    .find_nmonth_to_current_date(start_date, end_date, -1)?
-            }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "There is a problem with the parameter you entered. Please check again.\nEX) ctr 2023.07.07-2023.08.01",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_term] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
+        let Some(date_range) = self
+            .parse_command_args(CommandId::ConsumptionPerTerm, CommandLine::date_range_arg)
+            .await?
+        else {
+            return Err(anyhow!(
+                "[command_consumption_per_term] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
         };
 
+        let start_date: DateTime<Utc> = date_range.start_date.and_time(NaiveTime::MIN).and_utc();
+        let end_date: DateTime<Utc> = date_range.end_date.and_time(NaiveTime::MIN).and_utc();
+        let permon_datetime: PerDatetime = self
+            .process_service
+            .find_nmonth_to_current_date(start_date, end_date, -1)?;
+
         let user_seq: i64 = self
             .resolve_user_seq(telegram_token, telegram_user_id)
             .await?;
@@ -253,36 +206,27 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
+        let Some(date) = self
+            .parse_command_args(CommandId::ConsumptionPerDay, CommandLine::optional_date_arg)
+            .await?
+        else {
+            return Err(anyhow!(
+                "[command_consumption_per_day] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
+        };
 
-        let permon_datetime: PerDatetime = match args.len() {
-            1 => {
+        let permon_datetime: PerDatetime = match date {
+            None => {
                 let today: DateTime<Utc> = find_current_kor_naivedate();
                 self.process_service
                     .find_nday_to_current_date(today, today, -1)?
             }
-            2 if args.get(1).is_some_and(|d| {
-                is_valid_date_format(d, r"^\d{4}\.\d{2}\.\d{2}$").unwrap_or(false)
-            }) =>
-            {
-                let date: DateTime<Utc> =
-                    to_utc_datetime(&args[1], "%Y.%m.%d").inspect_err(|e| {
-                        error!("[command_consumption_per_day] Invalid date format: {:#}", e)
-                    })?;
+            Some(date) => {
+                let date: DateTime<Utc> = date.and_time(NaiveTime::MIN).and_utc();
                 self.process_service
                     .find_nday_to_current_date(date, date, -1)?
             }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "There is a problem with the parameter you entered. Please check again.\nEX) ct or ct 2023.11.11",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_day] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
         };
 
         let user_seq: i64 = self
@@ -327,30 +271,25 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
-
-        let permon_datetime: PerDatetime = match args.len() {
-            1 => {
-                let today: DateTime<Utc> = find_current_kor_naivedate();
-                let days_to_monday: i64 = Weekday::Mon.num_days_from_monday() as i64
-                    - today.weekday().num_days_from_monday() as i64;
-                let monday: DateTime<Utc> = today + chrono::Duration::days(days_to_monday);
-                let date_end: DateTime<Utc> = monday + chrono::Duration::days(6);
-                self.process_service
-                    .find_nday_to_current_date(monday, date_end, -7)?
-            }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "There is a problem with the parameter you entered. Please check again.\nEX) cw",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_week] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
-        };
+        if self
+            .parse_command_args(CommandId::ConsumptionPerWeek, CommandLine::no_args)
+            .await?
+            .is_none()
+        {
+            return Err(anyhow!(
+                "[command_consumption_per_week] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
+        }
+
+        let today: DateTime<Utc> = find_current_kor_naivedate();
+        let days_to_monday: i64 = Weekday::Mon.num_days_from_monday() as i64
+            - today.weekday().num_days_from_monday() as i64;
+        let monday: DateTime<Utc> = today + chrono::Duration::days(days_to_monday);
+        let date_end: DateTime<Utc> = monday + chrono::Duration::days(6);
+        let permon_datetime: PerDatetime = self
+            .process_service
+            .find_nday_to_current_date(monday, date_end, -7)?;
 
         let user_seq: i64 = self
             .resolve_user_seq(telegram_token, telegram_user_id)
@@ -396,37 +335,33 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
+        let Some(year) = self
+            .parse_command_args(
+                CommandId::ConsumptionPerYear,
+                CommandLine::optional_year_arg,
+            )
+            .await?
+        else {
+            return Err(anyhow!(
+                "[command_consumption_per_year] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
+        };
 
-        let permon_datetime: PerDatetime = match args.len() {
-            1 => {
+        let permon_datetime: PerDatetime = match year {
+            None => {
                 let cur_year: i32 = find_current_kor_naivedate().year();
                 let start_date: DateTime<Utc> = find_naivedate(cur_year, 1, 1)?;
                 let end_date: DateTime<Utc> = find_naivedate(cur_year, 12, 31)?;
                 self.process_service
                     .find_nmonth_to_current_date(start_date, end_date, -12)?
             }
-            2 if args
-                .get(1)
-                .is_some_and(|d| is_valid_date_format(d, r"^\d{4}$").unwrap_or(false)) =>
-            {
-                let year: i32 = args[1].parse()?;
+            Some(year) => {
                 let start_date: DateTime<Utc> = find_naivedate(year, 1, 1)?;
                 let end_date: DateTime<Utc> = find_naivedate(year, 12, 31)?;
                 self.process_service
                     .find_nmonth_to_current_date(start_date, end_date, -12)?
             }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "There is a problem with the parameter you entered. Please check again.\nEX01) cy\nEX02) cy 2023",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_year] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
         };
 
         let user_seq: i64 = self
@@ -451,10 +386,11 @@ impl<
 
     /// Shows the consumption summary for the caller's current salary period (`cs [YYYY.MM]`).
     ///
-    /// Salary periods are bounded by the 25th of each month and queried as a half-open interval
-    /// from one 25th up to, but not including, the next 25th.
+    /// Salary periods start on the caller's payday (`USERS.payday`, moved to the previous business
+    /// day when it falls on a weekend or holiday) and are queried as a half-open interval up to,
+    /// but not including, the next payday. The previous salary period is used for comparison.
     /// Defaults to the period containing today when no argument is provided.
-    /// Accepts an optional `YYYY.MM` argument to query the period ending on the 25th of that month.
+    /// Accepts an optional `YYYY.MM` argument to query the period ending on the payday of that month.
     ///
     /// # Arguments
     ///
@@ -473,61 +409,17 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
-
-        let permon_datetime: PerDatetime = match args.len() {
-            1 => {
-                let today: DateTime<Utc> = find_current_kor_naivedate();
-
-                let (year, month, day) = (today.year(), today.month(), today.day());
-                let cur_date_start: DateTime<Utc> = if day < 25 {
-                    find_add_month_from_naivedate(find_naivedate(year, month, 25)?, -1)?
-                } else {
-                    find_naivedate(year, month, 25)?
-                };
-                let cur_date_end: DateTime<Utc> = if day < 25 {
-                    find_naivedate(year, month, 25)?
-                } else {
-                    find_add_month_from_naivedate(find_naivedate(year, month, 25)?, 1)?
-                };
-                self.process_service.find_nmonth_to_current_date(
-                    cur_date_start,
-                    cur_date_end,
-                    -1,
-                )?
-            }
-            2 if args
-                .get(1)
-                .is_some_and(|d| is_valid_date_format(d, r"^\d{4}\.\d{2}$").unwrap_or(false)) =>
-            {
-                let ref_date: DateTime<Utc> =
-                    to_utc_datetime(&format!("{}.01", args[1]), "%Y.%m.%d").inspect_err(|e| {
-                        error!(
-                            "[command_consumption_per_salary] Invalid date format: {:#}",
-                            e
-                        )
-                    })?;
-                let cur_date_end: DateTime<Utc> =
-                    find_naivedate(ref_date.year(), ref_date.month(), 25)?;
-                let cur_date_start: DateTime<Utc> =
-                    find_add_month_from_naivedate(cur_date_end, -1)?;
-                self.process_service.find_nmonth_to_current_date(
-                    cur_date_start,
-                    cur_date_end,
-                    -1,
-                )?
-            }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "There is a problem with the parameter you entered. Please check again.\nEX) cs or cs 2023.11",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_salary] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
+        let Some(salary_month) = self
+            .parse_command_args(
+                CommandId::ConsumptionPerSalary,
+                CommandLine::optional_year_month_arg,
+            )
+            .await?
+        else {
+            return Err(anyhow!(
+                "[command_consumption_per_salary] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
         };
 
         let user_seq: i64 = self
@@ -538,6 +430,20 @@ impl<
             .resolve_telegram_room_seq(user_seq, telegram_token, telegram_user_id)
             .await?;
 
+        /* `YYYY.MM` selects the period that ends on the payday of that month. */
+        let ref_date: DateTime<Utc> = match salary_month {
+            Some(salary_month) => {
+                self.resolve_salary_payday(user_seq, salary_month.year, salary_month.month)
+                    .await?
+                    - chrono::Duration::days(1)
+            }
+            None => find_current_kor_naivedate(),
+        };
+
+        let permon_datetime: PerDatetime = self
+            .resolve_salary_per_datetime(user_seq, ref_date)
+            .await?;
+
         self.common_process_python_double(CommonProcessPythonDoubleDto {
             index_name: CONSUME_DETAIL.clone(),
             permon_datetime,
@@ -553,8 +459,9 @@ impl<
     /// Fetches and displays salary-cycle consumption data aggregated at the group level,
     /// comparing the current salary period against the same period one month prior.
     ///
-    /// The salary period runs from the 25th of the previous month to the 25th of the current month.
-    /// An optional `YYYY.MM` argument shifts the reference month; when omitted, today's date is used.
+    /// The salary period runs from the caller's previous business payday up to the next one.
+    /// An optional `YYYY.MM` argument selects the period ending on the payday of that month;
+    /// when omitted, the period containing today is used.
     ///
     /// # Arguments
     ///
@@ -574,60 +481,17 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
-
-        let permon_datetime: PerDatetime = match args.len() {
-            1 => {
-                let today: DateTime<Utc> = find_current_kor_naivedate();
-                let (year, month, day) = (today.year(), today.month(), today.day());
-                let cur_date_start: DateTime<Utc> = if day < 25 {
-                    find_add_month_from_naivedate(find_naivedate(year, month, 25)?, -1)?
-                } else {
-                    find_naivedate(year, month, 25)?
-                };
-                let cur_date_end: DateTime<Utc> = if day < 25 {
-                    find_naivedate(year, month, 25)?
-                } else {
-                    find_add_month_from_naivedate(find_naivedate(year, month, 25)?, 1)?
-                };
-                self.process_service.find_nmonth_to_current_date(
-                    cur_date_start,
-                    cur_date_end,
-                    -1,
-                )?
-            }
-            2 if args
-                .get(1)
-                .is_some_and(|d| is_valid_date_format(d, r"^\d{4}\.\d{2}$").unwrap_or(false)) =>
-            {
-                let ref_date: DateTime<Utc> =
-                    to_utc_datetime(&format!("{}.01", args[1]), "%Y.%m.%d").inspect_err(|e| {
-                        error!(
-                            "[command_consumption_per_salary_group] Invalid date format: {:#}",
-                            e
-                        )
-                    })?;
-                let cur_date_end: DateTime<Utc> =
-                    find_naivedate(ref_date.year(), ref_date.month(), 25)?;
-                let cur_date_start: DateTime<Utc> =
-                    find_add_month_from_naivedate(cur_date_end, -1)?;
-                self.process_service.find_nmonth_to_current_date(
-                    cur_date_start,
-                    cur_date_end,
-                    -1,
-                )?
-            }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "There is a problem with the parameter you entered. Please check again.\nEX) sg or sg 2023.11",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_salary_group] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
+        let Some(salary_month) = self
+            .parse_command_args(
+                CommandId::ConsumptionPerSalaryGroup,
+                CommandLine::optional_year_month_arg,
+            )
+            .await?
+        else {
+            return Err(anyhow!(
+                "[command_consumption_per_salary_group] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
         };
 
         let user_seq: i64 = self
@@ -638,6 +502,20 @@ impl<
             .resolve_telegram_group_seq(user_seq, telegram_token, telegram_user_id)
             .await?;
 
+        /* `YYYY.MM` selects the period that ends on the payday of that month. */
+        let ref_date: DateTime<Utc> = match salary_month {
+            Some(salary_month) => {
+                self.resolve_salary_payday(user_seq, salary_month.year, salary_month.month)
+                    .await?
+                    - chrono::Duration::days(1)
+            }
+            None => find_current_kor_naivedate(),
+        };
+
+        let permon_datetime: PerDatetime = self
+            .resolve_salary_per_datetime(user_seq, ref_date)
+            .await?;
+
         self.common_process_python_double(CommonProcessPythonDoubleDto {
             index_name: CONSUME_DETAIL.clone(),
             permon_datetime,
@@ -672,45 +550,33 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
+        let Some(year_month) = self
+            .parse_command_args(
+                CommandId::ConsumptionPerMonGroup,
+                CommandLine::optional_year_month_arg,
+            )
+            .await?
+        else {
+            return Err(anyhow!(
+                "[command_consumption_per_mon_group] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
+        };
 
-        let permon_datetime: PerDatetime = match args.len() {
-            1 => {
+        let permon_datetime: PerDatetime = match year_month {
+            None => {
                 let date_start: DateTime<Utc> = find_current_kor_naivedate_first_date()?;
                 let date_end: DateTime<Utc> = find_lastday_naivedate(date_start)?;
 
                 self.process_service
                     .find_nmonth_to_current_date(date_start, date_end, -1)?
             }
-            2 if args
-                .get(1)
-                .is_some_and(|d| is_valid_date_format(d, r"^\d{4}\.\d{2}$").unwrap_or(false)) =>
-            {
-                let parts: Vec<&str> = args[1].split('.').collect();
-                let year: i32 = parts
-                    .first()
-                    .ok_or_else(|| anyhow!("[command_consumption_per_mon_group] Missing year"))?
-                    .parse()?;
-                let month: u32 = parts
-                    .get(1)
-                    .ok_or_else(|| anyhow!("[command_consumption_per_mon_group] Missing month"))?
-                    .parse()?;
+            Some(YearMonthArg { year, month }) => {
                 let date_start: DateTime<Utc> = find_naivedate(year, month, 1)?;
                 let date_end: DateTime<Utc> = find_lastday_naivedate(date_start)?;
                 self.process_service
                     .find_nmonth_to_current_date(date_start, date_end, -1)?
             }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "Invalid date format. Please use format YYYY.MM like gm 2023.07",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_mon_group] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
         };
 
 
@@ -756,39 +622,30 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
+        let Some(date) = self
+            .parse_command_args(
+                CommandId::ConsumptionPerDayGroup,
+                CommandLine::optional_date_arg,
+            )
+            .await?
+        else {
+            return Err(anyhow!(
+                "[command_consumption_per_day_group] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
+        };
 
-        let permon_datetime: PerDatetime = match args.len() {
-            1 => {
+        let permon_datetime: PerDatetime = match date {
+            None => {
                 let today: DateTime<Utc> = find_current_kor_naivedate();
                 self.process_service
                     .find_nday_to_current_date(today, today, -1)?
             }
-            2 if args.get(1).is_some_and(|d| {
-                is_valid_date_format(d, r"^\d{4}\.\d{2}\.\d{2}$").unwrap_or(false)
-            }) =>
-            {
-                let date: DateTime<Utc> =
-                    to_utc_datetime(&args[1], "%Y.%m.%d").inspect_err(|e| {
-                        error!(
-                            "[command_consumption_per_day_group] Invalid date format: {:#}",
-                            e
-                        )
-                    })?;
+            Some(date) => {
+                let date: DateTime<Utc> = date.and_time(NaiveTime::MIN).and_utc();
                 self.process_service
                     .find_nday_to_current_date(date, date, -1)?
             }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "There is a problem with the parameter you entered. Please check again.\nEX) gt or gt 2023.11.11",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_day_group] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
         };
 
         let user_seq: i64 = self
@@ -833,30 +690,25 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
-
-        let permon_datetime: PerDatetime = match args.len() {
-            1 => {
-                let today: DateTime<Utc> = find_current_kor_naivedate();
-                let days_to_monday: i64 = Weekday::Mon.num_days_from_monday() as i64
-                    - today.weekday().num_days_from_monday() as i64;
-                let monday: DateTime<Utc> = today + chrono::Duration::days(days_to_monday);
-                let date_end: DateTime<Utc> = monday + chrono::Duration::days(6);
-                self.process_service
-                    .find_nday_to_current_date(monday, date_end, -7)?
-            }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "There is a problem with the parameter you entered. Please check again.\nEX) gw",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_week_group] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
-        };
+        if self
+            .parse_command_args(CommandId::ConsumptionPerWeekGroup, CommandLine::no_args)
+            .await?
+            .is_none()
+        {
+            return Err(anyhow!(
+                "[command_consumption_per_week_group] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
+        }
+
+        let today: DateTime<Utc> = find_current_kor_naivedate();
+        let days_to_monday: i64 = Weekday::Mon.num_days_from_monday() as i64
+            - today.weekday().num_days_from_monday() as i64;
+        let monday: DateTime<Utc> = today + chrono::Duration::days(days_to_monday);
+        let date_end: DateTime<Utc> = monday + chrono::Duration::days(6);
+        let permon_datetime: PerDatetime = self
+            .process_service
+            .find_nday_to_current_date(monday, date_end, -7)?;
 
         let user_seq: i64 = self
             .resolve_user_seq(telegram_token, telegram_user_id)
@@ -901,37 +753,33 @@ impl<
         telegram_token: &str,
         telegram_user_id: &str,
     ) -> anyhow::Result<()> {
-        let args: Vec<String> = self.to_preprocessed_tokens(" ");
+        let Some(year) = self
+            .parse_command_args(
+                CommandId::ConsumptionPerYearGroup,
+                CommandLine::optional_year_arg,
+            )
+            .await?
+        else {
+            return Err(anyhow!(
+                "[command_consumption_per_year_group] Invalid parameter: {:?}",
+                self.tele_bot_service.get_input_text()
+            ));
+        };
 
-        let permon_datetime: PerDatetime = match args.len() {
-            1 => {
+        let permon_datetime: PerDatetime = match year {
+            None => {
                 let cur_year = find_current_kor_naivedate().year();
                 let start_date: DateTime<Utc> = find_naivedate(cur_year, 1, 1)?;
                 let end_date: DateTime<Utc> = find_naivedate(cur_year, 12, 31)?;
                 self.process_service
                     .find_nmonth_to_current_date(start_date, end_date, -12)?
             }
-            2 if args
-                .get(1)
-                .is_some_and(|d| is_valid_date_format(d, r"^\d{4}$").unwrap_or(false)) =>
-            {
-                let year: i32 = args[1].parse()?;
+            Some(year) => {
                 let start_date: DateTime<Utc> = find_naivedate(year, 1, 1)?;
                 let end_date: DateTime<Utc> = find_naivedate(year, 12, 31)?;
                 self.process_service
                     .find_nmonth_to_current_date(start_date, end_date, -12)?
             }
-            _ => {
-                self.tele_bot_service
-                    .input_message_confirm(
-                        "There is a problem with the parameter you entered. Please check again.\nEX01) gy\nEX02) gy 2023",
-                    )
-                    .await?;
-                return Err(anyhow!(
-                    "[command_consumption_per_year_group] Invalid parameter: {:?}",
-                    self.tele_bot_service.get_input_text()
-                ));
-            }
         };
 
         let user_seq: i64 = self
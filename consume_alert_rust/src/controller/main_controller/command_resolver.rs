@@ -1,11 +1,13 @@
 use crate::common::*;
 
 use crate::service_traits::{
-    cache_service::*, elastic_query_service::*, graph_api_service::*, mysql_query_service::*,
-    process_service::*, producer_service::*, redis_service::*, telebot_service::*,
+    cache_service::*, consume_classifier::*, elastic_query_service::*, graph_api_service::*,
+    mysql_query_service::*, process_service::*, redis_service::*, telebot_service::*,
 };
 
-use crate::models::consume_index_prodt_type::*;
+use crate::models::{consume_index_prodt_type::*, dim_calendar::*, per_datetime::*};
+
+use crate::utils_modules::time_utils::*;
 
 use super::MainController;
 
@@ -15,16 +17,22 @@ impl<
         M: MysqlQueryService,
         T: TelebotService,
         P: ProcessService,
-        KP: ProducerService,
         R: RedisService,
         C: CacheService,
-    > MainController<G, E, M, T, P, KP, R, C>
+        CL: ConsumeClassifier,
+    > MainController<G, E, M, T, P, R, C, CL>
 {
-    /// Determines the consumption category for the given spending name via Elasticsearch.
+    /// Determines the consumption category for the given spending name via `consume_classifier`.
+    ///
+    /// Elasticsearch is used first unless the offline MySQL classifier is configured as primary;
+    /// either one serves as the fallback for the other. Keywords the caller taught through
+    /// `ce <id> type` corrections take precedence over global keywords when they match exactly
+    /// or at least as strongly.
     ///
     /// # Arguments
     ///
     /// * `spend_name` - The name or description of the spending item to classify
+    /// * `user_seq` - Internal user sequence of the caller
     ///
     /// # Returns
     ///
@@ -32,18 +40,19 @@ impl<
     ///
     /// # Errors
     ///
-    /// Returns an error if the Elasticsearch query fails.
+    /// Returns an error if every classifier fails.
     pub(super) async fn resolve_spend_type(
         &self,
         spend_name: &str,
+        user_seq: i64,
     ) -> anyhow::Result<ConsumingIndexProdtType> {
         let spent_type: ConsumingIndexProdtType = self
-            .elastic_query_service
-            .find_consume_type_judgement(spend_name)
+            .consume_classifier
+            .find_consume_type(spend_name, user_seq)
             .await
             .inspect_err(|e| {
                 error!(
-                    "[MainController::resolve_spend_type] Elasticsearch query failed: {:#}",
+                    "[MainController::resolve_spend_type] Classification failed: {:#}",
                     e
                 )
             })?;
@@ -168,4 +177,130 @@ impl<
             }
         }
     }
+
+    /// Returns the salary period of the caller that contains `ref_date`.
+    ///
+    /// The period runs from the caller's payday (`USERS.payday`) up to, but not including, the
+    /// next one; paydays on weekends or holidays move to the previous business day.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_seq` - Internal user sequence of the caller
+    /// * `ref_date` - KST calendar date (midnight, tagged as UTC) inside the wanted period
+    ///
+    /// # Returns
+    ///
+    /// Returns `(period_start, period_end)` as KST calendar dates tagged as UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or calendar lookup fails or a payday cannot be computed.
+    pub(super) async fn resolve_salary_period(
+        &self,
+        user_seq: i64,
+        ref_date: DateTime<Utc>,
+    ) -> anyhow::Result<(DateTime<Utc>, DateTime<Utc>)> {
+        let (payday, dim_calendars) = self
+            .resolve_payday_with_calendars(user_seq, ref_date)
+            .await?;
+
+        self.process_service
+            .find_salary_period(ref_date, payday, &dim_calendars)
+    }
+
+    /// Returns the salary period of the caller that contains `ref_date` together with the
+    /// period right before it, for the period-over-period comparison of `cs` and `gs`.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_seq` - Internal user sequence of the caller
+    /// * `ref_date` - KST calendar date (midnight, tagged as UTC) inside the wanted period
+    ///
+    /// # Returns
+    ///
+    /// Returns a `PerDatetime` whose `n_date_*` fields hold the previous salary period.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either salary period cannot be resolved.
+    pub(super) async fn resolve_salary_per_datetime(
+        &self,
+        user_seq: i64,
+        ref_date: DateTime<Utc>,
+    ) -> anyhow::Result<PerDatetime> {
+        let (date_start, date_end) = self.resolve_salary_period(user_seq, ref_date).await?;
+
+        let (prev_date_start, prev_date_end) = self
+            .resolve_salary_period(user_seq, date_start - chrono::Duration::days(1))
+            .await?;
+
+        Ok(PerDatetime::new(
+            date_start,
+            date_end,
+            prev_date_start,
+            prev_date_end,
+        ))
+    }
+
+    /// Returns the caller's business payday in the given month.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_seq` - Internal user sequence of the caller
+    /// * `year` - Year of the payday
+    /// * `month` - Month of the payday
+    ///
+    /// # Returns
+    ///
+    /// Returns the payday as a KST calendar date (midnight, tagged as UTC).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or calendar lookup fails or the payday cannot be computed.
+    pub(super) async fn resolve_salary_payday(
+        &self,
+        user_seq: i64,
+        year: i32,
+        month: u32,
+    ) -> anyhow::Result<DateTime<Utc>> {
+        let ref_date: DateTime<Utc> = find_naivedate(year, month, 1)?;
+
+        let (payday, dim_calendars) = self
+            .resolve_payday_with_calendars(user_seq, ref_date)
+            .await?;
+
+        self.process_service
+            .find_business_payday(year, month, payday, &dim_calendars)
+    }
+
+    /// Loads the caller's nominal payday and the calendar rows needed to shift the paydays of
+    /// the month before `ref_date` through two months after it.
+    async fn resolve_payday_with_calendars(
+        &self,
+        user_seq: i64,
+        ref_date: DateTime<Utc>,
+    ) -> anyhow::Result<(u32, Vec<DimCalendar>)> {
+        let payday: u32 = self
+            .mysql_query_service
+            .find_user_payday(user_seq)
+            .await?
+            .ok_or_else(|| {
+                anyhow!(
+                    "[resolve_payday_with_calendars] Payday not found: user_seq={}",
+                    user_seq
+                )
+            })?;
+
+        let month_start: DateTime<Utc> = find_naivedate(ref_date.year(), ref_date.month(), 1)?;
+        let calendar_from: DateTime<Utc> =
+            find_add_month_from_naivedate(month_start, -1)? - chrono::Duration::days(14);
+        let calendar_to: DateTime<Utc> = find_add_month_from_naivedate(month_start, 3)?;
+
+        let dim_calendars: Vec<DimCalendar> = self
+            .mysql_query_service
+            .find_dim_calendars(calendar_from.date_naive(), calendar_to.date_naive())
+            .await?;
+
+        Ok((payday, dim_calendars))
+    }
 }
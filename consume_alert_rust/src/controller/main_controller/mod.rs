@@ -1,36 +1,50 @@
 use crate::common::*;
 
+use crate::configuration::command_registry::*;
 use crate::dtos::MainControllerServicesDto;
+use crate::enums::{command_arg_error::*, command_id::*};
 use crate::service_traits::{
-    cache_service::*, elastic_query_service::*, graph_api_service::*, mysql_query_service::*,
-    process_service::*, producer_service::*, redis_service::*, telebot_service::*,
+    cache_service::*, consume_classifier::*, elastic_query_service::*, graph_api_service::*,
+    mysql_query_service::*, process_service::*, redis_service::*, telebot_service::*,
 };
+use crate::utils_modules::command_tokenizer::*;
 
 mod command_asset;
+mod command_budget;
 mod command_consume;
+mod command_help;
+mod command_payday;
 mod command_python_call;
 mod command_query;
 mod command_resolver;
 
+/// Trailing switch of the report commands (e.g. `cm 2026.10 -t`) that sends only the text
+/// summary and skips graph rendering.
+const TEXT_ONLY_SWITCH: &str = "-t";
+
+/// Notice sent in place of graphs that could not be rendered, after the text summary.
+const GRAPH_FAILURE_NOTICE: &str =
+    "그래프를 생성하지 못했습니다. 위의 텍스트 요약을 확인해 주세요.";
+
 pub struct MainController<
     G: GraphApiService,
     E: ElasticQueryService,
     M: MysqlQueryService,
     T: TelebotService,
     P: ProcessService,
-    KP: ProducerService,
     R: RedisService,
     C: CacheService,
+    CL: ConsumeClassifier,
 > {
     pub(super) graph_api_service: Arc<G>,
     pub(super) elastic_query_service: Arc<E>,
     pub(super) mysql_query_service: Arc<M>,
     pub(super) tele_bot_service: T,
     pub(super) process_service: Arc<P>,
-    pub(super) producer_service: Arc<KP>,
     #[allow(dead_code)]
     pub(super) redis_service: Arc<R>,
     pub(super) cache_service: Arc<C>,
+    pub(super) consume_classifier: Arc<CL>,
 }
 
 impl<
@@ -39,28 +53,29 @@ impl<
         M: MysqlQueryService,
         T: TelebotService,
         P: ProcessService,
-        KP: ProducerService,
         R: RedisService,
         C: CacheService,
-    > MainController<G, E, M, T, P, KP, R, C>
+        CL: ConsumeClassifier,
+    > MainController<G, E, M, T, P, R, C, CL>
 {
-    pub fn new(services: MainControllerServicesDto<G, E, M, T, P, KP, R, C>) -> Self {
+    pub fn new(services: MainControllerServicesDto<G, E, M, T, P, R, C, CL>) -> Self {
         Self {
             graph_api_service: services.graph_api_service,
             elastic_query_service: services.elastic_query_service,
             mysql_query_service: services.mysql_query_service,
             tele_bot_service: services.tele_bot_service,
             process_service: services.process_service,
-            producer_service: services.producer_service,
             redis_service: services.redis_service,
             cache_service: services.cache_service,
+            consume_classifier: services.consume_classifier,
         }
     }
 
     /// Dispatches the current Telegram input to the matching command handler.
     ///
     /// Reads the bot token, Telegram user id, and raw input text from `tele_bot_service`,
-    /// then routes to the appropriate handler based on the first whitespace-delimited token.
+    /// then routes to the handler that `COMMAND_REGISTRY` declares for the first
+    /// whitespace-delimited token or one of its aliases.
     /// Authentication and room resolution are performed inside each command handler.
     /// Unrecognised input falls through to the auto-consumption parser.
     ///
@@ -76,72 +91,100 @@ impl<
         let telegram_user_id: String = self.tele_bot_service.get_telegram_user_id();
         let input_text: String = self.tele_bot_service.get_input_text();
 
-        match input_text.split_whitespace().next().unwrap_or("") {
-            "c" => {
+        let command_id: Option<CommandId> =
+            find_command_spec(input_text.split_whitespace().next().unwrap_or(""))
+                .map(|command_spec| command_spec.command_id);
+
+        match command_id {
+            Some(CommandId::Help) => {
+                self.command_help(&telegram_token, &telegram_user_id)
+                    .await?
+            }
+            Some(CommandId::Consumption) => {
                 self.command_consumption(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "cd" => {
+            Some(CommandId::ConsumptionBudget) => {
+                self.command_consumption_budget(&telegram_token, &telegram_user_id)
+                    .await?
+            }
+            Some(CommandId::DeleteRecentConsumption) => {
                 self.command_delete_recent_consumption(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "cm" => {
+            Some(CommandId::ModifyConsumption) => {
+                self.command_modify_consumption(&telegram_token, &telegram_user_id)
+                    .await?
+            }
+            Some(CommandId::InstallmentBalance) => {
+                self.command_installment_balance(&telegram_token, &telegram_user_id)
+                    .await?
+            }
+            Some(CommandId::ConsumptionPerMon) => {
                 self.command_consumption_per_mon(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "ctr" => {
+            Some(CommandId::ConsumptionPerTerm) => {
                 self.command_consumption_per_term(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "ct" => {
+            Some(CommandId::ConsumptionPerDay) => {
                 self.command_consumption_per_day(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "cs" => {
+            Some(CommandId::ConsumptionPerSalary) => {
                 self.command_consumption_per_salary(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "cw" => {
+            Some(CommandId::ConsumptionPerWeek) => {
                 self.command_consumption_per_week(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "cy" => {
+            Some(CommandId::ConsumptionPerYear) => {
                 self.command_consumption_per_year(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "gs" => {
+            Some(CommandId::ConsumptionPerSalaryGroup) => {
                 self.command_consumption_per_salary_group(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "gm" => {
+            Some(CommandId::ConsumptionPerMonGroup) => {
                 self.command_consumption_per_mon_group(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "gt" => {
+            Some(CommandId::ConsumptionPerDayGroup) => {
                 self.command_consumption_per_day_group(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "gw" => {
+            Some(CommandId::ConsumptionPerWeekGroup) => {
                 self.command_consumption_per_week_group(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "gy" => {
+            Some(CommandId::ConsumptionPerYearGroup) => {
                 self.command_consumption_per_year_group(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "ew" => {
+            Some(CommandId::EarnedDetailByWon) => {
                 self.command_earend_detail_by_won(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "ed" => {
+            Some(CommandId::EarnedDetailByDollar) => {
                 self.command_earend_detail_by_dollor(&telegram_token, &telegram_user_id)
                     .await?
             }
-            "my" => {
+            Some(CommandId::Payday) => {
+                self.command_payday(&telegram_token, &telegram_user_id)
+                    .await?
+            }
+            Some(CommandId::ShowAllAsset) => {
                 self.command_show_all_asset(&telegram_token, &telegram_user_id)
                     .await?
             }
-            _ => {
+            Some(CommandId::NetWorthHistory) => {
+                self.command_net_worth_history(&telegram_token, &telegram_user_id)
+                    .await?
+            }
+            None => {
                 self.command_consumption_auto(&telegram_token, &telegram_user_id)
                     .await?
             }
@@ -152,24 +195,80 @@ impl<
 
     // ── Shared helpers ───────────────────────────────────────────────────────
 
-    /// Splits the raw command text after dropping its first two characters.
+    /// Tokenizes the current command text into its command word, arguments and switches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CommandArgError::UnterminatedQuote` if a quoted argument is never closed.
+    pub(super) fn to_command_line(&self) -> Result<CommandLine, CommandArgError> {
+        CommandLine::parse(&self.tele_bot_service.get_input_text())
+    }
+
+    /// Tokenizes the command text and converts its arguments with `parse`.
+    ///
+    /// When the arguments do not match, the error is sent to the user together with the
+    /// command's examples from `COMMAND_REGISTRY`.
     ///
     /// # Arguments
     ///
-    /// * `delimiter` - The string to split on after trimming the leading two characters
+    /// * `command_id` - The command whose examples are shown on failure
+    /// * `parse` - Converts the tokenized command into the handler's typed arguments
     ///
     /// # Returns
     ///
-    /// Returns a `Vec<String>` of trimmed tokens parsed from the input text.
-    /// Empty tokens are preserved when the split operation yields them.
-    pub(super) fn to_preprocessed_tokens(&self, delimiter: &str) -> Vec<String> {
-        let args: String = self.tele_bot_service.get_input_text();
+    /// Returns `Ok(Some(args))` on success, or `Ok(None)` after the usage reply was sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the usage reply cannot be sent to Telegram.
+    pub(super) async fn parse_command_args<A, F>(
+        &self,
+        command_id: CommandId,
+        parse: F,
+    ) -> anyhow::Result<Option<A>>
+    where
+        F: FnOnce(&CommandLine) -> Result<A, CommandArgError>,
+    {
+        match self
+            .to_command_line()
+            .and_then(|command_line| parse(&command_line))
+        {
+            Ok(args) => Ok(Some(args)),
+            Err(e) => {
+                self.reply_command_arg_error(command_id, &e).await?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Sends an argument error followed by the command's examples.
+    ///
+    /// # Arguments
+    ///
+    /// * `command_id` - The command whose examples are shown
+    /// * `command_arg_error` - What was wrong with the arguments
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Telegram message fails.
+    pub(super) async fn reply_command_arg_error(
+        &self,
+        command_id: CommandId,
+        command_arg_error: &CommandArgError,
+    ) -> anyhow::Result<()> {
+        self.tele_bot_service
+            .input_message_confirm(&format!(
+                "{}\n{}",
+                command_arg_error,
+                command_spec(command_id).examples_line()
+            ))
+            .await
+    }
 
-        args.chars()
-            .skip(2)
-            .collect::<String>()
-            .split(delimiter)
-            .map(|s| s.trim().to_string())
-            .collect()
+    /// Returns true when the command text contains [`TEXT_ONLY_SWITCH`], i.e. the caller asked
+    /// for the text summary without graphs.
+    pub(super) fn is_text_only_requested(&self) -> bool {
+        self.to_command_line()
+            .is_ok_and(|command_line| command_line.has_switch(TEXT_ONLY_SWITCH))
     }
 }
@@ -1 +1,9 @@
+pub mod asset_snapshot_controller;
+pub mod crypto_price_controller;
+pub mod indexing_controller;
 pub mod main_controller;
+pub mod outbox_relay_controller;
+pub mod reconcile_controller;
+pub mod report_mail_controller;
+pub mod stock_price_controller;
+pub mod webhook_controller;
@@ -0,0 +1,129 @@
+use crate::common::*;
+
+use crate::service_traits::{mysql_query_service::*, producer_service::*};
+
+use crate::models::{spent_detail_outbox::*, spent_detail_to_kafka::*};
+
+use crate::AppConfig;
+
+#[derive(new)]
+pub struct OutboxRelayController<M: MysqlQueryService, KP: ProducerService> {
+    mysql_query_service: Arc<M>,
+    producer_service: Arc<KP>,
+}
+
+impl<M, KP> OutboxRelayController<M, KP>
+where
+    M: MysqlQueryService + Sync + Send,
+    KP: ProducerService + Sync + Send,
+{
+    /// Publishes pending `SPENT_DETAIL_OUTBOX` rows to `PRODUCE_TOPIC` forever.
+    ///
+    /// Rows are relayed in `outbox_seq` order and marked sent once Kafka acknowledges them.
+    /// A failed row is retried on the next poll and stops the current batch, so later events of
+    /// the same spending are never published before earlier ones.
+    pub async fn main_call_function(&self) {
+        let app_config: &AppConfig = AppConfig::get_global();
+        let relay_interval: Duration =
+            Duration::from_millis(*app_config.outbox_relay_interval_ms());
+
+        info!("[OutboxRelayController::main_call_function] Outbox relay started");
+
+        loop {
+            match self.relay_pending_outboxes().await {
+                /* A full batch means more rows may be waiting, so poll again right away. */
+                Ok(relayed_cnt) if relayed_cnt as u64 >= *app_config.outbox_relay_batch_size() => {
+                    continue
+                }
+                Ok(_) => (),
+                Err(e) => error!("[OutboxRelayController::main_call_function] {:#}", e),
+            }
+
+            tokio::time::sleep(relay_interval).await;
+        }
+    }
+
+    /// Publishes pending outbox rows batch by batch until the outbox is drained or a row fails.
+    ///
+    /// Used by one-shot maintenance commands that exit before the background relay would run.
+    ///
+    /// # Returns
+    ///
+    /// Returns the total number of rows published and marked sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outbox cannot be read or a row's state cannot be updated.
+    pub async fn relay_all_pending(&self) -> anyhow::Result<usize> {
+        let batch_size: u64 = *AppConfig::get_global().outbox_relay_batch_size();
+        let mut total_relayed_cnt: usize = 0;
+
+        loop {
+            let relayed_cnt: usize = self.relay_pending_outboxes().await?;
+            total_relayed_cnt += relayed_cnt;
+
+            if (relayed_cnt as u64) < batch_size {
+                return Ok(total_relayed_cnt);
+            }
+        }
+    }
+
+    /// Publishes one batch of pending outbox rows.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of rows published and marked sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outbox cannot be read, a row cannot be published, or its state
+    /// cannot be updated.
+    async fn relay_pending_outboxes(&self) -> anyhow::Result<usize> {
+        let app_config: &AppConfig = AppConfig::get_global();
+
+        let pending_outboxes: Vec<SpentDetailOutbox> = self
+            .mysql_query_service
+            .find_pending_spent_detail_outboxes(*app_config.outbox_relay_batch_size())
+            .await?;
+
+        for (relayed_cnt, outbox) in pending_outboxes.iter().enumerate() {
+            let produce_payload: SpentDetailToKafka = outbox.to_spent_detail_to_kafka();
+            let partition_key: String = outbox.spent_idx().to_string();
+
+            if let Err(e) = self
+                .producer_service
+                .input_object_to_topic(
+                    app_config.produce_topic(),
+                    &produce_payload,
+                    Some(partition_key.as_str()),
+                )
+                .await
+            {
+                self.mysql_query_service
+                    .modify_spent_detail_outbox_failed_with_transaction(
+                        *outbox.outbox_seq(),
+                        outbox.attempt_cnt() + 1,
+                        &format!("{:#}", e),
+                    )
+                    .await?;
+
+                error!(
+                    "[OutboxRelayController::relay_pending_outboxes] Failed to publish outbox-{} (SPENT_DETAIL-{}, attempt {}): {:#}",
+                    outbox.outbox_seq(),
+                    outbox.spent_idx(),
+                    outbox.attempt_cnt() + 1,
+                    e
+                );
+
+                return Ok(relayed_cnt);
+            }
+
+            /* If marking fails the row is published again later; consumers index idempotently. */
+            self.mysql_query_service
+                .modify_spent_detail_outbox_sent_with_transaction(*outbox.outbox_seq())
+                .await?;
+        }
+
+        Ok(pending_outboxes.len())
+    }
+}
@@ -0,0 +1,107 @@
+use crate::common::*;
+
+use crate::service_traits::{elastic_query_service::*, mysql_query_service::*, process_service::*};
+
+use crate::dtos::ReconcileRequestDto;
+use crate::enums::indexing_type::*;
+use crate::models::{reconcile_result::*, spent_detail_summary::*};
+
+#[derive(new)]
+pub struct ReconcileController<E: ElasticQueryService, M: MysqlQueryService, P: ProcessService> {
+    elastic_query_service: Arc<E>,
+    mysql_query_service: Arc<M>,
+    process_service: Arc<P>,
+}
+
+impl<E, M, P> ReconcileController<E, M, P>
+where
+    E: ElasticQueryService + Sync + Send,
+    M: MysqlQueryService + Sync + Send,
+    P: ProcessService + Sync + Send,
+{
+    /// Compares SPENT_DETAIL with the CONSUME_DETAIL index for one scope and period and prints
+    /// the differences.
+    ///
+    /// Unless `dry_run` is set, an outbox event is recorded for every differing spending so the
+    /// indexing pipeline repairs it: missing rows are re-indexed (`I`), mismatched rows are
+    /// overwritten (`U`) and documents without an indexable row are deleted (`D`).
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Period, scope and dry-run flag parsed from the command line
+    ///
+    /// # Returns
+    ///
+    /// Returns the comparison result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either store cannot be queried or the outbox events cannot be stored.
+    pub async fn main_call_function(
+        &self,
+        request: &ReconcileRequestDto,
+    ) -> anyhow::Result<ReconcileResult> {
+        let (spent_at_from, spent_at_to) = request.to_spent_at_range()?;
+
+        let mysql_summaries: Vec<SpentDetailSummary> = self
+            .mysql_query_service
+            .find_spent_detail_summaries(*request.scope(), spent_at_from, spent_at_to)
+            .await?;
+
+        let es_summaries: Vec<SpentDetailSummary> = self
+            .elastic_query_service
+            .find_spent_detail_summaries(*request.scope(), spent_at_from, spent_at_to)
+            .await?;
+
+        let reconcile_result: ReconcileResult = self
+            .process_service
+            .find_reconcile_result(&mysql_summaries, &es_summaries);
+
+        println!(
+            "[reconcile] {:?} {} ~ {}\n{}",
+            request.scope(),
+            request.date_from().format("%Y-%m-%d"),
+            request.date_to().format("%Y-%m-%d"),
+            reconcile_result.to_report_string()
+        );
+
+        if reconcile_result.is_consistent() {
+            println!("[reconcile] MySQL and Elasticsearch are consistent.");
+            return Ok(reconcile_result);
+        }
+
+        if *request.dry_run() {
+            println!("[reconcile] Dry run: no indexing events were recorded.");
+            return Ok(reconcile_result);
+        }
+
+        let spent_detail_events: Vec<(i64, IndexingType)> = reconcile_result
+            .missing_spent_idxs()
+            .iter()
+            .map(|spent_idx| (*spent_idx, IndexingType::Insert))
+            .chain(
+                reconcile_result
+                    .mismatched_spent_idxs()
+                    .iter()
+                    .map(|spent_idx| (*spent_idx, IndexingType::Update)),
+            )
+            .chain(
+                reconcile_result
+                    .extra_spent_idxs()
+                    .iter()
+                    .map(|spent_idx| (*spent_idx, IndexingType::Delete)),
+            )
+            .collect();
+
+        self.mysql_query_service
+            .input_spent_detail_outboxes_with_transaction(&spent_detail_events)
+            .await?;
+
+        println!(
+            "[reconcile] Recorded {} indexing event(s) in SPENT_DETAIL_OUTBOX.",
+            spent_detail_events.len()
+        );
+
+        Ok(reconcile_result)
+    }
+}
@@ -0,0 +1,280 @@
+use crate::common::*;
+
+use crate::service_traits::{
+    graph_api_service::*, mysql_query_service::*, process_service::*, smtp_service::*,
+};
+
+use crate::models::{
+    agg_group::*, agg_result_set::*, consume_result_by_type::*, file_info::*, merchant_spending::*,
+    per_datetime::*, spent_detail_by_es::*, to_python_graph_circle::*, to_python_graph_line::*,
+};
+
+use crate::enums::{budget_scope::*, range_operator::*};
+
+use crate::views::consume_report_mail_view::*;
+
+use crate::utils_modules::schedule_utils::*;
+
+use crate::AppConfig;
+
+#[derive(new)]
+pub struct ReportMailController<
+    G: GraphApiService,
+    M: MysqlQueryService,
+    P: ProcessService,
+    S: SmtpService,
+> {
+    graph_api_service: Arc<G>,
+    mysql_query_service: Arc<M>,
+    process_service: Arc<P>,
+    smtp_service: S,
+}
+
+impl<G, M, P, S> ReportMailController<G, M, P, S>
+where
+    G: GraphApiService + Sync + Send,
+    M: MysqlQueryService + Sync + Send,
+    P: ProcessService + Sync + Send,
+    S: SmtpService + Sync + Send,
+{
+    /// Mails the monthly consumption reports on every `REPORT_MAIL_CRON` tick forever.
+    ///
+    /// Each run reports the calendar month before its KST run time, so a schedule such as
+    /// `0 0 9 1 * *` mails the previous month on the morning of the 1st. Returns immediately
+    /// when no schedule is configured.
+    pub async fn main_call_function(&self) {
+        let Some(report_mail_schedule) = AppConfig::get_global().report_mail_schedule() else {
+            return;
+        };
+
+        info!("[ReportMailController::main_call_function] Report mail scheduler started");
+
+        run_on_schedule(report_mail_schedule, "REPORT_MAIL_CRON", |run_at| {
+            self.send_monthly_reports(run_at)
+        })
+        .await;
+    }
+
+    /// Builds and mails the report of the month before `run_at` for every active aggregation group.
+    ///
+    /// Groups without active receivers are skipped. A failure of one group is logged and does
+    /// not stop the reports of the other groups.
+    ///
+    /// # Arguments
+    ///
+    /// * `run_at` - Scheduled time of this run
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report period or the active groups cannot be determined.
+    async fn send_monthly_reports(&self, run_at: DateTime<Utc>) -> anyhow::Result<()> {
+        let permon_datetime: PerDatetime = self.process_service.find_report_month_period(run_at)?;
+
+        let agg_groups: Vec<AggGroup> = self.mysql_query_service.find_active_agg_groups().await?;
+
+        for agg_group in &agg_groups {
+            if let Err(e) = self.send_group_report(agg_group, &permon_datetime).await {
+                error!(
+                    "[ReportMailController::send_monthly_reports] Failed to mail the report of AGG_GROUP-{}: {:#}",
+                    agg_group.agg_group_seq, e
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds the report of one aggregation group and mails it to the group's receivers.
+    ///
+    /// The report is read from `SPENT_DETAIL`, the source of truth, rather than Elasticsearch.
+    /// When the charts cannot be rendered the mail is still sent without them.
+    ///
+    /// # Arguments
+    ///
+    /// * `agg_group` - The group to report on
+    /// * `permon_datetime` - Report month and comparison month
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receivers or the spending cannot be read.
+    async fn send_group_report(
+        &self,
+        agg_group: &AggGroup,
+        permon_datetime: &PerDatetime,
+    ) -> anyhow::Result<()> {
+        let receivers: Vec<String> = self
+            .mysql_query_service
+            .find_report_mail_receivers(agg_group.agg_group_seq)
+            .await?;
+
+        if receivers.is_empty() {
+            info!(
+                "[ReportMailController::send_group_report] AGG_GROUP-{} has no active receivers, skipped",
+                agg_group.agg_group_seq
+            );
+            return Ok(());
+        }
+
+        let spent_detail_info: AggResultSet<SpentDetailByEs> = self
+            .find_group_spent_details(
+                agg_group.agg_group_seq,
+                permon_datetime.date_start,
+                permon_datetime.date_end,
+            )
+            .await?;
+        let versus_spent_detail_info: AggResultSet<SpentDetailByEs> = self
+            .find_group_spent_details(
+                agg_group.agg_group_seq,
+                permon_datetime.n_date_start,
+                permon_datetime.n_date_end,
+            )
+            .await?;
+
+        let consume_result_by_types: Vec<ConsumeResultByType> = self
+            .process_service
+            .find_consumption_result_by_category(&spent_detail_info)?;
+        let versus_money_by_type: HashMap<String, i64> = self
+            .process_service
+            .find_consumption_result_by_category(&versus_spent_detail_info)?
+            .into_iter()
+            .map(|consume_result| {
+                (
+                    consume_result.consume_prodt_type,
+                    consume_result.consume_prodt_cost,
+                )
+            })
+            .collect();
+        let top_merchants: Vec<MerchantSpending> = self.process_service.find_top_merchants(
+            &spent_detail_info,
+            *AppConfig::get_global().report_mail_top_merchant_cnt(),
+        );
+
+        let chart_imgs: Vec<FileInfo> = match self
+            .find_report_chart_imgs(
+                permon_datetime,
+                &spent_detail_info,
+                &versus_spent_detail_info,
+                &consume_result_by_types,
+            )
+            .await
+        {
+            Ok(chart_imgs) => chart_imgs,
+            Err(e) => {
+                warn!(
+                    "[ReportMailController::send_group_report] Charts of AGG_GROUP-{} are left out: {:#}",
+                    agg_group.agg_group_seq, e
+                );
+                Vec::new()
+            }
+        };
+
+        let report_view: ConsumeReportMailView = ConsumeReportMailView::new(
+            agg_group.agg_group_name.clone(),
+            permon_datetime.date_start,
+            permon_datetime.date_end,
+            *spent_detail_info.agg_result() as i64,
+            *versus_spent_detail_info.agg_result() as i64,
+            spent_detail_info.source_list().len(),
+            consume_result_by_types,
+            versus_money_by_type,
+            top_merchants,
+            chart_imgs
+                .iter()
+                .map(|chart_img| chart_img.file_name.clone())
+                .collect(),
+        );
+
+        let sent_cnt: usize = self
+            .smtp_service
+            .send_email(
+                &receivers,
+                &report_view.subject(),
+                &report_view.to_html(),
+                &chart_imgs,
+            )
+            .await?;
+
+        info!(
+            "[ReportMailController::send_group_report] Report of AGG_GROUP-{} mailed to {}/{} receivers",
+            agg_group.agg_group_seq,
+            sent_cnt,
+            receivers.len()
+        );
+
+        Ok(())
+    }
+
+    /// Reads the spending of a group within an inclusive period from MySQL.
+    async fn find_group_spent_details(
+        &self,
+        agg_group_seq: i64,
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+    ) -> anyhow::Result<AggResultSet<SpentDetailByEs>> {
+        self.mysql_query_service
+            .find_spent_details_by_range(
+                BudgetScope::Group,
+                agg_group_seq,
+                date_start,
+                date_end,
+                RangeOperator::GreaterThanOrEqual,
+                RangeOperator::LessThanOrEqual,
+            )
+            .await
+    }
+
+    /// Renders the cumulative line graph and the category pie embedded in the mail.
+    ///
+    /// # Returns
+    ///
+    /// Returns both images named by their Content-ID, line graph first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either graph cannot be rendered.
+    async fn find_report_chart_imgs(
+        &self,
+        permon_datetime: &PerDatetime,
+        spent_detail_info: &AggResultSet<SpentDetailByEs>,
+        versus_spent_detail_info: &AggResultSet<SpentDetailByEs>,
+        consume_result_by_types: &[ConsumeResultByType],
+    ) -> anyhow::Result<Vec<FileInfo>> {
+        let cur_python_graph_info: ToPythonGraphLine = ToPythonGraphLine::new(
+            "cur",
+            permon_datetime.date_start,
+            permon_datetime.date_end,
+            spent_detail_info,
+        )?;
+        let versus_python_graph_info: ToPythonGraphLine = ToPythonGraphLine::new(
+            "versus",
+            permon_datetime.n_date_start,
+            permon_datetime.n_date_end,
+            versus_spent_detail_info,
+        )?;
+        let circle_graph: ToPythonGraphCircle = self
+            .process_service
+            .to_python_graph_circle_by_consume_type(
+                consume_result_by_types,
+                *spent_detail_info.agg_result(),
+                permon_datetime.date_start,
+                permon_datetime.date_end,
+            )?;
+
+        let consume_detail_graph: Vec<u8> = self
+            .graph_api_service
+            .find_python_matplot_consume_detail_double(
+                &cur_python_graph_info,
+                &versus_python_graph_info,
+            )
+            .await?;
+        let circle_graph_img: Vec<u8> = self
+            .graph_api_service
+            .find_python_matplot_consume_type(&circle_graph)
+            .await?;
+
+        Ok(vec![
+            FileInfo::new(String::from("consume_detail"), consume_detail_graph),
+            FileInfo::new(String::from("consume_type"), circle_graph_img),
+        ])
+    }
+}
@@ -0,0 +1,198 @@
+use crate::common::*;
+
+use crate::service_traits::{mysql_query_service::*, quote_price_provider::*};
+
+use crate::models::stock_type::*;
+
+use crate::utils_modules::schedule_utils::*;
+
+use crate::AppConfig;
+
+#[derive(new)]
+pub struct StockPriceController<M: MysqlQueryService, Q: QuotePriceProvider> {
+    mysql_query_service: Arc<M>,
+    quote_price_provider: Arc<Q>,
+}
+
+impl<M, Q> StockPriceController<M, Q>
+where
+    M: MysqlQueryService + Sync + Send,
+    Q: QuotePriceProvider,
+{
+    /// Refreshes `STOCK.stock_price` of the open markets on every `STOCK_PRICE_CRON` tick forever.
+    ///
+    /// Returns immediately when no schedule is configured.
+    pub async fn main_call_function(&self) {
+        let Some(stock_price_schedule) = AppConfig::get_global().stock_price_schedule() else {
+            return;
+        };
+
+        info!(
+            "[StockPriceController::main_call_function] Stock price scheduler started with the '{}' provider",
+            self.quote_price_provider.provider_nm()
+        );
+
+        run_on_schedule(stock_price_schedule, "STOCK_PRICE_CRON", |_| {
+            self.refresh_stock_prices(Utc::now())
+        })
+        .await;
+    }
+
+    /// Quotes every stock of the markets in `STOCK_TYPE` whose regular session is running at
+    /// `at` and writes the prices to `STOCK.stock_price`, one transaction per market.
+    ///
+    /// Symbols that cannot be quoted keep their previous price. A failure of one market is
+    /// logged and does not stop the other markets.
+    ///
+    /// # Arguments
+    ///
+    /// * `at` - Time the refresh runs, used to decide which markets are open
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of STOCK rows updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the markets cannot be listed.
+    async fn refresh_stock_prices(&self, at: DateTime<Utc>) -> anyhow::Result<u64> {
+        let stock_types: Vec<StockType> = self.mysql_query_service.find_stock_types().await?;
+        let mut updated_cnt: u64 = 0;
+
+        for stock_type in stock_types
+            .iter()
+            .filter(|stock_type| stock_type.is_open(at))
+        {
+            match self.refresh_market_prices(stock_type).await {
+                Ok(market_updated_cnt) => updated_cnt += market_updated_cnt,
+                Err(e) => error!(
+                    "[StockPriceController::refresh_stock_prices] Failed to refresh {}: {:#}",
+                    stock_type.market, e
+                ),
+            }
+        }
+
+        Ok(updated_cnt)
+    }
+
+    /// Quotes and stores the prices of every symbol of one market.
+    async fn refresh_market_prices(&self, stock_type: &StockType) -> anyhow::Result<u64> {
+        let api_symbols: Vec<String> = self
+            .mysql_query_service
+            .find_stock_api_symbols(stock_type.market_seq)
+            .await?;
+
+        let stock_prices: Vec<(String, Decimal)> = find_quote_prices(
+            self.quote_price_provider.as_ref(),
+            stock_type,
+            &api_symbols,
+            Duration::from_millis(*AppConfig::get_global().kis_request_interval_ms()),
+        )
+        .await;
+
+        if stock_prices.is_empty() {
+            return Ok(0);
+        }
+
+        let updated_cnt: u64 = self
+            .mysql_query_service
+            .modify_stock_prices_with_transaction(stock_type.market_seq, &stock_prices)
+            .await?;
+
+        info!(
+            "[StockPriceController::refresh_market_prices] {}: quoted {}/{} symbol(s), updated {} row(s)",
+            stock_type.market,
+            stock_prices.len(),
+            api_symbols.len(),
+            updated_cnt
+        );
+
+        Ok(updated_cnt)
+    }
+}
+
+/// Quotes the symbols one by one, waiting `request_interval` between requests.
+///
+/// # Arguments
+///
+/// * `quote_price_provider` - Provider asked for each price
+/// * `stock_type` - Market the symbols are listed on
+/// * `api_symbols` - Symbols to quote
+/// * `request_interval` - Pause between two requests
+///
+/// # Returns
+///
+/// Returns `(api_symbol, price)` for every symbol that was quoted; failures are logged and
+/// left out.
+async fn find_quote_prices<Q: QuotePriceProvider>(
+    quote_price_provider: &Q,
+    stock_type: &StockType,
+    api_symbols: &[String],
+    request_interval: Duration,
+) -> Vec<(String, Decimal)> {
+    let mut stock_prices: Vec<(String, Decimal)> = Vec::new();
+
+    for (idx, api_symbol) in api_symbols.iter().enumerate() {
+        if idx > 0 && !request_interval.is_zero() {
+            tokio::time::sleep(request_interval).await;
+        }
+
+        match quote_price_provider
+            .find_quote_price(stock_type, api_symbol)
+            .await
+        {
+            Ok(stock_price) => stock_prices.push((api_symbol.clone(), stock_price)),
+            Err(e) => warn!(
+                "[StockPriceController::find_quote_prices] '{}' quote of {} {} failed: {:#}",
+                quote_price_provider.provider_nm(),
+                stock_type.market,
+                api_symbol,
+                e
+            ),
+        }
+    }
+
+    stock_prices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::services::quote_price_provider_impl::*;
+
+    #[tokio::test]
+    async fn test_find_quote_prices() {
+        let quote_price_provider: MockQuotePriceProvider =
+            MockQuotePriceProvider::new(HashMap::from([
+                ("AAPL".to_string(), Decimal::new(23145, 2)),
+                ("MSFT".to_string(), Decimal::new(41520, 2)),
+            ]));
+        let nasdaq: StockType = StockType::new(
+            2,
+            "NASDAQ".to_string(),
+            "USD".to_string(),
+            Utc::now(),
+            None,
+            "system".to_string(),
+            None,
+        );
+        let api_symbols: Vec<String> = vec![
+            "AAPL".to_string(),
+            "DELISTED".to_string(),
+            "MSFT".to_string(),
+        ];
+
+        let stock_prices: Vec<(String, Decimal)> =
+            find_quote_prices(&quote_price_provider, &nasdaq, &api_symbols, Duration::ZERO).await;
+
+        assert_eq!(
+            stock_prices,
+            vec![
+                ("AAPL".to_string(), Decimal::new(23145, 2)),
+                ("MSFT".to_string(), Decimal::new(41520, 2)),
+            ]
+        );
+        assert_eq!(quote_price_provider.requested_symbols(), api_symbols);
+    }
+}
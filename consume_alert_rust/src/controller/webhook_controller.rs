@@ -0,0 +1,375 @@
+use crate::common::*;
+
+use crate::AppConfig;
+
+use std::{convert::Infallible, net::SocketAddr};
+
+use futures::StreamExt;
+
+use hyper::{
+    body::to_bytes, server::conn::Http, service::service_fn, Body, Method, Request,
+    Response as HyperResponse, StatusCode as HyperStatusCode,
+};
+use teloxide::{
+    stop::{mk_stop_token, StopFlag, StopToken},
+    types::{Update, UpdateKind},
+    update_listeners::{StatefulListener, UpdateListener},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpListener,
+    sync::mpsc,
+};
+use tokio_native_tls::{native_tls, TlsAcceptor};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// Header Telegram fills with the secret token registered through `setWebhook`.
+const SECRET_TOKEN_HEADER: &str = "x-telegram-bot-api-secret-token";
+
+type UpdateSender = mpsc::UnboundedSender<Result<Update, Infallible>>;
+type UpdateStream =
+    futures::stream::TakeUntil<UnboundedReceiverStream<Result<Update, Infallible>>, StopFlag>;
+
+/* Where the updates of one bot go once the request has been authenticated. */
+struct WebhookRoute {
+    secret_token: String,
+    update_sender: UpdateSender,
+}
+
+/// Embedded HTTP(S) listener that receives Telegram webhook calls for every bot on one port.
+///
+/// Each bot is registered under its own path and secret token; the updates it receives are
+/// handed to that bot's Dispatcher through the listener returned by [`Self::register_bot`].
+pub struct WebhookController {
+    listen_addr: SocketAddr,
+    tls_acceptor: Option<TlsAcceptor>,
+    routes: HashMap<String, WebhookRoute>,
+}
+
+impl WebhookController {
+    /// Creates a listener from `WEBHOOK_LISTEN_ADDR` and the optional TLS key pair.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TLS certificate or key cannot be read or parsed.
+    pub fn new() -> anyhow::Result<Self> {
+        let app_config: &AppConfig = AppConfig::get_global();
+
+        let tls_acceptor: Option<TlsAcceptor> = match (
+            app_config.webhook_tls_cert_path(),
+            app_config.webhook_tls_key_path(),
+        ) {
+            (Some(cert_path), Some(key_path)) => {
+                Some(Self::load_tls_acceptor(cert_path, key_path)?)
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            listen_addr: *app_config.webhook_listen_addr(),
+            tls_acceptor,
+            routes: HashMap::new(),
+        })
+    }
+
+    /// Registers a bot and returns the update listener its Dispatcher should run on.
+    ///
+    /// The listener stops yielding updates once its stop token is triggered, which lets
+    /// `ShutdownToken::shutdown` drain the Dispatcher while the HTTP server keeps running
+    /// for the other bots.
+    ///
+    /// # Arguments
+    ///
+    /// * `route_path` - Request path Telegram posts this bot's updates to (e.g. `/telegram/123`)
+    /// * `secret_token` - Secret token registered with `setWebhook` for this bot
+    ///
+    /// # Returns
+    ///
+    /// Returns an `UpdateListener` fed by the webhook requests sent to `route_path`.
+    pub fn register_bot(
+        &mut self,
+        route_path: String,
+        secret_token: String,
+    ) -> impl UpdateListener<Err = Infallible> {
+        let (update_sender, update_receiver) = mpsc::unbounded_channel();
+        let (stop_token, stop_flag) = mk_stop_token();
+
+        self.routes.insert(
+            route_path,
+            WebhookRoute {
+                secret_token,
+                update_sender,
+            },
+        );
+
+        let update_stream: UpdateStream =
+            UnboundedReceiverStream::new(update_receiver).take_until(stop_flag);
+
+        StatefulListener::new(
+            (update_stream, stop_token),
+            listener_update_stream,
+            |state: &mut (UpdateStream, StopToken)| state.1.clone(),
+        )
+    }
+
+    /// Accepts webhook connections forever.
+    ///
+    /// Requests are answered with `404` for unknown paths, `401` for a wrong secret token and
+    /// `503` once the target bot has been shut down, so Telegram retries them later.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the listen address cannot be bound.
+    pub async fn main_call_function(self) -> anyhow::Result<()> {
+        let tcp_listener: TcpListener = TcpListener::bind(self.listen_addr).await.map_err(|e| {
+            anyhow!(
+                "[WebhookController::main_call_function] bind {}: {:?}",
+                self.listen_addr,
+                e
+            )
+        })?;
+
+        info!(
+            "[WebhookController::main_call_function] Webhook listener started on {} ({}, {} bot(s))",
+            self.listen_addr,
+            if self.tls_acceptor.is_some() { "https" } else { "http" },
+            self.routes.len()
+        );
+
+        let routes: Arc<HashMap<String, WebhookRoute>> = Arc::new(self.routes);
+
+        loop {
+            let (tcp_stream, peer_addr) = match tcp_listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    error!("[WebhookController::main_call_function] accept: {:?}", e);
+                    continue;
+                }
+            };
+
+            let routes: Arc<HashMap<String, WebhookRoute>> = Arc::clone(&routes);
+            let tls_acceptor: Option<TlsAcceptor> = self.tls_acceptor.clone();
+
+            tokio::spawn(async move {
+                let served: anyhow::Result<()> = match tls_acceptor {
+                    Some(tls_acceptor) => match tls_acceptor.accept(tcp_stream).await {
+                        Ok(tls_stream) => Self::serve_connection(tls_stream, routes).await,
+                        Err(e) => Err(anyhow!("TLS handshake failed: {:?}", e)),
+                    },
+                    None => Self::serve_connection(tcp_stream, routes).await,
+                };
+
+                if let Err(e) = served {
+                    warn!(
+                        "[WebhookController::main_call_function] {}: {:#}",
+                        peer_addr, e
+                    );
+                }
+            });
+        }
+    }
+
+    /// Serves the HTTP/1 requests of one accepted connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `io` - The plain or TLS-wrapped connection
+    /// * `routes` - Registered bots keyed by request path
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection fails before it is closed cleanly.
+    async fn serve_connection<I>(
+        io: I,
+        routes: Arc<HashMap<String, WebhookRoute>>,
+    ) -> anyhow::Result<()>
+    where
+        I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let service = service_fn(move |request: Request<Body>| {
+            let routes: Arc<HashMap<String, WebhookRoute>> = Arc::clone(&routes);
+            async move { Ok::<_, Infallible>(Self::handle_request(request, &routes).await) }
+        });
+
+        Http::new()
+            .http1_only(true)
+            .serve_connection(io, service)
+            .await
+            .map_err(|e| anyhow!("connection error: {:?}", e))
+    }
+
+    /// Authenticates one webhook call and forwards its update to the matching bot.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The incoming HTTP request
+    /// * `routes` - Registered bots keyed by request path
+    ///
+    /// # Returns
+    ///
+    /// Returns the HTTP response sent back to Telegram.
+    async fn handle_request(
+        request: Request<Body>,
+        routes: &HashMap<String, WebhookRoute>,
+    ) -> HyperResponse<Body> {
+        if request.method() != Method::POST {
+            return Self::status_response(HyperStatusCode::METHOD_NOT_ALLOWED);
+        }
+
+        let route: &WebhookRoute = match routes.get(request.uri().path()) {
+            Some(route) => route,
+            None => return Self::status_response(HyperStatusCode::NOT_FOUND),
+        };
+
+        let secret_matches: bool = request
+            .headers()
+            .get(SECRET_TOKEN_HEADER)
+            .map(|header| constant_time_eq(header.as_bytes(), route.secret_token.as_bytes()))
+            .unwrap_or(false);
+
+        if !secret_matches {
+            warn!(
+                "[WebhookController::handle_request] Rejected request with a missing or wrong secret token: {}",
+                request.uri().path()
+            );
+            return Self::status_response(HyperStatusCode::UNAUTHORIZED);
+        }
+
+        let body = match to_bytes(request.into_body()).await {
+            Ok(body) => body,
+            Err(e) => {
+                error!("[WebhookController::handle_request] read body: {:?}", e);
+                return Self::status_response(HyperStatusCode::BAD_REQUEST);
+            }
+        };
+
+        let mut update: Update = match serde_json::from_slice::<Update>(&body) {
+            Ok(update) => update,
+            Err(e) => {
+                /* Answer 200 anyway: Telegram would otherwise resend the same payload forever. */
+                error!(
+                    "[WebhookController::handle_request] Cannot parse update: {:?} / {}",
+                    e,
+                    String::from_utf8_lossy(&body)
+                );
+                return Self::status_response(HyperStatusCode::OK);
+            }
+        };
+
+        /* Unknown update kinds keep their raw JSON, the same way teloxide's polling does. */
+        if let UpdateKind::Error(value) = &mut update.kind {
+            *value = serde_json::from_slice(&body).unwrap_or_default();
+        }
+
+        match route.update_sender.send(Ok(update)) {
+            Ok(()) => Self::status_response(HyperStatusCode::OK),
+            Err(_) => Self::status_response(HyperStatusCode::SERVICE_UNAVAILABLE),
+        }
+    }
+
+    fn status_response(status: HyperStatusCode) -> HyperResponse<Body> {
+        let mut response: HyperResponse<Body> = HyperResponse::new(Body::empty());
+        *response.status_mut() = status;
+        response
+    }
+
+    /// Builds a TLS acceptor from a PEM certificate chain and a PKCS#8 PEM private key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file cannot be read or the pair is not a valid identity.
+    fn load_tls_acceptor(cert_path: &str, key_path: &str) -> anyhow::Result<TlsAcceptor> {
+        let cert_pem: Vec<u8> = std::fs::read(cert_path).map_err(|e| {
+            anyhow!(
+                "[WebhookController::load_tls_acceptor] {}: {:?}",
+                cert_path,
+                e
+            )
+        })?;
+        let key_pem: Vec<u8> = std::fs::read(key_path).map_err(|e| {
+            anyhow!(
+                "[WebhookController::load_tls_acceptor] {}: {:?}",
+                key_path,
+                e
+            )
+        })?;
+
+        let identity: native_tls::Identity = native_tls::Identity::from_pkcs8(&cert_pem, &key_pem)
+            .map_err(|e| anyhow!("[WebhookController::load_tls_acceptor] identity: {:?}", e))?;
+        let acceptor: native_tls::TlsAcceptor = native_tls::TlsAcceptor::new(identity)
+            .map_err(|e| anyhow!("[WebhookController::load_tls_acceptor] acceptor: {:?}", e))?;
+
+        Ok(TlsAcceptor::from(acceptor))
+    }
+}
+
+/* `StatefulListener` needs a named function here: a closure cannot return a borrow of its argument. */
+fn listener_update_stream(state: &mut (UpdateStream, StopToken)) -> &mut UpdateStream {
+    &mut state.0
+}
+
+/// Compares two byte strings without stopping at the first difference, so the response time
+/// does not reveal how much of a guessed secret token was correct.
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right.iter())
+            .fold(0u8, |diff, (l, r)| diff | (l ^ r))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq_matches_only_identical_secrets() {
+        assert!(constant_time_eq(b"secret-1", b"secret-1"));
+        assert!(!constant_time_eq(b"secret-1", b"secret-2"));
+        assert!(!constant_time_eq(b"secret", b"secret-1"));
+        assert!(!constant_time_eq(b"", b"secret"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_request_routes_only_authenticated_updates() {
+        let (update_sender, mut update_receiver) = mpsc::unbounded_channel();
+        let routes: HashMap<String, WebhookRoute> = HashMap::from([(
+            "/telegram/123".to_string(),
+            WebhookRoute {
+                secret_token: "secret-1".to_string(),
+                update_sender,
+            },
+        )]);
+
+        let webhook_request = |path: &str, secret: &str| {
+            Request::post(path)
+                .header(SECRET_TOKEN_HEADER, secret)
+                .body(Body::from(r#"{"update_id":7,"unknown_kind":{}}"#))
+                .unwrap()
+        };
+
+        let response = WebhookController::handle_request(
+            webhook_request("/telegram/456", "secret-1"),
+            &routes,
+        )
+        .await;
+        assert_eq!(response.status(), HyperStatusCode::NOT_FOUND);
+
+        let response = WebhookController::handle_request(
+            webhook_request("/telegram/123", "secret-2"),
+            &routes,
+        )
+        .await;
+        assert_eq!(response.status(), HyperStatusCode::UNAUTHORIZED);
+        assert!(update_receiver.try_recv().is_err());
+
+        let response = WebhookController::handle_request(
+            webhook_request("/telegram/123", "secret-1"),
+            &routes,
+        )
+        .await;
+        assert_eq!(response.status(), HyperStatusCode::OK);
+        assert_eq!(update_receiver.try_recv().unwrap().unwrap().id, 7);
+    }
+}
@@ -2,10 +2,12 @@ pub mod common_process_python_double_dto;
 pub mod es_range_group_seq_query_dto;
 pub mod es_range_room_seq_query_dto;
 pub mod main_controller_services_dto;
+pub mod reconcile_request_dto;
 pub mod stock_pie_data_dto;
 
 pub use common_process_python_double_dto::*;
 pub use es_range_group_seq_query_dto::*;
 pub use es_range_room_seq_query_dto::*;
 pub use main_controller_services_dto::*;
+pub use reconcile_request_dto::*;
 pub use stock_pie_data_dto::*;
\ No newline at end of file
@@ -0,0 +1,160 @@
+use crate::common::*;
+
+use crate::enums::reconcile_scope::*;
+
+use crate::utils_modules::time_utils::*;
+
+pub const RECONCILE_USAGE: &str = "Usage: consume_alert_rust reconcile --from YYYY-MM-DD --to YYYY-MM-DD (--user <user_seq> | --room <room_seq> | --group <agg_group_seq>) [--dry-run]";
+
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct ReconcileRequestDto {
+    /// Inclusive start of the period, as a KST calendar date (midnight, tagged as UTC)
+    pub date_from: DateTime<Utc>,
+    /// Inclusive end of the period, as a KST calendar date (midnight, tagged as UTC)
+    pub date_to: DateTime<Utc>,
+    pub scope: ReconcileScope,
+    /// Only report the differences without re-emitting indexing events
+    pub dry_run: bool,
+}
+
+impl ReconcileRequestDto {
+    /// Parses the arguments that follow the `reconcile` subcommand.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Command-line arguments after `reconcile`
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with `RECONCILE_USAGE` if an option is unknown, missing its value,
+    /// malformed, or if the period or scope is missing or ambiguous.
+    pub fn from_args(args: &[String]) -> anyhow::Result<Self> {
+        let mut date_from: Option<DateTime<Utc>> = None;
+        let mut date_to: Option<DateTime<Utc>> = None;
+        let mut scope: Option<ReconcileScope> = None;
+        let mut dry_run: bool = false;
+
+        let mut args_iter = args.iter();
+
+        while let Some(arg) = args_iter.next() {
+            if arg == "--dry-run" {
+                dry_run = true;
+                continue;
+            }
+
+            let value: &String = args_iter
+                .next()
+                .ok_or_else(|| anyhow!("Missing value for {}\n{}", arg, RECONCILE_USAGE))?;
+
+            let parse_seq = |value: &str| -> anyhow::Result<i64> {
+                value
+                    .parse::<i64>()
+                    .map_err(|_| anyhow!("Invalid {} value: {}\n{}", arg, value, RECONCILE_USAGE))
+            };
+
+            let parsed_scope: ReconcileScope = match arg.as_str() {
+                "--from" => {
+                    date_from = Some(to_utc_datetime(value, "%Y-%m-%d")?);
+                    continue;
+                }
+                "--to" => {
+                    date_to = Some(to_utc_datetime(value, "%Y-%m-%d")?);
+                    continue;
+                }
+                "--user" => ReconcileScope::User(parse_seq(value)?),
+                "--room" => ReconcileScope::Room(parse_seq(value)?),
+                "--group" => ReconcileScope::Group(parse_seq(value)?),
+                _ => return Err(anyhow!("Unknown option: {}\n{}", arg, RECONCILE_USAGE)),
+            };
+
+            if scope.replace(parsed_scope).is_some() {
+                return Err(anyhow!(
+                    "Only one of --user, --room or --group may be given\n{}",
+                    RECONCILE_USAGE
+                ));
+            }
+        }
+
+        let (date_from, date_to, scope) = match (date_from, date_to, scope) {
+            (Some(date_from), Some(date_to), Some(scope)) if date_from <= date_to => {
+                (date_from, date_to, scope)
+            }
+            _ => return Err(anyhow!("Invalid period or scope\n{}", RECONCILE_USAGE)),
+        };
+
+        Ok(ReconcileRequestDto {
+            date_from,
+            date_to,
+            scope,
+            dry_run,
+        })
+    }
+
+    /// Returns the period as real UTC instants: from KST midnight of `date_from` up to, but not
+    /// including, KST midnight of the day after `date_to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a KST datetime cannot be built.
+    pub fn to_spent_at_range(&self) -> anyhow::Result<(DateTime<Utc>, DateTime<Utc>)> {
+        let date_to_next: DateTime<Utc> = self.date_to + chrono::Duration::days(1);
+
+        let spent_at_from: DateTime<Utc> = find_kst_datetime(
+            self.date_from.year(),
+            self.date_from.month(),
+            self.date_from.day(),
+        )?
+        .with_timezone(&Utc);
+
+        let spent_at_to: DateTime<Utc> = find_kst_datetime(
+            date_to_next.year(),
+            date_to_next.month(),
+            date_to_next.day(),
+        )?
+        .with_timezone(&Utc);
+
+        Ok((spent_at_from, spent_at_to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_from_args() {
+        let request: ReconcileRequestDto = ReconcileRequestDto::from_args(&to_args(
+            "--from 2026-10-01 --to 2026-10-31 --group 3 --dry-run",
+        ))
+        .unwrap();
+
+        assert_eq!(*request.scope(), ReconcileScope::Group(3));
+        assert!(*request.dry_run());
+
+        let (spent_at_from, spent_at_to) = request.to_spent_at_range().unwrap();
+        assert_eq!(spent_at_from.to_rfc3339(), "2026-09-30T15:00:00+00:00");
+        assert_eq!(spent_at_to.to_rfc3339(), "2026-10-31T15:00:00+00:00");
+
+        assert!(
+            ReconcileRequestDto::from_args(&to_args("--from 2026-10-01 --to 2026-10-31")).is_err()
+        );
+        assert!(ReconcileRequestDto::from_args(&to_args(
+            "--from 2026-10-31 --to 2026-10-01 --room 1"
+        ))
+        .is_err());
+        assert!(ReconcileRequestDto::from_args(&to_args(
+            "--from 2026-10-01 --to 2026-10-31 --room 1 --user 2"
+        ))
+        .is_err());
+        assert!(ReconcileRequestDto::from_args(&to_args("--from 2026-10-01 --to")).is_err());
+    }
+}
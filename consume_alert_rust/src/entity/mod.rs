@@ -6,6 +6,7 @@ pub mod agg_group;
 pub mod cash_asset;
 pub mod common_consume_keyword_type;
 pub mod common_consume_prodt_keyword;
+pub mod consume_budget;
 pub mod crypto;
 pub mod crypto_asset;
 pub mod currency_code;
@@ -18,6 +19,7 @@ pub mod saving_asset;
 pub mod send_email_agg_group;
 pub mod spent_detail;
 pub mod spent_detail_indexing;
+pub mod spent_detail_outbox;
 pub mod spent_group_info;
 pub mod stock;
 pub mod stock_asset;
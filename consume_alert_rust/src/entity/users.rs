@@ -19,6 +19,7 @@ pub struct Model {
     pub updated_at: Option<DateTime>,
     pub created_by: String,
     pub updated_by: Option<String>,
+    pub payday: i8,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
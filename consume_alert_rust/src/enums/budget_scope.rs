@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Whom a budget applies to: a single Telegram room or every room of its aggregation group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BudgetScope {
+    Room,
+    Group,
+}
+
+impl BudgetScope {
+    /// Returns the value stored in `CONSUME_BUDGET.budget_scope`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetScope::Room => "ROOM",
+            BudgetScope::Group => "GROUP",
+        }
+    }
+
+    /// Returns the name shown to users in budget messages.
+    pub fn scope_nm(&self) -> &'static str {
+        match self {
+            BudgetScope::Room => "개인",
+            BudgetScope::Group => "그룹",
+        }
+    }
+}
+
+impl FromStr for BudgetScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "ROOM" => Ok(BudgetScope::Room),
+            "GROUP" => Ok(BudgetScope::Group),
+            _ => Err(format!("Invalid BudgetScope: {}", s)),
+        }
+    }
+}
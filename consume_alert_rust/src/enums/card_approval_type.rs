@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+/// Kind of card notification: a new approval or the cancellation/refund of an earlier one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CardApprovalType {
+    Approval,
+    Cancellation,
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+
+/// Typed failure returned by a `CardMessageParser` when a notification cannot be turned
+/// into a `SpentDetail`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardParseError {
+    /// No registered parser recognised the notification for the caller's card companies.
+    UnsupportedFormat(String),
+    /// The parser recognised the issuer but a required field was absent.
+    MissingField {
+        parser: &'static str,
+        field: &'static str,
+    },
+    /// A field was present but could not be converted (amount, date, time, ...).
+    InvalidField {
+        parser: &'static str,
+        field: &'static str,
+        value: String,
+    },
+    /// None of the caller's registered `card_alias` values appear in the card name.
+    PaymentMethodNotFound {
+        parser: &'static str,
+        card_name: String,
+    },
+    /// The inferred payment time lies further in the future than the receipt time allows.
+    FutureSpentAt {
+        spent_at: String,
+        received_at: String,
+    },
+}
+
+impl fmt::Display for CardParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardParseError::UnsupportedFormat(header) => write!(
+                f,
+                "[CardParseError] No card parser recognised the notification: {}",
+                header
+            ),
+            CardParseError::MissingField { parser, field } => write!(
+                f,
+                "[CardParseError][{}] Required field '{}' not found",
+                parser, field
+            ),
+            CardParseError::InvalidField {
+                parser,
+                field,
+                value,
+            } => write!(
+                f,
+                "[CardParseError][{}] Invalid value for field '{}': {}",
+                parser, field, value
+            ),
+            CardParseError::PaymentMethodNotFound { parser, card_name } => write!(
+                f,
+                "[CardParseError][{}] No matching payment method found for card_name: {}",
+                parser, card_name
+            ),
+            CardParseError::FutureSpentAt {
+                spent_at,
+                received_at,
+            } => write!(
+                f,
+                "[CardParseError] Payment time {} is later than the receipt time {}",
+                spent_at, received_at
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CardParseError {}
@@ -0,0 +1,67 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+
+/// Typed failure returned by `CommandLine` when a command's arguments do not match its grammar.
+///
+/// The `Display` text is written for the Telegram user; handlers append the command's
+/// examples from `COMMAND_REGISTRY` to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandArgError {
+    /// A `"` was opened but never closed.
+    UnterminatedQuote,
+    /// A required argument was not given.
+    MissingArg(&'static str),
+    /// More arguments were given than the command accepts.
+    UnexpectedArgs(Vec<String>),
+    /// The argument does not have a form the command accepts.
+    InvalidArg { arg_nm: &'static str, value: String },
+    /// The argument is not a `key:value` pair with both sides present.
+    InvalidKeyValue(String),
+    /// A numeric argument could not be parsed or is out of range.
+    InvalidNumber { field: &'static str, value: String },
+    /// A date argument does not match the expected format or is not a real date.
+    InvalidDate { value: String, format: &'static str },
+    /// The start of a date range lies after its end.
+    InvalidDateRange {
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    },
+}
+
+impl fmt::Display for CommandArgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandArgError::UnterminatedQuote => {
+                write!(f, "A quoted argument is missing its closing \".")
+            }
+            CommandArgError::MissingArg(arg_nm) => write!(f, "Missing argument: {}", arg_nm),
+            CommandArgError::UnexpectedArgs(args) => {
+                write!(f, "Unexpected argument(s): {}", args.join(" "))
+            }
+            CommandArgError::InvalidArg { arg_nm, value } => {
+                write!(f, "Invalid {}: {}", arg_nm, value)
+            }
+            CommandArgError::InvalidKeyValue(value) => {
+                write!(f, "Expected <name>:<amount> but got: {}", value)
+            }
+            CommandArgError::InvalidNumber { field, value } => {
+                write!(f, "The {} must be numeric: {}", field, value)
+            }
+            CommandArgError::InvalidDate { value, format } => {
+                write!(f, "Invalid date '{}'. Please use the format {}", value, format)
+            }
+            CommandArgError::InvalidDateRange {
+                start_date,
+                end_date,
+            } => write!(
+                f,
+                "Invalid date range. The start date ({}) must be earlier than or equal to the end date ({}).",
+                start_date.format("%Y.%m.%d"),
+                end_date.format("%Y.%m.%d")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CommandArgError {}
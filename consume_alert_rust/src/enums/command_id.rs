@@ -0,0 +1,26 @@
+/// Identifies the handler a registered Telegram command is dispatched to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandId {
+    Help,
+    Consumption,
+    ConsumptionBudget,
+    DeleteRecentConsumption,
+    ModifyConsumption,
+    InstallmentBalance,
+    ConsumptionPerMon,
+    ConsumptionPerTerm,
+    ConsumptionPerDay,
+    ConsumptionPerSalary,
+    ConsumptionPerWeek,
+    ConsumptionPerYear,
+    ConsumptionPerSalaryGroup,
+    ConsumptionPerMonGroup,
+    ConsumptionPerDayGroup,
+    ConsumptionPerWeekGroup,
+    ConsumptionPerYearGroup,
+    EarnedDetailByWon,
+    EarnedDetailByDollar,
+    Payday,
+    ShowAllAsset,
+    NetWorthHistory,
+}
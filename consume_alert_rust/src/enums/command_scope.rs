@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Whose data a Telegram command works on: the caller's room or its aggregation group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommandScope {
+    Room,
+    Group,
+}
+
+impl CommandScope {
+    /// Returns the name shown to users in help messages.
+    pub fn scope_nm(&self) -> &'static str {
+        match self {
+            CommandScope::Room => "개인",
+            CommandScope::Group => "그룹",
+        }
+    }
+}
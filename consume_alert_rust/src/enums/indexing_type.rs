@@ -8,6 +8,17 @@ pub enum IndexingType {
     Delete,
 }
 
+impl IndexingType {
+    /// Returns the single-letter code used in Kafka events and `SPENT_DETAIL_OUTBOX`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IndexingType::Insert => "I",
+            IndexingType::Update => "U",
+            IndexingType::Delete => "D",
+        }
+    }
+}
+
 impl FromStr for IndexingType {
     type Err = String;
 
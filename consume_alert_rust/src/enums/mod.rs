@@ -1,2 +1,11 @@
+pub mod budget_scope;
+pub mod card_approval_type;
+pub mod card_parse_error;
+pub mod command_arg_error;
+pub mod command_id;
+pub mod command_scope;
 pub mod indexing_type;
 pub mod range_operator;
+pub mod reconcile_scope;
+pub mod spent_at_status;
+pub mod spent_detail_edit;
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Which spending a reconciliation covers: one user, one Telegram room or one aggregation group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReconcileScope {
+    User(i64),
+    Room(i64),
+    Group(i64),
+}
+
+impl ReconcileScope {
+    /// Returns the `CONSUME_DETAIL` document field the scope filters on.
+    pub fn es_field(&self) -> &'static str {
+        match self {
+            ReconcileScope::User(_) => "user_seq",
+            ReconcileScope::Room(_) => "room_seq",
+            ReconcileScope::Group(_) => "agg_group_seq",
+        }
+    }
+
+    /// Returns the user, room or group sequence of the scope.
+    pub fn scope_seq(&self) -> i64 {
+        match self {
+            ReconcileScope::User(scope_seq)
+            | ReconcileScope::Room(scope_seq)
+            | ReconcileScope::Group(scope_seq) => *scope_seq,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Plausibility of the timestamp inferred from a card notification relative to its receipt time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpentAtStatus {
+    Normal,
+    /// The inferred year differs from the year the notification was received in.
+    YearAdjusted,
+    /// The payment happened long before the notification was received.
+    Stale,
+}
@@ -0,0 +1,13 @@
+use chrono::{DateTime, FixedOffset};
+
+/// Single-column correction applied to a recorded spending through the `ce` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpentDetailEdit {
+    ConsumeKeywordType {
+        consume_keyword_type_id: i64,
+        consume_keyword_type: String,
+    },
+    SpentName(String),
+    SpentMoney(i64),
+    SpentAt(DateTime<FixedOffset>),
+}
@@ -77,6 +77,24 @@ History     : 2023-05-04 Seunghwan Shin       # [v.1.0.0] first create
               2026-05-12 Seunghwan Shin       # [v.4.4.0] Modified modify_nh_card to handle multiple payment notification formats and persist all cases to the database
               2026-06-16 Seunghwan Shin       # [v.4.5.0] Removed image file storage and changed the API to return image bytes directly.
               2026-07-02 Seunghwan Shin       # [v.4.5.1] Fix Elasticsearch query filter issue caused by incomplete date format
+              2026-10-18 Seunghwan Shin       # [v.4.6.0] Replaced the NH/Samsung branches with a pluggable CardMessageParser registry and added Shinhan, KB, Hyundai, Lotte, Woori, Hana, BC and check-card parsers
+              2026-10-18 Seunghwan Shin       # [v.4.7.0] Added room/group budgets per salary period (cb) with threshold alerts after each recorded payment
+              2026-10-18 Seunghwan Shin       # [v.4.8.0] Made the payday configurable per user (pd) and moved paydays on weekends/holidays to the previous business day via DIM_CALENDAR
+              2026-10-18 Seunghwan Shin       # [v.4.9.0] Added an optional in-process Kafka consumer that indexes SPENT_DETAIL changes into Elasticsearch with a dead-letter topic
+              2026-10-18 Seunghwan Shin       # [v.4.10.0] SPENT_DETAIL changes are now recorded in SPENT_DETAIL_OUTBOX within the same transaction and published to Kafka by a background relay
+              2026-10-18 Seunghwan Shin       # [v.4.11.0] Added the "reconcile" subcommand that compares SPENT_DETAIL with the CONSUME_DETAIL index and re-emits indexing events for the differences
+              2026-10-18 Seunghwan Shin       # [v.4.12.0] Report commands now read SPENT_DETAIL from MySQL when Elasticsearch fails or exceeds ES_READ_TIMEOUT_MS
+              2026-10-18 Seunghwan Shin       # [v.4.13.0] Added an in-process plotters chart renderer with Korean font support, selected with GRAPH_RENDERER=native
+              2026-10-18 Seunghwan Shin       # [v.4.14.0] Report commands send their text first, retry graphs with backoff, report graph failures as a notice and accept a "-t" text-only switch
+              2026-10-18 Seunghwan Shin       # [v.4.15.0] Replaced teloxide::repl with Dispatchers that run different chats in parallel on a bounded command pool and drain in-flight commands on SIGTERM
+              2026-10-18 Seunghwan Shin       # [v.4.16.0] Added webhook mode (TELEGRAM_UPDATE_MODE=webhook) serving every bot on one embedded HTTP(S) listener with per-bot secret tokens
+              2026-10-18 Seunghwan Shin       # [v.4.17.0] Added a declarative command registry driving dispatch, usage hints, /help and the setMyCommands menu
+              2026-10-18 Seunghwan Shin       # [v.4.18.0] Replaced ad-hoc argument splitting with a quote-aware command tokenizer returning typed arguments and errors
+              2026-10-18 Seunghwan Shin       # [v.4.19.0] Added scheduled monthly HTML consumption report mails per aggregation group (REPORT_MAIL_CRON)
+              2026-10-18 Seunghwan Shin       # [v.4.20.0] Added the daily asset snapshot job (ASSET_SNAPSHOT_CRON) and the "asset-snapshot" subcommand filling USER_CURRENT_ASSET_SNAPSHOT
+              2026-10-18 Seunghwan Shin       # [v.4.21.0] Added the "mh" net-worth history command charting USER_CURRENT_ASSET_SNAPSHOT as a stacked area chart with a text table fallback
+              2026-10-18 Seunghwan Shin       # [v.4.22.0] Added the scheduled stock price refresh (STOCK_PRICE_CRON) behind QuotePriceProvider, quoting open markets through KIS with the token stored in KIS_API_TOKEN
+              2026-10-18 Seunghwan Shin       # [v.4.23.0] Added the scheduled crypto price refresh (CRYPTO_PRICE_CRON) behind CryptoQuoteProvider with an Upbit KRW provider, and a stale price warning in "my"
 */
 mod common;
 use common::*;
@@ -85,24 +103,35 @@ mod config;
 use config::AppConfig;
 
 mod repository;
-use repository::{es_repository::*, kafka_repository::*, mysql_repository::*, redis_repository::*};
+use repository::{
+    es_repository::*, kafka_consumer_repository::*, kafka_repository::*, mysql_repository::*,
+    redis_repository::*, smtp_repository::*,
+};
 
 mod utils_modules;
+use utils_modules::time_utils::*;
 
 mod schema;
 
 mod services;
 
 use services::{
-    cache_service_impl::*, elastic_query_service_impl::*, graph_api_service_impl::*,
-    mysql_query_service_impl::*, process_service_impl::*, producer_service_impl::*,
-    redis_service_impl::*, telebot_service_impl::*,
+    cache_service_impl::*, card_message_parser_impl::*, consume_classifier_impl::*,
+    consumer_service_impl::*, crypto_quote_provider_impl::*, elastic_query_service_impl::*,
+    graph_api_service_impl::*, mysql_query_service_impl::*, process_service_impl::*,
+    producer_service_impl::*, quote_price_provider_impl::*, redis_service_impl::*,
+    smtp_service_impl::*, telebot_service_impl::*,
 };
 
 mod controller;
-use controller::main_controller::*;
+use controller::{
+    asset_snapshot_controller::*, crypto_price_controller::*, indexing_controller::*,
+    main_controller::*, outbox_relay_controller::*, reconcile_controller::*,
+    report_mail_controller::*, stock_price_controller::*, webhook_controller::*,
+};
 
 mod configuration;
+use configuration::command_registry::COMMAND_REGISTRY;
 
 mod dtos;
 use dtos::*;
@@ -116,13 +145,22 @@ mod entity;
 mod views;
 
 mod service_traits;
+use service_traits::{consume_classifier::*, crypto_quote_provider::*};
+
+use teloxide::{
+    dispatching::ShutdownToken, error_handlers::LoggingErrorHandler, types::BotCommand,
+};
+use tokio::sync::Semaphore;
 
 /* ─── Concrete service types used throughout main ─────────────────────────── */
 type AppRedisService = RedisServiceImpl<RedisRepositoryImpl>;
 type AppElasticService = ElasticQueryServiceImpl<EsRepositoryPub>;
 type AppMysqlService = MysqlQueryServiceImpl<MysqlRepositoryImpl>;
 type AppProducerService = ProducerServiceImpl<KafkaRepositoryImpl>;
+type AppConsumerService = ConsumerServiceImpl<KafkaConsumerRepositoryImpl>;
 type AppCacheService = CacheServiceImpl<AppRedisService, AppMysqlService>;
+type AppQuotePriceProvider = KisQuotePriceProvider<AppMysqlService>;
+type AppConsumeClassifier = ConsumeClassifierChain;
 /* ─────────────────────────────────────────────────────────────────────────── */
 
 #[tokio::main]
@@ -183,98 +221,556 @@ async fn main() {
     let elastic_query_service: Arc<AppElasticService> =
         Arc::new(AppElasticService::new(elastic_conn));
     let mysql_query_service: Arc<AppMysqlService> = Arc::new(AppMysqlService::new(mysql_conn));
-    let process_service: Arc<ProcessServiceImpl> = Arc::new(ProcessServiceImpl::new());
+    let process_service: Arc<ProcessServiceImpl> = Arc::new(ProcessServiceImpl::new(Arc::new(
+        CardMessageParserRegistry::with_default_parsers(),
+    )));
     let producer_service: Arc<AppProducerService> = Arc::new(AppProducerService::new(kafka_conn));
 
+    /* One-shot maintenance: `consume_alert_rust reconcile ...` runs instead of the bots. */
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("reconcile") {
+        run_reconcile(
+            &args[2..],
+            elastic_query_service,
+            mysql_query_service,
+            process_service,
+            producer_service,
+        )
+        .await;
+        return;
+    }
+
+    /* One-shot maintenance: `consume_alert_rust asset-snapshot` (re)stores today's snapshot. */
+    if args.get(1).map(String::as_str) == Some("asset-snapshot") {
+        run_asset_snapshot(&args[2..], mysql_query_service).await;
+        return;
+    }
+
     let cache_service: Arc<AppCacheService> = Arc::new(AppCacheService::new(
         Arc::clone(&redis_service),
         Arc::clone(&mysql_query_service),
     ));
 
-    /* Build one Bot per token listed in BOT_TOKENS.
-     * Each bot runs its own independent teloxide::repl loop in a separate
-     * tokio task, but all bots share the same service instances via Arc. */
+    /* Category classifiers: the primary one is asked first, the other serves as its fallback. */
     let app_config: &AppConfig = AppConfig::get_global();
+    let elastic_consume_classifier: Arc<dyn ConsumeClassifier> = Arc::new(
+        ElasticConsumeClassifier::new(Arc::clone(&elastic_query_service)),
+    );
+    let mysql_consume_classifier: Arc<dyn ConsumeClassifier> =
+        Arc::new(MysqlConsumeClassifier::new(
+            Arc::clone(&mysql_query_service),
+            *app_config.consume_keyword_refresh_secs(),
+        ));
+
+    let mut consume_classifier_chain: AppConsumeClassifier = AppConsumeClassifier::default();
+    if app_config.consume_classifier_primary() == "mysql" {
+        consume_classifier_chain.register(mysql_consume_classifier);
+        consume_classifier_chain.register(elastic_consume_classifier);
+    } else {
+        consume_classifier_chain.register(elastic_consume_classifier);
+        consume_classifier_chain.register(mysql_consume_classifier);
+    }
+    info!(
+        "[main] Consume classifier order: {:?}",
+        consume_classifier_chain.classifier_nms()
+    );
+    let consume_classifier: Arc<AppConsumeClassifier> = Arc::new(consume_classifier_chain);
+
+    /* Background jobs run until the process exits; they are not part of the graceful shutdown
+     * because both resume safely from MySQL/Kafka state after a restart. */
+    /* Outbox relay: publishes SPENT_DETAIL change events recorded in SPENT_DETAIL_OUTBOX. */
+    let outbox_relay_controller = OutboxRelayController::new(
+        Arc::clone(&mysql_query_service),
+        Arc::clone(&producer_service),
+    );
+
+    tokio::spawn(async move { outbox_relay_controller.main_call_function().await });
+
+    /* Optional indexing consumer: mirrors SPENT_DETAIL change events into Elasticsearch. */
+    if *app_config.indexing_consumer_enabled() {
+        let kafka_consumer_conn: KafkaConsumerRepositoryImpl =
+            match KafkaConsumerRepositoryImpl::new(
+                app_config.produce_topic(),
+                app_config.indexing_consumer_group_id(),
+            ) {
+                Ok(kafka_consumer_conn) => kafka_consumer_conn,
+                Err(e) => {
+                    error!("[main] kafka_consumer_conn: {:#}", e);
+                    panic!("[main] kafka_consumer_conn: {:#}", e)
+                }
+            };
+
+        let indexing_controller = IndexingController::new(
+            Arc::clone(&elastic_query_service),
+            Arc::clone(&mysql_query_service),
+            AppConsumerService::new(kafka_consumer_conn),
+            Arc::clone(&producer_service),
+        );
+
+        tokio::spawn(async move { indexing_controller.main_call_function().await });
+    }
+
+    /* Optional daily asset snapshot: stores what `my` shows into USER_CURRENT_ASSET_SNAPSHOT. */
+    if app_config.asset_snapshot_schedule().is_some() {
+        let asset_snapshot_controller =
+            AssetSnapshotController::new(Arc::clone(&mysql_query_service));
+
+        tokio::spawn(async move { asset_snapshot_controller.main_call_function().await });
+    }
+
+    /* Optional stock price refresh: quotes the open markets through KIS into STOCK.stock_price. */
+    if app_config.stock_price_schedule().is_some() {
+        let quote_price_provider: AppQuotePriceProvider =
+            match KisQuotePriceProvider::new(Arc::clone(&mysql_query_service)) {
+                Ok(quote_price_provider) => quote_price_provider,
+                Err(e) => {
+                    error!("[main] quote_price_provider: {:#}", e);
+                    panic!("[main] quote_price_provider: {:#}", e)
+                }
+            };
+
+        let stock_price_controller = StockPriceController::new(
+            Arc::clone(&mysql_query_service),
+            Arc::new(quote_price_provider),
+        );
+
+        tokio::spawn(async move { stock_price_controller.main_call_function().await });
+    }
+
+    /* Optional crypto price refresh: quotes CRYPTO.crypto_price through the provider of each currency. */
+    if app_config.crypto_price_schedule().is_some() {
+        let upbit_quote_provider: UpbitCryptoQuoteProvider = match UpbitCryptoQuoteProvider::new() {
+            Ok(upbit_quote_provider) => upbit_quote_provider,
+            Err(e) => {
+                error!("[main] upbit_quote_provider: {:#}", e);
+                panic!("[main] upbit_quote_provider: {:#}", e)
+            }
+        };
+
+        let crypto_price_controller = CryptoPriceController::new(
+            Arc::clone(&mysql_query_service),
+            vec![Arc::new(upbit_quote_provider) as Arc<dyn CryptoQuoteProvider>],
+        );
+
+        tokio::spawn(async move { crypto_price_controller.main_call_function().await });
+    }
+
+    /* Optional monthly report mails: one HTML report per active aggregation group. */
+    if app_config.report_mail_schedule().is_some() {
+        let smtp_conn: SmtpRepositoryImpl = match SmtpRepositoryImpl::new() {
+            Ok(smtp_conn) => smtp_conn,
+            Err(e) => {
+                error!("[main] smtp_conn: {:#}", e);
+                panic!("[main] smtp_conn: {:#}", e)
+            }
+        };
+
+        let report_mail_controller = ReportMailController::new(
+            Arc::clone(&arc_graph_api_service),
+            Arc::clone(&mysql_query_service),
+            Arc::clone(&process_service),
+            SmtpServiceImpl::new(smtp_conn),
+        );
+
+        tokio::spawn(async move { report_mail_controller.main_call_function().await });
+    }
+
+    let bot_services: BotServices = BotServices {
+        graph_api_service: arc_graph_api_service,
+        elastic_query_service,
+        mysql_query_service,
+        process_service,
+        redis_service,
+        cache_service,
+        consume_classifier,
+        command_permits: Arc::new(Semaphore::new(*app_config.command_worker_limit())),
+    };
+
+    /* Build one Dispatcher per token listed in BOT_TOKENS.
+     * A Dispatcher handles the updates of one chat in order but different chats in parallel;
+     * `command_permits` bounds how many commands run at once across all bots. */
     let bots: Vec<Arc<Bot>> = app_config
         .bot_tokens()
         .iter()
         .map(|token| Arc::new(Bot::new(token)))
         .collect();
 
-    info!("[main] Starting {} bot(s)", bots.len());
+    info!(
+        "[main] Starting {} bot(s) with up to {} concurrent command(s)",
+        bots.len(),
+        app_config.command_worker_limit()
+    );
 
+    let mut shutdown_tokens: Vec<ShutdownToken> = Vec::new();
     let mut handles: Vec<task::JoinHandle<()>> = Vec::new();
 
-    for bot in bots {
-        let handle = tokio::spawn({
-            /* Clone Arc pointers — cheap, no data is copied. */
-            let graph_api_service: Arc<GraphApiServiceImpl> = Arc::clone(&arc_graph_api_service);
-            let elastic_query_service: Arc<AppElasticService> = Arc::clone(&elastic_query_service);
-            let mysql_query_service: Arc<AppMysqlService> = Arc::clone(&mysql_query_service);
-            let process_service: Arc<ProcessServiceImpl> = Arc::clone(&process_service);
-            let producer_service: Arc<AppProducerService> = Arc::clone(&producer_service);
-            let redis_service: Arc<AppRedisService> = Arc::clone(&redis_service);
-            let cache_service: Arc<AppCacheService> = Arc::clone(&cache_service);
-
-            async move {
+    /* In webhook mode every bot is served by one embedded listener instead of a poll loop. */
+    let mut webhook_controller: Option<WebhookController> =
+        if app_config.telegram_update_mode() == "webhook" {
+            match WebhookController::new() {
+                Ok(webhook_controller) => Some(webhook_controller),
+                Err(e) => {
+                    error!("[main] webhook_controller: {:#}", e);
+                    panic!("[main] webhook_controller: {:#}", e)
+                }
+            }
+        } else {
+            None
+        };
+
+    for (bot_idx, bot) in bots.into_iter().enumerate() {
+        /* A stale command menu is only cosmetic, so a failure here does not stop the bot. */
+        if let Err(e) = publish_bot_commands(&bot).await {
+            warn!("[main] publish_bot_commands: {:#}", e);
+        }
+
+        let mut dispatcher = Dispatcher::builder(
+            Arc::clone(&bot),
+            Update::filter_message().endpoint(handle_telegram_message),
+        )
+        .dependencies(dptree::deps![bot_services.clone()])
+        .default_handler(|_| async {})
+        .build();
+
+        shutdown_tokens.push(dispatcher.shutdown_token());
+
+        match webhook_controller.as_mut() {
+            Some(webhook_controller) => {
+                let secret_token: String = app_config.webhook_secret_tokens()[bot_idx].clone();
+                let route_path: String = match register_webhook(&bot, &secret_token).await {
+                    Ok(route_path) => route_path,
+                    Err(e) => {
+                        error!("[main] register_webhook: {:#}", e);
+                        panic!("[main] register_webhook: {:#}", e)
+                    }
+                };
+
+                let update_listener = webhook_controller.register_bot(route_path, secret_token);
+
+                handles.push(tokio::spawn(async move {
+                    dispatcher
+                        .dispatch_with_listener(
+                            update_listener,
+                            LoggingErrorHandler::with_custom_text("Webhook listener error"),
+                        )
+                        .await
+                }));
+            }
+            None => {
                 info!(
                     "[main] Bot polling started (token prefix: {}...)",
                     &bot.token()[..8]
                 );
 
-                /* Each bot runs its own repl loop.
-                 * teloxide::repl polls the Telegram API and dispatches messages
-                 * to the handler closure one at a time (per bot). */
-                teloxide::repl(bot, move |message: Message, bot: Arc<Bot>| {
-                    let graph_api_service: Arc<GraphApiServiceImpl> =
-                        Arc::clone(&graph_api_service);
-                    let elastic_query_service: Arc<AppElasticService> =
-                        Arc::clone(&elastic_query_service);
-                    let mysql_query_service: Arc<AppMysqlService> =
-                        Arc::clone(&mysql_query_service);
-                    let process_service: Arc<ProcessServiceImpl> = Arc::clone(&process_service);
-                    let producer_service: Arc<AppProducerService> = Arc::clone(&producer_service);
-                    let redis_service: Arc<AppRedisService> = Arc::clone(&redis_service);
-                    let cache_service: Arc<AppCacheService> = Arc::clone(&cache_service);
-
-                    async move {
-                        let tele_bot_service: TelebotServiceImpl =
-                            TelebotServiceImpl::new(bot, message);
-                        let main_controller = MainController::new(MainControllerServicesDto {
-                            graph_api_service,
-                            elastic_query_service,
-                            mysql_query_service,
-                            tele_bot_service,
-                            process_service,
-                            producer_service,
-                            redis_service,
-                            cache_service,
-                        });
-
-                        match main_controller.main_call_function().await {
-                            Ok(_) => {
-                                info!("respond success.");
-                            }
-                            Err(e) => {
-                                input_error_log(e).await;
-                            }
-                        };
-
-                        respond(())
-                    }
-                })
-                .await
+                handles.push(tokio::spawn(async move { dispatcher.dispatch().await }));
             }
-        });
+        }
+    }
 
-        handles.push(handle);
+    /* The listener outlives the Dispatchers during shutdown and answers 503 for stopped bots,
+     * so Telegram keeps those updates queued for the next instance. */
+    if let Some(webhook_controller) = webhook_controller {
+        tokio::spawn(async move {
+            if let Err(e) = webhook_controller.main_call_function().await {
+                error!("[main] webhook_controller: {:#}", e);
+                std::process::exit(1);
+            }
+        });
     }
 
-    /* Wait for all bot tasks.  In normal operation they run forever;
+    tokio::spawn(shutdown_on_signal(shutdown_tokens));
+
+    /* Wait for all bot tasks. In normal operation they run until a shutdown signal;
      * if one task panics the error is logged but the others keep running. */
     for handle in handles {
         if let Err(e) = handle.await {
             error!("[main] Bot task ended unexpectedly: {:?}", e);
         }
     }
+
+    info!("Consume Alert Program End");
+}
+
+/* Services shared by every Telegram update handler, injected into the Dispatchers. */
+#[derive(Clone)]
+struct BotServices {
+    graph_api_service: Arc<GraphApiServiceImpl>,
+    elastic_query_service: Arc<AppElasticService>,
+    mysql_query_service: Arc<AppMysqlService>,
+    process_service: Arc<ProcessServiceImpl>,
+    redis_service: Arc<AppRedisService>,
+    cache_service: Arc<AppCacheService>,
+    consume_classifier: Arc<AppConsumeClassifier>,
+    command_permits: Arc<Semaphore>,
+}
+
+/// Points the bot's Telegram webhook at `WEBHOOK_PUBLIC_URL/<bot id>` with its secret token.
+///
+/// The webhook is left registered on shutdown, so updates sent while the process restarts stay
+/// queued at Telegram instead of being dropped.
+///
+/// # Arguments
+///
+/// * `bot` - The bot whose webhook is registered
+/// * `secret_token` - Secret Telegram sends back in every webhook request of this bot
+///
+/// # Returns
+///
+/// Returns the request path the embedded listener must route to this bot.
+///
+/// # Errors
+///
+/// Returns an error if the webhook URL is invalid or Telegram rejects `setWebhook`.
+async fn register_webhook(bot: &Bot, secret_token: &str) -> anyhow::Result<String> {
+    let public_url: &String = AppConfig::get_global()
+        .webhook_public_url()
+        .as_ref()
+        .ok_or_else(|| anyhow!("[register_webhook] WEBHOOK_PUBLIC_URL is not set"))?;
+
+    /* The numeric bot id is the part of the token before ':' and is not itself a secret. */
+    let bot_id: &str = bot.token().split(':').next().unwrap_or_default();
+    let webhook_url: reqwest::Url = reqwest::Url::parse(&format!("{}/{}", public_url, bot_id))
+        .map_err(|e| anyhow!("[register_webhook] Invalid webhook url: {:?}", e))?;
+
+    bot.set_webhook(webhook_url.clone())
+        .secret_token(secret_token.to_string())
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "[register_webhook] setWebhook failed for bot {}: {:?}",
+                bot_id,
+                e
+            )
+        })?;
+
+    info!("[register_webhook] Bot webhook registered: {}", webhook_url);
+
+    Ok(webhook_url.path().to_string())
+}
+
+/// Publishes `COMMAND_REGISTRY` as the bot's Telegram command menu (`setMyCommands`).
+///
+/// Aliases are left out of the menu; they are still accepted when typed.
+///
+/// # Arguments
+///
+/// * `bot` - The bot whose command menu is replaced
+///
+/// # Errors
+///
+/// Returns an error if Telegram rejects `setMyCommands`.
+async fn publish_bot_commands(bot: &Bot) -> anyhow::Result<()> {
+    let bot_commands: Vec<BotCommand> = COMMAND_REGISTRY
+        .iter()
+        .map(|command_spec| BotCommand::new(command_spec.name, command_spec.summary))
+        .collect();
+
+    bot.set_my_commands(bot_commands)
+        .await
+        .map_err(|e| anyhow!("[publish_bot_commands] setMyCommands failed: {:?}", e))?;
+
+    Ok(())
+}
+
+/// Handles one Telegram message: waits for a free command slot, then runs the command.
+///
+/// # Arguments
+///
+/// * `bot` - The bot that received the message
+/// * `message` - The received message
+/// * `bot_services` - Shared services and the command slot pool
+///
+/// # Returns
+///
+/// Always returns `Ok(())`; command failures are logged instead of stopping the Dispatcher.
+async fn handle_telegram_message(
+    bot: Arc<Bot>,
+    message: Message,
+    bot_services: BotServices,
+) -> ResponseResult<()> {
+    let _command_permit = match bot_services.command_permits.acquire().await {
+        Ok(command_permit) => command_permit,
+        Err(e) => {
+            error!("[handle_telegram_message] Command pool closed: {:?}", e);
+            return respond(());
+        }
+    };
+
+    let tele_bot_service: TelebotServiceImpl = TelebotServiceImpl::new(bot, message);
+    let main_controller = MainController::new(MainControllerServicesDto {
+        graph_api_service: bot_services.graph_api_service,
+        elastic_query_service: bot_services.elastic_query_service,
+        mysql_query_service: bot_services.mysql_query_service,
+        tele_bot_service,
+        process_service: bot_services.process_service,
+        redis_service: bot_services.redis_service,
+        cache_service: bot_services.cache_service,
+        consume_classifier: bot_services.consume_classifier,
+    });
+
+    match main_controller.main_call_function().await {
+        Ok(_) => {
+            info!("respond success.");
+        }
+        Err(e) => {
+            input_error_log(e).await;
+        }
+    };
+
+    respond(())
+}
+
+/// Waits for SIGTERM or Ctrl-C, then stops every Dispatcher from taking new updates and lets
+/// them finish the commands already received.
+///
+/// The process exits with status 1 if the commands are not drained within
+/// `SHUTDOWN_TIMEOUT_SECS`.
+///
+/// # Arguments
+///
+/// * `shutdown_tokens` - One token per running Dispatcher
+async fn shutdown_on_signal(shutdown_tokens: Vec<ShutdownToken>) {
+    wait_for_shutdown_signal().await;
+
+    let shutdown_timeout: Duration =
+        Duration::from_secs(*AppConfig::get_global().shutdown_timeout_secs());
+
+    info!(
+        "[main] Shutdown requested, draining in-flight commands (timeout {:?})",
+        shutdown_timeout
+    );
+
+    let drain_dispatchers = async {
+        for shutdown_token in &shutdown_tokens {
+            match shutdown_token.shutdown() {
+                Ok(dispatcher_stopped) => dispatcher_stopped.await,
+                Err(e) => info!("[main] Dispatcher already idle: {:?}", e),
+            }
+        }
+    };
+
+    if tokio::time::timeout(shutdown_timeout, drain_dispatchers)
+        .await
+        .is_err()
+    {
+        error!(
+            "[main] In-flight commands were not drained within {:?}, exiting",
+            shutdown_timeout
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Resolves when the process receives SIGTERM (Unix only) or Ctrl-C.
+async fn wait_for_shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = sigterm.recv() => info!("[main] SIGTERM received"),
+                    _ = tokio::signal::ctrl_c() => info!("[main] Ctrl-C received"),
+                }
+                return;
+            }
+            Err(e) => error!("[main] Failed to listen for SIGTERM: {:?}", e),
+        }
+    }
+
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        error!("[main] Failed to listen for Ctrl-C: {:?}", e);
+        std::future::pending::<()>().await;
+    }
+
+    info!("[main] Ctrl-C received");
+}
+
+/// Runs the `reconcile` subcommand and drains the outbox it filled, then exits the process
+/// with a non-zero status if the arguments are invalid or any step fails.
+///
+/// # Arguments
+///
+/// * `args` - Command-line arguments after `reconcile`
+/// * `elastic_query_service` - Source of the CONSUME_DETAIL documents
+/// * `mysql_query_service` - Source of the SPENT_DETAIL rows and sink of the outbox events
+/// * `process_service` - Computes the differences between both stores
+/// * `producer_service` - Publishes the recorded outbox events
+async fn run_reconcile(
+    args: &[String],
+    elastic_query_service: Arc<AppElasticService>,
+    mysql_query_service: Arc<AppMysqlService>,
+    process_service: Arc<ProcessServiceImpl>,
+    producer_service: Arc<AppProducerService>,
+) {
+    let request: ReconcileRequestDto = match ReconcileRequestDto::from_args(args) {
+        Ok(request) => request,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let reconcile_controller = ReconcileController::new(
+        elastic_query_service,
+        Arc::clone(&mysql_query_service),
+        process_service,
+    );
+
+    if let Err(e) = reconcile_controller.main_call_function(&request).await {
+        error!("[main] reconcile: {:#}", e);
+        eprintln!("[reconcile] {:#}", e);
+        std::process::exit(1);
+    }
+
+    if *request.dry_run() {
+        return;
+    }
+
+    let outbox_relay_controller = OutboxRelayController::new(mysql_query_service, producer_service);
+
+    match outbox_relay_controller.relay_all_pending().await {
+        Ok(relayed_cnt) => println!("[reconcile] Published {} outbox event(s).", relayed_cnt),
+        Err(e) => {
+            error!("[main] reconcile relay: {:#}", e);
+            eprintln!(
+                "[reconcile] Outbox relay failed, the background relay will retry: {:#}",
+                e
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Runs the `asset-snapshot` subcommand, storing the asset snapshot of every user under
+/// today's KST date, then exits the process with a non-zero status if arguments are given or
+/// the snapshot fails. Missed days cannot be backfilled because only current holdings are known.
+///
+/// # Arguments
+///
+/// * `args` - Command-line arguments after `asset-snapshot`
+/// * `mysql_query_service` - Source of the holdings and sink of the snapshot rows
+async fn run_asset_snapshot(args: &[String], mysql_query_service: Arc<AppMysqlService>) {
+    const ASSET_SNAPSHOT_USAGE: &str = "Usage: consume_alert_rust asset-snapshot";
+
+    if !args.is_empty() {
+        eprintln!("{}", ASSET_SNAPSHOT_USAGE);
+        std::process::exit(2);
+    }
+
+    let asset_snapshot_controller = AssetSnapshotController::new(mysql_query_service);
+
+    match asset_snapshot_controller.take_snapshots().await {
+        Ok(stored_cnt) => println!(
+            "[asset-snapshot] Stored the {} snapshot of {} user(s).",
+            find_current_kor_naivedate().format("%Y-%m-%d"),
+            stored_cnt
+        ),
+        Err(e) => {
+            error!("[main] asset-snapshot: {:#}", e);
+            eprintln!("[asset-snapshot] {:#}", e);
+            std::process::exit(1);
+        }
+    }
 }
@@ -1,5 +1,7 @@
 use crate::common::*;
 
+use crate::entity::agg_group;
+
 /// Represents an aggregation group for grouping Telegram rooms.
 ///
 /// Maps to the `AGG_GROUP` table. One `AggGroup` can be associated with
@@ -39,3 +41,19 @@ pub struct AggGroup {
     /// Who last updated this record
     pub updated_by: Option<String>,
 }
+
+impl From<agg_group::Model> for AggGroup {
+    fn from(model: agg_group::Model) -> Self {
+        AggGroup {
+            agg_group_seq: model.agg_group_seq,
+            agg_group_name: model.agg_group_name,
+            is_active: model.is_active,
+            created_at: DateTime::from_naive_utc_and_offset(model.created_at, Utc),
+            updated_at: model
+                .updated_at
+                .map(|updated_at| DateTime::from_naive_utc_and_offset(updated_at, Utc)),
+            created_by: model.created_by,
+            updated_by: model.updated_by,
+        }
+    }
+}
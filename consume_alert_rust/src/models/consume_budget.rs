@@ -0,0 +1,179 @@
+use crate::common::*;
+
+use crate::entity::consume_budget::{self, ActiveModel};
+use crate::enums::budget_scope::*;
+
+/// `CONSUME_BUDGET.consume_keyword_type_id` value of a budget that covers every category.
+pub const OVERALL_BUDGET_TYPE_ID: i64 = 0;
+
+/// Category name shown for a budget that covers every category.
+pub const OVERALL_BUDGET_TYPE_NM: &str = "전체";
+
+#[doc = "Structure used to register or replace a budget in CONSUME_BUDGET."]
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct ConsumeBudget {
+    pub user_seq: i64,
+    pub budget_scope: BudgetScope,
+    pub scope_seq: i64,
+    pub consume_keyword_type_id: i64,
+    pub budget_money: i64,
+}
+
+impl ConsumeBudget {
+    /// Converts this `ConsumeBudget` into a SeaORM `ActiveModel` for database insertion.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(consume_budget::ActiveModel)` on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any field conversion fails.
+    pub fn to_active_model(&self) -> anyhow::Result<consume_budget::ActiveModel> {
+        Ok(ActiveModel {
+            budget_seq: NotSet,
+            user_seq: Set(self.user_seq),
+            budget_scope: Set(self.budget_scope.as_str().to_string()),
+            scope_seq: Set(self.scope_seq),
+            consume_keyword_type_id: Set(self.consume_keyword_type_id),
+            budget_money: Set(self.budget_money),
+            created_at: Set(Utc::now().naive_utc()),
+            updated_at: Set(None),
+            created_by: Set("system".to_string()),
+            updated_by: Set(None),
+        })
+    }
+}
+
+#[doc = "Budget row loaded from CONSUME_BUDGET together with its category name."]
+#[derive(Debug, Clone, FromQueryResult)]
+#[allow(dead_code)]
+pub struct ConsumeBudgetWithInfo {
+    pub budget_seq: i64,
+    pub budget_scope: String,
+    pub scope_seq: i64,
+    pub consume_keyword_type_id: i64,
+    pub consume_keyword_type: Option<String>,
+    pub budget_money: i64,
+}
+
+impl ConsumeBudgetWithInfo {
+    /// Builds the usage of this budget from the period totals of its scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget_scope` - Scope the budget was loaded for
+    /// * `spent_money_by_types` - Period totals per category of the same scope
+    ///
+    /// # Returns
+    ///
+    /// Returns a `BudgetUsage` whose spent amount is the category total, or the sum of all
+    /// categories for an overall budget.
+    pub fn to_budget_usage(
+        &self,
+        budget_scope: BudgetScope,
+        spent_money_by_types: &[SpentMoneyByType],
+    ) -> BudgetUsage {
+        let spent_money: i64 = spent_money_by_types
+            .iter()
+            .filter(|spent| {
+                self.consume_keyword_type_id == OVERALL_BUDGET_TYPE_ID
+                    || spent.consume_keyword_type_id == self.consume_keyword_type_id
+            })
+            .map(|spent| i64::try_from(spent.spent_money).unwrap_or_default())
+            .sum();
+
+        BudgetUsage::new(
+            budget_scope,
+            self.consume_keyword_type_id,
+            self.consume_keyword_type
+                .clone()
+                .unwrap_or_else(|| OVERALL_BUDGET_TYPE_NM.to_string()),
+            self.budget_money,
+            spent_money,
+        )
+    }
+}
+
+#[doc = "Total spending of a single category within a period, aggregated from SPENT_DETAIL."]
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct SpentMoneyByType {
+    pub consume_keyword_type_id: i64,
+    pub spent_money: Decimal,
+}
+
+#[doc = "How much of a budget has been used in the current salary period."]
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct BudgetUsage {
+    pub budget_scope: BudgetScope,
+    pub consume_keyword_type_id: i64,
+    pub consume_keyword_type_nm: String,
+    pub budget_money: i64,
+    pub spent_money: i64,
+}
+
+impl BudgetUsage {
+    /// Returns the used share of the budget in percent, rounded down.
+    pub fn to_used_percent(&self) -> i64 {
+        if self.budget_money <= 0 {
+            return 0;
+        }
+
+        self.spent_money.saturating_mul(100) / self.budget_money
+    }
+
+    /// Formats the budget and its current usage as a single Telegram line.
+    ///
+    /// # Returns
+    ///
+    /// Returns a line with the category, spent amount, budget and used percentage.
+    pub fn to_telegram_string(&self) -> String {
+        format!(
+            "- {}: {}원 / {}원 ({}%)",
+            self.consume_keyword_type_nm,
+            self.spent_money.to_formatted_string(&Locale::en),
+            self.budget_money.to_formatted_string(&Locale::en),
+            self.to_used_percent()
+        )
+    }
+
+    /// Formats a warning for a budget whose usage has just reached `threshold` percent.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The alert threshold, in percent, that was crossed
+    /// * `period_end` - Exclusive end of the salary period, i.e. the next payday (KST calendar date)
+    ///
+    /// # Returns
+    ///
+    /// Returns the warning message including the remaining amount of the period.
+    pub fn to_telegram_string_to_alert(&self, threshold: i64, period_end: DateTime<Utc>) -> String {
+        let remaining_money: i64 = self.budget_money - self.spent_money;
+
+        let remaining_line: String = if remaining_money >= 0 {
+            format!(
+                "남은 예산: {}원",
+                remaining_money.to_formatted_string(&Locale::en)
+            )
+        } else {
+            format!(
+                "초과 금액: {}원",
+                (-remaining_money).to_formatted_string(&Locale::en)
+            )
+        };
+
+        format!(
+            "[예산 {}% 도달 - {} {}]\n사용 금액: {}원 / {}원 ({}%)\n{}\n다음 급여일: {}",
+            threshold,
+            self.budget_scope.scope_nm(),
+            self.consume_keyword_type_nm,
+            self.spent_money.to_formatted_string(&Locale::en),
+            self.budget_money.to_formatted_string(&Locale::en),
+            self.to_used_percent(),
+            remaining_line,
+            period_end.format("%Y-%m-%d")
+        )
+    }
+}
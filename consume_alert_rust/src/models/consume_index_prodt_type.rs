@@ -7,4 +7,22 @@ pub struct ConsumingIndexProdtType {
     pub consume_keyword_type: String,
     pub consume_keyword: String,
     pub keyword_weight: i32,
+    /// Owner of a keyword learned from a user's correction; `None` for global keywords.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_seq: Option<i64>,
+}
+
+impl ConsumingIndexProdtType {
+    /// Builds the fallback "etc" category used when no keyword matches.
+    ///
+    /// # Arguments
+    ///
+    /// * `prodt_name` - The spending name that could not be classified
+    ///
+    /// # Returns
+    ///
+    /// Returns the `etc` category (id 21) carrying `prodt_name` as its keyword.
+    pub fn to_etc_type(prodt_name: &str) -> Self {
+        Self::new(21, String::from("etc"), prodt_name.to_string(), 0, None)
+    }
 }
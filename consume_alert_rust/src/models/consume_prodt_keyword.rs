@@ -0,0 +1,29 @@
+use crate::common::*;
+
+use super::consume_index_prodt_type::*;
+
+#[doc = "Row of COMMON_CONSUME_PRODT_KEYWORD joined with its COMMON_CONSUME_KEYWORD_TYPE, used by the offline classifier."]
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct ConsumeProdtKeyword {
+    pub consume_keyword_type_id: i64,
+    pub consume_keyword_type: String,
+    pub consume_keyword: String,
+    pub keyword_weight: i32,
+}
+
+impl ConsumeProdtKeyword {
+    /// Converts the keyword row into the classification result shared with the Elasticsearch path.
+    ///
+    /// # Returns
+    ///
+    /// Returns a global (`user_seq = None`) `ConsumingIndexProdtType`.
+    pub fn to_consuming_index_prodt_type(&self) -> ConsumingIndexProdtType {
+        ConsumingIndexProdtType::new(
+            self.consume_keyword_type_id,
+            self.consume_keyword_type.clone(),
+            self.consume_keyword.clone(),
+            self.keyword_weight,
+            None,
+        )
+    }
+}
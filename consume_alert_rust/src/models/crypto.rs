@@ -3,6 +3,7 @@
 use rust_decimal::Decimal;
 
 use crate::common::*;
+use crate::entity::crypto;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult, Getters)]
 #[getset(get = "pub")]
@@ -16,4 +17,26 @@ pub struct Crypto {
     pub updated_at: Option<DateTime<Utc>>,
     pub created_by: String,
     pub updated_by: Option<String>,
+    pub price_updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<crypto::Model> for Crypto {
+    fn from(model: crypto::Model) -> Self {
+        Crypto {
+            crypto_seq: model.crypto_seq,
+            crypto_name: model.crypto_name,
+            crypto_price: model.crypto_price,
+            api_symbol: model.api_symbol,
+            currency_code: model.currency_code,
+            created_at: DateTime::from_naive_utc_and_offset(model.created_at, Utc),
+            updated_at: model
+                .updated_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            created_by: model.created_by,
+            updated_by: model.updated_by,
+            price_updated_at: model
+                .price_updated_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+        }
+    }
 }
@@ -5,4 +5,19 @@ use crate::common::*;
 pub struct CryptoResp {
     pub crypto_name: String,
     pub crypto_total_price: Decimal,
+    /// Last time the scheduler refreshed `CRYPTO.crypto_price` (`price_updated_at`, or
+    /// `created_at` if never), unaffected by manual edits of the row
+    pub price_updated_at: DateTime<Utc>,
+}
+
+impl CryptoResp {
+    /// Returns true when the price was last written more than `stale_after` before `at`.
+    ///
+    /// # Arguments
+    ///
+    /// * `at` - The time the price is shown
+    /// * `stale_after` - Age after which a price is no longer trusted
+    pub fn is_price_stale(&self, at: DateTime<Utc>, stale_after: chrono::Duration) -> bool {
+        at - self.price_updated_at > stale_after
+    }
 }
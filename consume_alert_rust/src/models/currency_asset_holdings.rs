@@ -0,0 +1,150 @@
+use crate::common::*;
+
+use crate::models::{
+    cash_asset::*, crypto_resp::*, deposit_asset::*, saving_asset::*, stock_resp::*,
+    user_current_asset_snapshot::*,
+};
+
+/// Currencies the assets are held and aggregated in, in the order `my` lists them.
+pub const ASSET_CURRENCY_CODES: [&str; 2] = ["KRW", "USD"];
+
+#[doc = "Every asset a user holds in one currency, as shown by `my` and stored by the daily snapshot."]
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct CurrencyAssetHoldings {
+    pub currency_code: String,
+    pub deposit_assets: Vec<DepositAsset>,
+    pub saving_assets: Vec<SavingAsset>,
+    pub stock_resps: Vec<StockResp>,
+    pub crypto_resps: Vec<CryptoResp>,
+    pub cash_assets: Vec<CashAsset>,
+}
+
+impl CurrencyAssetHoldings {
+    pub fn deposit_amount(&self) -> Decimal {
+        self.deposit_assets
+            .iter()
+            .map(|deposit| deposit.deposit_amount)
+            .sum()
+    }
+
+    pub fn saving_amount(&self) -> Decimal {
+        self.saving_assets
+            .iter()
+            .map(|saving| saving.accum_saving_amount)
+            .sum()
+    }
+
+    pub fn stock_amount(&self) -> Decimal {
+        self.stock_resps.iter().map(StockResp::stock_amount).sum()
+    }
+
+    pub fn crypto_amount(&self) -> Decimal {
+        self.crypto_resps
+            .iter()
+            .map(|crypto| crypto.crypto_total_price)
+            .sum()
+    }
+
+    pub fn cash_amount(&self) -> Decimal {
+        self.cash_assets.iter().map(|cash| cash.cash).sum()
+    }
+
+    /// Summarizes the holdings into the snapshot row of one day.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_seq` - Owner of the holdings
+    /// * `aggregated_at` - Snapshot day as a KST calendar date (midnight, tagged as UTC)
+    ///
+    /// # Returns
+    ///
+    /// Returns an unsaved `UserCurrentAssetSnapshot` with one amount per asset type.
+    pub fn to_user_current_asset_snapshot(
+        &self,
+        user_seq: i64,
+        aggregated_at: DateTime<Utc>,
+    ) -> UserCurrentAssetSnapshot {
+        UserCurrentAssetSnapshot::new(
+            0,
+            user_seq,
+            self.currency_code.clone(),
+            aggregated_at,
+            self.cash_amount(),
+            self.stock_amount(),
+            self.crypto_amount(),
+            self.deposit_amount(),
+            self.saving_amount(),
+            Utc::now(),
+            None,
+            "system".to_string(),
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::utils_modules::time_utils::*;
+
+    #[test]
+    fn test_to_user_current_asset_snapshot() {
+        let created_at: DateTime<Utc> = Utc::now();
+        let holdings: CurrencyAssetHoldings = CurrencyAssetHoldings::new(
+            "USD".to_string(),
+            Vec::new(),
+            Vec::new(),
+            vec![
+                StockResp::new(
+                    1,
+                    "Apple".to_string(),
+                    "AAPL".to_string(),
+                    Decimal::new(2005, 1),
+                    3,
+                    Decimal::new(150, 0),
+                    "USD".to_string(),
+                ),
+                StockResp::new(
+                    2,
+                    "Microsoft".to_string(),
+                    "MSFT".to_string(),
+                    Decimal::new(400, 0),
+                    1,
+                    Decimal::new(300, 0),
+                    "USD".to_string(),
+                ),
+            ],
+            vec![CryptoResp::new(
+                "BTC".to_string(),
+                Decimal::new(1000, 0),
+                created_at,
+            )],
+            vec![CashAsset::new(
+                1,
+                "Wallet".to_string(),
+                Decimal::new(50, 0),
+                1,
+                "USD".to_string(),
+                created_at,
+                None,
+                "system".to_string(),
+                None,
+            )],
+        );
+
+        let aggregated_at: DateTime<Utc> = find_naivedate(2026, 10, 18).unwrap();
+        let snapshot: UserCurrentAssetSnapshot =
+            holdings.to_user_current_asset_snapshot(7, aggregated_at);
+
+        assert_eq!(*snapshot.user_seq(), 7);
+        assert_eq!(snapshot.currency_code(), "USD");
+        assert_eq!(*snapshot.aggregated_at(), aggregated_at);
+        assert_eq!(*snapshot.stock_amount(), Decimal::new(10015, 1));
+        assert_eq!(*snapshot.crypto_amount(), Decimal::new(1000, 0));
+        assert_eq!(*snapshot.cash_amount(), Decimal::new(50, 0));
+        assert_eq!(*snapshot.deposit_amount(), Decimal::ZERO);
+        assert_eq!(*snapshot.saving_amount(), Decimal::ZERO);
+    }
+}
@@ -0,0 +1,29 @@
+use crate::common::*;
+
+use crate::entity::dim_calendar;
+
+#[doc = "Business-day flags of a single date, loaded from DIM_CALENDAR."]
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct DimCalendar {
+    pub dt: NaiveDate,
+    pub is_weekend: bool,
+    pub is_holiday: bool,
+}
+
+impl DimCalendar {
+    /// Returns true when the date is neither a weekend nor a holiday.
+    pub fn is_business_day(&self) -> bool {
+        !self.is_weekend && !self.is_holiday
+    }
+}
+
+impl From<dim_calendar::Model> for DimCalendar {
+    fn from(model: dim_calendar::Model) -> Self {
+        DimCalendar {
+            dt: model.dt,
+            is_weekend: model.is_weekend == 1,
+            is_holiday: model.is_holiday == 1,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::common::*;
+
+#[doc = "Future monthly row of an installment plan, loaded from SPENT_DETAIL."]
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct InstallmentRemain {
+    pub spent_group_id: i64,
+    pub spent_name: String,
+    pub spent_money: i64,
+    pub card_alias: String,
+}
+
+#[doc = "Remaining balance of a single installment plan."]
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct InstallmentPlanBalance {
+    pub spent_name: String,
+    pub remaining_cnt: i64,
+    pub remaining_money: i64,
+}
+
+#[doc = "Remaining installment balance of a single card."]
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct InstallmentBalance {
+    pub card_alias: String,
+    pub remaining_money: i64,
+    pub plans: Vec<InstallmentPlanBalance>,
+}
+
+impl InstallmentBalance {
+    /// Formats the card balance and its plans as a Telegram-friendly message string.
+    ///
+    /// # Returns
+    ///
+    /// Returns a formatted string with the card total followed by one line per plan.
+    pub fn to_telegram_string(&self) -> String {
+        let plan_lines: Vec<String> = self
+            .plans
+            .iter()
+            .map(|plan| {
+                format!(
+                    "- {}: {}원 ({}회 남음)",
+                    plan.spent_name,
+                    plan.remaining_money.to_formatted_string(&Locale::en),
+                    plan.remaining_cnt
+                )
+            })
+            .collect();
+
+        format!(
+            "[{}] 남은 할부금: {}원\n{}",
+            self.card_alias,
+            self.remaining_money.to_formatted_string(&Locale::en),
+            plan_lines.join("\n")
+        )
+    }
+}
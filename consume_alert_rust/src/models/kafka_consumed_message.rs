@@ -0,0 +1,79 @@
+use crate::common::*;
+
+#[doc = "Owned copy of a message received from Kafka, detached from the consumer."]
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct KafkaConsumedMessage {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
+    pub key: Option<String>,
+    pub payload: Option<String>,
+}
+
+impl KafkaConsumedMessage {
+    /// Deserializes the JSON payload of this message.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(T)` with the deserialized payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message has no payload or the payload is not valid JSON for `T`.
+    pub fn to_object<T: DeserializeOwned>(&self) -> anyhow::Result<T> {
+        let payload: &str = self.payload.as_deref().ok_or_else(|| {
+            anyhow!(
+                "[KafkaConsumedMessage::to_object] Empty payload: {}/{}/{}",
+                self.topic,
+                self.partition,
+                self.offset
+            )
+        })?;
+
+        serde_json::from_str(payload).map_err(|e| {
+            anyhow!(
+                "[KafkaConsumedMessage::to_object] Invalid payload {}/{}/{}: {:?}",
+                self.topic,
+                self.partition,
+                self.offset,
+                e
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::enums::indexing_type::*;
+    use crate::models::spent_detail_to_kafka::*;
+
+    fn to_message(payload: Option<&str>) -> KafkaConsumedMessage {
+        KafkaConsumedMessage::new(
+            "spent-detail".to_string(),
+            0,
+            42,
+            None,
+            payload.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn test_to_object() {
+        let event: SpentDetailToKafka = to_message(Some(
+            r#"{"spent_idx":7,"indexing_type":"U","reg_at":"2026-10-18T01:02:03Z"}"#,
+        ))
+        .to_object()
+        .unwrap();
+
+        assert_eq!(event.spent_idx, 7);
+        assert_eq!(event.to_indexing_type().unwrap(), IndexingType::Update);
+
+        assert!(to_message(Some("not json"))
+            .to_object::<SpentDetailToKafka>()
+            .is_err());
+        assert!(to_message(None).to_object::<SpentDetailToKafka>().is_err());
+    }
+}
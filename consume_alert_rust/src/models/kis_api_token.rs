@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use crate::common::*;
+use crate::entity::kis_api_token::{self, ActiveModel};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult, Getters, new)]
 #[getset(get = "pub")]
@@ -12,3 +13,42 @@ pub struct KisApiToken {
     pub created_by: String,
     pub updated_by: Option<String>,
 }
+
+impl From<kis_api_token::Model> for KisApiToken {
+    fn from(model: kis_api_token::Model) -> Self {
+        KisApiToken::new(
+            model.access_token,
+            DateTime::from_naive_utc_and_offset(model.token_expired_at, Utc),
+            DateTime::from_naive_utc_and_offset(model.created_at, Utc),
+            model
+                .updated_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            model.created_by,
+            model.updated_by,
+        )
+    }
+}
+
+impl KisApiToken {
+    /// Returns true when the token can still be used for at least `margin` after `at`.
+    ///
+    /// # Arguments
+    ///
+    /// * `at` - The time of use
+    /// * `margin` - Time the token must stay valid beyond `at`
+    pub fn is_usable(&self, at: DateTime<Utc>, margin: chrono::Duration) -> bool {
+        at + margin < self.token_expired_at
+    }
+
+    /// Converts this `KisApiToken` into a SeaORM `ActiveModel` for database insertion.
+    pub fn to_active_model(&self) -> ActiveModel {
+        ActiveModel {
+            access_token: Set(self.access_token.clone()),
+            token_expired_at: Set(self.token_expired_at.naive_utc()),
+            created_at: Set(self.created_at.naive_utc()),
+            updated_at: Set(self.updated_at.map(|dt| dt.naive_utc())),
+            created_by: Set(self.created_by.clone()),
+            updated_by: Set(self.updated_by.clone()),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use crate::common::*;
+
+#[doc = "Total spending at one merchant (`spent_name`) within a report period."]
+#[derive(Debug, Clone, PartialEq, Eq, Getters, new)]
+#[getset(get = "pub")]
+pub struct MerchantSpending {
+    pub spent_name: String,
+    pub spent_money: i64,
+    pub spent_cnt: usize,
+}
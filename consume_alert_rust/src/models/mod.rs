@@ -3,30 +3,44 @@ pub mod agg_result_set;
 pub mod asset_resp;
 pub mod assets;
 pub mod cash_asset;
+pub mod consume_budget;
 pub mod consume_index_prodt_type;
 pub mod consume_prodt_info;
+pub mod consume_prodt_keyword;
 pub mod consume_result_by_type;
 pub mod crypto;
 pub mod crypto_asset;
 pub mod crypto_resp;
+pub mod currency_asset_holdings;
 pub mod currency_code;
 pub mod currency_exchange_rate_snapshot;
 pub mod deposit_asset;
+pub mod dim_calendar;
 pub mod document_with_id;
 pub mod earned_detail;
 pub mod file_info;
+pub mod installment_balance;
+pub mod kafka_consumed_message;
 pub mod kis_api_token;
+pub mod merchant_spending;
+pub mod net_worth_history;
 pub mod per_datetime;
+pub mod reconcile_result;
 pub mod saving_asset;
 pub mod score_manager;
 pub mod send_email_agg_group;
 pub mod spent_detail;
+pub mod spent_detail_by_card_approval;
 pub mod spent_detail_by_es;
 pub mod spent_detail_by_es_kst;
 pub mod spent_detail_by_installment;
 pub mod spent_detail_indexing;
+pub mod spent_detail_indexing_failure;
+pub mod spent_detail_outbox;
+pub mod spent_detail_summary;
 pub mod spent_detail_to_kafka;
 pub mod spent_detail_with_info;
+pub mod spent_group_info;
 pub mod stock;
 pub mod stock_asset;
 pub mod stock_pie_data;
@@ -0,0 +1,325 @@
+use crate::common::*;
+
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::utils_modules::numeric_utils::*;
+
+/// Asset classes of a net-worth point, in the order `my` lists them and the chart stacks them.
+pub const NET_WORTH_ASSET_TYPES: [&str; 5] = ["예금", "적금", "주식", "크립토", "현금"];
+
+/// Number of asset classes listed under the biggest movers.
+const BIGGEST_MOVER_CNT: usize = 3;
+
+#[doc = "Net worth of one month in KRW, taken from the last asset snapshot of that month."]
+#[derive(Debug, Clone, Serialize, Deserialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct NetWorthPoint {
+    /// Month of the snapshot, e.g. `2026.10`
+    pub period_label: String,
+    /// Snapshot day the amounts were taken from
+    pub aggregated_at: DateTime<Utc>,
+    pub deposit_amount_krw: Decimal,
+    pub saving_amount_krw: Decimal,
+    pub stock_amount_krw: Decimal,
+    pub crypto_amount_krw: Decimal,
+    pub cash_amount_krw: Decimal,
+}
+
+impl NetWorthPoint {
+    /// Returns the amount of every asset class, in [`NET_WORTH_ASSET_TYPES`] order.
+    pub fn asset_amounts_krw(&self) -> [Decimal; 5] {
+        [
+            self.deposit_amount_krw,
+            self.saving_amount_krw,
+            self.stock_amount_krw,
+            self.crypto_amount_krw,
+            self.cash_amount_krw,
+        ]
+    }
+
+    pub fn total_amount_krw(&self) -> Decimal {
+        self.asset_amounts_krw().iter().sum()
+    }
+}
+
+#[doc = "Monthly net-worth series of a user, oldest month first, sent to the graph API as is."]
+#[derive(Debug, Clone, Serialize, Deserialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct NetWorthHistory {
+    pub asset_types: Vec<String>,
+    pub points: Vec<NetWorthPoint>,
+}
+
+impl NetWorthHistory {
+    /// Returns the change of the total net worth from the previous month to the last month.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` when fewer than two months are available.
+    pub fn month_over_month_change(&self) -> Option<Decimal> {
+        let [.., prev_point, last_point] = self.points.as_slice() else {
+            return None;
+        };
+
+        Some(last_point.total_amount_krw() - prev_point.total_amount_krw())
+    }
+
+    /// Returns the asset classes whose amount changed the most from the previous month,
+    /// largest absolute change first.
+    ///
+    /// # Returns
+    ///
+    /// Returns up to three `(asset class, change)` pairs, leaving out unchanged classes.
+    pub fn biggest_movers(&self) -> Vec<(&'static str, Decimal)> {
+        let [.., prev_point, last_point] = self.points.as_slice() else {
+            return Vec::new();
+        };
+
+        let mut movers: Vec<(&'static str, Decimal)> = NET_WORTH_ASSET_TYPES
+            .iter()
+            .zip(
+                last_point
+                    .asset_amounts_krw()
+                    .iter()
+                    .zip(prev_point.asset_amounts_krw()),
+            )
+            .map(|(asset_type, (last_amount, prev_amount))| {
+                (*asset_type, *last_amount - prev_amount)
+            })
+            .filter(|(_, change)| !change.is_zero())
+            .collect();
+
+        movers.sort_by_key(|(_, change)| std::cmp::Reverse(change.abs()));
+        movers.truncate(BIGGEST_MOVER_CNT);
+        movers
+    }
+
+    /// Returns the growth of the total net worth from the first to the last month, in percent.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` with fewer than two months or when the first month is not positive.
+    pub fn period_growth_rate(&self) -> Option<f64> {
+        let (first_total, last_total) = self.first_and_last_total()?;
+
+        Some((last_total / first_total - 1.0) * 100.0)
+    }
+
+    /// Returns the compound average monthly growth of the total net worth, in percent.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` with fewer than two months or when the first or last month is not positive.
+    pub fn average_monthly_growth_rate(&self) -> Option<f64> {
+        let (first_total, last_total) = self.first_and_last_total()?;
+
+        if last_total <= 0.0 {
+            return None;
+        }
+
+        let month_cnt: f64 = (self.points.len() - 1) as f64;
+        Some(((last_total / first_total).powf(1.0 / month_cnt) - 1.0) * 100.0)
+    }
+
+    fn first_and_last_total(&self) -> Option<(f64, f64)> {
+        let [first_point, .., last_point] = self.points.as_slice() else {
+            return None;
+        };
+
+        let first_total: f64 = first_point.total_amount_krw().to_f64()?;
+        let last_total: f64 = last_point.total_amount_krw().to_f64()?;
+
+        (first_total > 0.0).then_some((first_total, last_total))
+    }
+
+    /// Formats the latest net worth and its trend as a Telegram message.
+    ///
+    /// # Returns
+    ///
+    /// Returns the latest total and breakdown, the month-over-month change, the biggest movers
+    /// and the growth rates, or a notice when no snapshot exists.
+    pub fn to_summary_string(&self) -> String {
+        let Some(last_point) = self.points.last() else {
+            return "저장된 자산 스냅샷이 없습니다.".to_string();
+        };
+
+        let mut msg: String = format!(
+            "[순자산 추이] {} ~ {}\n{} 총자산 = {}₩\n",
+            self.points[0].period_label,
+            last_point.period_label,
+            last_point.period_label,
+            format_krw(last_point.total_amount_krw())
+        );
+
+        for (asset_type, amount) in NET_WORTH_ASSET_TYPES
+            .iter()
+            .zip(last_point.asset_amounts_krw())
+        {
+            msg.push_str(&format!("*  {} : {}₩\n", asset_type, format_krw(amount)));
+        }
+
+        if let Some(change) = self.month_over_month_change() {
+            msg.push_str(&format!(
+                "전월 대비 : {}₩\n",
+                format_decimal_with_commas(change, 0)
+            ));
+        }
+
+        let movers: Vec<(&'static str, Decimal)> = self.biggest_movers();
+        if !movers.is_empty() {
+            msg.push_str(&format!(
+                "주요 변동 : {}\n",
+                movers
+                    .iter()
+                    .map(|(asset_type, change)| format!(
+                        "{} {}₩",
+                        asset_type,
+                        format_decimal_with_commas(*change, 0)
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+
+        if let Some(period_growth_rate) = self.period_growth_rate() {
+            msg.push_str(&format!("기간 성장률 : {:+.2}%\n", period_growth_rate));
+        }
+
+        if let Some(average_monthly_growth_rate) = self.average_monthly_growth_rate() {
+            msg.push_str(&format!(
+                "월평균 성장률 : {:+.2}%\n",
+                average_monthly_growth_rate
+            ));
+        }
+
+        msg.push_str("(USD 자산은 현재 환율로 환산)");
+        msg
+    }
+
+    /// Formats every month as a text table, used when the chart is not sent.
+    ///
+    /// # Returns
+    ///
+    /// Returns one block per month with the total, its change and the amount of every class.
+    pub fn to_table_string(&self) -> String {
+        let sep: &str = "--------------------------------------------";
+        let mut msg: String = String::from("[월별 순자산]\n");
+        let mut prev_total: Option<Decimal> = None;
+
+        for point in &self.points {
+            let total: Decimal = point.total_amount_krw();
+
+            msg.push_str(&format!(
+                "{}\n{} : {}₩",
+                sep,
+                point.period_label,
+                format_krw(total)
+            ));
+            if let Some(prev_total) = prev_total {
+                msg.push_str(&format!(
+                    " ({}₩)",
+                    format_decimal_with_commas(total - prev_total, 0)
+                ));
+            }
+            msg.push('\n');
+
+            msg.push_str(
+                &NET_WORTH_ASSET_TYPES
+                    .iter()
+                    .zip(point.asset_amounts_krw())
+                    .map(|(asset_type, amount)| format!("{} {}", asset_type, format_krw(amount)))
+                    .collect::<Vec<String>>()
+                    .join(" / "),
+            );
+            msg.push('\n');
+
+            prev_total = Some(total);
+        }
+
+        msg.push_str(sep);
+        msg
+    }
+}
+
+/// Formats a KRW amount rounded to the won, e.g. `1,234,567`.
+fn format_krw(amount: Decimal) -> String {
+    amount
+        .round()
+        .to_i64()
+        .unwrap_or_default()
+        .to_formatted_string(&Locale::en)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_point(period_label: &str, amounts: [i64; 5]) -> NetWorthPoint {
+        NetWorthPoint::new(
+            period_label.to_string(),
+            Utc::now(),
+            Decimal::from(amounts[0]),
+            Decimal::from(amounts[1]),
+            Decimal::from(amounts[2]),
+            Decimal::from(amounts[3]),
+            Decimal::from(amounts[4]),
+        )
+    }
+
+    #[test]
+    fn test_net_worth_history_trend() {
+        let history: NetWorthHistory = NetWorthHistory::new(
+            NET_WORTH_ASSET_TYPES
+                .iter()
+                .map(|t| t.to_string())
+                .collect(),
+            vec![
+                to_point("2026.08", [1000, 0, 0, 0, 0]),
+                to_point("2026.09", [1000, 100, 500, 0, 0]),
+                to_point("2026.10", [1000, 200, 200, 50, 0]),
+            ],
+        );
+
+        assert_eq!(history.month_over_month_change(), Some(Decimal::from(-150)));
+        assert_eq!(
+            history.biggest_movers(),
+            vec![
+                ("주식", Decimal::from(-300)),
+                ("적금", Decimal::from(100)),
+                ("크립토", Decimal::from(50)),
+            ]
+        );
+        assert!((history.period_growth_rate().unwrap() - 45.0).abs() < 1e-9);
+        assert!(
+            (history.average_monthly_growth_rate().unwrap() - (1.45_f64.sqrt() - 1.0) * 100.0)
+                .abs()
+                < 1e-9
+        );
+
+        let summary: String = history.to_summary_string();
+        assert!(summary.contains("2026.10 총자산 = 1,450₩"));
+        assert!(summary.contains("전월 대비 : -150₩"));
+        assert!(summary.contains("주요 변동 : 주식 -300₩, 적금 +100₩, 크립토 +50₩"));
+
+        let table: String = history.to_table_string();
+        assert!(table.contains("2026.09 : 1,600₩ (+600₩)"));
+        assert!(table.contains("예금 1,000 / 적금 200 / 주식 200 / 크립토 50 / 현금 0"));
+    }
+
+    #[test]
+    fn test_net_worth_history_without_comparison() {
+        let history: NetWorthHistory =
+            NetWorthHistory::new(Vec::new(), vec![to_point("2026.10", [0, 0, 0, 0, 500])]);
+
+        assert_eq!(history.month_over_month_change(), None);
+        assert!(history.biggest_movers().is_empty());
+        assert_eq!(history.period_growth_rate(), None);
+        assert_eq!(history.average_monthly_growth_rate(), None);
+
+        let empty_history: NetWorthHistory = NetWorthHistory::new(Vec::new(), Vec::new());
+        assert_eq!(
+            empty_history.to_summary_string(),
+            "저장된 자산 스냅샷이 없습니다."
+        );
+    }
+}
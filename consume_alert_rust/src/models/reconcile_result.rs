@@ -0,0 +1,47 @@
+use crate::common::*;
+
+#[doc = "Differences between SPENT_DETAIL and the CONSUME_DETAIL index for one scope and period."]
+#[derive(Debug, Clone, Default, Getters, new)]
+#[getset(get = "pub")]
+pub struct ReconcileResult {
+    pub mysql_cnt: usize,
+    pub mysql_money: i64,
+    pub es_cnt: usize,
+    pub es_money: i64,
+    /// Rows in MySQL without a document in Elasticsearch
+    pub missing_spent_idxs: Vec<i64>,
+    /// Documents in Elasticsearch without an indexable row in MySQL
+    pub extra_spent_idxs: Vec<i64>,
+    /// Rows present in both whose amounts differ
+    pub mismatched_spent_idxs: Vec<i64>,
+}
+
+impl ReconcileResult {
+    /// Returns true when both stores hold exactly the same spending.
+    pub fn is_consistent(&self) -> bool {
+        self.missing_spent_idxs.is_empty()
+            && self.extra_spent_idxs.is_empty()
+            && self.mismatched_spent_idxs.is_empty()
+    }
+
+    /// Formats the comparison as a multi-line report for the maintenance console.
+    ///
+    /// # Returns
+    ///
+    /// Returns the counts, money sums and the differing `spent_idx` values of both stores.
+    pub fn to_report_string(&self) -> String {
+        format!(
+            "MySQL: {} rows / {} won\nElasticsearch: {} docs / {} won\nmissing in Elasticsearch ({}): {:?}\nextra in Elasticsearch ({}): {:?}\namount mismatch ({}): {:?}",
+            self.mysql_cnt,
+            self.mysql_money.to_formatted_string(&Locale::en),
+            self.es_cnt,
+            self.es_money.to_formatted_string(&Locale::en),
+            self.missing_spent_idxs.len(),
+            self.missing_spent_idxs,
+            self.extra_spent_idxs.len(),
+            self.extra_spent_idxs,
+            self.mismatched_spent_idxs.len(),
+            self.mismatched_spent_idxs
+        )
+    }
+}
@@ -0,0 +1,14 @@
+use crate::common::*;
+
+use crate::enums::card_approval_type::*;
+
+use super::spent_detail::*;
+
+#[doc = "Structure containing a parsed card notification, whether it approves or cancels a payment, and its installment months (0 = lump sum)."]
+#[derive(Debug, Serialize, Deserialize, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct SpentDetailByCardApproval {
+    pub approval_type: CardApprovalType,
+    pub installment: i64,
+    pub spent_detail: SpentDetail,
+}
@@ -5,7 +5,7 @@
 
 use crate::common::*;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Getters)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult, Getters)]
 #[getset(get = "pub")]
 pub struct SpentDetailByEs {
     /// Primary key of the spent detail
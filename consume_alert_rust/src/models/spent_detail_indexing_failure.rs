@@ -0,0 +1,13 @@
+use crate::common::*;
+
+#[doc = "Dead-letter record of a SPENT_DETAIL change event that could not be indexed."]
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct SpentDetailIndexingFailure {
+    pub source_topic: String,
+    pub source_partition: i32,
+    pub source_offset: i64,
+    pub payload: Option<String>,
+    pub error_message: String,
+    pub attempts: u32,
+    pub failed_at: DateTime<Utc>,
+}
@@ -0,0 +1,39 @@
+use crate::common::*;
+
+use crate::entity::spent_detail_outbox;
+
+use crate::models::spent_detail_to_kafka::*;
+
+#[doc = "SPENT_DETAIL change event waiting in SPENT_DETAIL_OUTBOX to be published to Kafka."]
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct SpentDetailOutbox {
+    pub outbox_seq: i64,
+    pub spent_idx: i64,
+    pub indexing_type: String,
+    pub reg_at: DateTime<Utc>,
+    pub attempt_cnt: i32,
+}
+
+impl SpentDetailOutbox {
+    /// Converts this outbox row into the Kafka event published for it.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `SpentDetailToKafka` carrying the row's `spent_idx`, indexing type and change time.
+    pub fn to_spent_detail_to_kafka(&self) -> SpentDetailToKafka {
+        SpentDetailToKafka::new(self.spent_idx, self.indexing_type.clone(), self.reg_at)
+    }
+}
+
+impl From<spent_detail_outbox::Model> for SpentDetailOutbox {
+    fn from(model: spent_detail_outbox::Model) -> Self {
+        SpentDetailOutbox {
+            outbox_seq: model.outbox_seq,
+            spent_idx: model.spent_idx,
+            indexing_type: model.indexing_type,
+            reg_at: model.reg_at.and_utc(),
+            attempt_cnt: model.attempt_cnt,
+        }
+    }
+}
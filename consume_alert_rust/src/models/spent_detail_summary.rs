@@ -0,0 +1,9 @@
+use crate::common::*;
+
+#[doc = "Identity and amount of a spending, as stored in SPENT_DETAIL or the CONSUME_DETAIL index."]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromQueryResult, Getters, new)]
+#[getset(get = "pub")]
+pub struct SpentDetailSummary {
+    pub spent_idx: i64,
+    pub spent_money: i64,
+}
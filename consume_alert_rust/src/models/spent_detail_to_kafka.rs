@@ -19,7 +19,6 @@ impl SpentDetailToKafka {
     /// # Errors
     ///
     /// Returns an error if the `indexing_type` string does not match any known variant.
-    #[allow(dead_code)]
     pub fn to_indexing_type(&self) -> anyhow::Result<IndexingType> {
         self.indexing_type
             .parse::<IndexingType>()
@@ -11,6 +11,7 @@ pub struct SpentDetailWithInfo {
     pub spent_at: NaiveDateTime,
     pub created_at: NaiveDateTime,
     pub user_seq: i64,
+    pub spent_group_id: i64,
     pub consume_keyword_type_id: i64,
     pub consume_keyword_type: String,
     pub room_seq: i64,
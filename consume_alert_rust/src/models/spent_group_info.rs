@@ -0,0 +1,40 @@
+use crate::common::*;
+
+use crate::entity::spent_group_info::{self, ActiveModel};
+
+/// `SPENT_GROUP_INFO.status` value for groups that bundle the monthly rows of one installment plan.
+pub const INSTALLMENT_GROUP_STATUS: &str = "INSTALLMENT";
+
+/// `SPENT_GROUP_INFO.status` value for installment plans cancelled by a cancellation notification.
+pub const CANCELLED_INSTALLMENT_GROUP_STATUS: &str = "INSTALLMENT_CANCELLED";
+
+#[doc = "Structure used to create a spending group that links several SPENT_DETAIL rows."]
+#[derive(Debug, Serialize, Deserialize, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct SpentGroupInfo {
+    pub spent_group_nm: String,
+    pub status: String,
+}
+
+impl SpentGroupInfo {
+    /// Converts this `SpentGroupInfo` into a SeaORM `ActiveModel` for database insertion.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(spent_group_info::ActiveModel)` on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any field conversion fails.
+    pub fn to_active_model(&self) -> anyhow::Result<spent_group_info::ActiveModel> {
+        Ok(ActiveModel {
+            spent_group_id: NotSet,
+            spent_group_nm: Set(Some(self.spent_group_nm.clone())),
+            status: Set(Some(self.status.clone())),
+            created_at: Set(Utc::now().naive_utc()),
+            updated_at: Set(None),
+            created_by: Set("system".to_string()),
+            updated_by: Set(None),
+        })
+    }
+}
@@ -27,6 +27,11 @@ pub struct StockRespDetail {
 }
 
 impl StockResp {
+    /// Returns the market value of the holding (`stock_price * stock_cnt`) in its own currency.
+    pub fn stock_amount(&self) -> Decimal {
+        self.stock_price * Decimal::from(self.stock_cnt)
+    }
+
     pub fn convert_to_stock_resp_detail(
         &self,
         total_stock_amount_krw: Decimal,
@@ -1,6 +1,9 @@
 #![allow(dead_code)]
 
 use crate::common::*;
+use crate::entity::stock_type;
+
+use chrono_tz::{America::New_York, Tz};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult, Getters, new)]
 #[getset(get = "pub")]
@@ -13,3 +16,112 @@ pub struct StockType {
     pub created_by: String,
     pub updated_by: Option<String>,
 }
+
+impl From<stock_type::Model> for StockType {
+    fn from(model: stock_type::Model) -> Self {
+        StockType::new(
+            model.market_seq,
+            model.market,
+            model.currency_code,
+            DateTime::from_naive_utc_and_offset(model.created_at, Utc),
+            model
+                .updated_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            model.created_by,
+            model.updated_by,
+        )
+    }
+}
+
+impl StockType {
+    /// Returns true for the Korean exchanges, i.e. markets quoted in KRW.
+    pub fn is_domestic(&self) -> bool {
+        self.currency_code == "KRW"
+    }
+
+    /// Returns the regular session of the market: its time zone, opening and closing time.
+    ///
+    /// Korean markets (KRW) trade 09:00~15:30 KST and US markets (USD) 09:30~16:00 New York
+    /// time. Markets in any other currency have no known session.
+    fn find_regular_session(&self) -> Option<(Tz, NaiveTime, NaiveTime)> {
+        match self.currency_code.as_str() {
+            "KRW" => Some((
+                Seoul,
+                NaiveTime::from_hms_opt(9, 0, 0)?,
+                NaiveTime::from_hms_opt(15, 30, 0)?,
+            )),
+            "USD" => Some((
+                New_York,
+                NaiveTime::from_hms_opt(9, 30, 0)?,
+                NaiveTime::from_hms_opt(16, 0, 0)?,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns true while the regular session of the market is running at `at`.
+    ///
+    /// The closing time is included so a refresh scheduled on the close still records the
+    /// closing price. Exchange holidays are not known here and count as trading days.
+    ///
+    /// # Arguments
+    ///
+    /// * `at` - The time to check
+    ///
+    /// # Returns
+    ///
+    /// Returns false on weekends, outside the session, or for a market without a known session.
+    pub fn is_open(&self, at: DateTime<Utc>) -> bool {
+        let Some((market_tz, open_time, close_time)) = self.find_regular_session() else {
+            return false;
+        };
+
+        let local_at: DateTime<Tz> = at.with_timezone(&market_tz);
+
+        !matches!(local_at.weekday(), Weekday::Sat | Weekday::Sun)
+            && (open_time..=close_time).contains(&local_at.time())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_stock_type(market_seq: i64, market: &str, currency_code: &str) -> StockType {
+        StockType::new(
+            market_seq,
+            market.to_string(),
+            currency_code.to_string(),
+            Utc::now(),
+            None,
+            "system".to_string(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_stock_type_is_open() {
+        let kospi: StockType = to_stock_type(1, "KOSPI", "KRW");
+        let nasdaq: StockType = to_stock_type(2, "NASDAQ", "USD");
+        let tse: StockType = to_stock_type(3, "TSE", "JPY");
+        let kst = |d: u32, h: u32, mi: u32| {
+            Seoul
+                .with_ymd_and_hms(2026, 10, d, h, mi, 0)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+
+        /* 2026-10-16 is a Friday. */
+        assert!(kospi.is_open(kst(16, 9, 0)));
+        assert!(kospi.is_open(kst(16, 15, 30)));
+        assert!(!kospi.is_open(kst(16, 15, 31)));
+        assert!(!kospi.is_open(kst(17, 10, 0)));
+
+        /* 23:00 KST on Friday is 10:00 EDT; 08:00 KST on Saturday is 19:00 EDT on Friday. */
+        assert!(nasdaq.is_open(kst(16, 23, 0)));
+        assert!(!nasdaq.is_open(kst(17, 8, 0)));
+        assert!(!nasdaq.is_open(kst(16, 10, 0)));
+
+        assert!(!tse.is_open(kst(16, 10, 0)));
+    }
+}
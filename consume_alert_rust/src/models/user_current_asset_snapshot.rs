@@ -1,6 +1,7 @@
 use rust_decimal::Decimal;
 
 use crate::common::*;
+use crate::entity::user_current_asset_snapshot::{self, ActiveModel};
 
 #[allow(dead_code, clippy::too_many_arguments)]
 #[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult, Getters, new)]
@@ -20,3 +21,54 @@ pub struct UserCurrentAssetSnapshot {
     pub created_by: String,
     pub updated_by: Option<String>,
 }
+
+impl UserCurrentAssetSnapshot {
+    /// Converts this `UserCurrentAssetSnapshot` into a SeaORM `ActiveModel` for database insertion.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(user_current_asset_snapshot::ActiveModel)` with `summary_seq` left to the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any field conversion fails.
+    pub fn to_active_model(&self) -> anyhow::Result<ActiveModel> {
+        Ok(ActiveModel {
+            summary_seq: NotSet,
+            user_seq: Set(self.user_seq),
+            currency_code: Set(self.currency_code.clone()),
+            aggregated_at: Set(self.aggregated_at.naive_utc()),
+            cash_amount: Set(self.cash_amount),
+            stock_amount: Set(self.stock_amount),
+            crypto_amount: Set(self.crypto_amount),
+            deposit_amount: Set(self.deposit_amount),
+            saving_amount: Set(self.saving_amount),
+            created_at: Set(self.created_at.naive_utc()),
+            updated_at: Set(self.updated_at.map(|dt| dt.naive_utc())),
+            created_by: Set(self.created_by.clone()),
+            updated_by: Set(self.updated_by.clone()),
+        })
+    }
+}
+
+impl From<user_current_asset_snapshot::Model> for UserCurrentAssetSnapshot {
+    fn from(model: user_current_asset_snapshot::Model) -> Self {
+        UserCurrentAssetSnapshot::new(
+            model.summary_seq,
+            model.user_seq,
+            model.currency_code,
+            DateTime::from_naive_utc_and_offset(model.aggregated_at, Utc),
+            model.cash_amount,
+            model.stock_amount,
+            model.crypto_amount,
+            model.deposit_amount,
+            model.saving_amount,
+            DateTime::from_naive_utc_and_offset(model.created_at, Utc),
+            model
+                .updated_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            model.created_by,
+            model.updated_by,
+        )
+    }
+}
@@ -22,7 +22,26 @@ pub trait EsRepository {
         index_name: &str,
     ) -> Result<Value, anyhow::Error>;
 
+    /// Indexes a JSON document under `doc_id`, replacing any existing document with the same ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `doc_id` - The document ID to write
+    /// * `document` - The JSON document to index
+    /// * `index_name` - The name of the index to write to
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails or the response indicates a non-success status.
+    async fn input_query(
+        &self,
+        doc_id: &str,
+        document: &Value,
+        index_name: &str,
+    ) -> Result<(), anyhow::Error>;
+
     /// Deletes a document identified by `doc_id` from the specified Elasticsearch index.
+    /// A document that does not exist is treated as already deleted.
     ///
     /// # Arguments
     ///
@@ -32,7 +51,6 @@ pub trait EsRepository {
     /// # Errors
     ///
     /// Returns an error if the HTTP request fails or the response indicates a non-success status.
-    #[allow(dead_code)]
     async fn delete_query(&self, doc_id: &str, index_name: &str) -> Result<(), anyhow::Error>;
 }
 
@@ -115,6 +133,36 @@ impl EsRepository for EsRepositoryPub {
         }
     }
 
+    #[doc = "Function that EXECUTES elasticsearch queries - indexing"]
+    async fn input_query(
+        &self,
+        doc_id: &str,
+        document: &Value,
+        index_name: &str,
+    ) -> anyhow::Result<()> {
+        let response: Response = self
+            .es_client
+            .index(IndexParts::IndexId(index_name, doc_id))
+            .body(document)
+            .send()
+            .await?;
+
+        if response.status_code().is_success() {
+            info!(
+                "[EsRepositoryPub::input_query] index name: {}, doc_id: {}",
+                index_name, doc_id
+            );
+            Ok(())
+        } else {
+            let error_body: String = response.text().await?;
+            Err(anyhow!(
+                "[EsRepositoryPub::input_query] Failed to index document {}: {:?}",
+                doc_id,
+                error_body
+            ))
+        }
+    }
+
     #[doc = "Function that EXECUTES elasticsearch queries - delete"]
     async fn delete_query(&self, doc_id: &str, index_name: &str) -> anyhow::Result<()> {
         let response = self
@@ -123,7 +171,14 @@ impl EsRepository for EsRepositoryPub {
             .send()
             .await?;
 
-        if response.status_code().is_success() {
+        /* A document that is already gone counts as deleted, so redelivered deletes succeed. */
+        if response.status_code() == StatusCode::NOT_FOUND {
+            info!(
+                "[EsRepositoryPub::delete_query] document already absent - index name: {}, doc_id: {}",
+                index_name, doc_id
+            );
+            Ok(())
+        } else if response.status_code().is_success() {
             info!(
                 "[EsRepositoryPub::delete_query] index name: {}, doc_id: {}",
                 index_name, doc_id
@@ -0,0 +1,162 @@
+use crate::common::*;
+
+use crate::models::kafka_consumed_message::*;
+
+#[async_trait]
+pub trait KafkaConsumerRepository {
+    /// Waits for the next message of the subscribed topics.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(KafkaConsumedMessage)` holding an owned copy of the received message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Kafka client reports a consumer error.
+    async fn find_message(&self) -> Result<KafkaConsumedMessage, anyhow::Error>;
+
+    /// Synchronously commits the offset following `message`, so it is not delivered again
+    /// to this consumer group.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message that has been fully handled
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the offset cannot be committed.
+    fn commit_message(&self, message: &KafkaConsumedMessage) -> Result<(), anyhow::Error>;
+}
+
+pub struct KafkaConsumerRepositoryImpl {
+    consumer: StreamConsumer,
+}
+
+impl KafkaConsumerRepositoryImpl {
+    /// Creates a new `KafkaConsumerRepositoryImpl` subscribed to `topic`, reading broker and
+    /// security settings from environment variables.
+    ///
+    /// Auto commit is disabled; offsets are committed through `commit_message` once a message
+    /// has been handled, which gives at-least-once delivery.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - The Kafka topic to subscribe to
+    /// * `group_id` - The consumer group id
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(KafkaConsumerRepositoryImpl)` on successful consumer creation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if required environment variables are missing, or the Kafka consumer
+    /// cannot be created or subscribed.
+    pub fn new(topic: &str, group_id: &str) -> anyhow::Result<Self> {
+        let kafka_brokers: String = env::var("KAFKA_BROKERS").inspect_err(|e| {
+            error!(
+                "[KafkaConsumerRepositoryImpl::new] 'KAFKA_BROKERS' must be set: {:#}",
+                e
+            );
+        })?;
+
+        let security_protocol: String =
+            env::var("KAFKA_SECURITY_PROTOCOL").unwrap_or_else(|_| "PLAINTEXT".to_string());
+
+        let mut config: ClientConfig = ClientConfig::new();
+        config
+            .set("bootstrap.servers", &kafka_brokers)
+            .set("group.id", group_id)
+            .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", "earliest")
+            .set("session.timeout.ms", "30000")
+            .set("security.protocol", &security_protocol);
+
+        if security_protocol.contains("SASL") {
+            let sasl_mechanism: String = env::var("KAFKA_SASL_MECHANISM").map_err(|_| {
+                anyhow!("[KafkaConsumerRepositoryImpl::new] 'KAFKA_SASL_MECHANISM' must be set when using SASL")
+            })?;
+            let sasl_username: String = env::var("KAFKA_SASL_USERNAME").map_err(|_| {
+                anyhow!("[KafkaConsumerRepositoryImpl::new] 'KAFKA_SASL_USERNAME' must be set when using SASL")
+            })?;
+            let sasl_password: String = env::var("KAFKA_SASL_PASSWORD").map_err(|_| {
+                anyhow!("[KafkaConsumerRepositoryImpl::new] 'KAFKA_SASL_PASSWORD' must be set when using SASL")
+            })?;
+
+            config
+                .set("sasl.mechanism", &sasl_mechanism)
+                .set("sasl.username", &sasl_username)
+                .set("sasl.password", &sasl_password);
+        }
+
+        let consumer: StreamConsumer = config.create().map_err(|e| {
+            anyhow!(
+                "[KafkaConsumerRepositoryImpl::new] Failed to create consumer: {:?}",
+                e
+            )
+        })?;
+
+        consumer.subscribe(&[topic]).map_err(|e| {
+            anyhow!(
+                "[KafkaConsumerRepositoryImpl::new] Failed to subscribe to {}: {:?}",
+                topic,
+                e
+            )
+        })?;
+
+        Ok(KafkaConsumerRepositoryImpl { consumer })
+    }
+}
+
+#[async_trait]
+impl KafkaConsumerRepository for KafkaConsumerRepositoryImpl {
+    #[doc = "Function that receives the next message from the subscribed Kafka topic"]
+    async fn find_message(&self) -> anyhow::Result<KafkaConsumedMessage> {
+        let message: BorrowedMessage<'_> = self.consumer.recv().await.map_err(|e| {
+            anyhow!(
+                "[KafkaConsumerRepositoryImpl::find_message] Failed to receive message: {:?}",
+                e
+            )
+        })?;
+
+        Ok(KafkaConsumedMessage::new(
+            message.topic().to_string(),
+            message.partition(),
+            message.offset(),
+            message
+                .key()
+                .map(|key| String::from_utf8_lossy(key).into_owned()),
+            message
+                .payload()
+                .map(|payload| String::from_utf8_lossy(payload).into_owned()),
+        ))
+    }
+
+    #[doc = "Function that commits the offset after the given Kafka message"]
+    fn commit_message(&self, message: &KafkaConsumedMessage) -> anyhow::Result<()> {
+        let mut offsets: TopicPartitionList = TopicPartitionList::new();
+        offsets
+            .add_partition_offset(
+                message.topic(),
+                *message.partition(),
+                Offset::Offset(message.offset() + 1),
+            )
+            .map_err(|e| {
+                anyhow!(
+                    "[KafkaConsumerRepositoryImpl::commit_message] Invalid offset {:?}: {:?}",
+                    message,
+                    e
+                )
+            })?;
+
+        self.consumer
+            .commit(&offsets, CommitMode::Sync)
+            .map_err(|e| {
+                anyhow!(
+                    "[KafkaConsumerRepositoryImpl::commit_message] Failed to commit {:?}: {:?}",
+                    message,
+                    e
+                )
+            })
+    }
+}
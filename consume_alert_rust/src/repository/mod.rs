@@ -1,5 +1,7 @@
 pub mod es_repository;
+pub mod kafka_consumer_repository;
 pub mod kafka_repository;
 pub mod mysql_repository;
 pub mod redis_repository;
+pub mod smtp_repository;
 //pub mod es_multi_repository;
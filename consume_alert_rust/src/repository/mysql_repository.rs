@@ -1,10 +1,17 @@
 use crate::common::*;
-use crate::entity::{earned_detail, spent_detail};
+use crate::entity::{
+    consume_budget, crypto, earned_detail, kis_api_token, spent_detail, spent_detail_outbox,
+    spent_group_info, stock, user_current_asset_snapshot, users,
+};
+use crate::enums::indexing_type::*;
+
+use sea_orm::sea_query::{Expr, OnConflict};
 
 #[async_trait]
 pub trait MysqlRepository {
     /// Inserts a single [`spent_detail::ActiveModel`] within a transaction and returns
     /// the auto-incremented `spent_idx` assigned by the database.
+    /// An insert event is recorded in [`spent_detail_outbox`] within the same transaction.
     ///
     /// # Arguments
     ///
@@ -19,10 +26,10 @@ pub trait MysqlRepository {
         active_model: spent_detail::ActiveModel,
     ) -> anyhow::Result<i64>;
 
-    #[allow(dead_code)]
-    /// Inserts multiple [`spent_detail::ActiveModel`] records one by one within a single
-    /// transaction and returns the auto-incremented `spent_idx` assigned by the database
-    /// for each record.
+    /// Inserts a [`spent_group_info::ActiveModel`] and the [`spent_detail::ActiveModel`] records
+    /// that belong to it within a single transaction, so a group row never exists without its
+    /// details. Every record is linked to the new group through `spent_group_id`, and the
+    /// auto-incremented `spent_idx` assigned by the database is returned for each record.
     ///
     /// # Why one-by-one instead of bulk insert
     ///
@@ -36,9 +43,12 @@ pub trait MysqlRepository {
     /// The returned `Vec<i64>` is in the **same order** as `active_models`.
     /// Each `ids[i]` is the `spent_idx` assigned to `active_models[i]`.
     /// This holds because the loop is strictly sequential — no parallelism is involved.
+    /// An insert event per record is recorded in [`spent_detail_outbox`] within the same
+    /// transaction.
     ///
     /// # Arguments
     ///
+    /// * `group_active_model` - The group the records belong to.
     /// * `active_models` - Records to insert, in the order they should be tracked.
     ///
     /// # Returns
@@ -46,12 +56,14 @@ pub trait MysqlRepository {
     /// * `Ok(Vec<i64>)` - Auto-incremented `spent_idx` values, one per input record,
     ///   in insertion order.
     /// * `Err` - The transaction is rolled back and the error is propagated.
-    async fn input_spent_details_with_transaction(
+    async fn input_spent_group_details_with_transaction(
         &self,
+        group_active_model: spent_group_info::ActiveModel,
         active_models: Vec<spent_detail::ActiveModel>,
     ) -> anyhow::Result<Vec<i64>>;
 
     /// Deletes a single [`spent_detail`] row identified by `spent_idx` within a transaction.
+    /// A delete event is recorded in [`spent_detail_outbox`] within the same transaction.
     ///
     /// # Returns
     ///
@@ -59,11 +71,160 @@ pub trait MysqlRepository {
     /// * `Err`    - The transaction is rolled back and the error is propagated.
     async fn delete_spent_detail_with_transaction(&self, spent_idx: i64) -> anyhow::Result<()>;
 
+    /// Updates the columns that are `Set` in `active_model` for the [`spent_detail`] row
+    /// identified by its `spent_idx`, within a transaction.
+    /// An `indexing_type` event is recorded in [`spent_detail_outbox`] within the same
+    /// transaction (e.g. `Delete` when the row is excluded from indexing).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Row updated and transaction committed.
+    /// * `Err`    - The transaction is rolled back and the error is propagated.
+    async fn update_spent_detail_with_transaction(
+        &self,
+        active_model: spent_detail::ActiveModel,
+        indexing_type: IndexingType,
+    ) -> anyhow::Result<()>;
+
+    /// Records SPENT_DETAIL change events in [`spent_detail_outbox`] within a single transaction,
+    /// without touching the rows themselves (e.g. to re-emit events after index drift).
+    ///
+    /// # Arguments
+    ///
+    /// * `spent_detail_events` - `(spent_idx, indexing_type)` pairs, recorded in this order.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Events stored and transaction committed.
+    /// * `Err`    - The transaction is rolled back and the error is propagated.
+    async fn input_spent_detail_outboxes_with_transaction(
+        &self,
+        spent_detail_events: Vec<(i64, IndexingType)>,
+    ) -> anyhow::Result<()>;
+
+    /// Updates the columns that are `Set` in `active_model` for the [`spent_detail_outbox`] row
+    /// identified by its `outbox_seq`, within a transaction.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Row updated and transaction committed.
+    /// * `Err`    - The transaction is rolled back and the error is propagated.
+    async fn update_spent_detail_outbox_with_transaction(
+        &self,
+        active_model: spent_detail_outbox::ActiveModel,
+    ) -> anyhow::Result<()>;
+
+    /// Updates the columns that are `Set` in `active_model` for the [`users`] row
+    /// identified by its `user_seq`, within a transaction.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Row updated and transaction committed.
+    /// * `Err`    - The transaction is rolled back and the error is propagated.
+    async fn update_users_with_transaction(
+        &self,
+        active_model: users::ActiveModel,
+    ) -> anyhow::Result<()>;
+
     async fn input_earned_detail_with_transaction(
         &self,
         active_model: earned_detail::ActiveModel,
     ) -> anyhow::Result<i64>;
 
+    /// Marks every active [`spent_detail`] row of a spending group as cancelled
+    /// (`should_index = 0`) and sets the group `status`, within a transaction.
+    /// A delete event per cancelled row is recorded in [`spent_detail_outbox`] within the same
+    /// transaction.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - Number of SPENT_DETAIL rows cancelled; transaction committed.
+    /// * `Err`     - The transaction is rolled back and the error is propagated.
+    async fn update_spent_group_cancelled_with_transaction(
+        &self,
+        spent_group_id: i64,
+        status: &str,
+    ) -> anyhow::Result<u64>;
+
+    /// Inserts a [`consume_budget::ActiveModel`] within a transaction, replacing the amount of
+    /// the budget that already exists for the same scope and category.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Budget stored and transaction committed.
+    /// * `Err`    - The transaction is rolled back and the error is propagated.
+    async fn upsert_consume_budget_with_transaction(
+        &self,
+        active_model: consume_budget::ActiveModel,
+    ) -> anyhow::Result<()>;
+
+    /// Deletes the [`consume_budget`] row of the given scope and category within a transaction.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - Number of deleted rows (0 when no such budget existed).
+    /// * `Err`     - The transaction is rolled back and the error is propagated.
+    async fn delete_consume_budget_with_transaction(
+        &self,
+        budget_scope: &str,
+        scope_seq: i64,
+        consume_keyword_type_id: i64,
+    ) -> anyhow::Result<u64>;
+
+    /// Replaces the [`user_current_asset_snapshot`] rows of one user and day within a
+    /// transaction, so re-running a snapshot never leaves duplicates or a partial day behind.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Previous rows of the day deleted, new rows inserted and transaction committed.
+    /// * `Err`    - The transaction is rolled back and the error is propagated.
+    async fn replace_user_current_asset_snapshots_with_transaction(
+        &self,
+        user_seq: i64,
+        aggregated_at: NaiveDateTime,
+        active_models: Vec<user_current_asset_snapshot::ActiveModel>,
+    ) -> anyhow::Result<()>;
+
+    /// Writes refreshed [`stock`] prices of one market within a transaction, matching rows by
+    /// `api_symbol`, so a refresh is stored completely or not at all.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - Number of STOCK rows updated; transaction committed.
+    /// * `Err`     - The transaction is rolled back and the error is propagated.
+    async fn update_stock_prices_with_transaction(
+        &self,
+        market_seq: i64,
+        stock_prices: Vec<(String, Decimal)>,
+        updated_at: NaiveDateTime,
+    ) -> anyhow::Result<u64>;
+
+    /// Writes refreshed [`crypto`] prices of one quote currency within a transaction, matching
+    /// rows by `api_symbol`; `updated_at` is stamped as both `price_updated_at` and `updated_at`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - Number of CRYPTO rows updated; transaction committed.
+    /// * `Err`     - The transaction is rolled back and the error is propagated.
+    async fn update_crypto_prices_with_transaction(
+        &self,
+        currency_code: &str,
+        crypto_prices: Vec<(String, Decimal)>,
+        updated_at: NaiveDateTime,
+    ) -> anyhow::Result<u64>;
+
+    /// Replaces the stored [`kis_api_token`] with a newly issued one within a transaction, so
+    /// the table always holds exactly one token.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Previous tokens deleted, new token inserted and transaction committed.
+    /// * `Err`    - The transaction is rolled back and the error is propagated.
+    async fn replace_kis_api_token_with_transaction(
+        &self,
+        active_model: kis_api_token::ActiveModel,
+    ) -> anyhow::Result<()>;
+
     /// Returns a reference to the DatabaseConnection.
     ///
     /// # Returns
@@ -103,6 +264,52 @@ impl MysqlRepositoryImpl {
 
         Ok(Self { db_conn })
     }
+
+    /// Records a SPENT_DETAIL change in `SPENT_DETAIL_OUTBOX` inside the caller's transaction,
+    /// so the change event is stored if and only if the change itself is committed.
+    ///
+    /// # Arguments
+    ///
+    /// * `txn` - The open transaction that writes the SPENT_DETAIL change
+    /// * `spent_idx` - Primary key of the changed SPENT_DETAIL row
+    /// * `indexing_type` - Kind of change to publish
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outbox row cannot be inserted.
+    async fn input_spent_detail_outbox(
+        txn: &DatabaseTransaction,
+        spent_idx: i64,
+        indexing_type: IndexingType,
+    ) -> anyhow::Result<()> {
+        let now: NaiveDateTime = Utc::now().naive_utc();
+
+        let active_model: spent_detail_outbox::ActiveModel = spent_detail_outbox::ActiveModel {
+            outbox_seq: NotSet,
+            spent_idx: Set(spent_idx),
+            indexing_type: Set(indexing_type.as_str().to_string()),
+            reg_at: Set(now),
+            is_sent: Set(0),
+            attempt_cnt: Set(0),
+            last_error: Set(None),
+            sent_at: Set(None),
+            created_at: Set(now),
+            created_by: Set("system".to_string()),
+        };
+
+        spent_detail_outbox::Entity::insert(active_model)
+            .exec(txn)
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlRepositoryImpl::input_spent_detail_outbox] Failed to insert outbox for SPENT_DETAIL-{}: {:?}",
+                    spent_idx,
+                    e
+                )
+            })?;
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -147,6 +354,9 @@ impl MysqlRepository for MysqlRepositoryImpl {
             )
         })?;
 
+        Self::input_spent_detail_outbox(&txn, insert_result.last_insert_id, IndexingType::Insert)
+            .await?;
+
         txn.commit()
             .await
             .inspect_err(|e| {
@@ -159,11 +369,14 @@ impl MysqlRepository for MysqlRepositoryImpl {
         Ok(insert_result.last_insert_id)
     }
 
-    /// Inserts multiple `spent_detail` records sequentially within a single transaction, returning all generated primary keys.
+    /// Inserts a `spent_group_info` record and its `spent_detail` records sequentially within a
+    /// single transaction, returning the generated primary keys of the details.
     ///
     /// # Arguments
     ///
-    /// * `active_models` - Vector of SeaORM active models to insert in order
+    /// * `group_active_model` - SeaORM active model of the group to insert first
+    /// * `active_models` - Vector of SeaORM active models to insert in order; their
+    ///   `spent_group_id` is replaced by the id of the new group
     ///
     /// # Returns
     ///
@@ -172,8 +385,9 @@ impl MysqlRepository for MysqlRepositoryImpl {
     /// # Errors
     ///
     /// Returns an error if any insert or the transaction commit fails; the entire transaction is rolled back.
-    async fn input_spent_details_with_transaction(
+    async fn input_spent_group_details_with_transaction(
         &self,
+        group_active_model: spent_group_info::ActiveModel,
         active_models: Vec<spent_detail::ActiveModel>,
     ) -> anyhow::Result<Vec<i64>> {
         if active_models.is_empty() {
@@ -187,28 +401,46 @@ impl MysqlRepository for MysqlRepositoryImpl {
             .begin()
             .await
             .map_err(|e| anyhow!(
-                "[MysqlRepositoryImpl::input_spent_details_with_transaction] Failed to begin transaction: {:?}",
+                "[MysqlRepositoryImpl::input_spent_group_details_with_transaction] Failed to begin transaction: {:?}",
                 e
             ))?;
 
+        let spent_group_id: i64 = spent_group_info::Entity::insert(group_active_model)
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::input_spent_group_details_with_transaction] Failed to insert group: {:?}",
+                e
+            ))?
+            .last_insert_id;
+
         // `inserted_ids[i]` will hold the `spent_idx` assigned to `active_models[i]`.
         // Pre-allocating avoids reallocations inside the loop.
         let mut inserted_ids: Vec<i64> = Vec::with_capacity(active_models.len());
 
-        for (position, active_model) in active_models.into_iter().enumerate() {
+        for (position, mut active_model) in active_models.into_iter().enumerate() {
+            active_model.spent_group_id = Set(spent_group_id);
+
             let insert_result: InsertResult<spent_detail::ActiveModel> =
                 spent_detail::Entity::insert(active_model)
                     .exec(&txn)
                     .await
                     .map_err(|e| {
                         anyhow!(
-                            "[MysqlRepositoryImpl::input_spent_details_with_transaction] \
+                            "[MysqlRepositoryImpl::input_spent_group_details_with_transaction] \
                          Failed to insert record at position {}: {:?}",
                             position,
                             e
                         )
                     })?;
 
+            Self::input_spent_detail_outbox(
+                &txn,
+                insert_result.last_insert_id,
+                IndexingType::Insert,
+            )
+            .await?;
+
             // `last_insert_id` is the AUTO_INCREMENT value the DB assigned to this row.
             // Pushing immediately after the insert preserves insertion order.
             inserted_ids.push(insert_result.last_insert_id);
@@ -217,7 +449,7 @@ impl MysqlRepository for MysqlRepositoryImpl {
         txn.commit()
             .await
             .map_err(|e| anyhow!(
-                "[MysqlRepositoryImpl::input_spent_details_with_transaction] Failed to commit transaction: {:?}",
+                "[MysqlRepositoryImpl::input_spent_group_details_with_transaction] Failed to commit transaction: {:?}",
                 e
             ))?;
 
@@ -251,6 +483,8 @@ impl MysqlRepository for MysqlRepositoryImpl {
                 e
             ))?;
 
+        Self::input_spent_detail_outbox(&txn, spent_idx, IndexingType::Delete).await?;
+
         txn.commit()
             .await
             .map_err(|e| anyhow!(
@@ -261,6 +495,166 @@ impl MysqlRepository for MysqlRepositoryImpl {
         Ok(())
     }
 
+    /// Updates a `spent_detail` row within a transaction; only `Set` columns are written.
+    ///
+    /// # Arguments
+    ///
+    /// * `active_model` - The SeaORM active model carrying the primary key and the columns to change
+    /// * `indexing_type` - Kind of change recorded in the outbox
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if beginning, executing the update, or committing the transaction fails.
+    async fn update_spent_detail_with_transaction(
+        &self,
+        active_model: spent_detail::ActiveModel,
+        indexing_type: IndexingType,
+    ) -> anyhow::Result<()> {
+        let txn: DatabaseTransaction = self
+            .db_conn
+            .begin()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_spent_detail_with_transaction] Failed to begin transaction: {:?}",
+                e
+            ))?;
+
+        let updated: spent_detail::Model = spent_detail::Entity::update(active_model)
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_spent_detail_with_transaction] Failed to update record: {:?}",
+                e
+            ))?;
+
+        Self::input_spent_detail_outbox(&txn, updated.spent_idx, indexing_type).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_spent_detail_with_transaction] Failed to commit transaction: {:?}",
+                e
+            ))?;
+
+        Ok(())
+    }
+
+    /// Inserts outbox rows for the given SPENT_DETAIL change events within a single transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `spent_detail_events` - `(spent_idx, indexing_type)` pairs to record in order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if beginning, any insert, or committing the transaction fails.
+    async fn input_spent_detail_outboxes_with_transaction(
+        &self,
+        spent_detail_events: Vec<(i64, IndexingType)>,
+    ) -> anyhow::Result<()> {
+        let txn: DatabaseTransaction = self
+            .db_conn
+            .begin()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::input_spent_detail_outboxes_with_transaction] Failed to begin transaction: {:?}",
+                e
+            ))?;
+
+        for (spent_idx, indexing_type) in spent_detail_events {
+            Self::input_spent_detail_outbox(&txn, spent_idx, indexing_type).await?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::input_spent_detail_outboxes_with_transaction] Failed to commit transaction: {:?}",
+                e
+            ))?;
+
+        Ok(())
+    }
+
+    /// Updates a `spent_detail_outbox` row within a transaction; only `Set` columns are written.
+    ///
+    /// # Arguments
+    ///
+    /// * `active_model` - The SeaORM active model carrying the primary key and the columns to change
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if beginning, executing the update, or committing the transaction fails.
+    async fn update_spent_detail_outbox_with_transaction(
+        &self,
+        active_model: spent_detail_outbox::ActiveModel,
+    ) -> anyhow::Result<()> {
+        let txn: DatabaseTransaction = self
+            .db_conn
+            .begin()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_spent_detail_outbox_with_transaction] Failed to begin transaction: {:?}",
+                e
+            ))?;
+
+        spent_detail_outbox::Entity::update(active_model)
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_spent_detail_outbox_with_transaction] Failed to update record: {:?}",
+                e
+            ))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_spent_detail_outbox_with_transaction] Failed to commit transaction: {:?}",
+                e
+            ))?;
+
+        Ok(())
+    }
+
+    /// Updates a `users` row within a transaction; only `Set` columns are written.
+    ///
+    /// # Arguments
+    ///
+    /// * `active_model` - The SeaORM active model carrying the primary key and the columns to change
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if beginning, executing the update, or committing the transaction fails.
+    async fn update_users_with_transaction(
+        &self,
+        active_model: users::ActiveModel,
+    ) -> anyhow::Result<()> {
+        let txn: DatabaseTransaction = self
+            .db_conn
+            .begin()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_users_with_transaction] Failed to begin transaction: {:?}",
+                e
+            ))?;
+
+        users::Entity::update(active_model)
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_users_with_transaction] Failed to update record: {:?}",
+                e
+            ))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_users_with_transaction] Failed to commit transaction: {:?}",
+                e
+            ))?;
+
+        Ok(())
+    }
+
     async fn input_earned_detail_with_transaction(
         &self,
         active_model: earned_detail::ActiveModel,
@@ -299,6 +693,420 @@ impl MysqlRepository for MysqlRepositoryImpl {
         Ok(insert_result.last_insert_id)
     }
 
+    /// Cancels the active `spent_detail` rows of a spending group and updates the group status
+    /// within a transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `spent_group_id` - Primary key of the group to cancel
+    /// * `status` - New `SPENT_GROUP_INFO.status` of the group
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(u64)` with the number of cancelled `spent_detail` rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if beginning, any query, or committing the transaction fails.
+    async fn update_spent_group_cancelled_with_transaction(
+        &self,
+        spent_group_id: i64,
+        status: &str,
+    ) -> anyhow::Result<u64> {
+        let txn: DatabaseTransaction = self
+            .db_conn
+            .begin()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_spent_group_cancelled_with_transaction] Failed to begin transaction: {:?}",
+                e
+            ))?;
+
+        let now: NaiveDateTime = Utc::now().naive_utc();
+
+        let spent_idxs: Vec<i64> = spent_detail::Entity::find()
+            .select_only()
+            .column(spent_detail::Column::SpentIdx)
+            .filter(spent_detail::Column::SpentGroupId.eq(spent_group_id))
+            .filter(spent_detail::Column::ShouldIndex.eq(1))
+            .into_tuple::<i64>()
+            .all(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_spent_group_cancelled_with_transaction] Failed to find group rows: {:?}",
+                e
+            ))?;
+
+        spent_detail::Entity::update_many()
+            .col_expr(spent_detail::Column::ShouldIndex, Expr::value(0))
+            .col_expr(spent_detail::Column::UpdatedAt, Expr::value(now))
+            .col_expr(spent_detail::Column::UpdatedBy, Expr::value("system"))
+            .filter(spent_detail::Column::SpentIdx.is_in(spent_idxs.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_spent_group_cancelled_with_transaction] Failed to cancel group rows: {:?}",
+                e
+            ))?;
+
+        /* Cancelled rows are no longer indexed, so they leave the index like deletions. */
+        for spent_idx in &spent_idxs {
+            Self::input_spent_detail_outbox(&txn, *spent_idx, IndexingType::Delete).await?;
+        }
+
+        spent_group_info::Entity::update_many()
+            .col_expr(spent_group_info::Column::Status, Expr::value(status))
+            .col_expr(spent_group_info::Column::UpdatedAt, Expr::value(now))
+            .col_expr(spent_group_info::Column::UpdatedBy, Expr::value("system"))
+            .filter(spent_group_info::Column::SpentGroupId.eq(spent_group_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_spent_group_cancelled_with_transaction] Failed to update group status: {:?}",
+                e
+            ))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_spent_group_cancelled_with_transaction] Failed to commit transaction: {:?}",
+                e
+            ))?;
+
+        Ok(spent_idxs.len() as u64)
+    }
+
+    /// Inserts or replaces a `consume_budget` record within a transaction.
+    ///
+    /// The unique key `(budget_scope, scope_seq, consume_keyword_type_id)` decides whether an
+    /// existing budget is replaced; on conflict only the amount and audit columns change.
+    ///
+    /// # Arguments
+    ///
+    /// * `active_model` - The SeaORM active model representing the budget to store
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if beginning, executing, or committing the transaction fails.
+    async fn upsert_consume_budget_with_transaction(
+        &self,
+        active_model: consume_budget::ActiveModel,
+    ) -> anyhow::Result<()> {
+        let txn: DatabaseTransaction = self
+            .db_conn
+            .begin()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::upsert_consume_budget_with_transaction] Failed to begin transaction: {:?}",
+                e
+            ))?;
+
+        consume_budget::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([
+                    consume_budget::Column::BudgetScope,
+                    consume_budget::Column::ScopeSeq,
+                    consume_budget::Column::ConsumeKeywordTypeId,
+                ])
+                .value(consume_budget::Column::UpdatedAt, Utc::now().naive_utc())
+                .value(consume_budget::Column::UpdatedBy, "system")
+                .update_columns([
+                    consume_budget::Column::UserSeq,
+                    consume_budget::Column::BudgetMoney,
+                ])
+                .to_owned(),
+            )
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::upsert_consume_budget_with_transaction] Failed to upsert record: {:?}",
+                e
+            ))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::upsert_consume_budget_with_transaction] Failed to commit transaction: {:?}",
+                e
+            ))?;
+
+        Ok(())
+    }
+
+    /// Deletes the `consume_budget` record of one scope and category within a transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget_scope` - Stored scope value (`ROOM` or `GROUP`)
+    /// * `scope_seq` - `room_seq` or `agg_group_seq` the budget belongs to
+    /// * `consume_keyword_type_id` - Category of the budget, `0` for the overall budget
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(u64)` with the number of deleted rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if beginning, executing the delete, or committing the transaction fails.
+    async fn delete_consume_budget_with_transaction(
+        &self,
+        budget_scope: &str,
+        scope_seq: i64,
+        consume_keyword_type_id: i64,
+    ) -> anyhow::Result<u64> {
+        let txn: DatabaseTransaction = self
+            .db_conn
+            .begin()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::delete_consume_budget_with_transaction] Failed to begin transaction: {:?}",
+                e
+            ))?;
+
+        let delete_result = consume_budget::Entity::delete_many()
+            .filter(consume_budget::Column::BudgetScope.eq(budget_scope))
+            .filter(consume_budget::Column::ScopeSeq.eq(scope_seq))
+            .filter(consume_budget::Column::ConsumeKeywordTypeId.eq(consume_keyword_type_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::delete_consume_budget_with_transaction] Failed to delete record: {:?}",
+                e
+            ))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::delete_consume_budget_with_transaction] Failed to commit transaction: {:?}",
+                e
+            ))?;
+
+        Ok(delete_result.rows_affected)
+    }
+
+    /// Replaces the `user_current_asset_snapshot` records of one user and day within a transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_seq` - Owner of the snapshot
+    /// * `aggregated_at` - Snapshot day whose previous rows are deleted
+    /// * `active_models` - Rows of the day, one per currency
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if beginning, deleting, inserting or committing the transaction fails.
+    async fn replace_user_current_asset_snapshots_with_transaction(
+        &self,
+        user_seq: i64,
+        aggregated_at: NaiveDateTime,
+        active_models: Vec<user_current_asset_snapshot::ActiveModel>,
+    ) -> anyhow::Result<()> {
+        let txn: DatabaseTransaction = self
+            .db_conn
+            .begin()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::replace_user_current_asset_snapshots_with_transaction] Failed to begin transaction: {:?}",
+                e
+            ))?;
+
+        user_current_asset_snapshot::Entity::delete_many()
+            .filter(user_current_asset_snapshot::Column::UserSeq.eq(user_seq))
+            .filter(user_current_asset_snapshot::Column::AggregatedAt.eq(aggregated_at))
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::replace_user_current_asset_snapshots_with_transaction] Failed to delete records: {:?}",
+                e
+            ))?;
+
+        if !active_models.is_empty() {
+            user_current_asset_snapshot::Entity::insert_many(active_models)
+                .exec(&txn)
+                .await
+                .map_err(|e| anyhow!(
+                    "[MysqlRepositoryImpl::replace_user_current_asset_snapshots_with_transaction] Failed to insert records: {:?}",
+                    e
+                ))?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::replace_user_current_asset_snapshots_with_transaction] Failed to commit transaction: {:?}",
+                e
+            ))?;
+
+        Ok(())
+    }
+
+    /// Updates `stock_price` of every STOCK row of `market_seq` whose `api_symbol` was quoted.
+    ///
+    /// # Arguments
+    ///
+    /// * `market_seq` - Market the symbols belong to
+    /// * `stock_prices` - `(api_symbol, price)` pairs
+    /// * `updated_at` - Time recorded as `updated_at`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if beginning, executing an update, or committing the transaction fails.
+    async fn update_stock_prices_with_transaction(
+        &self,
+        market_seq: i64,
+        stock_prices: Vec<(String, Decimal)>,
+        updated_at: NaiveDateTime,
+    ) -> anyhow::Result<u64> {
+        let txn: DatabaseTransaction = self
+            .db_conn
+            .begin()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_stock_prices_with_transaction] Failed to begin transaction: {:?}",
+                e
+            ))?;
+
+        let mut updated_cnt: u64 = 0;
+
+        for (api_symbol, stock_price) in stock_prices {
+            let update_result = stock::Entity::update_many()
+                .col_expr(stock::Column::StockPrice, Expr::value(Some(stock_price)))
+                .col_expr(stock::Column::UpdatedAt, Expr::value(Some(updated_at)))
+                .col_expr(
+                    stock::Column::UpdatedBy,
+                    Expr::value(Some("system".to_string())),
+                )
+                .filter(stock::Column::MarketSeq.eq(market_seq))
+                .filter(stock::Column::ApiSymbol.eq(api_symbol.as_str()))
+                .exec(&txn)
+                .await
+                .map_err(|e| anyhow!(
+                    "[MysqlRepositoryImpl::update_stock_prices_with_transaction] Failed to update '{}': {:?}",
+                    api_symbol,
+                    e
+                ))?;
+
+            updated_cnt += update_result.rows_affected;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_stock_prices_with_transaction] Failed to commit transaction: {:?}",
+                e
+            ))?;
+
+        Ok(updated_cnt)
+    }
+
+    /// Updates `crypto_price` of every CRYPTO row of `currency_code` whose `api_symbol` was quoted.
+    ///
+    /// # Arguments
+    ///
+    /// * `currency_code` - Quote currency of the prices
+    /// * `crypto_prices` - `(api_symbol, price)` pairs
+    /// * `updated_at` - Time recorded as `price_updated_at` and `updated_at`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if beginning, executing an update, or committing the transaction fails.
+    async fn update_crypto_prices_with_transaction(
+        &self,
+        currency_code: &str,
+        crypto_prices: Vec<(String, Decimal)>,
+        updated_at: NaiveDateTime,
+    ) -> anyhow::Result<u64> {
+        let txn: DatabaseTransaction = self
+            .db_conn
+            .begin()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_crypto_prices_with_transaction] Failed to begin transaction: {:?}",
+                e
+            ))?;
+
+        let mut updated_cnt: u64 = 0;
+
+        for (api_symbol, crypto_price) in crypto_prices {
+            let update_result = crypto::Entity::update_many()
+                .col_expr(crypto::Column::CryptoPrice, Expr::value(crypto_price))
+                .col_expr(crypto::Column::PriceUpdatedAt, Expr::value(Some(updated_at)))
+                .col_expr(crypto::Column::UpdatedAt, Expr::value(Some(updated_at)))
+                .col_expr(
+                    crypto::Column::UpdatedBy,
+                    Expr::value(Some("system".to_string())),
+                )
+                .filter(crypto::Column::CurrencyCode.eq(currency_code))
+                .filter(crypto::Column::ApiSymbol.eq(api_symbol.as_str()))
+                .exec(&txn)
+                .await
+                .map_err(|e| anyhow!(
+                    "[MysqlRepositoryImpl::update_crypto_prices_with_transaction] Failed to update '{}': {:?}",
+                    api_symbol,
+                    e
+                ))?;
+
+            updated_cnt += update_result.rows_affected;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::update_crypto_prices_with_transaction] Failed to commit transaction: {:?}",
+                e
+            ))?;
+
+        Ok(updated_cnt)
+    }
+
+    /// Deletes every stored KIS access token and inserts `active_model` in their place.
+    ///
+    /// # Arguments
+    ///
+    /// * `active_model` - The newly issued token
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if beginning, deleting, inserting or committing the transaction fails.
+    async fn replace_kis_api_token_with_transaction(
+        &self,
+        active_model: kis_api_token::ActiveModel,
+    ) -> anyhow::Result<()> {
+        let txn: DatabaseTransaction = self
+            .db_conn
+            .begin()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::replace_kis_api_token_with_transaction] Failed to begin transaction: {:?}",
+                e
+            ))?;
+
+        kis_api_token::Entity::delete_many()
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::replace_kis_api_token_with_transaction] Failed to delete records: {:?}",
+                e
+            ))?;
+
+        kis_api_token::Entity::insert(active_model)
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::replace_kis_api_token_with_transaction] Failed to insert record: {:?}",
+                e
+            ))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(
+                "[MysqlRepositoryImpl::replace_kis_api_token_with_transaction] Failed to commit transaction: {:?}",
+                e
+            ))?;
+
+        Ok(())
+    }
+
     #[doc = "Get a reference to the underlying database connection"]
     fn get_connection(&self) -> &DatabaseConnection {
         &self.db_conn
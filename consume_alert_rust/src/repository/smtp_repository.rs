@@ -1,8 +1,9 @@
 use crate::common::*;
 use crate::config::AppConfig;
+use crate::models::file_info::*;
 
 use lettre::{
-    message::{MultiPart, SinglePart},
+    message::{header::ContentType, Attachment, MultiPart, SinglePart},
     transport::smtp::authentication::Credentials as SmtpCredentials,
     AsyncSmtpTransport, AsyncTransport, Message as MailMessage,
 };
@@ -14,6 +15,7 @@ pub trait SmtpRepository: Send + Sync {
         to: &str,
         subject: &str,
         html_body: &str,
+        inline_images: &[FileInfo],
     ) -> anyhow::Result<()>;
 }
 
@@ -30,13 +32,19 @@ impl SmtpRepositoryImpl {
     ///
     /// # Errors
     ///
-    /// Returns an error if `AppConfig` has not been initialized before this call.
+    /// Returns an error if `SMTP_SERVER`, `SMTP_ID` or `SMTP_PW` is not configured.
     pub fn new() -> anyhow::Result<Self> {
         let cfg: &AppConfig = AppConfig::get_global();
+        let find_setting = |setting: &Option<String>, env_nm: &str| {
+            setting
+                .clone()
+                .ok_or_else(|| anyhow!("[SmtpRepositoryImpl::new] {} is not configured", env_nm))
+        };
+
         Ok(Self {
-            smtp_server: cfg.smtp_server().clone(),
-            smtp_id: cfg.smtp_id().clone(),
-            smtp_pw: cfg.smtp_pw().clone(),
+            smtp_server: find_setting(cfg.smtp_server(), "SMTP_SERVER")?,
+            smtp_id: find_setting(cfg.smtp_id(), "SMTP_ID")?,
+            smtp_pw: find_setting(cfg.smtp_pw(), "SMTP_PW")?,
         })
     }
 }
@@ -45,11 +53,15 @@ impl SmtpRepositoryImpl {
 impl SmtpRepository for SmtpRepositoryImpl {
     /// Sends an HTML email to a single recipient via the configured SMTP server.
     ///
+    /// Each inline image is attached with its `file_name` as Content-ID, so the body can show it
+    /// with `<img src="cid:<file_name>">`.
+    ///
     /// # Arguments
     ///
     /// * `to` - Recipient email address
     /// * `subject` - Email subject line
     /// * `html_body` - HTML content of the email body
+    /// * `inline_images` - PNG images referenced from the body
     ///
     /// # Errors
     ///
@@ -59,7 +71,27 @@ impl SmtpRepository for SmtpRepositoryImpl {
         to: &str,
         subject: &str,
         html_body: &str,
+        inline_images: &[FileInfo],
     ) -> anyhow::Result<()> {
+        let image_content_type: ContentType = ContentType::parse("image/png").map_err(|e| {
+            anyhow!(
+                "[SmtpRepositoryImpl::send_html_email] Invalid image content type: {:?}",
+                e
+            )
+        })?;
+
+        let html_part: MultiPart = inline_images.iter().fold(
+            MultiPart::related().singlepart(SinglePart::html(html_body.to_string())),
+            |html_part, inline_image| {
+                html_part.singlepart(
+                    Attachment::new_inline(inline_image.file_name().clone()).body(
+                        inline_image.file_bytes().clone(),
+                        image_content_type.clone(),
+                    ),
+                )
+            },
+        );
+
         let email: MailMessage = MailMessage::builder()
             .from(self.smtp_id.parse().map_err(|e| {
                 anyhow!(
@@ -76,9 +108,7 @@ impl SmtpRepository for SmtpRepositoryImpl {
                 )
             })?)
             .subject(subject)
-            .multipart(
-                MultiPart::alternative().singlepart(SinglePart::html(html_body.to_string())),
-            )
+            .multipart(MultiPart::alternative().multipart(html_part))
             .map_err(|e| {
                 anyhow!(
                     "[SmtpRepositoryImpl::send_html_email] Failed to build email message: {:?}",
@@ -89,17 +119,18 @@ impl SmtpRepository for SmtpRepositoryImpl {
         let creds: SmtpCredentials =
             SmtpCredentials::new(self.smtp_id.clone(), self.smtp_pw.clone());
 
-        let mailer: AsyncSmtpTransport<lettre::Tokio1Executor> =
-            AsyncSmtpTransport::<lettre::Tokio1Executor>::relay(&self.smtp_server)
-                .map_err(|e| {
-                    anyhow!(
-                        "[SmtpRepositoryImpl::send_html_email] Failed to connect to SMTP server '{}': {:?}",
-                        self.smtp_server,
-                        e
-                    )
-                })?
-                .credentials(creds)
-                .build();
+        let mailer: AsyncSmtpTransport<lettre::Tokio1Executor> = AsyncSmtpTransport::<
+            lettre::Tokio1Executor,
+        >::relay(&self.smtp_server)
+        .map_err(|e| {
+            anyhow!(
+                "[SmtpRepositoryImpl::send_html_email] Failed to connect to SMTP server '{}': {:?}",
+                self.smtp_server,
+                e
+            )
+        })?
+        .credentials(creds)
+        .build();
 
         mailer.send(email).await.map_err(|e| {
             anyhow!(
@@ -0,0 +1,17 @@
+use crate::common::*;
+
+use crate::enums::card_parse_error::*;
+use crate::models::{spent_detail::*, user_payment_methods::*};
+
+pub trait CardMessageParser: Send + Sync + std::fmt::Debug {
+    fn card_company_nm(&self) -> &'static str;
+    fn is_match(&self, split_args_vec: &[String]) -> bool;
+    fn parse(
+        &self,
+        split_args_vec: &[String],
+        user_seq: i64,
+        room_seq: i64,
+        user_payment_methods: &[UserPaymentMethods],
+        received_at: DateTime<FixedOffset>,
+    ) -> Result<SpentDetail, CardParseError>;
+}
@@ -0,0 +1,13 @@
+use crate::common::*;
+
+use crate::models::consume_index_prodt_type::*;
+
+#[async_trait]
+pub trait ConsumeClassifier: Send + Sync {
+    fn classifier_nm(&self) -> &'static str;
+    async fn find_consume_type(
+        &self,
+        prodt_name: &str,
+        user_seq: i64,
+    ) -> anyhow::Result<ConsumingIndexProdtType>;
+}
@@ -0,0 +1,9 @@
+use crate::common::*;
+
+use crate::models::kafka_consumed_message::*;
+
+#[async_trait]
+pub trait ConsumerService {
+    async fn find_message(&self) -> Result<KafkaConsumedMessage, anyhow::Error>;
+    fn commit_message(&self, message: &KafkaConsumedMessage) -> Result<(), anyhow::Error>;
+}
@@ -0,0 +1,11 @@
+use crate::common::*;
+
+#[async_trait]
+pub trait CryptoQuoteProvider: Send + Sync {
+    fn provider_nm(&self) -> &'static str;
+    fn currency_code(&self) -> &'static str;
+    async fn find_quote_prices(
+        &self,
+        api_symbols: &[String],
+    ) -> anyhow::Result<HashMap<String, Decimal>>;
+}
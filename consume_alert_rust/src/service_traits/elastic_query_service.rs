@@ -1,7 +1,11 @@
 use crate::common::*;
 
 use crate::dtos::{EsRangeGroupSeqQueryDto, EsRangeRoomSeqQueryDto};
-use crate::models::{agg_result_set::*, consume_index_prodt_type::*, document_with_id::*};
+use crate::enums::reconcile_scope::*;
+use crate::models::{
+    agg_result_set::*, consume_index_prodt_type::*, document_with_id::*, spent_detail_indexing::*,
+    spent_detail_summary::*,
+};
 
 #[async_trait]
 pub trait ElasticQueryService {
@@ -12,7 +16,26 @@ pub trait ElasticQueryService {
     async fn find_consume_type_judgement(
         &self,
         prodt_name: &str,
+        user_seq: i64,
     ) -> Result<ConsumingIndexProdtType, anyhow::Error>;
+    async fn input_learned_consume_keyword(
+        &self,
+        user_seq: i64,
+        consume_keyword: &str,
+        consume_keyword_type_id: i64,
+        consume_keyword_type: &str,
+    ) -> Result<(), anyhow::Error>;
+    async fn input_spent_detail_indexing(
+        &self,
+        spent_detail_indexing: &SpentDetailIndexing,
+    ) -> Result<(), anyhow::Error>;
+    async fn delete_spent_detail_indexing(&self, spent_idx: i64) -> Result<(), anyhow::Error>;
+    async fn find_spent_detail_summaries(
+        &self,
+        reconcile_scope: ReconcileScope,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> Result<Vec<SpentDetailSummary>, anyhow::Error>;
     #[allow(dead_code)]
     async fn find_info_orderby_cnt<T: DeserializeOwned>(
         &self,
@@ -1,7 +1,8 @@
 use crate::common::*;
 
 use crate::models::{
-    assets::*, stock_pie_data::*, to_python_graph_circle::*, to_python_graph_line::*,
+    assets::*, net_worth_history::*, stock_pie_data::*, to_python_graph_circle::*,
+    to_python_graph_line::*,
 };
 
 #[async_trait]
@@ -33,4 +34,9 @@ pub trait GraphApiService {
         &self,
         stock_pie_data: StockPieData,
     ) -> anyhow::Result<Vec<u8>>;
+
+    async fn find_python_matplot_net_worth_history(
+        &self,
+        net_worth_history: &NetWorthHistory,
+    ) -> anyhow::Result<Vec<u8>>;
 }
@@ -1,8 +1,14 @@
 pub mod cache_service;
+pub mod card_message_parser;
+pub mod consume_classifier;
+pub mod consumer_service;
+pub mod crypto_quote_provider;
 pub mod elastic_query_service;
 pub mod graph_api_service;
 pub mod mysql_query_service;
 pub mod process_service;
 pub mod producer_service;
+pub mod quote_price_provider;
 pub mod redis_service;
+pub mod smtp_service;
 pub mod telebot_service;
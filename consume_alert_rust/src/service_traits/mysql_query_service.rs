@@ -1,9 +1,16 @@
 use crate::common::*;
 
+use crate::enums::{
+    budget_scope::*, indexing_type::*, range_operator::*, reconcile_scope::*, spent_detail_edit::*,
+};
+
 use crate::models::{
-    cash_asset::*, crypto_resp::*, currency_exchange_rate_snapshot::*, deposit_asset::*,
-    earned_detail::*, saving_asset::*, spent_detail::*, spent_detail_with_info::*, stock_resp::*,
-    user_payment_methods::*,
+    agg_group::*, agg_result_set::*, consume_budget::*, consume_prodt_keyword::*, crypto::*,
+    currency_asset_holdings::*, currency_exchange_rate_snapshot::*, dim_calendar::*,
+    earned_detail::*, installment_balance::*, kis_api_token::*, spent_detail::*,
+    spent_detail_by_es::*, spent_detail_indexing::*, spent_detail_outbox::*,
+    spent_detail_summary::*, spent_detail_with_info::*, spent_group_info::*, stock_type::*,
+    user_current_asset_snapshot::*, user_payment_methods::*,
 };
 
 #[async_trait]
@@ -16,9 +23,9 @@ pub trait MysqlQueryService {
         &self,
         spent_detail: &SpentDetail,
     ) -> anyhow::Result<i64>;
-    #[allow(dead_code)]
-    async fn input_prodt_details_with_transaction(
+    async fn input_spent_group_details_with_transaction(
         &self,
+        spent_group_info: &SpentGroupInfo,
         spent_details: &[SpentDetail],
     ) -> anyhow::Result<Vec<i64>>;
     async fn has_telegram_room_by_token_and_id(
@@ -38,6 +45,20 @@ pub trait MysqlQueryService {
     ) -> anyhow::Result<Option<i64>>;
     #[allow(dead_code)]
     async fn find_user_id_by_seq(&self, user_seq: i64) -> anyhow::Result<Option<String>>;
+    async fn find_user_payday(&self, user_seq: i64) -> anyhow::Result<Option<u32>>;
+    async fn modify_user_payday_with_transaction(
+        &self,
+        user_seq: i64,
+        payday: u32,
+    ) -> anyhow::Result<()>;
+    async fn find_user_seqs(&self) -> anyhow::Result<Vec<i64>>;
+    async fn find_active_agg_groups(&self) -> anyhow::Result<Vec<AggGroup>>;
+    async fn find_report_mail_receivers(&self, agg_group_seq: i64) -> anyhow::Result<Vec<String>>;
+    async fn find_dim_calendars(
+        &self,
+        date_from: NaiveDate,
+        date_to: NaiveDate,
+    ) -> anyhow::Result<Vec<DimCalendar>>;
     #[allow(dead_code)]
     async fn find_latest_spent_idx(
         &self,
@@ -49,12 +70,108 @@ pub trait MysqlQueryService {
         user_seq: i64,
         room_seq: i64,
     ) -> anyhow::Result<Option<SpentDetailWithInfo>>;
-    #[allow(dead_code)]
     async fn find_spent_detail_with_info(
         &self,
         spent_idx: i64,
     ) -> anyhow::Result<Option<SpentDetailWithInfo>>;
+    async fn find_spent_detail_indexing(
+        &self,
+        spent_idx: i64,
+    ) -> anyhow::Result<Option<SpentDetailIndexing>>;
     async fn delete_spent_detail_with_transaction(&self, spent_idx: i64) -> anyhow::Result<()>;
+    async fn find_spent_detail_summaries(
+        &self,
+        reconcile_scope: ReconcileScope,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentDetailSummary>>;
+    async fn input_spent_detail_outboxes_with_transaction(
+        &self,
+        spent_detail_events: &[(i64, IndexingType)],
+    ) -> anyhow::Result<()>;
+    async fn find_pending_spent_detail_outboxes(
+        &self,
+        limit: u64,
+    ) -> anyhow::Result<Vec<SpentDetailOutbox>>;
+    async fn modify_spent_detail_outbox_sent_with_transaction(
+        &self,
+        outbox_seq: i64,
+    ) -> anyhow::Result<()>;
+    async fn modify_spent_detail_outbox_failed_with_transaction(
+        &self,
+        outbox_seq: i64,
+        attempt_cnt: i32,
+        last_error: &str,
+    ) -> anyhow::Result<()>;
+    async fn find_consume_keyword_type_id(
+        &self,
+        consume_keyword_type: &str,
+    ) -> anyhow::Result<Option<i64>>;
+    async fn find_consume_prodt_keywords(&self) -> anyhow::Result<Vec<ConsumeProdtKeyword>>;
+    async fn modify_spent_detail_with_transaction(
+        &self,
+        spent_idx: i64,
+        spent_detail_edit: &SpentDetailEdit,
+    ) -> anyhow::Result<()>;
+    async fn find_consume_budgets(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+    ) -> anyhow::Result<Vec<ConsumeBudgetWithInfo>>;
+    async fn upsert_consume_budget_with_transaction(
+        &self,
+        consume_budget: &ConsumeBudget,
+    ) -> anyhow::Result<()>;
+    async fn delete_consume_budget_with_transaction(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+        consume_keyword_type_id: i64,
+    ) -> anyhow::Result<u64>;
+    async fn find_spent_money_by_type(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentMoneyByType>>;
+    async fn find_spent_details_by_range(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        start_op: RangeOperator,
+        end_op: RangeOperator,
+    ) -> anyhow::Result<AggResultSet<SpentDetailByEs>>;
+    async fn find_cancellable_spent_details(
+        &self,
+        user_seq: i64,
+        payment_method_id: i64,
+        spent_money: i64,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentDetailWithInfo>>;
+    async fn modify_spent_detail_cancelled_with_transaction(
+        &self,
+        spent_idx: i64,
+    ) -> anyhow::Result<()>;
+    async fn find_cancellable_installment_details(
+        &self,
+        user_seq: i64,
+        payment_method_id: i64,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentDetailWithInfo>>;
+    async fn modify_spent_group_cancelled_with_transaction(
+        &self,
+        spent_group_id: i64,
+    ) -> anyhow::Result<u64>;
+    async fn find_remaining_installments(
+        &self,
+        user_seq: i64,
+        spent_at_from: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<InstallmentRemain>>;
     async fn find_user_payment_methods(
         &self,
         user_seq: i64,
@@ -66,46 +183,47 @@ pub trait MysqlQueryService {
         target_currency_code: &str,
     ) -> anyhow::Result<Vec<CurrencyExchangeRateSnapshot>>;
 
-    async fn find_deposit_asset(
+    async fn find_currency_asset_holdings(
         &self,
         user_seq: i64,
         currency_code: &str,
-    ) -> anyhow::Result<Vec<DepositAsset>>;
+    ) -> anyhow::Result<CurrencyAssetHoldings>;
 
-    async fn find_saving_asset(
+    async fn replace_user_current_asset_snapshots_with_transaction(
         &self,
         user_seq: i64,
-        currency_code: &str,
-    ) -> anyhow::Result<Vec<SavingAsset>>;
-    /*
-        SELECT
-            s.stock_seq,
-            s.stock_name,
-            s.stock_price,
-            sa.stock_cnt,
-            sa.avg_purchase_price
-        FROM STOCK_ASSET sa
-        INNER JOIN STOCK s ON sa.stock_seq = s.stock_seq
-        INNER JOIN STOCK_TYPE st ON s.market_seq = st.market_seq
-        WHERE st.currency_code = 'USD'
-        AND sa.user_seq = 1
-        AND sa.stock_cnt > 0;
-    */
-    async fn find_stock_response(
+        aggregated_at: DateTime<Utc>,
+        snapshots: &[UserCurrentAssetSnapshot],
+    ) -> anyhow::Result<()>;
+
+    async fn find_user_current_asset_snapshots(
         &self,
         user_seq: i64,
-        currency_code: &str,
-    ) -> anyhow::Result<Vec<StockResp>>;
+        aggregated_from: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<UserCurrentAssetSnapshot>>;
 
-    async fn find_crypto_response(
+    async fn find_stock_types(&self) -> anyhow::Result<Vec<StockType>>;
+
+    async fn find_stock_api_symbols(&self, market_seq: i64) -> anyhow::Result<Vec<String>>;
+
+    async fn modify_stock_prices_with_transaction(
         &self,
-        user_seq: i64,
-        currency_code: &str,
-    ) -> anyhow::Result<Vec<CryptoResp>>;
+        market_seq: i64,
+        stock_prices: &[(String, Decimal)],
+    ) -> anyhow::Result<u64>;
+
+    async fn find_kis_api_token(&self) -> anyhow::Result<Option<KisApiToken>>;
 
-    async fn find_cash_asset(
+    async fn replace_kis_api_token_with_transaction(
+        &self,
+        kis_api_token: &KisApiToken,
+    ) -> anyhow::Result<()>;
+
+    async fn find_cryptos(&self) -> anyhow::Result<Vec<Crypto>>;
+
+    async fn modify_crypto_prices_with_transaction(
         &self,
-        user_seq: i64,
         currency_code: &str,
-    ) -> anyhow::Result<Vec<CashAsset>>;
+        crypto_prices: &[(String, Decimal)],
+    ) -> anyhow::Result<u64>;
 }
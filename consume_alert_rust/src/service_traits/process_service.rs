@@ -1,8 +1,13 @@
 use crate::common::*;
 
+use crate::enums::spent_at_status::*;
+
 use crate::models::{
-    agg_result_set::*, consume_result_by_type::*, per_datetime::*, spent_detail::*,
-    spent_detail_by_installment::*, to_python_graph_circle::*, user_payment_methods::*,
+    agg_result_set::*, consume_result_by_type::*, dim_calendar::*, installment_balance::*,
+    merchant_spending::*, net_worth_history::*, per_datetime::*, reconcile_result::*,
+    spent_detail::*, spent_detail_by_card_approval::*, spent_detail_by_installment::*,
+    spent_detail_summary::*, spent_detail_with_info::*, to_python_graph_circle::*,
+    user_current_asset_snapshot::*, user_payment_methods::*,
 };
 
 #[async_trait]
@@ -13,19 +18,60 @@ pub trait ProcessService {
     //     user_seq: i64,
     //     room_seq: i64,
     // ) -> Result<SpentDetailByInstallment, anyhow::Error>;
+    fn to_consume_notification_lines(&self, input_text: &str) -> anyhow::Result<Vec<String>>;
     fn modify_by_consume_filter(
         &self,
         split_args_vec: &[String],
         user_seq: i64,
         room_seq: i64,
         user_payment_methods: Vec<UserPaymentMethods>,
-    ) -> anyhow::Result<SpentDetail>;
+        received_at: DateTime<Utc>,
+    ) -> anyhow::Result<SpentDetailByCardApproval>;
     //) -> anyhow::Result<SpentDetail>;
-    #[allow(dead_code)]
+    fn find_spent_at_status(
+        &self,
+        spent_at: DateTime<FixedOffset>,
+        received_at: DateTime<Utc>,
+        future_tolerance_mins: i64,
+        stale_days: i64,
+    ) -> anyhow::Result<SpentAtStatus>;
+    fn find_cancellation_target(
+        &self,
+        cancellation: &SpentDetail,
+        candidates: &[SpentDetailWithInfo],
+    ) -> Option<SpentDetailWithInfo>;
+    fn find_installment_purchases(
+        &self,
+        installment_details: &[SpentDetailWithInfo],
+    ) -> Vec<SpentDetailWithInfo>;
     fn find_spent_detail_installment_process(
         &self,
         spent_detail_by_installment: &SpentDetailByInstallment,
     ) -> Result<Vec<SpentDetail>, anyhow::Error>;
+    fn find_installment_balance_by_card(
+        &self,
+        installment_remains: &[InstallmentRemain],
+    ) -> Vec<InstallmentBalance>;
+    fn find_business_payday(
+        &self,
+        year: i32,
+        month: u32,
+        payday: u32,
+        dim_calendars: &[DimCalendar],
+    ) -> anyhow::Result<DateTime<Utc>>;
+    fn find_salary_period(
+        &self,
+        ref_date: DateTime<Utc>,
+        payday: u32,
+        dim_calendars: &[DimCalendar],
+    ) -> anyhow::Result<(DateTime<Utc>, DateTime<Utc>)>;
+    fn find_crossed_budget_threshold(
+        &self,
+        budget_money: i64,
+        spent_before: i64,
+        spent_after: i64,
+        thresholds: &[i64],
+    ) -> Option<i64>;
     fn find_nmonth_to_current_date(
         &self,
         date_start: DateTime<Utc>,
@@ -45,10 +91,26 @@ pub trait ProcessService {
         &self,
         spent_details: &AggResultSet<T>,
     ) -> Result<Vec<ConsumeResultByType>, anyhow::Error>;
+    fn find_top_merchants<T: crate::models::to_python_graph_line::SpentDetailSource>(
+        &self,
+        spent_details: &AggResultSet<T>,
+        top_cnt: usize,
+    ) -> Vec<MerchantSpending>;
+    fn find_report_month_period(&self, run_at: DateTime<Utc>) -> anyhow::Result<PerDatetime>;
     fn find_nday_to_current_date(
         &self,
         date_start: DateTime<Utc>,
         date_end: DateTime<Utc>,
         nday: i32,
     ) -> Result<PerDatetime, anyhow::Error>;
+    fn find_reconcile_result(
+        &self,
+        mysql_summaries: &[SpentDetailSummary],
+        es_summaries: &[SpentDetailSummary],
+    ) -> ReconcileResult;
+    fn find_net_worth_history(
+        &self,
+        snapshots: &[UserCurrentAssetSnapshot],
+        usd_to_krw: Decimal,
+    ) -> NetWorthHistory;
 }
@@ -0,0 +1,13 @@
+use crate::common::*;
+
+use crate::models::stock_type::*;
+
+#[async_trait]
+pub trait QuotePriceProvider: Send + Sync {
+    fn provider_nm(&self) -> &'static str;
+    async fn find_quote_price(
+        &self,
+        stock_type: &StockType,
+        api_symbol: &str,
+    ) -> anyhow::Result<Decimal>;
+}
@@ -1,12 +1,14 @@
 use crate::common::*;
 
+use crate::models::file_info::*;
+
 #[async_trait]
 pub trait SmtpService {
-    async fn send_email(&self, subject: &str, html_body: &str) -> anyhow::Result<()>;
-    async fn send_email_to(
+    async fn send_email(
         &self,
-        address: &str,
+        receivers: &[String],
         subject: &str,
         html_body: &str,
-    ) -> anyhow::Result<()>;
+        inline_images: &[FileInfo],
+    ) -> anyhow::Result<usize>;
 }
@@ -45,4 +45,6 @@ pub trait TelebotService {
     fn get_telegram_token(&self) -> String;
 
     fn get_telegram_user_id(&self) -> String;
+
+    fn get_message_date(&self) -> DateTime<Utc>;
 }
@@ -0,0 +1,48 @@
+use crate::common::*;
+
+use crate::enums::card_parse_error::*;
+use crate::models::{spent_detail::*, user_payment_methods::*};
+use crate::service_traits::card_message_parser::*;
+
+use super::{build_standard_pattern, find_header_line, parse_standard_layout};
+
+const PARSER_NM: &str = "BcCardParser";
+
+static BC_CARD_PATTERN: once_lazy<Regex> =
+    once_lazy::new(|| build_standard_pattern(r"(?:bc|비씨)\S*?"));
+
+/// Parser for BC (비씨) card approval notifications.
+///
+/// Layout: `bc(1234)승인 홍*동 12,000원 일시불 01/15 12:30 스타벅스 누적1,234,000원`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BcCardParser;
+
+impl CardMessageParser for BcCardParser {
+    fn card_company_nm(&self) -> &'static str {
+        "bc"
+    }
+
+    fn is_match(&self, split_args_vec: &[String]) -> bool {
+        find_header_line(split_args_vec)
+            .is_some_and(|line| line.contains("bc") || line.contains("비씨"))
+    }
+
+    fn parse(
+        &self,
+        split_args_vec: &[String],
+        user_seq: i64,
+        room_seq: i64,
+        user_payment_methods: &[UserPaymentMethods],
+        received_at: DateTime<FixedOffset>,
+    ) -> Result<SpentDetail, CardParseError> {
+        parse_standard_layout(
+            PARSER_NM,
+            &BC_CARD_PATTERN,
+            split_args_vec,
+            user_seq,
+            room_seq,
+            user_payment_methods,
+            received_at,
+        )
+    }
+}
@@ -0,0 +1,78 @@
+use crate::common::*;
+
+use crate::enums::card_parse_error::*;
+use crate::models::{spent_detail::*, user_payment_methods::*};
+use crate::service_traits::card_message_parser::*;
+
+use super::{build_standard_pattern, find_header_line, parse_standard_layout};
+
+const PARSER_NM: &str = "CheckCardParser";
+
+static CHECK_CARD_PATTERN: once_lazy<Regex> =
+    once_lazy::new(|| build_standard_pattern(r"\S*?체크\S*?"));
+
+/// Parser for debit (체크) card approval notifications of one issuer.
+///
+/// Layout: `nh체크(1234)승인 홍*동 12,000원 01/15 12:30 스타벅스 잔액100,000원`
+///
+/// Debit notifications usually carry no installment marker and end with the account balance.
+/// The issuer is read from the header in front of `체크`, so the parser is keyed by the issuer's
+/// `card_company_nm` like the credit card parsers and one instance is registered per issuer.
+#[derive(Debug, Clone, Copy)]
+pub struct CheckCardParser {
+    card_company_nm: &'static str,
+    issuer_keywords: &'static [&'static str],
+}
+
+impl CheckCardParser {
+    /// Creates a debit card parser for one issuer.
+    ///
+    /// # Arguments
+    ///
+    /// * `card_company_nm` - `USER_PAYMENT_METHODS.card_company_nm` of the issuer : ex) "nh"
+    /// * `issuer_keywords` - Header keywords in front of `체크` naming the issuer : ex) ["nh", "농협"]
+    pub const fn new(
+        card_company_nm: &'static str,
+        issuer_keywords: &'static [&'static str],
+    ) -> Self {
+        Self {
+            card_company_nm,
+            issuer_keywords,
+        }
+    }
+}
+
+impl CardMessageParser for CheckCardParser {
+    fn card_company_nm(&self) -> &'static str {
+        self.card_company_nm
+    }
+
+    fn is_match(&self, split_args_vec: &[String]) -> bool {
+        find_header_line(split_args_vec).is_some_and(|line| {
+            line.split_once("체크").is_some_and(|(issuer, _)| {
+                self.issuer_keywords
+                    .iter()
+                    .any(|keyword| issuer.contains(keyword))
+            })
+        })
+    }
+
+    fn parse(
+        &self,
+        split_args_vec: &[String],
+        user_seq: i64,
+        room_seq: i64,
+        user_payment_methods: &[UserPaymentMethods],
+        received_at: DateTime<FixedOffset>,
+    ) -> Result<SpentDetail, CardParseError> {
+        parse_standard_layout(
+            PARSER_NM,
+            &CHECK_CARD_PATTERN,
+            split_args_vec,
+            user_seq,
+            room_seq,
+            user_payment_methods,
+            received_at,
+        )
+    }
+}
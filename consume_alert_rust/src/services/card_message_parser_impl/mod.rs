@@ -0,0 +1,347 @@
+mod bc_card_parser;
+mod check_card_parser;
+mod hana_card_parser;
+mod hyundai_card_parser;
+mod kb_card_parser;
+mod lotte_card_parser;
+mod nh_card_parser;
+mod samsung_card_parser;
+mod shinhan_card_parser;
+mod woori_card_parser;
+
+pub use bc_card_parser::*;
+pub use check_card_parser::*;
+pub use hana_card_parser::*;
+pub use hyundai_card_parser::*;
+pub use kb_card_parser::*;
+pub use lotte_card_parser::*;
+pub use nh_card_parser::*;
+pub use samsung_card_parser::*;
+pub use shinhan_card_parser::*;
+pub use woori_card_parser::*;
+
+use crate::common::*;
+
+use crate::enums::{card_approval_type::*, card_parse_error::*};
+use crate::models::{spent_detail::*, user_payment_methods::*};
+use crate::service_traits::card_message_parser::*;
+
+/// Ordered collection of card notification parsers.
+///
+/// `ProcessServiceImpl` walks the parsers in registration order and hands the message to the
+/// first one whose `card_company_nm` is registered for the caller and whose `is_match` accepts it.
+/// New issuers are supported by registering another parser; the dispatcher does not change.
+#[derive(Debug, Default)]
+pub struct CardMessageParserRegistry {
+    parsers: Vec<Box<dyn CardMessageParser>>,
+}
+
+impl CardMessageParserRegistry {
+    /// Builds a registry containing every card parser shipped with the application.
+    ///
+    /// The debit (check-card) parsers are registered first: their headers also carry the issuer
+    /// name (`nh체크`), so the issuer's credit card parser would otherwise claim them.
+    pub fn with_default_parsers() -> Self {
+        let mut registry: Self = Self::default();
+
+        registry.register(Box::new(CheckCardParser::new("nh", &["nh", "농협"])));
+        registry.register(Box::new(CheckCardParser::new("kb", &["kb", "국민"])));
+        registry.register(Box::new(CheckCardParser::new("신한", &["신한"])));
+        registry.register(Box::new(CheckCardParser::new("우리", &["우리"])));
+        registry.register(Box::new(CheckCardParser::new("하나", &["하나"])));
+        registry.register(Box::new(CheckCardParser::new("bc", &["bc", "비씨"])));
+
+        registry.register(Box::new(NhCardParser));
+        registry.register(Box::new(SamsungCardParser));
+        registry.register(Box::new(ShinhanCardParser));
+        registry.register(Box::new(KbCardParser));
+        registry.register(Box::new(HyundaiCardParser));
+        registry.register(Box::new(LotteCardParser));
+        registry.register(Box::new(WooriCardParser));
+        registry.register(Box::new(HanaCardParser));
+        registry.register(Box::new(BcCardParser));
+
+        registry
+    }
+
+    /// Appends a parser to the end of the lookup order.
+    ///
+    /// # Arguments
+    ///
+    /// * `parser` - The parser to register
+    pub fn register(&mut self, parser: Box<dyn CardMessageParser>) {
+        self.parsers.push(parser);
+    }
+
+    /// Returns the registered parsers in lookup order.
+    pub fn parsers(&self) -> &[Box<dyn CardMessageParser>] {
+        &self.parsers
+    }
+}
+
+/// Normalises a `card_company_nm` value so that DB values and parser keys compare equal.
+pub fn to_card_company_key(card_company_nm: &str) -> String {
+    card_company_nm.trim().to_lowercase()
+}
+
+/// Returns the notification lines starting at the card header, dropping the `web발신` marker
+/// some carriers prepend.
+pub(crate) fn find_message_lines(split_args_vec: &[String]) -> &[String] {
+    let header_idx: usize = split_args_vec
+        .iter()
+        .position(|line| !line.trim().is_empty() && line.trim() != "web발신")
+        .unwrap_or(split_args_vec.len());
+
+    &split_args_vec[header_idx..]
+}
+
+/// Returns the line that carries the card header, skipping the `web발신` marker some carriers prepend.
+pub(crate) fn find_header_line(split_args_vec: &[String]) -> Option<&String> {
+    find_message_lines(split_args_vec).first()
+}
+
+/// Keywords in the header line that mark a cancellation (승인취소) or refund notification.
+const CANCELLATION_KEYWORDS: [&str; 3] = ["취소", "환불", "반품"];
+
+/// Decides whether a notification approves a payment or cancels/refunds an earlier one.
+///
+/// Only the header line is inspected so that merchant names containing the keywords do not
+/// flip a normal multi-line approval into a cancellation.
+pub fn find_card_approval_type(split_args_vec: &[String]) -> CardApprovalType {
+    let is_cancellation: bool = find_header_line(split_args_vec).is_some_and(|line| {
+        let header: &str = line.split("원").next().unwrap_or(line);
+        CANCELLATION_KEYWORDS
+            .iter()
+            .any(|keyword| header.contains(keyword))
+    });
+
+    if is_cancellation {
+        CardApprovalType::Cancellation
+    } else {
+        CardApprovalType::Approval
+    }
+}
+
+/// Splits `intput_str` on whitespace and strips every string in `replacements` from each token.
+///
+/// # Arguments
+///
+/// * `intput_str` - Applied String : ex) "289,545원 일시불"
+/// * `replacements` - Character vector to replace : ex) [",", "원"]
+///
+/// # Returns
+///
+/// Returns the cleaned tokens : ex) ["289545", "일시불"]
+pub(crate) fn to_string_vector_by_replace(intput_str: &str, replacements: &[&str]) -> Vec<String> {
+    intput_str
+        .split_whitespace()
+        .map(|s| {
+            replacements
+                .iter()
+                .fold(s.to_string(), |acc, replace| acc.replace(replace, ""))
+        })
+        .collect()
+}
+
+/// Parses the money spent stored at `idx` of `consume_price_vec`.
+///
+/// # Errors
+///
+/// Returns `CardParseError::MissingField` if the index is out of range, or
+/// `CardParseError::InvalidField` if the value is not an integer.
+pub(crate) fn find_consume_prodt_money(
+    parser: &'static str,
+    consume_price_vec: &[String],
+    idx: usize,
+) -> Result<i64, CardParseError> {
+    let price_str: &String = consume_price_vec
+        .get(idx)
+        .ok_or(CardParseError::MissingField {
+            parser,
+            field: "spent_money",
+        })?;
+
+    price_str
+        .parse::<i64>()
+        .map_err(|_| CardParseError::InvalidField {
+            parser,
+            field: "spent_money",
+            value: price_str.to_string(),
+        })
+}
+
+/// Parses `["MM/DD", "HH:MM"]` into a Seoul (KST, UTC+9) timestamp.
+///
+/// Card alerts carry no year, so the year before, of, and after `received_at` are tried and the
+/// candidate closest to `received_at` wins. A `12/31 23:50` alert handled on January 1 therefore
+/// lands in the previous year instead of eleven months in the future.
+///
+/// # Arguments
+///
+/// * `parser` - Parser name used in error values
+/// * `consume_time_name_vec` - Date and time tokens : ex) ["12/31", "23:50"]
+/// * `received_at` - Time the notification was received (Telegram message date)
+///
+/// # Errors
+///
+/// Returns `CardParseError::MissingField` if either element is absent, or
+/// `CardParseError::InvalidField` if the date or time cannot be parsed.
+pub(crate) fn to_consume_datetime_seoul(
+    parser: &'static str,
+    consume_time_name_vec: &[String],
+    received_at: DateTime<FixedOffset>,
+) -> Result<DateTime<FixedOffset>, CardParseError> {
+    /* "11/25" */
+    let parsed_date: &String =
+        consume_time_name_vec
+            .first()
+            .ok_or(CardParseError::MissingField {
+                parser,
+                field: "spent_date",
+            })?;
+
+    /* "10:02" */
+    let parsed_time: &String =
+        consume_time_name_vec
+            .get(1)
+            .ok_or(CardParseError::MissingField {
+                parser,
+                field: "spent_time",
+            })?;
+
+    let format_time: NaiveTime = NaiveTime::parse_from_str(parsed_time, "%H:%M").map_err(|_| {
+        CardParseError::InvalidField {
+            parser,
+            field: "spent_time",
+            value: parsed_time.to_string(),
+        }
+    })?;
+
+    let received_year: i32 = received_at.with_timezone(&Seoul).year();
+
+    /* "02/29" only exists in leap years, so invalid candidates are simply skipped. */
+    let datetime_seoul: DateTime<FixedOffset> =
+        [received_year - 1, received_year, received_year + 1]
+            .into_iter()
+            .filter_map(|year| {
+                let format_date: NaiveDate =
+                    NaiveDate::parse_from_str(&format!("{}/{}", year, parsed_date), "%Y/%m/%d")
+                        .ok()?;
+
+                Seoul
+                    .from_local_datetime(&NaiveDateTime::new(format_date, format_time))
+                    .single()
+                    .map(|datetime| datetime.fixed_offset())
+            })
+            .min_by_key(|candidate| (*candidate - received_at).num_seconds().abs())
+            .ok_or_else(|| CardParseError::InvalidField {
+                parser,
+                field: "spent_date",
+                value: parsed_date.to_string(),
+            })?;
+
+    Ok(datetime_seoul)
+}
+
+/// Finds the payment method whose `card_alias` appears in `card_name`.
+///
+/// # Errors
+///
+/// Returns `CardParseError::PaymentMethodNotFound` if no alias matches.
+pub(crate) fn find_payment_method_id(
+    parser: &'static str,
+    card_name: &str,
+    user_payment_methods: &[UserPaymentMethods],
+) -> Result<i64, CardParseError> {
+    user_payment_methods
+        .iter()
+        .find(|elem| card_name.contains(elem.card_alias().as_str()))
+        .map(|elem| *elem.payment_method_id())
+        .ok_or_else(|| CardParseError::PaymentMethodNotFound {
+            parser,
+            card_name: card_name.to_string(),
+        })
+}
+
+/// Builds the pattern shared by issuers that send
+/// `<header>승인|승인취소 [holder] <amount>원 [일시불|NN개월] <MM/DD> <HH:MM> <merchant> [누적/잔액 ...]`,
+/// either on one line or split across several lines.
+///
+/// # Arguments
+///
+/// * `header` - Regex fragment matching the issuer-specific card header (captured as `card`)
+pub(crate) fn build_standard_pattern(header: &str) -> Regex {
+    let pattern: String = format!(
+        r"^(?:web발신\s+)?(?P<card>{})\s*(?:승인취소|취소승인|승인|취소|환불)\s+(?:\S+\s+)?(?P<money>[\d,]+)원\s*(?:\(?(?P<installment>일시불|\d+개월)\)?\s*)?(?P<date>\d{{1,2}}/\d{{1,2}})\s+(?P<time>\d{{1,2}}:\d{{2}})\s+(?P<name>.+?)(?:\s+(?:누적|잔액)\s*[\d,\-]+원?)?$",
+        header
+    );
+
+    Regex::new(&pattern)
+        .expect("[card_message_parser_impl::build_standard_pattern] Invalid card pattern")
+}
+
+/// Parses a notification that follows the layout described in [`build_standard_pattern`].
+///
+/// # Arguments
+///
+/// * `parser` - Parser name used in error values
+/// * `pattern` - The issuer pattern built by `build_standard_pattern`
+/// * `split_args_vec` - Tokenized lines extracted from the notification text
+/// * `user_seq` - Unique identifier of the user
+/// * `room_seq` - Unique identifier of the Telegram room
+/// * `user_payment_methods` - Payment methods registered by the user for this issuer
+/// * `received_at` - Time the notification was received, used to infer the year
+///
+/// # Errors
+///
+/// Returns a `CardParseError` if the text does not fit the pattern, a field cannot be parsed,
+/// or the card alias cannot be matched.
+pub(crate) fn parse_standard_layout(
+    parser: &'static str,
+    pattern: &Regex,
+    split_args_vec: &[String],
+    user_seq: i64,
+    room_seq: i64,
+    user_payment_methods: &[UserPaymentMethods],
+    received_at: DateTime<FixedOffset>,
+) -> Result<SpentDetail, CardParseError> {
+    let joined: String = split_args_vec.join(" ");
+
+    let caps: regex::Captures<'_> =
+        pattern
+            .captures(joined.trim())
+            .ok_or_else(|| CardParseError::InvalidField {
+                parser,
+                field: "message",
+                value: joined.clone(),
+            })?;
+
+    let capture = |field: &'static str| -> Result<String, CardParseError> {
+        caps.name(field)
+            .map(|m| m.as_str().trim().to_string())
+            .ok_or(CardParseError::MissingField { parser, field })
+    };
+
+    let card_name: String = capture("card")?;
+    let payment_method_id: i64 = find_payment_method_id(parser, &card_name, user_payment_methods)?;
+
+    let consume_price_vec: Vec<String> = to_string_vector_by_replace(&capture("money")?, &[","]);
+    let spent_money: i64 = find_consume_prodt_money(parser, &consume_price_vec, 0)?;
+
+    let spent_at: DateTime<FixedOffset> =
+        to_consume_datetime_seoul(parser, &[capture("date")?, capture("time")?], received_at)?;
+
+    let spent_name: String = capture("name")?;
+
+    Ok(SpentDetail {
+        spent_name,
+        spent_money,
+        spent_at,
+        should_index: 1,
+        user_seq,
+        spent_group_id: 0,
+        consume_keyword_type_id: 0,
+        room_seq,
+        payment_method_id,
+    })
+}
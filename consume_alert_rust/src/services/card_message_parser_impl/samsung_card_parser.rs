@@ -0,0 +1,85 @@
+use crate::common::*;
+
+use crate::enums::card_parse_error::*;
+use crate::models::{spent_detail::*, user_payment_methods::*};
+use crate::service_traits::card_message_parser::*;
+
+use super::{
+    find_consume_prodt_money, find_header_line, find_message_lines, find_payment_method_id,
+    to_consume_datetime_seoul, to_string_vector_by_replace,
+};
+
+const PARSER_NM: &str = "SamsungCardParser";
+
+/// Parser for Samsung (삼성) card approval notifications.
+///
+/// Layout: `삼성1337승인 홍*동 / 12,000원 일시불 / 01/15 12:30 스타벅스 / 누적1,234,000원`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SamsungCardParser;
+
+impl CardMessageParser for SamsungCardParser {
+    fn card_company_nm(&self) -> &'static str {
+        "삼성"
+    }
+
+    fn is_match(&self, split_args_vec: &[String]) -> bool {
+        find_header_line(split_args_vec).is_some_and(|line| line.contains("삼성"))
+    }
+
+    fn parse(
+        &self,
+        split_args_vec: &[String],
+        user_seq: i64,
+        room_seq: i64,
+        user_payment_methods: &[UserPaymentMethods],
+        received_at: DateTime<FixedOffset>,
+    ) -> Result<SpentDetail, CardParseError> {
+        let split_args_vec: &[String] = find_message_lines(split_args_vec);
+        let split_val: Vec<&str> = vec![",", "원"];
+
+        let card_name: &str = split_args_vec.first().ok_or(CardParseError::MissingField {
+            parser: PARSER_NM,
+            field: "card_name",
+        })?;
+
+        let payment_method_id: i64 =
+            find_payment_method_id(PARSER_NM, card_name, user_payment_methods)?;
+
+        // Extract price and payment type
+        let price_str: &str = split_args_vec.get(1).ok_or(CardParseError::MissingField {
+            parser: PARSER_NM,
+            field: "spent_money",
+        })?;
+        let consume_price_vec: Vec<String> = to_string_vector_by_replace(price_str, &split_val);
+        let spent_money: i64 = find_consume_prodt_money(PARSER_NM, &consume_price_vec, 0)?;
+
+        // Extract time and product name
+        let time_str: &str = split_args_vec.get(2).ok_or(CardParseError::MissingField {
+            parser: PARSER_NM,
+            field: "spent_at",
+        })?;
+        let consume_time_vec: Vec<String> = time_str.split(" ").map(|s| s.to_string()).collect();
+        let spent_at: DateTime<FixedOffset> =
+            to_consume_datetime_seoul(PARSER_NM, &consume_time_vec, received_at)?;
+
+        let spent_name: String = consume_time_vec
+            .get(2)
+            .ok_or(CardParseError::MissingField {
+                parser: PARSER_NM,
+                field: "spent_name",
+            })?
+            .to_string();
+
+        Ok(SpentDetail {
+            spent_name,
+            spent_money,
+            spent_at,
+            should_index: 1,
+            user_seq,
+            spent_group_id: 0,
+            consume_keyword_type_id: 0,
+            room_seq,
+            payment_method_id,
+        })
+    }
+}
@@ -0,0 +1,79 @@
+use crate::common::*;
+
+use crate::models::consume_index_prodt_type::*;
+use crate::service_traits::consume_classifier::*;
+
+/// Ordered list of classifiers; the first one that answers without an error wins.
+///
+/// The order is chosen at start-up (`CONSUME_CLASSIFIER_PRIMARY`), so the offline MySQL
+/// classifier can serve either as the fallback for Elasticsearch or as the primary strategy.
+#[derive(Default)]
+pub struct ConsumeClassifierChain {
+    classifiers: Vec<Arc<dyn ConsumeClassifier>>,
+}
+
+impl ConsumeClassifierChain {
+    /// Appends a classifier to the end of the lookup order.
+    ///
+    /// # Arguments
+    ///
+    /// * `classifier` - The classifier to register
+    pub fn register(&mut self, classifier: Arc<dyn ConsumeClassifier>) {
+        self.classifiers.push(classifier);
+    }
+
+    /// Returns the names of the registered classifiers in lookup order.
+    pub fn classifier_nms(&self) -> Vec<&'static str> {
+        self.classifiers
+            .iter()
+            .map(|classifier| classifier.classifier_nm())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ConsumeClassifier for ConsumeClassifierChain {
+    fn classifier_nm(&self) -> &'static str {
+        "chain"
+    }
+
+    /// Asks each registered classifier in turn and returns the first successful answer.
+    ///
+    /// # Arguments
+    ///
+    /// * `prodt_name` - The spending name to classify
+    /// * `user_seq` - Internal user sequence of the caller
+    ///
+    /// # Returns
+    ///
+    /// Returns the category chosen by the first classifier that did not fail.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last error if every classifier fails, or an error if none is registered.
+    async fn find_consume_type(
+        &self,
+        prodt_name: &str,
+        user_seq: i64,
+    ) -> anyhow::Result<ConsumingIndexProdtType> {
+        let mut last_error: Option<anyhow::Error> = None;
+
+        for classifier in &self.classifiers {
+            match classifier.find_consume_type(prodt_name, user_seq).await {
+                Ok(consume_type) => return Ok(consume_type),
+                Err(e) => {
+                    error!(
+                        "[ConsumeClassifierChain::find_consume_type] '{}' classifier failed, trying the next one: {:#}",
+                        classifier.classifier_nm(),
+                        e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            anyhow!("[ConsumeClassifierChain::find_consume_type] No classifier is registered")
+        }))
+    }
+}
@@ -0,0 +1,45 @@
+use crate::common::*;
+
+use crate::models::consume_index_prodt_type::*;
+use crate::service_traits::{consume_classifier::*, elastic_query_service::*};
+
+/// Classifies spending through the `CONSUME_TYPE` Elasticsearch index, including the
+/// keywords each user learned through `ce <id> type` corrections.
+#[derive(Debug, Getters, Clone, new)]
+pub struct ElasticConsumeClassifier<E: ElasticQueryService> {
+    elastic_query_service: Arc<E>,
+}
+
+#[async_trait]
+impl<E> ConsumeClassifier for ElasticConsumeClassifier<E>
+where
+    E: ElasticQueryService + Sync + Send,
+{
+    fn classifier_nm(&self) -> &'static str {
+        "elastic"
+    }
+
+    /// Delegates to `ElasticQueryService::find_consume_type_judgement`.
+    ///
+    /// # Arguments
+    ///
+    /// * `prodt_name` - The spending name to classify
+    /// * `user_seq` - Internal user sequence whose learned keywords are considered
+    ///
+    /// # Returns
+    ///
+    /// Returns the matched category, or `etc` when the index has no match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Elasticsearch query fails.
+    async fn find_consume_type(
+        &self,
+        prodt_name: &str,
+        user_seq: i64,
+    ) -> anyhow::Result<ConsumingIndexProdtType> {
+        self.elastic_query_service
+            .find_consume_type_judgement(prodt_name, user_seq)
+            .await
+    }
+}
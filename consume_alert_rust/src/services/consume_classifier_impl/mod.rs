@@ -0,0 +1,7 @@
+mod consume_classifier_chain;
+mod elastic_consume_classifier;
+mod mysql_consume_classifier;
+
+pub use consume_classifier_chain::*;
+pub use elastic_consume_classifier::*;
+pub use mysql_consume_classifier::*;
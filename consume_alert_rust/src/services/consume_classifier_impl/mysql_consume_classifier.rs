@@ -0,0 +1,298 @@
+use crate::common::*;
+
+use std::sync::RwLock;
+
+use crate::models::{consume_index_prodt_type::*, consume_prodt_keyword::*, score_manager::*};
+use crate::service_traits::{consume_classifier::*, mysql_query_service::*};
+
+/* Match tiers, lower is better. Each tier outranks any distance/weight within the next one. */
+const EXACT_MATCH_TIER: i64 = 0;
+const PREFIX_MATCH_TIER: i64 = 1;
+const CONTAINS_MATCH_TIER: i64 = 2;
+const LEVENSHTEIN_MATCH_TIER: i64 = 3;
+const MATCH_TIER_SIZE: i64 = 100_000;
+
+#[derive(Debug)]
+struct ConsumeKeywordSnapshot {
+    keywords: Vec<ConsumeProdtKeyword>,
+    refreshed_at: DateTime<Utc>,
+}
+
+/// Classifies spending from an in-memory copy of `COMMON_CONSUME_PRODT_KEYWORD` and
+/// `COMMON_CONSUME_KEYWORD_TYPE`, so spending can still be categorised while Elasticsearch is down.
+///
+/// The snapshot is reloaded from MySQL once it is older than `refresh_interval`. Only one lookup
+/// reloads it at a time while concurrent lookups keep serving the previous snapshot, which also
+/// stays in use if the reload fails. Keywords learned per user live only in Elasticsearch and
+/// are not considered here.
+#[derive(Debug)]
+pub struct MysqlConsumeClassifier<M: MysqlQueryService> {
+    mysql_query_service: Arc<M>,
+    refresh_interval: chrono::Duration,
+    snapshot: RwLock<Option<ConsumeKeywordSnapshot>>,
+    refresh_lock: tokio::sync::Mutex<()>,
+}
+
+impl<M: MysqlQueryService> MysqlConsumeClassifier<M> {
+    /// Creates a classifier with an empty snapshot; the first lookup loads it.
+    ///
+    /// # Arguments
+    ///
+    /// * `mysql_query_service` - Service used to load the keyword tables
+    /// * `refresh_interval_secs` - Age in seconds after which the snapshot is reloaded
+    pub fn new(mysql_query_service: Arc<M>, refresh_interval_secs: i64) -> Self {
+        Self {
+            mysql_query_service,
+            refresh_interval: chrono::Duration::seconds(refresh_interval_secs),
+            snapshot: RwLock::new(None),
+            refresh_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Reloads the keyword snapshot from MySQL.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of keywords in the new snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the MySQL query fails; the previous snapshot is left untouched.
+    pub async fn refresh_snapshot(&self) -> anyhow::Result<usize> {
+        let keywords: Vec<ConsumeProdtKeyword> = self
+            .mysql_query_service
+            .find_consume_prodt_keywords()
+            .await?;
+        let keyword_cnt: usize = keywords.len();
+
+        let mut snapshot = self.snapshot.write().map_err(|e| {
+            anyhow!(
+                "[MysqlConsumeClassifier::refresh_snapshot] Snapshot lock poisoned: {:?}",
+                e
+            )
+        })?;
+
+        *snapshot = Some(ConsumeKeywordSnapshot {
+            keywords,
+            refreshed_at: Utc::now(),
+        });
+
+        info!(
+            "[MysqlConsumeClassifier::refresh_snapshot] Loaded {} consume keywords",
+            keyword_cnt
+        );
+
+        Ok(keyword_cnt)
+    }
+
+    /// Returns true when there is no snapshot yet or it is older than `refresh_interval`.
+    fn is_snapshot_stale(&self) -> bool {
+        self.snapshot.read().map_or(true, |snapshot| {
+            snapshot
+                .as_ref()
+                .is_none_or(|snapshot| Utc::now() - snapshot.refreshed_at > self.refresh_interval)
+        })
+    }
+
+    /// Returns true once a snapshot has been loaded, stale or not.
+    fn is_snapshot_loaded(&self) -> bool {
+        self.snapshot
+            .read()
+            .is_ok_and(|snapshot| snapshot.is_some())
+    }
+
+    /// Reloads a stale snapshot with at most one MySQL query in flight.
+    ///
+    /// While another lookup is reloading, the caller returns at once and keeps using the
+    /// previous snapshot; it only waits for that reload when no snapshot exists yet.
+    async fn refresh_stale_snapshot(&self) {
+        let _refresh_guard: tokio::sync::MutexGuard<'_, ()> = match self.refresh_lock.try_lock() {
+            Ok(refresh_guard) => refresh_guard,
+            Err(_) if self.is_snapshot_loaded() => return,
+            Err(_) => self.refresh_lock.lock().await,
+        };
+
+        /* Another lookup may have finished the reload while this one was waiting. */
+        if !self.is_snapshot_stale() {
+            return;
+        }
+
+        if let Err(e) = self.refresh_snapshot().await {
+            error!(
+                "[MysqlConsumeClassifier::refresh_stale_snapshot] Failed to refresh keywords, using the previous snapshot: {:#}",
+                e
+            );
+        }
+    }
+}
+
+/// Picks the best keyword for `prodt_name`: exact match first, then prefix, substring and
+/// finally Levenshtein distance within a third of the longer string. Inside a tier, a smaller
+/// distance and then a larger `keyword_weight` win.
+///
+/// # Arguments
+///
+/// * `keywords` - The keyword snapshot
+/// * `prodt_name` - The spending name to classify
+///
+/// # Returns
+///
+/// Returns `None` when no keyword is close enough.
+pub(crate) fn find_best_keyword_match(
+    keywords: &[ConsumeProdtKeyword],
+    prodt_name: &str,
+) -> Option<ConsumingIndexProdtType> {
+    let normalized_name: String = to_normalized_keyword(prodt_name);
+
+    if normalized_name.is_empty() {
+        return None;
+    }
+
+    let mut manager: ScoreManager<ConsumingIndexProdtType> =
+        ScoreManager::<ConsumingIndexProdtType>::new();
+
+    for keyword in keywords {
+        let normalized_keyword: String = to_normalized_keyword(&keyword.consume_keyword);
+
+        if normalized_keyword.is_empty() {
+            continue;
+        }
+
+        let word_dist: usize = levenshtein(&normalized_keyword, &normalized_name);
+        let max_len: usize = normalized_keyword
+            .chars()
+            .count()
+            .max(normalized_name.chars().count());
+
+        let tier: i64 = if normalized_name == normalized_keyword {
+            EXACT_MATCH_TIER
+        } else if normalized_name.starts_with(&normalized_keyword)
+            || normalized_keyword.starts_with(&normalized_name)
+        {
+            PREFIX_MATCH_TIER
+        } else if normalized_name.contains(&normalized_keyword) {
+            CONTAINS_MATCH_TIER
+        } else if word_dist * 3 <= max_len {
+            LEVENSHTEIN_MATCH_TIER
+        } else {
+            continue;
+        };
+
+        let word_dist_i64: i64 = i64::try_from(word_dist).unwrap_or(MATCH_TIER_SIZE / 1_000);
+        let score: i64 = tier * MATCH_TIER_SIZE + word_dist_i64 * 1_000
+            - i64::from(keyword.keyword_weight).clamp(0, 999);
+
+        manager.input_data(score, keyword.to_consuming_index_prodt_type());
+    }
+
+    manager.delete_lowest().map(|scored_data| scored_data.data)
+}
+
+/// Lowercases and strips whitespace so "스타벅스 강남점" and "스타벅스강남점" compare equal.
+fn to_normalized_keyword(keyword: &str) -> String {
+    keyword
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[async_trait]
+impl<M> ConsumeClassifier for MysqlConsumeClassifier<M>
+where
+    M: MysqlQueryService + Sync + Send,
+{
+    fn classifier_nm(&self) -> &'static str {
+        "mysql"
+    }
+
+    /// Classifies `prodt_name` against the keyword snapshot, reloading it first when stale
+    /// unless another lookup is already doing so.
+    ///
+    /// # Arguments
+    ///
+    /// * `prodt_name` - The spending name to classify
+    /// * `_user_seq` - Unused; learned per-user keywords exist only in Elasticsearch
+    ///
+    /// # Returns
+    ///
+    /// Returns the matched category, or `etc` when no keyword is close enough.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no snapshot could ever be loaded.
+    async fn find_consume_type(
+        &self,
+        prodt_name: &str,
+        _user_seq: i64,
+    ) -> anyhow::Result<ConsumingIndexProdtType> {
+        if self.is_snapshot_stale() {
+            self.refresh_stale_snapshot().await;
+        }
+
+        let snapshot = self.snapshot.read().map_err(|e| {
+            anyhow!(
+                "[MysqlConsumeClassifier::find_consume_type] Snapshot lock poisoned: {:?}",
+                e
+            )
+        })?;
+
+        let keywords: &[ConsumeProdtKeyword] = &snapshot
+            .as_ref()
+            .ok_or_else(|| {
+                anyhow!(
+                    "[MysqlConsumeClassifier::find_consume_type] Keyword snapshot is not loaded"
+                )
+            })?
+            .keywords;
+
+        Ok(find_best_keyword_match(keywords, prodt_name)
+            .unwrap_or_else(|| ConsumingIndexProdtType::to_etc_type(prodt_name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_keyword(
+        consume_keyword_type_id: i64,
+        consume_keyword: &str,
+        keyword_weight: i32,
+    ) -> ConsumeProdtKeyword {
+        ConsumeProdtKeyword {
+            consume_keyword_type_id,
+            consume_keyword_type: format!("type-{}", consume_keyword_type_id),
+            consume_keyword: consume_keyword.to_string(),
+            keyword_weight,
+        }
+    }
+
+    fn find_type_id(keywords: &[ConsumeProdtKeyword], prodt_name: &str) -> Option<i64> {
+        find_best_keyword_match(keywords, prodt_name)
+            .map(|consume_type| *consume_type.consume_keyword_type_id())
+    }
+
+    #[test]
+    fn test_find_best_keyword_match() {
+        let keywords: Vec<ConsumeProdtKeyword> = vec![
+            to_keyword(1, "스타벅스", 1),
+            to_keyword(2, "스타벅스 리저브", 1),
+            to_keyword(3, "gs25", 1),
+            to_keyword(4, "쿠팡이츠", 1),
+            to_keyword(5, "쿠팡", 5),
+        ];
+
+        /* Exact match beats the shorter prefix keyword. */
+        assert_eq!(find_type_id(&keywords, "스타벅스리저브"), Some(2));
+        /* Prefix: branch names follow the merchant. */
+        assert_eq!(find_type_id(&keywords, "스타벅스 강남점"), Some(1));
+        assert_eq!(find_type_id(&keywords, "GS25역삼점"), Some(3));
+        /* Substring. */
+        assert_eq!(find_type_id(&keywords, "(주)쿠팡이츠서비스"), Some(4));
+        /* Levenshtein: one typo in a four-letter keyword. */
+        assert_eq!(find_type_id(&keywords, "스타박스"), Some(1));
+        assert_eq!(find_type_id(&keywords, "이마트"), None);
+        assert_eq!(find_type_id(&keywords, "  "), None);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::common::*;
+
+use crate::repository::kafka_consumer_repository::*;
+
+use crate::models::kafka_consumed_message::*;
+
+use crate::service_traits::consumer_service::*;
+
+#[derive(Getters, new)]
+pub struct ConsumerServiceImpl<K: KafkaConsumerRepository> {
+    kafka_consumer_conn: K,
+}
+
+#[async_trait]
+impl<K> ConsumerService for ConsumerServiceImpl<K>
+where
+    K: KafkaConsumerRepository + Send + Sync,
+{
+    #[doc = "Receive the next message of the subscribed Kafka topic"]
+    /// # Returns
+    /// * `Result<KafkaConsumedMessage, anyhow::Error>` - Owned copy of the received message
+    async fn find_message(&self) -> Result<KafkaConsumedMessage, anyhow::Error> {
+        self.kafka_consumer_conn.find_message().await.map_err(|e| {
+            anyhow!(
+                "[ConsumerServiceImpl::find_message] Failed to receive message: {:?}",
+                e
+            )
+        })
+    }
+
+    #[doc = "Commit a handled Kafka message so it is not delivered again"]
+    /// # Arguments
+    /// * `message` - The message that has been fully handled
+    ///
+    /// # Returns
+    /// * `Result<(), anyhow::Error>` - Ok if the offset was committed
+    fn commit_message(&self, message: &KafkaConsumedMessage) -> Result<(), anyhow::Error> {
+        self.kafka_consumer_conn
+            .commit_message(message)
+            .map_err(|e| {
+                anyhow!(
+                    "[ConsumerServiceImpl::commit_message] Failed to commit message: {:?}",
+                    e
+                )
+            })
+    }
+}
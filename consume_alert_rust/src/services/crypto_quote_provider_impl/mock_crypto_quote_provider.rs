@@ -0,0 +1,36 @@
+use crate::common::*;
+
+use crate::service_traits::crypto_quote_provider::*;
+
+/// Answers crypto quotes of one currency from a fixed price list so the price refresh can be
+/// tested without a real exchange. Symbols missing from the list are left out of the answer.
+#[derive(Debug, new)]
+pub struct MockCryptoQuoteProvider {
+    currency_code: &'static str,
+    quote_prices: HashMap<String, Decimal>,
+}
+
+#[async_trait]
+impl CryptoQuoteProvider for MockCryptoQuoteProvider {
+    fn provider_nm(&self) -> &'static str {
+        "mock"
+    }
+
+    fn currency_code(&self) -> &'static str {
+        self.currency_code
+    }
+
+    async fn find_quote_prices(
+        &self,
+        api_symbols: &[String],
+    ) -> anyhow::Result<HashMap<String, Decimal>> {
+        Ok(api_symbols
+            .iter()
+            .filter_map(|api_symbol| {
+                self.quote_prices
+                    .get(api_symbol)
+                    .map(|quote_price| (api_symbol.clone(), *quote_price))
+            })
+            .collect())
+    }
+}
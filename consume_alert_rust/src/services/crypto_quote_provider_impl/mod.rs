@@ -0,0 +1,7 @@
+#[cfg(test)]
+mod mock_crypto_quote_provider;
+mod upbit_crypto_quote_provider;
+
+#[cfg(test)]
+pub use mock_crypto_quote_provider::*;
+pub use upbit_crypto_quote_provider::*;
@@ -0,0 +1,181 @@
+use crate::common::*;
+
+use crate::service_traits::crypto_quote_provider::*;
+
+use crate::AppConfig;
+
+/// Maximum number of markets asked for in one ticker request.
+const UPBIT_MARKETS_PER_REQUEST: usize = 100;
+
+#[derive(Debug, Deserialize)]
+struct UpbitTicker {
+    /// Market code, e.g. `KRW-BTC`
+    market: String,
+    /// Last traded price
+    trade_price: Decimal,
+}
+
+/// Quotes KRW crypto prices from the Upbit ticker API.
+///
+/// `CRYPTO.api_symbol` may hold either the Upbit market code (`KRW-BTC`) or the bare coin
+/// symbol (`BTC`), which is quoted against KRW.
+#[derive(Debug, Clone)]
+pub struct UpbitCryptoQuoteProvider {
+    http_client: Client,
+    upbit_api_url: Url,
+}
+
+impl UpbitCryptoQuoteProvider {
+    /// Creates an Upbit quote provider from `UPBIT_API_URL`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `UpbitCryptoQuoteProvider` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API URL is invalid.
+    pub fn new() -> anyhow::Result<Self> {
+        let raw_url: &str = AppConfig::get_global().upbit_api_url();
+
+        let upbit_api_url: Url = Url::parse(raw_url).map_err(|e| {
+            anyhow!(
+                "[UpbitCryptoQuoteProvider::new] Invalid UPBIT_API_URL '{}': {}",
+                raw_url,
+                e
+            )
+        })?;
+
+        Ok(Self {
+            http_client: Client::new(),
+            upbit_api_url,
+        })
+    }
+
+    /// Requests the tickers of the given Upbit markets in one call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails; Upbit rejects the whole request when any of
+    /// the markets does not exist.
+    async fn find_tickers(&self, markets: &[String]) -> anyhow::Result<Vec<UpbitTicker>> {
+        let ticker_uri: Url = self.upbit_api_url.join("/v1/ticker")?;
+
+        let res: reqwest::Response = self
+            .http_client
+            .get(ticker_uri)
+            .query(&[("markets", markets.join(","))])
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            Ok(res.json().await?)
+        } else {
+            let status: reqwest::StatusCode = res.status();
+            let error_body: String = res.text().await.unwrap_or_default();
+            Err(anyhow!(
+                "[UpbitCryptoQuoteProvider::find_tickers] Ticker request for {:?} failed. Status: {}, Body: {}",
+                markets,
+                status,
+                error_body
+            ))
+        }
+    }
+}
+
+/// Returns the Upbit market code of a `CRYPTO.api_symbol`.
+fn to_upbit_market(api_symbol: &str) -> String {
+    let api_symbol: String = api_symbol.trim().to_uppercase();
+
+    if api_symbol.contains('-') {
+        api_symbol
+    } else {
+        format!("KRW-{}", api_symbol)
+    }
+}
+
+#[async_trait]
+impl CryptoQuoteProvider for UpbitCryptoQuoteProvider {
+    fn provider_nm(&self) -> &'static str {
+        "upbit"
+    }
+
+    fn currency_code(&self) -> &'static str {
+        "KRW"
+    }
+
+    /// Quotes the symbols in batches. A batch Upbit rejects, usually because one market is
+    /// unknown, is retried one symbol at a time so the other symbols are still quoted.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_symbols` - `CRYPTO.api_symbol` values to quote
+    ///
+    /// # Returns
+    ///
+    /// Returns the KRW price of every symbol that could be quoted, keyed by `api_symbol`.
+    async fn find_quote_prices(
+        &self,
+        api_symbols: &[String],
+    ) -> anyhow::Result<HashMap<String, Decimal>> {
+        let mut quote_prices: HashMap<String, Decimal> = HashMap::new();
+
+        for api_symbol_chunk in api_symbols.chunks(UPBIT_MARKETS_PER_REQUEST) {
+            let symbol_by_market: HashMap<String, &String> = api_symbol_chunk
+                .iter()
+                .map(|api_symbol| (to_upbit_market(api_symbol), api_symbol))
+                .collect();
+            let markets: Vec<String> = symbol_by_market.keys().cloned().collect();
+
+            let tickers: Vec<UpbitTicker> = match self.find_tickers(&markets).await {
+                Ok(tickers) => tickers,
+                Err(e) => {
+                    warn!(
+                        "[UpbitCryptoQuoteProvider::find_quote_prices] Batch failed, quoting one by one: {:#}",
+                        e
+                    );
+
+                    let mut tickers: Vec<UpbitTicker> = Vec::new();
+                    for market in &markets {
+                        match self.find_tickers(std::slice::from_ref(market)).await {
+                            Ok(market_tickers) => tickers.extend(market_tickers),
+                            Err(e) => {
+                                warn!("[UpbitCryptoQuoteProvider::find_quote_prices] {:#}", e)
+                            }
+                        }
+                    }
+                    tickers
+                }
+            };
+
+            for ticker in tickers {
+                if let Some(api_symbol) = symbol_by_market.get(&ticker.market) {
+                    quote_prices.insert((*api_symbol).clone(), ticker.trade_price);
+                }
+            }
+        }
+
+        Ok(quote_prices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upbit_ticker() {
+        assert_eq!(to_upbit_market("btc"), "KRW-BTC");
+        assert_eq!(to_upbit_market("KRW-ETH"), "KRW-ETH");
+
+        let tickers: Vec<UpbitTicker> = serde_json::from_value(json!([
+            { "market": "KRW-BTC", "trade_price": 95120000.0, "change": "RISE" },
+            { "market": "KRW-XRP", "trade_price": 812.5 }
+        ]))
+        .unwrap();
+
+        assert_eq!(tickers[0].market, "KRW-BTC");
+        assert_eq!(tickers[0].trade_price, Decimal::new(95120000, 0));
+        assert_eq!(tickers[1].trade_price, Decimal::new(8125, 1));
+    }
+}
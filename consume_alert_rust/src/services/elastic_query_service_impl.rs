@@ -3,14 +3,123 @@ use crate::common::*;
 use crate::repository::es_repository::*;
 
 use crate::dtos::{EsRangeGroupSeqQueryDto, EsRangeRoomSeqQueryDto};
+use crate::enums::reconcile_scope::*;
 use crate::models::{
     agg_result_set::*, consume_index_prodt_type::*, document_with_id::*, score_manager::*,
+    spent_detail_indexing::*, spent_detail_summary::*,
 };
 
 use crate::configuration::elasitc_index_name::*;
 
 use crate::service_traits::elastic_query_service::*;
 
+/* Weight of a keyword learned from a user's correction (lower ES score wins after weighting). */
+const LEARNED_KEYWORD_WEIGHT: i32 = 10;
+
+/* Page size used when scanning CONSUME_DETAIL for reconciliation. */
+const SPENT_DETAIL_SUMMARY_PAGE_SIZE: usize = 1000;
+
+#[doc = "Function that picks the most likely consumption type among keyword hits (internal helper)"]
+/// Global hits and the hits learned by `user_seq` are ranked separately. The best learned hit
+/// wins only when its keyword is exactly `prodt_name` or its Elasticsearch score is at least
+/// that of the best global hit, so a weak partial match of a correction cannot override a
+/// strong global keyword. Keywords learned by other users are ignored.
+///
+/// # Arguments
+/// * `results` - Keyword documents returned by Elasticsearch
+/// * `prodt_name` - consumtion name
+/// * `user_seq` - Unique identifier of the user
+///
+/// # Returns
+/// * Result<Option<ConsumingIndexProdtType>, anyhow::Error> - None when there is no hit
+fn find_best_consume_type(
+    results: Vec<DocumentWithId<ConsumingIndexProdtType>>,
+    prodt_name: &str,
+    user_seq: i64,
+) -> Result<Option<ConsumingIndexProdtType>, anyhow::Error> {
+    let (user_results, global_results): (Vec<_>, Vec<_>) = results
+        .into_iter()
+        .filter(|consume_type| {
+            !matches!(*consume_type.source().user_seq(), Some(owner_seq) if owner_seq != user_seq)
+        })
+        .partition(|consume_type| *consume_type.source().user_seq() == Some(user_seq));
+
+    let best_user_hit: Option<DocumentWithId<ConsumingIndexProdtType>> =
+        find_lowest_ranked(user_results, prodt_name)?;
+    let best_global_hit: Option<DocumentWithId<ConsumingIndexProdtType>> =
+        find_lowest_ranked(global_results, prodt_name)?;
+
+    let best_hit: Option<DocumentWithId<ConsumingIndexProdtType>> =
+        match (best_user_hit, best_global_hit) {
+            (Some(user_hit), Some(global_hit)) => {
+                let is_exact_match: bool = user_hit
+                    .source()
+                    .consume_keyword()
+                    .trim()
+                    .eq_ignore_ascii_case(prodt_name.trim());
+
+                if is_exact_match || user_hit.score() >= global_hit.score() {
+                    Some(user_hit)
+                } else {
+                    Some(global_hit)
+                }
+            }
+            (user_hit, global_hit) => user_hit.or(global_hit),
+        };
+
+    Ok(best_hit.map(|hit| hit.source))
+}
+
+#[doc = "Function that returns the keyword hit with the lowest weighted rank (internal helper)"]
+/// The rank is the Levenshtein distance to `prodt_name` minus the Elasticsearch score
+/// multiplied by the keyword weight.
+///
+/// # Arguments
+/// * `candidates` - Keyword documents to rank
+/// * `prodt_name` - consumtion name
+///
+/// # Returns
+/// * Result<Option<DocumentWithId<ConsumingIndexProdtType>>, anyhow::Error> - None when there is no candidate
+fn find_lowest_ranked(
+    candidates: Vec<DocumentWithId<ConsumingIndexProdtType>>,
+    prodt_name: &str,
+) -> Result<Option<DocumentWithId<ConsumingIndexProdtType>>, anyhow::Error> {
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+
+    let mut manager: ScoreManager<DocumentWithId<ConsumingIndexProdtType>> =
+        ScoreManager::<DocumentWithId<ConsumingIndexProdtType>>::new();
+
+    for consume_type in candidates {
+        let keyword_weight: f64 = *consume_type.source().keyword_weight() as f64;
+        let score: f64 = *consume_type.score() * -1.0 * keyword_weight;
+
+        if !score.is_finite() {
+            return Err(anyhow!(
+                "[ElasticQueryServiceImpl::find_lowest_ranked] Invalid score value: {}",
+                score
+            ));
+        }
+
+        let score_i64: i64 = score as i64;
+        let keyword: &str = consume_type.source.consume_keyword();
+
+        /* Use the 'levenshtein' algorithm to determine word match */
+        let word_dist: usize = levenshtein(keyword, prodt_name);
+        let word_dist_i64: i64 = word_dist.try_into()?;
+
+        manager.input_data(word_dist_i64 + score_i64, consume_type);
+    }
+
+    let score_data_keyword: ScoredData<DocumentWithId<ConsumingIndexProdtType>> =
+        manager.delete_lowest().ok_or_else(|| {
+            anyhow!("[ElasticQueryServiceImpl::find_lowest_ranked] The mapped data for variable 'score_data_keyword' does not exist.")
+        })?;
+
+    Ok(Some(score_data_keyword.data))
+}
+
 #[derive(Debug, Getters, Clone, new)]
 pub struct ElasticQueryServiceImpl<R: EsRepository> {
     elastic_conn: R,
@@ -71,19 +180,38 @@ impl<R: EsRepository + Sync + Send + std::fmt::Debug> ElasticQueryService
     }
 
     #[doc = "Function that classifies the consumption details provided as parameters into a specific consumption type"]
+    /// Keywords learned from the user's own corrections take precedence over global keywords
+    /// when they match exactly or at least as strongly.
+    ///
     /// # Arguments
     /// * `prodt_name` - consumtion name
+    /// * `user_seq` - Unique identifier of the user whose learned keywords are considered
     ///
     /// # Returns
     /// * Result<ConsumingIndexProdtType, anyhow::Error>
     async fn find_consume_type_judgement(
         &self,
         prodt_name: &str,
+        user_seq: i64,
     ) -> Result<ConsumingIndexProdtType, anyhow::Error> {
+        /* Global keywords have no 'user_seq'; learned keywords are visible only to their owner. */
         let es_query: Value = json!({
             "query": {
-                "match": {
-                    "consume_keyword": prodt_name
+                "bool": {
+                    "must": {
+                        "match": {
+                            "consume_keyword": prodt_name
+                        }
+                    },
+                    "filter": {
+                        "bool": {
+                            "should": [
+                                { "term": { "user_seq": user_seq } },
+                                { "bool": { "must_not": { "exists": { "field": "user_seq" } } } }
+                            ],
+                            "minimum_should_match": 1
+                        }
+                    }
                 }
             }
         });
@@ -109,48 +237,172 @@ impl<R: EsRepository + Sync + Send + std::fmt::Debug> ElasticQueryService
                 )
             })?;
 
-        if results.is_empty() {
-            return Ok(ConsumingIndexProdtType::new(
-                21,
-                String::from("etc"),
-                prodt_name.to_string(),
-                0,
-            ));
-        } else {
-            let mut manager: ScoreManager<ConsumingIndexProdtType> =
-                ScoreManager::<ConsumingIndexProdtType>::new();
+        match find_best_consume_type(results, prodt_name, user_seq)? {
+            Some(consume_type) => Ok(consume_type),
+            None => Ok(ConsumingIndexProdtType::to_etc_type(prodt_name)),
+        }
+    }
 
-            for consume_type in results {
-                let keyword_weight: f64 = *consume_type.source().keyword_weight() as f64;
-                let score: f64 = *consume_type.score() * -1.0 * keyword_weight;
+    #[doc = "Function that stores a merchant-to-category pair learned from a user's correction"]
+    /// The document id is derived from the user and keyword, so correcting the same merchant
+    /// again replaces the previous choice instead of adding a competing keyword.
+    ///
+    /// # Arguments
+    /// * `user_seq` - Unique identifier of the user who made the correction
+    /// * `consume_keyword` - Merchant name to learn : ex) "스타벅스"
+    /// * `consume_keyword_type_id` - Category chosen by the user
+    /// * `consume_keyword_type` - Name of the chosen category
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn input_learned_consume_keyword(
+        &self,
+        user_seq: i64,
+        consume_keyword: &str,
+        consume_keyword_type_id: i64,
+        consume_keyword_type: &str,
+    ) -> Result<(), anyhow::Error> {
+        let learned_keyword: ConsumingIndexProdtType = ConsumingIndexProdtType::new(
+            consume_keyword_type_id,
+            consume_keyword_type.to_string(),
+            consume_keyword.to_string(),
+            LEARNED_KEYWORD_WEIGHT,
+            Some(user_seq),
+        );
+
+        let doc_id: String = format!("user-{}-{}", user_seq, consume_keyword);
+
+        self.elastic_conn
+            .input_query(
+                &doc_id,
+                &serde_json::to_value(&learned_keyword)?,
+                &CONSUME_TYPE,
+            )
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[ElasticQueryServiceImpl::input_learned_consume_keyword] {:?}",
+                    e
+                )
+            })
+    }
 
-                if !score.is_finite() {
-                    return Err(anyhow!("[ElasticQueryServiceImpl::find_consume_type_judgement] Invalid score value: {}", score));
+    #[doc = "Function that indexes a denormalised SPENT_DETAIL row into the CONSUME_DETAIL index"]
+    /// The document id is the `spent_idx`, so repeated events for the same row overwrite it.
+    ///
+    /// # Arguments
+    /// * `spent_detail_indexing` - Denormalised spending row to index
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn input_spent_detail_indexing(
+        &self,
+        spent_detail_indexing: &SpentDetailIndexing,
+    ) -> Result<(), anyhow::Error> {
+        self.elastic_conn
+            .input_query(
+                &spent_detail_indexing.spent_idx().to_string(),
+                &serde_json::to_value(spent_detail_indexing)?,
+                &CONSUME_DETAIL,
+            )
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[ElasticQueryServiceImpl::input_spent_detail_indexing] {:?}",
+                    e
+                )
+            })
+    }
+
+    #[doc = "Function that removes a SPENT_DETAIL row from the CONSUME_DETAIL index"]
+    /// # Arguments
+    /// * `spent_idx` - Primary key of the spending row, used as the document id
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn delete_spent_detail_indexing(&self, spent_idx: i64) -> Result<(), anyhow::Error> {
+        self.elastic_conn
+            .delete_query(&spent_idx.to_string(), &CONSUME_DETAIL)
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[ElasticQueryServiceImpl::delete_spent_detail_indexing] {:?}",
+                    e
+                )
+            })
+    }
+
+    #[doc = "Function that lists the id and amount of every CONSUME_DETAIL document in a scope and period"]
+    /// Pages through the index with `search_after` on `spent_idx`, so the result is not capped
+    /// by the index's `max_result_window`.
+    ///
+    /// # Arguments
+    /// * `reconcile_scope` - User, room or group whose documents are listed
+    /// * `spent_at_from` - Inclusive lower bound of `spent_at` (UTC)
+    /// * `spent_at_to` - Exclusive upper bound of `spent_at` (UTC)
+    ///
+    /// # Returns
+    /// * Result<Vec<SpentDetailSummary>, anyhow::Error> - Sorted by `spent_idx` ascending
+    async fn find_spent_detail_summaries(
+        &self,
+        reconcile_scope: ReconcileScope,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> Result<Vec<SpentDetailSummary>, anyhow::Error> {
+        let mut summaries: Vec<SpentDetailSummary> = Vec::new();
+        let mut search_after: Option<i64> = None;
+
+        loop {
+            let mut query: Value = json!({
+                "size": SPENT_DETAIL_SUMMARY_PAGE_SIZE,
+                "_source": ["spent_idx", "spent_money"],
+                "sort": [{ "spent_idx": "asc" }],
+                "query": {
+                    "bool": {
+                        "filter": [
+                            { "term": { reconcile_scope.es_field(): reconcile_scope.scope_seq() } },
+                            {
+                                "range": {
+                                    "spent_at": {
+                                        "gte": spent_at_from.to_rfc3339(),
+                                        "lt": spent_at_to.to_rfc3339()
+                                    }
+                                }
+                            }
+                        ]
+                    }
                 }
+            });
 
-                let score_i64: i64 = score as i64;
-                let keyword: &str = consume_type.source.consume_keyword();
+            if let Some(last_spent_idx) = search_after {
+                query["search_after"] = json!([last_spent_idx]);
+            }
+
+            let response_body: Value = self
+                .elastic_conn
+                .find_search_query(&query, &CONSUME_DETAIL)
+                .await
+                .map_err(|e| {
+                    anyhow!(
+                        "[ElasticQueryServiceImpl::find_spent_detail_summaries] {:?}",
+                        e
+                    )
+                })?;
 
-                /* Use the 'levenshtein' algorithm to determine word match */
-                let word_dist: usize = levenshtein(keyword, prodt_name);
-                let word_dist_i64: i64 = word_dist.try_into()?;
+            let page: Vec<DocumentWithId<SpentDetailSummary>> =
+                self.find_query_result_vec(&response_body).await?;
+            let page_len: usize = page.len();
 
-                //println!("{:?} // {:?} // {:?}", word_dist_i64,score_i64, consume_type.source);
+            summaries.extend(page.into_iter().map(|doc| doc.source));
 
-                manager.input_data(word_dist_i64 + score_i64, consume_type.source);
+            if page_len < SPENT_DETAIL_SUMMARY_PAGE_SIZE {
+                break;
             }
 
-            let score_data_keyword: ScoredData<ConsumingIndexProdtType> = match manager
-                .delete_lowest()
-            {
-                Some(score_data_keyword) => score_data_keyword,
-                None => {
-                    return Err(anyhow!("[ElasticQueryServiceImpl::find_consume_type_judgement] The mapped data for variable 'score_data_keyword' does not exist."));
-                }
-            };
-
-            return Ok(score_data_keyword.data);
+            search_after = summaries.last().map(|summary| summary.spent_idx);
         }
+
+        Ok(summaries)
     }
 
     #[doc = "Function that returns data by applying an order in a particular index"]
@@ -378,3 +630,96 @@ impl<R: EsRepository + Sync + Send + std::fmt::Debug> ElasticQueryService
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_keyword_hit(
+        consume_keyword_type_id: i64,
+        consume_keyword: &str,
+        score: f64,
+        user_seq: Option<i64>,
+    ) -> DocumentWithId<ConsumingIndexProdtType> {
+        DocumentWithId::new(
+            format!("{}-{}", consume_keyword, consume_keyword_type_id),
+            score,
+            ConsumingIndexProdtType::new(
+                consume_keyword_type_id,
+                format!("type-{}", consume_keyword_type_id),
+                consume_keyword.to_string(),
+                1,
+                user_seq,
+            ),
+        )
+    }
+
+    #[test]
+    fn test_find_best_consume_type() {
+        let global_hit = || to_keyword_hit(1, "스타벅스", 9.0, None);
+
+        /* Without a learned keyword the global keyword is used. */
+        let consume_type: ConsumingIndexProdtType =
+            find_best_consume_type(vec![global_hit()], "스타벅스", 10)
+                .unwrap()
+                .unwrap();
+        assert_eq!(*consume_type.consume_keyword_type_id(), 1);
+
+        /* The user's own exact correction wins even with a weaker Elasticsearch score. */
+        let consume_type: ConsumingIndexProdtType = find_best_consume_type(
+            vec![global_hit(), to_keyword_hit(7, "스타벅스", 1.0, Some(10))],
+            "스타벅스",
+            10,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(*consume_type.consume_keyword_type_id(), 7);
+
+        /* A weak partial match of a correction does not override a strong global hit. */
+        let consume_type: ConsumingIndexProdtType = find_best_consume_type(
+            vec![
+                to_keyword_hit(1, "cu 편의점", 9.0, None),
+                to_keyword_hit(7, "gs25 편의점", 2.0, Some(10)),
+            ],
+            "cu 편의점",
+            10,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(*consume_type.consume_keyword_type_id(), 1);
+
+        /* A partial correction scoring at least as high as the global hit still wins. */
+        let consume_type: ConsumingIndexProdtType = find_best_consume_type(
+            vec![
+                to_keyword_hit(1, "편의점", 4.0, None),
+                to_keyword_hit(7, "gs25 편의점", 6.0, Some(10)),
+            ],
+            "gs25 편의점 강남점",
+            10,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(*consume_type.consume_keyword_type_id(), 7);
+
+        /* Another user's correction is ignored, even when it is the only hit. */
+        let consume_type: ConsumingIndexProdtType = find_best_consume_type(
+            vec![global_hit(), to_keyword_hit(7, "스타벅스", 20.0, Some(99))],
+            "스타벅스",
+            10,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(*consume_type.consume_keyword_type_id(), 1);
+        assert!(find_best_consume_type(
+            vec![to_keyword_hit(7, "스타벅스", 20.0, Some(99))],
+            "스타벅스",
+            10
+        )
+        .unwrap()
+        .is_none());
+
+        assert!(find_best_consume_type(vec![], "스타벅스", 10)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,194 @@
+mod native_graph_api_service;
+mod python_graph_api_service;
+
+pub use native_graph_api_service::*;
+pub use python_graph_api_service::*;
+
+use crate::common::*;
+
+use crate::models::{
+    assets::*, net_worth_history::*, stock_pie_data::*, to_python_graph_circle::*,
+    to_python_graph_line::*,
+};
+
+use crate::service_traits::graph_api_service::*;
+
+use crate::AppConfig;
+
+/// Chart renderer selected by `GRAPH_RENDERER`: the external matplotlib API (`python`) or the
+/// in-process plotters renderer (`native`).
+///
+/// The external API has no net worth history chart, so that chart is always drawn by the
+/// native renderer, which is also set up in `python` mode when `CHART_FONT_PATH` loads.
+#[derive(Clone)]
+pub struct GraphApiServiceImpl {
+    graph_renderer: Arc<dyn GraphApiService + Send + Sync>,
+    net_worth_renderer: Option<Arc<NativeGraphApiService>>,
+}
+
+impl GraphApiServiceImpl {
+    /// Creates the chart renderer configured by `GRAPH_RENDERER`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `GraphApiServiceImpl` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `GRAPH_API_URL` is missing or invalid for the `python` renderer, or
+    /// if `CHART_FONT_PATH` cannot be loaded for the `native` renderer.
+    pub fn new() -> anyhow::Result<Self> {
+        let app_config: &AppConfig = AppConfig::get_global();
+
+        let (graph_renderer, net_worth_renderer): (
+            Arc<dyn GraphApiService + Send + Sync>,
+            Option<Arc<NativeGraphApiService>>,
+        ) = match app_config.graph_renderer().as_str() {
+            "native" => {
+                let native_renderer: Arc<NativeGraphApiService> =
+                    Arc::new(NativeGraphApiService::new(app_config.chart_font_path())?);
+                (native_renderer.clone(), Some(native_renderer))
+            }
+            _ => {
+                let net_worth_renderer: Option<Arc<NativeGraphApiService>> =
+                    match NativeGraphApiService::new(app_config.chart_font_path()) {
+                        Ok(native_renderer) => Some(Arc::new(native_renderer)),
+                        Err(e) => {
+                            warn!(
+                                "[GraphApiServiceImpl::new] Net worth history charts are disabled: {:#}",
+                                e
+                            );
+                            None
+                        }
+                    };
+                (Arc::new(PythonGraphApiService::new()?), net_worth_renderer)
+            }
+        };
+
+        info!(
+            "[GraphApiServiceImpl::new] Graph renderer: {}",
+            app_config.graph_renderer()
+        );
+
+        Ok(Self {
+            graph_renderer,
+            net_worth_renderer,
+        })
+    }
+
+    /// Runs a graph request, retrying failures with exponential backoff
+    /// (`GRAPH_RETRY_BASE_DELAY_MS`, doubled after every attempt).
+    ///
+    /// # Arguments
+    ///
+    /// * `graph_nm` - Graph name used in logs
+    /// * `render` - Issues one graph request
+    ///
+    /// # Returns
+    ///
+    /// Returns the PNG bytes of the first successful attempt.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last error once `GRAPH_RETRY_MAX_ATTEMPTS` attempts have failed.
+    async fn find_graph_with_retry<F, Fut>(
+        &self,
+        graph_nm: &str,
+        render: F,
+    ) -> anyhow::Result<Vec<u8>>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = anyhow::Result<Vec<u8>>>,
+    {
+        let app_config: &AppConfig = AppConfig::get_global();
+        let max_attempts: u32 = *app_config.graph_retry_max_attempts();
+        let mut retry_delay: Duration =
+            Duration::from_millis(*app_config.graph_retry_base_delay_ms());
+
+        let mut attempt: u32 = 1;
+
+        loop {
+            match render().await {
+                Ok(graph_bytes) => return Ok(graph_bytes),
+                Err(e) if attempt >= max_attempts => return Err(e.context(format!(
+                    "[GraphApiServiceImpl::find_graph_with_retry] '{}' failed after {} attempt(s)",
+                    graph_nm, attempt
+                ))),
+                Err(e) => {
+                    error!(
+                        "[GraphApiServiceImpl::find_graph_with_retry] '{}' attempt {}/{} failed, retrying in {:?}: {:#}",
+                        graph_nm, attempt, max_attempts, retry_delay, e
+                    );
+                    tokio::time::sleep(retry_delay).await;
+                    retry_delay *= 2;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl GraphApiService for GraphApiServiceImpl {
+    async fn find_python_matplot_consume_detail_double(
+        &self,
+        cur_python_graph_info: &ToPythonGraphLine,
+        versus_python_graph_info: &ToPythonGraphLine,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.find_graph_with_retry("consume_detail", || {
+            self.graph_renderer
+                .find_python_matplot_consume_detail_double(
+                    cur_python_graph_info,
+                    versus_python_graph_info,
+                )
+        })
+        .await
+    }
+
+    async fn find_python_matplot_consume_type(
+        &self,
+        to_python_graph_circle: &ToPythonGraphCircle,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.find_graph_with_retry("consume_type", || {
+            self.graph_renderer
+                .find_python_matplot_consume_type(to_python_graph_circle)
+        })
+        .await
+    }
+
+    async fn find_python_matplot_asset_pie(&self, assets: Assets) -> anyhow::Result<Vec<u8>> {
+        self.find_graph_with_retry("asset_pie", || {
+            self.graph_renderer
+                .find_python_matplot_asset_pie(assets.clone())
+        })
+        .await
+    }
+
+    async fn find_python_matplot_stock_pie(
+        &self,
+        stock_pie_data: StockPieData,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.find_graph_with_retry("stock_pie", || {
+            self.graph_renderer
+                .find_python_matplot_stock_pie(stock_pie_data.clone())
+        })
+        .await
+    }
+
+    async fn find_python_matplot_net_worth_history(
+        &self,
+        net_worth_history: &NetWorthHistory,
+    ) -> anyhow::Result<Vec<u8>> {
+        /* Rendered in-process only, so a failure is not worth retrying. */
+        match &self.net_worth_renderer {
+            Some(net_worth_renderer) => {
+                net_worth_renderer
+                    .find_python_matplot_net_worth_history(net_worth_history)
+                    .await
+            }
+            None => Err(anyhow!(
+                "[GraphApiServiceImpl::find_python_matplot_net_worth_history] The native renderer is unavailable, check CHART_FONT_PATH"
+            )),
+        }
+    }
+}
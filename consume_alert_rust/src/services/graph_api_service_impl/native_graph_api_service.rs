@@ -0,0 +1,526 @@
+use crate::common::*;
+
+use plotters::prelude::*;
+use plotters::style::{register_font, FontStyle};
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::models::{
+    assets::*, net_worth_history::*, stock_pie_data::*, to_python_graph_circle::*,
+    to_python_graph_line::*,
+};
+
+use crate::service_traits::graph_api_service::*;
+
+/* The chart font is registered under the family plotters falls back to for captions, axis
+ * labels and legends, so every text element is drawn with it (including Hangul). */
+const CHART_FONT_FAMILY: &str = "sans-serif";
+
+const LINE_CHART_SIZE: (u32, u32) = (1200, 800);
+const PIE_CHART_SIZE: (u32, u32) = (1000, 1000);
+
+const PIE_COLORS: [RGBColor; 12] = [
+    RGBColor(31, 119, 180),
+    RGBColor(255, 127, 14),
+    RGBColor(44, 160, 44),
+    RGBColor(214, 39, 40),
+    RGBColor(148, 103, 189),
+    RGBColor(140, 86, 75),
+    RGBColor(227, 119, 194),
+    RGBColor(127, 127, 127),
+    RGBColor(188, 189, 34),
+    RGBColor(23, 190, 207),
+    RGBColor(174, 199, 232),
+    RGBColor(255, 187, 120),
+];
+
+#[derive(Debug, Clone)]
+pub struct NativeGraphApiService;
+
+impl NativeGraphApiService {
+    /// Creates a renderer that draws charts in-process, registering the font used for all text.
+    ///
+    /// # Arguments
+    ///
+    /// * `font_path` - TTF/OTF file with Hangul glyphs (e.g. `data/fonts/BMDOHYEON_ttf.ttf`)
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `NativeGraphApiService` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the font file cannot be read or parsed.
+    pub fn new(font_path: &str) -> anyhow::Result<Self> {
+        let font_bytes: Vec<u8> = std::fs::read(font_path).map_err(|e| {
+            anyhow!(
+                "[NativeGraphApiService::new] Failed to read chart font '{}': {}",
+                font_path,
+                e
+            )
+        })?;
+
+        /* plotters keeps registered fonts for the life of the process. */
+        let font_bytes: &'static [u8] = Box::leak(font_bytes.into_boxed_slice());
+
+        register_font(CHART_FONT_FAMILY, FontStyle::Normal, font_bytes).map_err(|_| {
+            anyhow!(
+                "[NativeGraphApiService::new] '{}' is not a valid TTF/OTF font",
+                font_path
+            )
+        })?;
+
+        Ok(Self)
+    }
+}
+
+#[doc = "Function that encodes an RGB frame buffer as PNG bytes"]
+fn to_png_bytes(rgb_buffer: &[u8], (width, height): (u32, u32)) -> anyhow::Result<Vec<u8>> {
+    let mut png_bytes: Vec<u8> = Vec::new();
+
+    let mut encoder: png::Encoder<'_, &mut Vec<u8>> =
+        png::Encoder::new(&mut png_bytes, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(rgb_buffer)?;
+    writer.finish()?;
+
+    Ok(png_bytes)
+}
+
+#[doc = "Function that draws the cumulative spending of two periods as a line chart"]
+/// # Arguments
+/// * `cur_python_graph_info` - Cumulative daily spending of the requested period
+/// * `versus_python_graph_info` - Cumulative daily spending of the comparison period
+///
+/// # Returns
+/// * Result<Vec<u8>, anyhow::Error> - PNG bytes
+fn render_consume_detail_double(
+    cur_python_graph_info: &ToPythonGraphLine,
+    versus_python_graph_info: &ToPythonGraphLine,
+) -> anyhow::Result<Vec<u8>> {
+    let mut rgb_buffer: Vec<u8> = vec![0; (LINE_CHART_SIZE.0 * LINE_CHART_SIZE.1 * 3) as usize];
+
+    {
+        let root = BitMapBackend::with_buffer(&mut rgb_buffer, LINE_CHART_SIZE).into_drawing_area();
+        root.fill(&WHITE)
+            .map_err(|e| anyhow!("[render_consume_detail_double] {:?}", e))?;
+
+        let series: [(&ToPythonGraphLine, RGBColor); 2] = [
+            (cur_python_graph_info, RGBColor(214, 39, 40)),
+            (versus_python_graph_info, RGBColor(31, 119, 180)),
+        ];
+
+        let max_day: usize = series
+            .iter()
+            .map(|(graph_info, _)| graph_info.consume_accumulate_list().len())
+            .max()
+            .unwrap_or(0)
+            .max(2);
+
+        let max_cost: i64 = series
+            .iter()
+            .filter_map(|(graph_info, _)| graph_info.consume_accumulate_list().last().copied())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(
+                format!(
+                    "{} ~ {} 누적 소비 (총 {}원)",
+                    cur_python_graph_info.start_dt(),
+                    cur_python_graph_info.end_dt(),
+                    (*cur_python_graph_info.total_cost() as i64).to_formatted_string(&Locale::en)
+                ),
+                (CHART_FONT_FAMILY, 36),
+            )
+            .margin(30)
+            .x_label_area_size(50)
+            .y_label_area_size(120)
+            .build_cartesian_2d(1..max_day as i64, 0..(max_cost + max_cost / 10))
+            .map_err(|e| anyhow!("[render_consume_detail_double] {:?}", e))?;
+
+        chart
+            .configure_mesh()
+            .x_desc("일차")
+            .y_label_formatter(&|cost| cost.to_formatted_string(&Locale::en))
+            .label_style((CHART_FONT_FAMILY, 18))
+            .draw()
+            .map_err(|e| anyhow!("[render_consume_detail_double] {:?}", e))?;
+
+        for (graph_info, color) in series {
+            chart
+                .draw_series(LineSeries::new(
+                    graph_info
+                        .consume_accumulate_list()
+                        .iter()
+                        .enumerate()
+                        .map(|(day, cost)| (day as i64 + 1, *cost)),
+                    color.stroke_width(3),
+                ))
+                .map_err(|e| anyhow!("[render_consume_detail_double] {:?}", e))?
+                .label(format!(
+                    "{} ~ {}",
+                    graph_info.start_dt(),
+                    graph_info.end_dt()
+                ))
+                .legend(move |(x, y)| {
+                    PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(3))
+                });
+        }
+
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .label_font((CHART_FONT_FAMILY, 20))
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()
+            .map_err(|e| anyhow!("[render_consume_detail_double] {:?}", e))?;
+
+        root.present()
+            .map_err(|e| anyhow!("[render_consume_detail_double] {:?}", e))?;
+    }
+
+    to_png_bytes(&rgb_buffer, LINE_CHART_SIZE)
+}
+
+#[doc = "Function that draws labelled amounts as a pie chart with percentages"]
+/// Non-positive amounts are left out so a negative balance never distorts the pie.
+///
+/// # Arguments
+/// * `caption` - Chart title
+/// * `labels` - Slice labels
+/// * `sizes` - Slice amounts, in the same order as `labels`
+///
+/// # Returns
+/// * Result<Vec<u8>, anyhow::Error> - PNG bytes
+fn render_pie(caption: &str, labels: &[String], sizes: &[f64]) -> anyhow::Result<Vec<u8>> {
+    let (labels, sizes): (Vec<String>, Vec<f64>) = labels
+        .iter()
+        .zip(sizes)
+        .filter(|(_, size)| **size > 0.0)
+        .map(|(label, size)| (label.clone(), *size))
+        .unzip();
+
+    let colors: Vec<RGBColor> = (0..sizes.len())
+        .map(|idx| PIE_COLORS[idx % PIE_COLORS.len()])
+        .collect();
+
+    let mut rgb_buffer: Vec<u8> = vec![0; (PIE_CHART_SIZE.0 * PIE_CHART_SIZE.1 * 3) as usize];
+
+    {
+        let root = BitMapBackend::with_buffer(&mut rgb_buffer, PIE_CHART_SIZE).into_drawing_area();
+        root.fill(&WHITE)
+            .map_err(|e| anyhow!("[render_pie] {:?}", e))?;
+
+        let root = root
+            .margin(30, 30, 30, 30)
+            .titled(caption, (CHART_FONT_FAMILY, 36))
+            .map_err(|e| anyhow!("[render_pie] {:?}", e))?;
+
+        let (width, height) = root.dim_in_pixel();
+        let center: (i32, i32) = (width as i32 / 2, height as i32 / 2);
+        let radius: f64 = f64::from(width.min(height)) * 0.33;
+
+        if sizes.is_empty() {
+            root.draw_text(
+                "데이터가 없습니다.",
+                &(CHART_FONT_FAMILY, 28).into_text_style(&root),
+                (center.0 - 100, center.1),
+            )
+            .map_err(|e| anyhow!("[render_pie] {:?}", e))?;
+        } else {
+            let mut pie = Pie::new(&center, &radius, &sizes, &colors, &labels);
+            pie.start_angle(-90.0);
+            pie.label_style((CHART_FONT_FAMILY, 24).into_font().color(&BLACK));
+            pie.percentages((CHART_FONT_FAMILY, 20).into_font().color(&WHITE));
+
+            root.draw(&pie)
+                .map_err(|e| anyhow!("[render_pie] {:?}", e))?;
+        }
+
+        root.present()
+            .map_err(|e| anyhow!("[render_pie] {:?}", e))?;
+    }
+
+    to_png_bytes(&rgb_buffer, PIE_CHART_SIZE)
+}
+
+#[doc = "Function that draws the monthly net worth as an area chart stacked by asset class"]
+/// Negative amounts are drawn as zero so a single class never pulls the stack below the axis.
+///
+/// # Arguments
+/// * `net_worth_history` - Monthly net worth, oldest month first
+///
+/// # Returns
+/// * Result<Vec<u8>, anyhow::Error> - PNG bytes
+fn render_net_worth_history(net_worth_history: &NetWorthHistory) -> anyhow::Result<Vec<u8>> {
+    let points: &Vec<NetWorthPoint> = net_worth_history.points();
+
+    /* stacked_amounts[class][month] holds the top edge of that class in that month. */
+    let mut stacked_amounts: Vec<Vec<i64>> = vec![Vec::new(); NET_WORTH_ASSET_TYPES.len()];
+    for point in points {
+        let mut stacked_amount: i64 = 0;
+        for (idx, amount) in point.asset_amounts_krw().iter().enumerate() {
+            stacked_amount += amount.round().to_i64().unwrap_or_default().max(0);
+            stacked_amounts[idx].push(stacked_amount);
+        }
+    }
+
+    let max_amount: i64 = stacked_amounts
+        .last()
+        .and_then(|top_amounts| top_amounts.iter().max().copied())
+        .unwrap_or(0)
+        .max(1);
+    let max_month: i64 = (points.len() as i64 - 1).max(1);
+
+    let mut rgb_buffer: Vec<u8> = vec![0; (LINE_CHART_SIZE.0 * LINE_CHART_SIZE.1 * 3) as usize];
+
+    {
+        let root = BitMapBackend::with_buffer(&mut rgb_buffer, LINE_CHART_SIZE).into_drawing_area();
+        root.fill(&WHITE)
+            .map_err(|e| anyhow!("[render_net_worth_history] {:?}", e))?;
+
+        let caption: String = match (points.first(), points.last()) {
+            (Some(first_point), Some(last_point)) => format!(
+                "{} ~ {} 순자산 추이",
+                first_point.period_label, last_point.period_label
+            ),
+            _ => "순자산 추이".to_string(),
+        };
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(caption, (CHART_FONT_FAMILY, 36))
+            .margin(30)
+            .x_label_area_size(50)
+            .y_label_area_size(160)
+            .build_cartesian_2d(0..max_month, 0..(max_amount + max_amount / 10))
+            .map_err(|e| anyhow!("[render_net_worth_history] {:?}", e))?;
+
+        chart
+            .configure_mesh()
+            .x_labels(points.len().max(2))
+            .x_label_formatter(&|month| {
+                points
+                    .get(*month as usize)
+                    .map(|point| point.period_label.clone())
+                    .unwrap_or_default()
+            })
+            .y_label_formatter(&|amount| amount.to_formatted_string(&Locale::en))
+            .label_style((CHART_FONT_FAMILY, 18))
+            .draw()
+            .map_err(|e| anyhow!("[render_net_worth_history] {:?}", e))?;
+
+        /* Drawn from the top class down, so each lower class covers the part of the area
+         * above that belongs to it. */
+        for (idx, asset_type) in NET_WORTH_ASSET_TYPES.iter().enumerate().rev() {
+            let color: RGBColor = PIE_COLORS[idx % PIE_COLORS.len()];
+
+            chart
+                .draw_series(AreaSeries::new(
+                    stacked_amounts[idx]
+                        .iter()
+                        .enumerate()
+                        .map(|(month, amount)| (month as i64, *amount)),
+                    0,
+                    color.mix(0.8),
+                ))
+                .map_err(|e| anyhow!("[render_net_worth_history] {:?}", e))?
+                .label(*asset_type)
+                .legend(move |(x, y)| {
+                    Rectangle::new([(x, y - 8), (x + 20, y + 8)], color.filled())
+                });
+        }
+
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .label_font((CHART_FONT_FAMILY, 20))
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()
+            .map_err(|e| anyhow!("[render_net_worth_history] {:?}", e))?;
+
+        root.present()
+            .map_err(|e| anyhow!("[render_net_worth_history] {:?}", e))?;
+    }
+
+    to_png_bytes(&rgb_buffer, LINE_CHART_SIZE)
+}
+
+#[doc = "Function that runs a CPU-bound chart renderer off the async worker threads"]
+async fn render_blocking<F>(render: F) -> anyhow::Result<Vec<u8>>
+where
+    F: FnOnce() -> anyhow::Result<Vec<u8>> + Send + 'static,
+{
+    tokio::task::spawn_blocking(render).await.map_err(|e| {
+        anyhow!(
+            "[NativeGraphApiService] Chart rendering task failed: {:?}",
+            e
+        )
+    })?
+}
+
+#[async_trait]
+impl GraphApiService for NativeGraphApiService {
+    async fn find_python_matplot_consume_detail_double(
+        &self,
+        cur_python_graph_info: &ToPythonGraphLine,
+        versus_python_graph_info: &ToPythonGraphLine,
+    ) -> anyhow::Result<Vec<u8>> {
+        let cur_python_graph_info: ToPythonGraphLine = cur_python_graph_info.clone();
+        let versus_python_graph_info: ToPythonGraphLine = versus_python_graph_info.clone();
+
+        render_blocking(move || {
+            render_consume_detail_double(&cur_python_graph_info, &versus_python_graph_info)
+        })
+        .await
+    }
+
+    async fn find_python_matplot_consume_type(
+        &self,
+        to_python_graph_circle: &ToPythonGraphCircle,
+    ) -> anyhow::Result<Vec<u8>> {
+        let caption: String = format!(
+            "{} ~ {} 카테고리별 소비 (총 {}원)",
+            to_python_graph_circle.start_dt(),
+            to_python_graph_circle.end_dt(),
+            (*to_python_graph_circle.total_cost() as i64).to_formatted_string(&Locale::en)
+        );
+        let labels: Vec<String> = to_python_graph_circle.prodt_type_vec().clone();
+        let sizes: Vec<f64> = to_python_graph_circle.prodt_type_cost_per_vec().clone();
+
+        render_blocking(move || render_pie(&caption, &labels, &sizes)).await
+    }
+
+    async fn find_python_matplot_asset_pie(&self, assets: Assets) -> anyhow::Result<Vec<u8>> {
+        let caption: String = format!(
+            "자산 구성 (총 {}원)",
+            assets
+                .total_asset_amount_krw()
+                .round()
+                .to_i64()
+                .unwrap_or_default()
+                .to_formatted_string(&Locale::en)
+        );
+
+        let mut asset_types: Vec<(String, f64)> = assets
+            .asset_map()
+            .iter()
+            .map(|(asset_type, asset_resps)| {
+                let asset_krw: Decimal = asset_resps.iter().map(|asset| asset.asset_krw).sum();
+                (asset_type.clone(), asset_krw.to_f64().unwrap_or_default())
+            })
+            .collect();
+        asset_types.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let (labels, sizes): (Vec<String>, Vec<f64>) = asset_types.into_iter().unzip();
+
+        render_blocking(move || render_pie(&caption, &labels, &sizes)).await
+    }
+
+    async fn find_python_matplot_stock_pie(
+        &self,
+        stock_pie_data: StockPieData,
+    ) -> anyhow::Result<Vec<u8>> {
+        let caption: String = format!(
+            "주식 구성 (총 {}원)",
+            stock_pie_data
+                .total_stock_amount_krw()
+                .round()
+                .to_i64()
+                .unwrap_or_default()
+                .to_formatted_string(&Locale::en)
+        );
+        let labels: Vec<String> = stock_pie_data.stock_names;
+        let sizes: Vec<f64> = stock_pie_data
+            .stock_amount_krw
+            .iter()
+            .map(|amount| amount.to_f64().unwrap_or_default())
+            .collect();
+
+        render_blocking(move || render_pie(&caption, &labels, &sizes)).await
+    }
+
+    async fn find_python_matplot_net_worth_history(
+        &self,
+        net_worth_history: &NetWorthHistory,
+    ) -> anyhow::Result<Vec<u8>> {
+        let net_worth_history: NetWorthHistory = net_worth_history.clone();
+
+        render_blocking(move || render_net_worth_history(&net_worth_history)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+    #[test]
+    fn test_render_charts_to_png() {
+        NativeGraphApiService::new("data/fonts/BMDOHYEON_ttf.ttf").unwrap();
+
+        let pie: Vec<u8> = render_pie(
+            "카테고리별 소비",
+            &["식비".to_string(), "교통".to_string(), "환불".to_string()],
+            &[60.0, 40.0, -5.0],
+        )
+        .unwrap();
+        assert_eq!(pie[..8], PNG_SIGNATURE);
+
+        let empty_pie: Vec<u8> = render_pie("주식 구성", &[], &[]).unwrap();
+        assert_eq!(empty_pie[..8], PNG_SIGNATURE);
+
+        let to_graph_line = |line_type: &str, consume_accumulate_list: Vec<i64>| {
+            serde_json::from_value::<ToPythonGraphLine>(json!({
+                "line_type": line_type,
+                "start_dt": "2026-10-01",
+                "end_dt": "2026-10-31",
+                "total_cost": 32000.0,
+                "consume_accumulate_list": consume_accumulate_list
+            }))
+            .unwrap()
+        };
+
+        let line: Vec<u8> = render_consume_detail_double(
+            &to_graph_line("cur", vec![12000, 20000, 32000]),
+            &to_graph_line("versus", vec![]),
+        )
+        .unwrap();
+        assert_eq!(line[..8], PNG_SIGNATURE);
+
+        let to_point = |period_label: &str, stock_amount: i64| {
+            NetWorthPoint::new(
+                period_label.to_string(),
+                Utc::now(),
+                Decimal::from(1_000_000),
+                Decimal::from(300_000),
+                Decimal::from(stock_amount),
+                Decimal::ZERO,
+                Decimal::from(50_000),
+            )
+        };
+
+        let net_worth: Vec<u8> = render_net_worth_history(&NetWorthHistory::new(
+            NET_WORTH_ASSET_TYPES
+                .iter()
+                .map(|t| t.to_string())
+                .collect(),
+            vec![to_point("2026.09", 500_000), to_point("2026.10", -20_000)],
+        ))
+        .unwrap();
+        assert_eq!(net_worth[..8], PNG_SIGNATURE);
+
+        let single_net_worth: Vec<u8> = render_net_worth_history(&NetWorthHistory::new(
+            Vec::new(),
+            vec![to_point("2026.10", 500_000)],
+        ))
+        .unwrap();
+        assert_eq!(single_net_worth[..8], PNG_SIGNATURE);
+    }
+}
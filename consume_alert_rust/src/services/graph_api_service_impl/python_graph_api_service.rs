@@ -1,7 +1,8 @@
 use crate::common::*;
 
 use crate::models::{
-    assets::*, stock_pie_data::*, to_python_graph_circle::*, to_python_graph_line::*,
+    assets::*, net_worth_history::*, stock_pie_data::*, to_python_graph_circle::*,
+    to_python_graph_line::*,
 };
 
 use crate::service_traits::graph_api_service::*;
@@ -14,27 +15,27 @@ fn initialize_http_clients() -> Client {
 }
 
 #[derive(Debug, Getters, Clone)]
-pub struct GraphApiServiceImpl {
+pub struct PythonGraphApiService {
     graph_api_url: reqwest::Url,
 }
 
-impl GraphApiServiceImpl {
-    /// Creates a new `GraphApiServiceImpl` by reading the graph API URL from the environment.
+impl PythonGraphApiService {
+    /// Creates a new `PythonGraphApiService` by reading the graph API URL from the environment.
     ///
     /// # Returns
     ///
-    /// Returns a new `GraphApiServiceImpl` instance.
+    /// Returns a new `PythonGraphApiService` instance.
     pub fn new() -> anyhow::Result<Self> {
         let raw_url: String = env::var("GRAPH_API_URL").inspect_err(|e| {
             error!(
-                "[GraphApiServiceImpl::new] 'GRAPH_API_URL' must be set: {:#}",
+                "[PythonGraphApiService::new] 'GRAPH_API_URL' must be set: {:#}",
                 e
             );
         })?;
 
         let graph_api_url = reqwest::Url::parse(&raw_url).map_err(|e| {
             anyhow!(
-                "[GraphApiServiceImpl::new] Invalid GRAPH_API_URL '{}': {}",
+                "[PythonGraphApiService::new] Invalid GRAPH_API_URL '{}': {}",
                 raw_url,
                 e
             )
@@ -50,7 +51,7 @@ impl GraphApiServiceImpl {
     ) -> anyhow::Result<Vec<u8>> {
         let post_uri: Url = self.graph_api_url.join(uri).map_err(|e| {
             anyhow!(
-                "[GraphApiServiceImpl::call_python_graph_api_bytes] Invalid URI '{}': {}",
+                "[PythonGraphApiService::call_python_graph_api_bytes] Invalid URI '{}': {}",
                 uri,
                 e
             )
@@ -66,7 +67,7 @@ impl GraphApiServiceImpl {
             let status: reqwest::StatusCode = res.status();
             let error_body: String = res.text().await.unwrap_or_default();
             Err(anyhow!(
-                "[GraphApiServiceImpl::call_python_graph_api_bytes] Request for '{}' failed. Status: {}, Body: {}",
+                "[PythonGraphApiService::call_python_graph_api_bytes] Request for '{}' failed. Status: {}, Body: {}",
                 post_uri,
                 status,
                 error_body
@@ -76,7 +77,7 @@ impl GraphApiServiceImpl {
 }
 
 #[async_trait]
-impl GraphApiService for GraphApiServiceImpl {
+impl GraphApiService for PythonGraphApiService {
     async fn find_python_matplot_consume_detail_double(
         &self,
         cur_python_graph_info: &ToPythonGraphLine,
@@ -111,4 +112,15 @@ impl GraphApiService for GraphApiServiceImpl {
         self.call_python_graph_api_bytes("/api/stock_pie_image", stock_pie_data)
             .await
     }
+
+    /// The external graph API has no net worth history endpoint; `GraphApiServiceImpl` draws
+    /// this chart with the native renderer instead.
+    async fn find_python_matplot_net_worth_history(
+        &self,
+        _net_worth_history: &NetWorthHistory,
+    ) -> anyhow::Result<Vec<u8>> {
+        Err(anyhow!(
+            "[PythonGraphApiService::find_python_matplot_net_worth_history] Not provided by the python graph API"
+        ))
+    }
 }
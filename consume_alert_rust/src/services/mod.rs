@@ -1,8 +1,14 @@
 pub mod cache_service_impl;
+pub mod card_message_parser_impl;
+pub mod consume_classifier_impl;
+pub mod consumer_service_impl;
+pub mod crypto_quote_provider_impl;
 pub mod elastic_query_service_impl;
 pub mod graph_api_service_impl;
 pub mod mysql_query_service_impl;
 pub mod process_service_impl;
 pub mod producer_service_impl;
+pub mod quote_price_provider_impl;
 pub mod redis_service_impl;
+pub mod smtp_service_impl;
 pub mod telebot_service_impl;
@@ -1,3 +1,4 @@
+use crate::enums::budget_scope::*;
 use crate::repository::mysql_repository::*;
 
 use super::MysqlQueryServiceImpl;
@@ -8,4 +9,19 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
             .delete_spent_detail_with_transaction(spent_idx)
             .await
     }
+
+    pub async fn delete_consume_budget_with_transaction(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+        consume_keyword_type_id: i64,
+    ) -> anyhow::Result<u64> {
+        self.db_conn
+            .delete_consume_budget_with_transaction(
+                budget_scope.as_str(),
+                scope_seq,
+                consume_keyword_type_id,
+            )
+            .await
+    }
 }
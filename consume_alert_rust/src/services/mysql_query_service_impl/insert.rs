@@ -1,7 +1,13 @@
 use crate::common::*;
 
-use crate::entity::{earned_detail, spent_detail};
-use crate::models::{earned_detail::*, spent_detail::*};
+use crate::entity::{
+    consume_budget, earned_detail, spent_detail, spent_group_info, user_current_asset_snapshot,
+};
+use crate::enums::indexing_type::*;
+use crate::models::{
+    consume_budget::*, earned_detail::*, kis_api_token::*, spent_detail::*, spent_group_info::*,
+    user_current_asset_snapshot::*,
+};
 use crate::repository::mysql_repository::*;
 
 use super::MysqlQueryServiceImpl;
@@ -41,11 +47,19 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
             .await
     }
 
-    #[allow(dead_code)]
-    pub async fn input_prodt_details_with_transaction(
+    pub async fn input_spent_group_details_with_transaction(
         &self,
+        spent_group_info: &SpentGroupInfo,
         spent_details: &[SpentDetail],
     ) -> anyhow::Result<Vec<i64>> {
+        let group_active_model: spent_group_info::ActiveModel =
+            spent_group_info.to_active_model().inspect_err(|e| {
+                error!(
+                    "[input_spent_group_details_with_transaction] Failed to convert to ActiveModel: {:#}",
+                    e
+                )
+            })?;
+
         let mut active_models: Vec<spent_detail::ActiveModel> =
             Vec::with_capacity(spent_details.len());
 
@@ -53,7 +67,7 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
             let active_model: spent_detail::ActiveModel =
                 detail.to_active_model().map_err(|e| {
                     anyhow!(
-                        "[MysqlQueryServiceImpl::input_prodt_details_with_transaction] \
+                        "[MysqlQueryServiceImpl::input_spent_group_details_with_transaction] \
                      Failed to convert SpentDetail at position {} to ActiveModel: {:?}",
                         position,
                         e
@@ -64,7 +78,69 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
         }
 
         self.db_conn
-            .input_spent_details_with_transaction(active_models)
+            .input_spent_group_details_with_transaction(group_active_model, active_models)
+            .await
+    }
+
+    pub async fn upsert_consume_budget_with_transaction(
+        &self,
+        consume_budget: &ConsumeBudget,
+    ) -> anyhow::Result<()> {
+        let active_model: consume_budget::ActiveModel =
+            consume_budget.to_active_model().inspect_err(|e| {
+                error!(
+                    "[upsert_consume_budget_with_transaction] Failed to convert to ActiveModel: {:#}",
+                    e
+                )
+            })?;
+
+        self.db_conn
+            .upsert_consume_budget_with_transaction(active_model)
+            .await
+    }
+
+    pub async fn input_spent_detail_outboxes_with_transaction(
+        &self,
+        spent_detail_events: &[(i64, IndexingType)],
+    ) -> anyhow::Result<()> {
+        self.db_conn
+            .input_spent_detail_outboxes_with_transaction(spent_detail_events.to_vec())
+            .await
+    }
+
+    pub async fn replace_user_current_asset_snapshots_with_transaction(
+        &self,
+        user_seq: i64,
+        aggregated_at: DateTime<Utc>,
+        snapshots: &[UserCurrentAssetSnapshot],
+    ) -> anyhow::Result<()> {
+        let active_models: Vec<user_current_asset_snapshot::ActiveModel> = snapshots
+            .iter()
+            .map(UserCurrentAssetSnapshot::to_active_model)
+            .collect::<anyhow::Result<_>>()
+            .inspect_err(|e| {
+                error!(
+                    "[MysqlQueryServiceImpl::replace_user_current_asset_snapshots_with_transaction] \
+                     Failed to convert to ActiveModel: {:#}",
+                    e
+                )
+            })?;
+
+        self.db_conn
+            .replace_user_current_asset_snapshots_with_transaction(
+                user_seq,
+                aggregated_at.naive_utc(),
+                active_models,
+            )
+            .await
+    }
+
+    pub async fn replace_kis_api_token_with_transaction(
+        &self,
+        kis_api_token: &KisApiToken,
+    ) -> anyhow::Result<()> {
+        self.db_conn
+            .replace_kis_api_token_with_transaction(kis_api_token.to_active_model())
             .await
     }
 }
@@ -5,10 +5,17 @@ mod update;
 
 use crate::common::*;
 
+use crate::enums::{
+    budget_scope::*, indexing_type::*, range_operator::*, reconcile_scope::*, spent_detail_edit::*,
+};
+
 use crate::models::{
-    cash_asset::*, crypto_resp::*, currency_exchange_rate_snapshot::*, deposit_asset::*,
-    earned_detail::*, saving_asset::*, spent_detail::*, spent_detail_with_info::*, stock_resp::*,
-    user_payment_methods::*,
+    agg_group::*, agg_result_set::*, consume_budget::*, consume_prodt_keyword::*, crypto::*,
+    currency_asset_holdings::*, currency_exchange_rate_snapshot::*, dim_calendar::*,
+    earned_detail::*, installment_balance::*, kis_api_token::*, spent_detail::*,
+    spent_detail_by_es::*, spent_detail_indexing::*, spent_detail_outbox::*,
+    spent_detail_summary::*, spent_detail_with_info::*, spent_group_info::*, stock_type::*,
+    user_current_asset_snapshot::*, user_payment_methods::*,
 };
 use crate::repository::mysql_repository::*;
 
@@ -36,11 +43,12 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryService for MysqlQueryServiceIm
         self.input_prodt_detail_with_transaction(spent_detail).await
     }
 
-    async fn input_prodt_details_with_transaction(
+    async fn input_spent_group_details_with_transaction(
         &self,
+        spent_group_info: &SpentGroupInfo,
         spent_details: &[SpentDetail],
     ) -> anyhow::Result<Vec<i64>> {
-        self.input_prodt_details_with_transaction(spent_details)
+        self.input_spent_group_details_with_transaction(spent_group_info, spent_details)
             .await
     }
 
@@ -75,6 +83,39 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryService for MysqlQueryServiceIm
         self.find_user_id_by_seq(user_seq).await
     }
 
+    async fn find_user_payday(&self, user_seq: i64) -> anyhow::Result<Option<u32>> {
+        self.find_user_payday(user_seq).await
+    }
+
+    async fn modify_user_payday_with_transaction(
+        &self,
+        user_seq: i64,
+        payday: u32,
+    ) -> anyhow::Result<()> {
+        self.modify_user_payday_with_transaction(user_seq, payday)
+            .await
+    }
+
+    async fn find_user_seqs(&self) -> anyhow::Result<Vec<i64>> {
+        self.find_user_seqs().await
+    }
+
+    async fn find_active_agg_groups(&self) -> anyhow::Result<Vec<AggGroup>> {
+        self.find_active_agg_groups().await
+    }
+
+    async fn find_report_mail_receivers(&self, agg_group_seq: i64) -> anyhow::Result<Vec<String>> {
+        self.find_report_mail_receivers(agg_group_seq).await
+    }
+
+    async fn find_dim_calendars(
+        &self,
+        date_from: NaiveDate,
+        date_to: NaiveDate,
+    ) -> anyhow::Result<Vec<DimCalendar>> {
+        self.find_dim_calendars(date_from, date_to).await
+    }
+
     async fn find_latest_spent_idx(
         &self,
         user_seq: i64,
@@ -98,10 +139,201 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryService for MysqlQueryServiceIm
         self.find_spent_detail_with_info(spent_idx).await
     }
 
+    async fn find_spent_detail_indexing(
+        &self,
+        spent_idx: i64,
+    ) -> anyhow::Result<Option<SpentDetailIndexing>> {
+        self.find_spent_detail_indexing(spent_idx).await
+    }
+
     async fn delete_spent_detail_with_transaction(&self, spent_idx: i64) -> anyhow::Result<()> {
         self.delete_spent_detail_with_transaction(spent_idx).await
     }
 
+    async fn find_spent_detail_summaries(
+        &self,
+        reconcile_scope: ReconcileScope,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentDetailSummary>> {
+        self.find_spent_detail_summaries(reconcile_scope, spent_at_from, spent_at_to)
+            .await
+    }
+
+    async fn input_spent_detail_outboxes_with_transaction(
+        &self,
+        spent_detail_events: &[(i64, IndexingType)],
+    ) -> anyhow::Result<()> {
+        self.input_spent_detail_outboxes_with_transaction(spent_detail_events)
+            .await
+    }
+
+    async fn find_pending_spent_detail_outboxes(
+        &self,
+        limit: u64,
+    ) -> anyhow::Result<Vec<SpentDetailOutbox>> {
+        self.find_pending_spent_detail_outboxes(limit).await
+    }
+
+    async fn modify_spent_detail_outbox_sent_with_transaction(
+        &self,
+        outbox_seq: i64,
+    ) -> anyhow::Result<()> {
+        self.modify_spent_detail_outbox_sent_with_transaction(outbox_seq)
+            .await
+    }
+
+    async fn modify_spent_detail_outbox_failed_with_transaction(
+        &self,
+        outbox_seq: i64,
+        attempt_cnt: i32,
+        last_error: &str,
+    ) -> anyhow::Result<()> {
+        self.modify_spent_detail_outbox_failed_with_transaction(outbox_seq, attempt_cnt, last_error)
+            .await
+    }
+
+    async fn find_consume_keyword_type_id(
+        &self,
+        consume_keyword_type: &str,
+    ) -> anyhow::Result<Option<i64>> {
+        self.find_consume_keyword_type_id(consume_keyword_type)
+            .await
+    }
+
+    async fn find_consume_prodt_keywords(&self) -> anyhow::Result<Vec<ConsumeProdtKeyword>> {
+        self.find_consume_prodt_keywords().await
+    }
+
+    async fn modify_spent_detail_with_transaction(
+        &self,
+        spent_idx: i64,
+        spent_detail_edit: &SpentDetailEdit,
+    ) -> anyhow::Result<()> {
+        self.modify_spent_detail_with_transaction(spent_idx, spent_detail_edit)
+            .await
+    }
+
+    async fn find_consume_budgets(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+    ) -> anyhow::Result<Vec<ConsumeBudgetWithInfo>> {
+        self.find_consume_budgets(budget_scope, scope_seq).await
+    }
+
+    async fn upsert_consume_budget_with_transaction(
+        &self,
+        consume_budget: &ConsumeBudget,
+    ) -> anyhow::Result<()> {
+        self.upsert_consume_budget_with_transaction(consume_budget)
+            .await
+    }
+
+    async fn delete_consume_budget_with_transaction(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+        consume_keyword_type_id: i64,
+    ) -> anyhow::Result<u64> {
+        self.delete_consume_budget_with_transaction(
+            budget_scope,
+            scope_seq,
+            consume_keyword_type_id,
+        )
+        .await
+    }
+
+    async fn find_spent_money_by_type(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentMoneyByType>> {
+        self.find_spent_money_by_type(budget_scope, scope_seq, spent_at_from, spent_at_to)
+            .await
+    }
+
+    async fn find_spent_details_by_range(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        start_op: RangeOperator,
+        end_op: RangeOperator,
+    ) -> anyhow::Result<AggResultSet<SpentDetailByEs>> {
+        self.find_spent_details_by_range(
+            budget_scope,
+            scope_seq,
+            start_date,
+            end_date,
+            start_op,
+            end_op,
+        )
+        .await
+    }
+
+    async fn find_cancellable_spent_details(
+        &self,
+        user_seq: i64,
+        payment_method_id: i64,
+        spent_money: i64,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentDetailWithInfo>> {
+        self.find_cancellable_spent_details(
+            user_seq,
+            payment_method_id,
+            spent_money,
+            spent_at_from,
+            spent_at_to,
+        )
+        .await
+    }
+
+    async fn modify_spent_detail_cancelled_with_transaction(
+        &self,
+        spent_idx: i64,
+    ) -> anyhow::Result<()> {
+        self.modify_spent_detail_cancelled_with_transaction(spent_idx)
+            .await
+    }
+
+    async fn find_cancellable_installment_details(
+        &self,
+        user_seq: i64,
+        payment_method_id: i64,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentDetailWithInfo>> {
+        self.find_cancellable_installment_details(
+            user_seq,
+            payment_method_id,
+            spent_at_from,
+            spent_at_to,
+        )
+        .await
+    }
+
+    async fn modify_spent_group_cancelled_with_transaction(
+        &self,
+        spent_group_id: i64,
+    ) -> anyhow::Result<u64> {
+        self.modify_spent_group_cancelled_with_transaction(spent_group_id)
+            .await
+    }
+
+    async fn find_remaining_installments(
+        &self,
+        user_seq: i64,
+        spent_at_from: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<InstallmentRemain>> {
+        self.find_remaining_installments(user_seq, spent_at_from)
+            .await
+    }
+
     async fn find_user_payment_methods(
         &self,
         user_seq: i64,
@@ -119,43 +351,77 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryService for MysqlQueryServiceIm
             .await
     }
 
-    async fn find_deposit_asset(
+    async fn find_currency_asset_holdings(
         &self,
         user_seq: i64,
         currency_code: &str,
-    ) -> anyhow::Result<Vec<DepositAsset>> {
-        self.find_deposit_asset(user_seq, currency_code).await
+    ) -> anyhow::Result<CurrencyAssetHoldings> {
+        self.find_currency_asset_holdings(user_seq, currency_code)
+            .await
     }
 
-    async fn find_saving_asset(
+    async fn replace_user_current_asset_snapshots_with_transaction(
         &self,
         user_seq: i64,
-        currency_code: &str,
-    ) -> anyhow::Result<Vec<SavingAsset>> {
-        self.find_saving_asset(user_seq, currency_code).await
+        aggregated_at: DateTime<Utc>,
+        snapshots: &[UserCurrentAssetSnapshot],
+    ) -> anyhow::Result<()> {
+        self.replace_user_current_asset_snapshots_with_transaction(
+            user_seq,
+            aggregated_at,
+            snapshots,
+        )
+        .await
     }
 
-    async fn find_stock_response(
+    async fn find_user_current_asset_snapshots(
         &self,
         user_seq: i64,
-        currency_code: &str,
-    ) -> anyhow::Result<Vec<StockResp>> {
-        self.find_stock_response(user_seq, currency_code).await
+        aggregated_from: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<UserCurrentAssetSnapshot>> {
+        self.find_user_current_asset_snapshots(user_seq, aggregated_from)
+            .await
+    }
+
+    async fn find_stock_types(&self) -> anyhow::Result<Vec<StockType>> {
+        self.find_stock_types().await
+    }
+
+    async fn find_stock_api_symbols(&self, market_seq: i64) -> anyhow::Result<Vec<String>> {
+        self.find_stock_api_symbols(market_seq).await
     }
 
-    async fn find_crypto_response(
+    async fn modify_stock_prices_with_transaction(
         &self,
-        user_seq: i64,
-        currency_code: &str,
-    ) -> anyhow::Result<Vec<CryptoResp>> {
-        self.find_crypto_response(user_seq, currency_code).await
+        market_seq: i64,
+        stock_prices: &[(String, Decimal)],
+    ) -> anyhow::Result<u64> {
+        self.modify_stock_prices_with_transaction(market_seq, stock_prices)
+            .await
     }
 
-    async fn find_cash_asset(
+    async fn find_kis_api_token(&self) -> anyhow::Result<Option<KisApiToken>> {
+        self.find_kis_api_token().await
+    }
+
+    async fn replace_kis_api_token_with_transaction(
+        &self,
+        kis_api_token: &KisApiToken,
+    ) -> anyhow::Result<()> {
+        self.replace_kis_api_token_with_transaction(kis_api_token)
+            .await
+    }
+
+    async fn find_cryptos(&self) -> anyhow::Result<Vec<Crypto>> {
+        self.find_cryptos().await
+    }
+
+    async fn modify_crypto_prices_with_transaction(
         &self,
-        user_seq: i64,
         currency_code: &str,
-    ) -> anyhow::Result<Vec<CashAsset>> {
-        self.find_cash_asset(user_seq, currency_code).await
+        crypto_prices: &[(String, Decimal)],
+    ) -> anyhow::Result<u64> {
+        self.modify_crypto_prices_with_transaction(currency_code, crypto_prices)
+            .await
     }
 }
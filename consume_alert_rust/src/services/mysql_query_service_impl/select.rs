@@ -1,19 +1,42 @@
 use crate::common::*;
 
 use crate::entity::{
-    cash_asset, common_consume_keyword_type, crypto, crypto_asset, currency_exchange_rate_snapshot,
-    deposit_asset, saving_asset, spent_detail, stock, stock_asset, stock_type, telegram_room,
-    user_payment_methods, users,
+    agg_group, cash_asset, common_consume_keyword_type, common_consume_prodt_keyword,
+    consume_budget, crypto, crypto_asset, currency_exchange_rate_snapshot, deposit_asset,
+    dim_calendar, kis_api_token, saving_asset, send_email_agg_group, spent_detail,
+    spent_detail_outbox, spent_group_info, stock, stock_asset, stock_type, telegram_room,
+    user_current_asset_snapshot, user_payment_methods, users, users_email,
 };
 
+use crate::enums::{budget_scope::*, range_operator::*, reconcile_scope::*};
+
 use crate::models::{
-    cash_asset::*, crypto_resp::*, currency_exchange_rate_snapshot::*, deposit_asset::*,
-    saving_asset::*, spent_detail_with_info::*, stock_resp::*, user_payment_methods::*,
+    agg_group::*, agg_result_set::*, cash_asset::*, consume_budget::*, consume_prodt_keyword::*,
+    crypto::*, crypto_resp::*, currency_asset_holdings::*, currency_exchange_rate_snapshot::*,
+    deposit_asset::*, dim_calendar::*, document_with_id::*, installment_balance::*,
+    kis_api_token::*, saving_asset::*, spent_detail_by_es::*, spent_detail_indexing::*,
+    spent_detail_outbox::*, spent_detail_summary::*, spent_detail_with_info::*,
+    spent_group_info::*, stock_resp::*, stock_type::*, user_current_asset_snapshot::*,
+    user_payment_methods::*,
 };
 use crate::repository::mysql_repository::*;
 
 use super::MysqlQueryServiceImpl;
 
+/// Builds the `SPENT_DETAIL.spent_at` bound that matches an Elasticsearch range operator.
+fn to_spent_at_condition(
+    range_operator: RangeOperator,
+    spent_at: DateTime<Utc>,
+) -> sea_orm::sea_query::SimpleExpr {
+    let spent_at: NaiveDateTime = spent_at.naive_utc();
+
+    match range_operator {
+        RangeOperator::GreaterThanOrEqual => spent_detail::Column::SpentAt.gte(spent_at),
+        RangeOperator::LessThan => spent_detail::Column::SpentAt.lt(spent_at),
+        RangeOperator::LessThanOrEqual => spent_detail::Column::SpentAt.lte(spent_at),
+    }
+}
+
 impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
     pub async fn has_telegram_room_by_token_and_id(
         &self,
@@ -92,6 +115,105 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
         Ok(result.map(|user| user.user_id))
     }
 
+    pub async fn find_user_payday(&self, user_seq: i64) -> anyhow::Result<Option<u32>> {
+        let result: Option<users::Model> = users::Entity::find()
+            .filter(users::Column::UserSeq.eq(user_seq))
+            .one(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_user_payday] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(result.and_then(|user| u32::try_from(user.payday).ok()))
+    }
+
+    pub async fn find_user_seqs(&self) -> anyhow::Result<Vec<i64>> {
+        let user_seqs: Vec<i64> = users::Entity::find()
+            .select_only()
+            .column(users::Column::UserSeq)
+            .order_by_asc(users::Column::UserSeq)
+            .into_tuple()
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_user_seqs] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(user_seqs)
+    }
+
+    pub async fn find_active_agg_groups(&self) -> anyhow::Result<Vec<AggGroup>> {
+        let results: Vec<agg_group::Model> = agg_group::Entity::find()
+            .filter(agg_group::Column::IsActive.eq(true))
+            .order_by_asc(agg_group::Column::AggGroupSeq)
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_active_agg_groups] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results.into_iter().map(Into::into).collect())
+    }
+
+    /// Active `SEND_EMAIL_AGG_GROUP` addresses of a group whose `USERS_EMAIL` row accepts mail.
+    pub async fn find_report_mail_receivers(
+        &self,
+        agg_group_seq: i64,
+    ) -> anyhow::Result<Vec<String>> {
+        let results: Vec<send_email_agg_group::Model> = send_email_agg_group::Entity::find()
+            .join(
+                JoinType::InnerJoin,
+                send_email_agg_group::Relation::UsersEmail.def(),
+            )
+            .filter(send_email_agg_group::Column::AggGroupSeq.eq(agg_group_seq))
+            .filter(send_email_agg_group::Column::IsActive.eq(true))
+            .filter(users_email::Column::IsRecv.eq(true))
+            .order_by_asc(send_email_agg_group::Column::EmailId)
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_report_mail_receivers] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results
+            .into_iter()
+            .map(|receiver| receiver.email_id)
+            .collect())
+    }
+
+    pub async fn find_dim_calendars(
+        &self,
+        date_from: NaiveDate,
+        date_to: NaiveDate,
+    ) -> anyhow::Result<Vec<DimCalendar>> {
+        let results: Vec<dim_calendar::Model> = dim_calendar::Entity::find()
+            .filter(dim_calendar::Column::Dt.gte(date_from))
+            .filter(dim_calendar::Column::Dt.lte(date_to))
+            .order_by_asc(dim_calendar::Column::Dt)
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_dim_calendars] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results.into_iter().map(Into::into).collect())
+    }
+
     #[allow(dead_code)]
     pub async fn find_latest_spent_idx(
         &self,
@@ -127,6 +249,7 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
             .column(spent_detail::Column::SpentAt)
             .column(spent_detail::Column::CreatedAt)
             .column(spent_detail::Column::UserSeq)
+            .column(spent_detail::Column::SpentGroupId)
             .column(spent_detail::Column::ConsumeKeywordTypeId)
             .column(common_consume_keyword_type::Column::ConsumeKeywordType)
             .column(spent_detail::Column::RoomSeq)
@@ -152,7 +275,6 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
         Ok(result)
     }
 
-    #[allow(dead_code)]
     pub async fn find_spent_detail_with_info(
         &self,
         spent_idx: i64,
@@ -165,6 +287,7 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
             .column(spent_detail::Column::SpentAt)
             .column(spent_detail::Column::CreatedAt)
             .column(spent_detail::Column::UserSeq)
+            .column(spent_detail::Column::SpentGroupId)
             .column(spent_detail::Column::ConsumeKeywordTypeId)
             .column(common_consume_keyword_type::Column::ConsumeKeywordType)
             .column(spent_detail::Column::RoomSeq)
@@ -175,6 +298,7 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
             )
             .join(JoinType::InnerJoin, spent_detail::Relation::Users.def())
             .filter(spent_detail::Column::SpentIdx.eq(spent_idx))
+            .filter(spent_detail::Column::ShouldIndex.eq(1))
             .into_model::<SpentDetailWithInfo>()
             .one(self.db_conn.get_connection())
             .await
@@ -188,6 +312,463 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
         Ok(result)
     }
 
+    pub async fn find_spent_detail_indexing(
+        &self,
+        spent_idx: i64,
+    ) -> anyhow::Result<Option<SpentDetailIndexing>> {
+        use sea_orm::sea_query::{Expr, Func, SimpleExpr};
+
+        let result: Option<SpentDetailIndexing> = spent_detail::Entity::find()
+            .select_only()
+            .column(spent_detail::Column::SpentIdx)
+            .column(spent_detail::Column::SpentName)
+            .column(spent_detail::Column::SpentMoney)
+            .column(spent_detail::Column::SpentAt)
+            .column(spent_detail::Column::CreatedAt)
+            .column(spent_detail::Column::UserSeq)
+            .column(spent_detail::Column::ConsumeKeywordTypeId)
+            .column(common_consume_keyword_type::Column::ConsumeKeywordType)
+            .column(spent_detail::Column::RoomSeq)
+            .column(users::Column::UserId)
+            .column(user_payment_methods::Column::CardAlias)
+            .column(spent_detail::Column::UpdatedAt)
+            .column(spent_detail::Column::UpdatedBy)
+            .column_as(
+                SimpleExpr::from(Func::if_null(
+                    Expr::col((telegram_room::Entity, telegram_room::Column::AggGroupSeq)),
+                    0,
+                )),
+                "agg_group_seq",
+            )
+            .join(
+                JoinType::InnerJoin,
+                spent_detail::Relation::CommonConsumeKeywordType.def(),
+            )
+            .join(JoinType::InnerJoin, spent_detail::Relation::Users.def())
+            .join(
+                JoinType::InnerJoin,
+                spent_detail::Relation::UserPaymentMethods.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                spent_detail::Relation::TelegramRoom.def(),
+            )
+            .filter(spent_detail::Column::SpentIdx.eq(spent_idx))
+            .filter(spent_detail::Column::ShouldIndex.eq(1))
+            .into_model::<SpentDetailIndexing>()
+            .one(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_spent_detail_indexing] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(result)
+    }
+
+    pub async fn find_pending_spent_detail_outboxes(
+        &self,
+        limit: u64,
+    ) -> anyhow::Result<Vec<SpentDetailOutbox>> {
+        let results: Vec<spent_detail_outbox::Model> = spent_detail_outbox::Entity::find()
+            .filter(spent_detail_outbox::Column::IsSent.eq(0))
+            .order_by_asc(spent_detail_outbox::Column::OutboxSeq)
+            .limit(limit)
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_pending_spent_detail_outboxes] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn find_consume_keyword_type_id(
+        &self,
+        consume_keyword_type: &str,
+    ) -> anyhow::Result<Option<i64>> {
+        let result: Option<common_consume_keyword_type::Model> =
+            common_consume_keyword_type::Entity::find()
+                .filter(common_consume_keyword_type::Column::ConsumeKeywordType.eq(consume_keyword_type))
+                .one(self.db_conn.get_connection())
+                .await
+                .map_err(|e| {
+                    anyhow!(
+                        "[MysqlQueryServiceImpl::find_consume_keyword_type_id] Failed to query: {:?}",
+                        e
+                    )
+                })?;
+
+        Ok(result.map(|keyword_type| keyword_type.consume_keyword_type_id))
+    }
+
+    pub async fn find_consume_prodt_keywords(&self) -> anyhow::Result<Vec<ConsumeProdtKeyword>> {
+        let results: Vec<ConsumeProdtKeyword> = common_consume_prodt_keyword::Entity::find()
+            .select_only()
+            .column(common_consume_prodt_keyword::Column::ConsumeKeywordTypeId)
+            .column(common_consume_keyword_type::Column::ConsumeKeywordType)
+            .column(common_consume_prodt_keyword::Column::ConsumeKeyword)
+            .column(common_consume_prodt_keyword::Column::KeywordWeight)
+            .join(
+                JoinType::InnerJoin,
+                common_consume_prodt_keyword::Relation::CommonConsumeKeywordType.def(),
+            )
+            .into_model::<ConsumeProdtKeyword>()
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_consume_prodt_keywords] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results)
+    }
+
+    pub async fn find_consume_budgets(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+    ) -> anyhow::Result<Vec<ConsumeBudgetWithInfo>> {
+        let results: Vec<ConsumeBudgetWithInfo> = consume_budget::Entity::find()
+            .select_only()
+            .column(consume_budget::Column::BudgetSeq)
+            .column(consume_budget::Column::BudgetScope)
+            .column(consume_budget::Column::ScopeSeq)
+            .column(consume_budget::Column::ConsumeKeywordTypeId)
+            .column(common_consume_keyword_type::Column::ConsumeKeywordType)
+            .column(consume_budget::Column::BudgetMoney)
+            .join(
+                JoinType::LeftJoin,
+                consume_budget::Relation::CommonConsumeKeywordType.def(),
+            )
+            .filter(consume_budget::Column::BudgetScope.eq(budget_scope.as_str()))
+            .filter(consume_budget::Column::ScopeSeq.eq(scope_seq))
+            .order_by_asc(consume_budget::Column::ConsumeKeywordTypeId)
+            .into_model::<ConsumeBudgetWithInfo>()
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_consume_budgets] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results)
+    }
+
+    pub async fn find_spent_money_by_type(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentMoneyByType>> {
+        let query = spent_detail::Entity::find()
+            .select_only()
+            .column(spent_detail::Column::ConsumeKeywordTypeId)
+            .column_as(spent_detail::Column::SpentMoney.sum(), "spent_money")
+            .filter(spent_detail::Column::ShouldIndex.eq(1))
+            .filter(spent_detail::Column::SpentAt.gte(spent_at_from.naive_utc()))
+            .filter(spent_detail::Column::SpentAt.lt(spent_at_to.naive_utc()));
+
+        let query = match budget_scope {
+            BudgetScope::Room => query.filter(spent_detail::Column::RoomSeq.eq(scope_seq)),
+            BudgetScope::Group => query
+                .join(
+                    JoinType::InnerJoin,
+                    spent_detail::Relation::TelegramRoom.def(),
+                )
+                .filter(telegram_room::Column::AggGroupSeq.eq(scope_seq)),
+        };
+
+        let results: Vec<SpentMoneyByType> = query
+            .group_by(spent_detail::Column::ConsumeKeywordTypeId)
+            .into_model::<SpentMoneyByType>()
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_spent_money_by_type] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results)
+    }
+
+    pub async fn find_spent_details_by_range(
+        &self,
+        budget_scope: BudgetScope,
+        scope_seq: i64,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        start_op: RangeOperator,
+        end_op: RangeOperator,
+    ) -> anyhow::Result<AggResultSet<SpentDetailByEs>> {
+        use sea_orm::sea_query::Expr;
+
+        let query = spent_detail::Entity::find()
+            .select_only()
+            .column(spent_detail::Column::SpentIdx)
+            .column(spent_detail::Column::SpentName)
+            .column(spent_detail::Column::SpentMoney)
+            .column(spent_detail::Column::SpentAt)
+            .column(spent_detail::Column::CreatedAt)
+            .column(spent_detail::Column::UserSeq)
+            .column(spent_detail::Column::ConsumeKeywordTypeId)
+            .column(common_consume_keyword_type::Column::ConsumeKeywordType)
+            .column(spent_detail::Column::RoomSeq)
+            .column_as(Expr::cust("NULL"), "produced_at")
+            .join(
+                JoinType::InnerJoin,
+                spent_detail::Relation::CommonConsumeKeywordType.def(),
+            )
+            .filter(spent_detail::Column::ShouldIndex.eq(1))
+            .filter(to_spent_at_condition(start_op, start_date))
+            .filter(to_spent_at_condition(end_op, end_date));
+
+        let query = match budget_scope {
+            BudgetScope::Room => query.filter(spent_detail::Column::RoomSeq.eq(scope_seq)),
+            BudgetScope::Group => query
+                .join(
+                    JoinType::InnerJoin,
+                    spent_detail::Relation::TelegramRoom.def(),
+                )
+                .filter(telegram_room::Column::AggGroupSeq.eq(scope_seq)),
+        };
+
+        let spent_details: Vec<SpentDetailByEs> = query
+            .order_by_asc(spent_detail::Column::SpentAt)
+            .into_model::<SpentDetailByEs>()
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_spent_details_by_range] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        let agg_result: f64 = spent_details
+            .iter()
+            .map(|spent_detail| spent_detail.spent_money as f64)
+            .sum();
+
+        let source_list: Vec<DocumentWithId<SpentDetailByEs>> = spent_details
+            .into_iter()
+            .map(|spent_detail| {
+                DocumentWithId::new(spent_detail.spent_idx.to_string(), 0.0, spent_detail)
+            })
+            .collect();
+
+        Ok(AggResultSet::new(agg_result, source_list))
+    }
+
+    pub async fn find_spent_detail_summaries(
+        &self,
+        reconcile_scope: ReconcileScope,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentDetailSummary>> {
+        let query = spent_detail::Entity::find()
+            .select_only()
+            .column(spent_detail::Column::SpentIdx)
+            .column(spent_detail::Column::SpentMoney)
+            .filter(spent_detail::Column::ShouldIndex.eq(1))
+            .filter(spent_detail::Column::SpentAt.gte(spent_at_from.naive_utc()))
+            .filter(spent_detail::Column::SpentAt.lt(spent_at_to.naive_utc()));
+
+        let query = match reconcile_scope {
+            ReconcileScope::User(user_seq) => {
+                query.filter(spent_detail::Column::UserSeq.eq(user_seq))
+            }
+            ReconcileScope::Room(room_seq) => {
+                query.filter(spent_detail::Column::RoomSeq.eq(room_seq))
+            }
+            ReconcileScope::Group(agg_group_seq) => query
+                .join(
+                    JoinType::InnerJoin,
+                    spent_detail::Relation::TelegramRoom.def(),
+                )
+                .filter(telegram_room::Column::AggGroupSeq.eq(agg_group_seq)),
+        };
+
+        let results: Vec<SpentDetailSummary> = query
+            .order_by_asc(spent_detail::Column::SpentIdx)
+            .into_model::<SpentDetailSummary>()
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_spent_detail_summaries] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results)
+    }
+
+    pub async fn find_cancellable_spent_details(
+        &self,
+        user_seq: i64,
+        payment_method_id: i64,
+        spent_money: i64,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentDetailWithInfo>> {
+        let results: Vec<SpentDetailWithInfo> = spent_detail::Entity::find()
+            .select_only()
+            .column(spent_detail::Column::SpentIdx)
+            .column(spent_detail::Column::SpentName)
+            .column(spent_detail::Column::SpentMoney)
+            .column(spent_detail::Column::SpentAt)
+            .column(spent_detail::Column::CreatedAt)
+            .column(spent_detail::Column::UserSeq)
+            .column(spent_detail::Column::SpentGroupId)
+            .column(spent_detail::Column::ConsumeKeywordTypeId)
+            .column(common_consume_keyword_type::Column::ConsumeKeywordType)
+            .column(spent_detail::Column::RoomSeq)
+            .column(users::Column::UserId)
+            .join(
+                JoinType::InnerJoin,
+                spent_detail::Relation::CommonConsumeKeywordType.def(),
+            )
+            .join(JoinType::InnerJoin, spent_detail::Relation::Users.def())
+            .filter(spent_detail::Column::UserSeq.eq(user_seq))
+            .filter(spent_detail::Column::PaymentMethodId.eq(payment_method_id))
+            .filter(spent_detail::Column::SpentMoney.eq(spent_money))
+            .filter(spent_detail::Column::ShouldIndex.eq(1))
+            .filter(spent_detail::Column::SpentAt.gte(spent_at_from.naive_utc()))
+            .filter(spent_detail::Column::SpentAt.lte(spent_at_to.naive_utc()))
+            .order_by_desc(spent_detail::Column::SpentAt)
+            .into_model::<SpentDetailWithInfo>()
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_cancellable_spent_details] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results)
+    }
+
+    pub async fn find_cancellable_installment_details(
+        &self,
+        user_seq: i64,
+        payment_method_id: i64,
+        spent_at_from: DateTime<Utc>,
+        spent_at_to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<SpentDetailWithInfo>> {
+        /* Installment plans of the card with a monthly row inside the window. */
+        let spent_group_ids: Vec<i64> = spent_detail::Entity::find()
+            .select_only()
+            .column(spent_detail::Column::SpentGroupId)
+            .distinct()
+            .join(
+                JoinType::InnerJoin,
+                spent_detail::Relation::SpentGroupInfo.def(),
+            )
+            .filter(spent_detail::Column::UserSeq.eq(user_seq))
+            .filter(spent_detail::Column::PaymentMethodId.eq(payment_method_id))
+            .filter(spent_detail::Column::ShouldIndex.eq(1))
+            .filter(spent_detail::Column::SpentAt.gte(spent_at_from.naive_utc()))
+            .filter(spent_detail::Column::SpentAt.lte(spent_at_to.naive_utc()))
+            .filter(spent_group_info::Column::Status.eq(INSTALLMENT_GROUP_STATUS))
+            .into_tuple()
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_cancellable_installment_details] Failed to query groups: {:?}",
+                    e
+                )
+            })?;
+
+        if spent_group_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let results: Vec<SpentDetailWithInfo> = spent_detail::Entity::find()
+            .select_only()
+            .column(spent_detail::Column::SpentIdx)
+            .column(spent_detail::Column::SpentName)
+            .column(spent_detail::Column::SpentMoney)
+            .column(spent_detail::Column::SpentAt)
+            .column(spent_detail::Column::CreatedAt)
+            .column(spent_detail::Column::UserSeq)
+            .column(spent_detail::Column::SpentGroupId)
+            .column(spent_detail::Column::ConsumeKeywordTypeId)
+            .column(common_consume_keyword_type::Column::ConsumeKeywordType)
+            .column(spent_detail::Column::RoomSeq)
+            .column(users::Column::UserId)
+            .join(
+                JoinType::InnerJoin,
+                spent_detail::Relation::CommonConsumeKeywordType.def(),
+            )
+            .join(JoinType::InnerJoin, spent_detail::Relation::Users.def())
+            .filter(spent_detail::Column::SpentGroupId.is_in(spent_group_ids))
+            .filter(spent_detail::Column::ShouldIndex.eq(1))
+            .order_by_asc(spent_detail::Column::SpentAt)
+            .into_model::<SpentDetailWithInfo>()
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_cancellable_installment_details] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results)
+    }
+
+    pub async fn find_remaining_installments(
+        &self,
+        user_seq: i64,
+        spent_at_from: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<InstallmentRemain>> {
+        let results: Vec<InstallmentRemain> = spent_detail::Entity::find()
+            .select_only()
+            .column(spent_detail::Column::SpentGroupId)
+            .column(spent_detail::Column::SpentName)
+            .column(spent_detail::Column::SpentMoney)
+            .column(user_payment_methods::Column::CardAlias)
+            .join(
+                JoinType::InnerJoin,
+                spent_detail::Relation::UserPaymentMethods.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                spent_detail::Relation::SpentGroupInfo.def(),
+            )
+            .filter(spent_detail::Column::UserSeq.eq(user_seq))
+            .filter(spent_detail::Column::ShouldIndex.eq(1))
+            .filter(spent_detail::Column::SpentAt.gt(spent_at_from.naive_utc()))
+            .filter(spent_group_info::Column::Status.eq(INSTALLMENT_GROUP_STATUS))
+            .order_by_asc(spent_detail::Column::SpentAt)
+            .into_model::<InstallmentRemain>()
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_remaining_installments] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results)
+    }
+
     pub async fn find_user_payment_methods(
         &self,
         user_seq: i64,
@@ -280,6 +861,20 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
         Ok(saving_assets)
     }
 
+    /*
+        SELECT
+            s.stock_seq,
+            s.stock_name,
+            s.stock_price,
+            sa.stock_cnt,
+            sa.avg_purchase_price
+        FROM STOCK_ASSET sa
+        INNER JOIN STOCK s ON sa.stock_seq = s.stock_seq
+        INNER JOIN STOCK_TYPE st ON s.market_seq = st.market_seq
+        WHERE st.currency_code = 'USD'
+        AND sa.user_seq = 1
+        AND sa.stock_cnt > 0;
+    */
     pub async fn find_stock_response(
         &self,
         user_seq: i64,
@@ -317,7 +912,7 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
         user_seq: i64,
         currency_code: &str,
     ) -> anyhow::Result<Vec<CryptoResp>> {
-        use sea_orm::sea_query::Expr;
+        use sea_orm::sea_query::{Expr, Func, SimpleExpr};
 
         let results: Vec<CryptoResp> = crypto_asset::Entity::find()
             .select_only()
@@ -332,6 +927,13 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
                 ))),
                 "crypto_total_price",
             )
+            .column_as(
+                SimpleExpr::from(Func::if_null(
+                    Expr::col((crypto::Entity, crypto::Column::PriceUpdatedAt)),
+                    Expr::col((crypto::Entity, crypto::Column::CreatedAt)),
+                )),
+                "price_updated_at",
+            )
             .join(JoinType::InnerJoin, crypto_asset::Relation::Crypto.def())
             .filter(crypto::Column::CurrencyCode.eq(currency_code))
             .filter(crypto_asset::Column::UserSeq.eq(user_seq))
@@ -368,4 +970,109 @@ impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
 
         Ok(results.into_iter().map(Into::into).collect())
     }
+
+    pub async fn find_currency_asset_holdings(
+        &self,
+        user_seq: i64,
+        currency_code: &str,
+    ) -> anyhow::Result<CurrencyAssetHoldings> {
+        Ok(CurrencyAssetHoldings::new(
+            currency_code.to_string(),
+            self.find_deposit_asset(user_seq, currency_code).await?,
+            self.find_saving_asset(user_seq, currency_code).await?,
+            self.find_stock_response(user_seq, currency_code).await?,
+            self.find_crypto_response(user_seq, currency_code).await?,
+            self.find_cash_asset(user_seq, currency_code).await?,
+        ))
+    }
+
+    pub async fn find_user_current_asset_snapshots(
+        &self,
+        user_seq: i64,
+        aggregated_from: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<UserCurrentAssetSnapshot>> {
+        let results: Vec<user_current_asset_snapshot::Model> =
+            user_current_asset_snapshot::Entity::find()
+                .filter(user_current_asset_snapshot::Column::UserSeq.eq(user_seq))
+                .filter(
+                    user_current_asset_snapshot::Column::AggregatedAt
+                        .gte(aggregated_from.naive_utc()),
+                )
+                .order_by_asc(user_current_asset_snapshot::Column::AggregatedAt)
+                .all(self.db_conn.get_connection())
+                .await
+                .map_err(|e| {
+                    anyhow!(
+                        "[MysqlQueryServiceImpl::find_user_current_asset_snapshots] Failed to query: {:?}",
+                        e
+                    )
+                })?;
+
+        Ok(results.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn find_stock_types(&self) -> anyhow::Result<Vec<StockType>> {
+        let results: Vec<stock_type::Model> = stock_type::Entity::find()
+            .order_by_asc(stock_type::Column::MarketSeq)
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_stock_types] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn find_stock_api_symbols(&self, market_seq: i64) -> anyhow::Result<Vec<String>> {
+        let api_symbols: Vec<String> = stock::Entity::find()
+            .select_only()
+            .column(stock::Column::ApiSymbol)
+            .distinct()
+            .filter(stock::Column::MarketSeq.eq(market_seq))
+            .order_by_asc(stock::Column::ApiSymbol)
+            .into_tuple()
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_stock_api_symbols] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(api_symbols)
+    }
+
+    pub async fn find_kis_api_token(&self) -> anyhow::Result<Option<KisApiToken>> {
+        let result: Option<kis_api_token::Model> = kis_api_token::Entity::find()
+            .order_by_desc(kis_api_token::Column::TokenExpiredAt)
+            .one(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_kis_api_token] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(result.map(Into::into))
+    }
+
+    pub async fn find_cryptos(&self) -> anyhow::Result<Vec<Crypto>> {
+        let results: Vec<crypto::Model> = crypto::Entity::find()
+            .order_by_asc(crypto::Column::CryptoSeq)
+            .all(self.db_conn.get_connection())
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[MysqlQueryServiceImpl::find_cryptos] Failed to query: {:?}",
+                    e
+                )
+            })?;
+
+        Ok(results.into_iter().map(Into::into).collect())
+    }
 }
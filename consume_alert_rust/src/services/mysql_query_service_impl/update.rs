@@ -1,5 +1,159 @@
+use crate::common::*;
+
+use crate::entity::{spent_detail, spent_detail_outbox, users};
+use crate::enums::{indexing_type::*, spent_detail_edit::*};
+use crate::models::spent_group_info::*;
 use crate::repository::mysql_repository::*;
 
 use super::MysqlQueryServiceImpl;
 
-impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {}
+impl<R: MysqlRepository + Send + Sync> MysqlQueryServiceImpl<R> {
+    pub async fn modify_spent_detail_cancelled_with_transaction(
+        &self,
+        spent_idx: i64,
+    ) -> anyhow::Result<()> {
+        let active_model: spent_detail::ActiveModel = spent_detail::ActiveModel {
+            spent_idx: Set(spent_idx),
+            should_index: Set(0),
+            updated_at: Set(Some(Utc::now().naive_utc())),
+            updated_by: Set(Some("system".to_string())),
+            ..Default::default()
+        };
+
+        /* A cancelled payment is no longer indexed, so it leaves the index like a deletion. */
+        self.db_conn
+            .update_spent_detail_with_transaction(active_model, IndexingType::Delete)
+            .await
+    }
+
+    pub async fn modify_spent_group_cancelled_with_transaction(
+        &self,
+        spent_group_id: i64,
+    ) -> anyhow::Result<u64> {
+        self.db_conn
+            .update_spent_group_cancelled_with_transaction(
+                spent_group_id,
+                CANCELLED_INSTALLMENT_GROUP_STATUS,
+            )
+            .await
+    }
+
+    pub async fn modify_spent_detail_with_transaction(
+        &self,
+        spent_idx: i64,
+        spent_detail_edit: &SpentDetailEdit,
+    ) -> anyhow::Result<()> {
+        let mut active_model: spent_detail::ActiveModel = spent_detail::ActiveModel {
+            spent_idx: Set(spent_idx),
+            updated_at: Set(Some(Utc::now().naive_utc())),
+            updated_by: Set(Some("system".to_string())),
+            ..Default::default()
+        };
+
+        match spent_detail_edit {
+            SpentDetailEdit::ConsumeKeywordType {
+                consume_keyword_type_id,
+                ..
+            } => active_model.consume_keyword_type_id = Set(*consume_keyword_type_id),
+            SpentDetailEdit::SpentName(spent_name) => {
+                active_model.spent_name = Set(spent_name.clone())
+            }
+            SpentDetailEdit::SpentMoney(spent_money) => {
+                active_model.spent_money = Set(*spent_money)
+            }
+            SpentDetailEdit::SpentAt(spent_at) => active_model.spent_at = Set(spent_at.naive_utc()),
+        }
+
+        self.db_conn
+            .update_spent_detail_with_transaction(active_model, IndexingType::Update)
+            .await
+    }
+
+    pub async fn modify_user_payday_with_transaction(
+        &self,
+        user_seq: i64,
+        payday: u32,
+    ) -> anyhow::Result<()> {
+        let payday: i8 = i8::try_from(payday).map_err(|e| {
+            anyhow!(
+                "[MysqlQueryServiceImpl::modify_user_payday_with_transaction] Invalid payday {}: {:?}",
+                payday,
+                e
+            )
+        })?;
+
+        let active_model: users::ActiveModel = users::ActiveModel {
+            user_seq: Set(user_seq),
+            payday: Set(payday),
+            updated_at: Set(Some(Utc::now().naive_utc())),
+            updated_by: Set(Some("system".to_string())),
+            ..Default::default()
+        };
+
+        self.db_conn
+            .update_users_with_transaction(active_model)
+            .await
+    }
+
+    pub async fn modify_spent_detail_outbox_sent_with_transaction(
+        &self,
+        outbox_seq: i64,
+    ) -> anyhow::Result<()> {
+        let active_model: spent_detail_outbox::ActiveModel = spent_detail_outbox::ActiveModel {
+            outbox_seq: Set(outbox_seq),
+            is_sent: Set(1),
+            sent_at: Set(Some(Utc::now().naive_utc())),
+            ..Default::default()
+        };
+
+        self.db_conn
+            .update_spent_detail_outbox_with_transaction(active_model)
+            .await
+    }
+
+    pub async fn modify_spent_detail_outbox_failed_with_transaction(
+        &self,
+        outbox_seq: i64,
+        attempt_cnt: i32,
+        last_error: &str,
+    ) -> anyhow::Result<()> {
+        let active_model: spent_detail_outbox::ActiveModel = spent_detail_outbox::ActiveModel {
+            outbox_seq: Set(outbox_seq),
+            attempt_cnt: Set(attempt_cnt),
+            last_error: Set(Some(last_error.chars().take(1000).collect())),
+            ..Default::default()
+        };
+
+        self.db_conn
+            .update_spent_detail_outbox_with_transaction(active_model)
+            .await
+    }
+
+    pub async fn modify_stock_prices_with_transaction(
+        &self,
+        market_seq: i64,
+        stock_prices: &[(String, Decimal)],
+    ) -> anyhow::Result<u64> {
+        self.db_conn
+            .update_stock_prices_with_transaction(
+                market_seq,
+                stock_prices.to_vec(),
+                Utc::now().naive_utc(),
+            )
+            .await
+    }
+
+    pub async fn modify_crypto_prices_with_transaction(
+        &self,
+        currency_code: &str,
+        crypto_prices: &[(String, Decimal)],
+    ) -> anyhow::Result<u64> {
+        self.db_conn
+            .update_crypto_prices_with_transaction(
+                currency_code,
+                crypto_prices.to_vec(),
+                Utc::now().naive_utc(),
+            )
+            .await
+    }
+}
@@ -3,120 +3,73 @@ use crate::common::*;
 use crate::utils_modules::time_utils::*;
 
 use crate::models::{
-    agg_result_set::*, consume_result_by_type::*, document_with_id::*, per_datetime::*,
-    spent_detail::*, spent_detail_by_installment::*, to_python_graph_circle::*,
-    user_payment_methods::*,
+    agg_result_set::*, consume_result_by_type::*, dim_calendar::*, document_with_id::*,
+    installment_balance::*, merchant_spending::*, net_worth_history::*, per_datetime::*,
+    reconcile_result::*, spent_detail::*, spent_detail_by_card_approval::*,
+    spent_detail_by_installment::*, spent_detail_summary::*, spent_detail_with_info::*,
+    to_python_graph_circle::*, user_current_asset_snapshot::*, user_payment_methods::*,
 };
 
-use crate::service_traits::process_service::*;
+use std::collections::BTreeMap;
 
-#[derive(Debug, Getters, Clone, new)]
-pub struct ProcessServiceImpl;
+use crate::service_traits::{card_message_parser::*, process_service::*};
 
-impl ProcessServiceImpl {
-    #[doc = "Functions that vectorize by spaces, excluding certain characters from a string (internal helper)"]
-    /// # Arguments
-    /// * `intput_str`  - Applied String : ex) "289,545원 일시불"
-    /// * `replacements`- Character vector to replace : ex) [",", "원"]
-    ///
-    /// # Returns
-    /// * Result<Vec<String>, anyhow::Error> - ex) ["289545", "일시불"]
-    fn to_string_vector_by_replace(
-        &self,
-        intput_str: &str,
-        replacements: &[&str],
-    ) -> Result<Vec<String>, anyhow::Error> {
-        let consume_price_vec: Vec<String> = intput_str
-            .to_string()
-            .split_whitespace()
-            .map(|s| {
-                replacements
-                    .iter()
-                    .fold(s.to_string(), |acc, replace| acc.replace(replace, ""))
-            })
-            .collect();
+use crate::services::card_message_parser_impl::*;
 
-        Ok(consume_price_vec)
-    }
+use crate::enums::{card_parse_error::*, spent_at_status::*};
+
+/// Days a payday may be moved back over weekends and holidays before giving up.
+const MAX_PAYDAY_SHIFT_DAYS: i64 = 14;
+
+#[derive(Debug, Getters, Clone, new)]
+pub struct ProcessServiceImpl {
+    card_message_parser_registry: Arc<CardMessageParserRegistry>,
+}
 
-    #[doc = "Function that parses the money spent (internal helper)"]
+impl ProcessServiceImpl {
+    #[doc = "Installment filtering function : string -> i64 (internal helper)"]
     /// # Arguments
-    /// * `consume_price_vec`  - Vector with money spent data
-    /// * `idx`- Index of the vector to be accessed
+    /// * `payment_type` - Lump sum or installment payment type : ex) "일시불", "03개월"
     ///
     /// # Returns
-    /// * Result<i64, anyhow::Error
-    fn find_consume_prodt_money(
-        &self,
-        consume_price_vec: &[String],
-        idx: usize,
-    ) -> Result<i64, anyhow::Error> {
-        let consume_price: i64 = consume_price_vec
-            .get(idx)
-            .ok_or_else(|| anyhow!("[Index Out Of Range Error][find_consume_prodt_money()] Invalid index '{:?}' of 'consume_price_vec' vector was accessed.", idx))?
-            .parse::<i64>()?;
+    /// * Result<i64, anyhow::Error> - Number of monthly installments, 0 for a lump sum
+    fn get_installment_payment_filtering(&self, payment_type: &str) -> Result<i64, anyhow::Error> {
+        let installment_payment: i64 = match payment_type {
+            "일시불" => 0,
+            _ => payment_type
+                .strip_suffix("개월")
+                .ok_or_else(|| {
+                    anyhow!(
+                        "[ProcessServiceImpl::get_installment_payment_filtering] Unknown payment type: {}",
+                        payment_type
+                    )
+                })?
+                .parse::<i64>()?,
+        };
 
-        Ok(consume_price)
+        /* A one-month plan is charged at once, exactly like a lump sum. */
+        Ok(if installment_payment > 1 {
+            installment_payment
+        } else {
+            0
+        })
     }
 
-    #[doc = "Function that parses date data and returns DateTime<FixedOffset> in Seoul time (KST, UTC+9) (internal helper)"]
+    #[doc = "Function that finds the installment months written in a card notification (internal helper)"]
     /// # Arguments
-    /// * `consume_time_name_vec` - Vector with date, time data : ex) ["11/25", "10:02"]
+    /// * `split_args_vec` - Tokenized lines extracted from the notification text
     ///
     /// # Returns
-    /// * Result<DateTime<FixedOffset>, anyhow::Error>
-    fn to_consume_datetime_seoul(
-        &self,
-        consume_time_name_vec: &[String],
-    ) -> Result<DateTime<FixedOffset>, anyhow::Error> {
-        /* "11/25" */
-        let parsed_date: &String = consume_time_name_vec
-            .first()
-            .ok_or_else(|| anyhow!("[Index Out Of Range Error][to_consume_datetime_seoul()] Invalid index '{:?}' of 'consume_time_name_vec' vector was accessed.", 0))?;
-
-        /* "10:02" */
-        let parsed_time: &String = consume_time_name_vec
-            .get(1)
-            .ok_or_else(|| anyhow!("[Index Out Of Range Error][to_consume_datetime_seoul()] Invalid index '{:?}' of 'consume_time_name_vec' vector was accessed.", 1))?;
-
-        let cur_year: i32 = find_current_kor_naivedate().year();
-        let formatted_date_str: String = format!("{}/{}", cur_year, parsed_date);
-        let format_date: NaiveDate = NaiveDate::parse_from_str(&formatted_date_str, "%Y/%m/%d")?;
-        let format_time: NaiveTime = NaiveTime::parse_from_str(parsed_time, "%H:%M")?;
-        let format_naive_datetime: NaiveDateTime = NaiveDateTime::new(format_date, format_time);
-
-        let datetime_seoul: DateTime<FixedOffset> = Seoul
-            .from_local_datetime(&format_naive_datetime)
-            .single()
-            .ok_or_else(|| {
-                anyhow!(
-                    "[Error][to_consume_datetime_seoul()] Failed to convert to DateTime<FixedOffset>"
-                )
-            })?
-            .fixed_offset();
-
-        Ok(datetime_seoul)
+    /// * i64 - Number of monthly installments, 0 when the notification has no installment marker
+    fn find_installment_from_lines(&self, split_args_vec: &[String]) -> i64 {
+        split_args_vec
+            .iter()
+            .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == '(' || c == ')'))
+            .find(|token| *token == "일시불" || token.ends_with("개월"))
+            .and_then(|token| self.get_installment_payment_filtering(token).ok())
+            .unwrap_or(0)
     }
 
-    // #[doc = "Installment filtering function : string -> i64 (internal helper)"]
-    // /// # Arguments
-    // /// * `payment_type` - Lump sum or installment payment type
-    // ///
-    // /// # Returns
-    // /// * Result<i64, anyhow::Error>
-    // fn get_installment_payment_filtering(&self, payment_type: &str) -> Result<i64, anyhow::Error> {
-    //     let installment_payment: i64 = match payment_type {
-    //         "일시불" => 0,
-    //         "03개월" => 3,
-    //         "06개월" => 6,
-    //         "09개월" => 9,
-    //         "12개월" => 12,
-    //         _ => 0,
-    //     };
-
-    //     Ok(installment_payment)
-    // }
-
     #[doc = "Function that calculates the money spent by category (internal helper)"]
     /// # Arguments
     /// * `total_cost` - total money spent
@@ -145,240 +98,265 @@ impl ProcessServiceImpl {
 
         Ok(consume_result_by_types)
     }
+}
 
-    /// Parses an NH card payment notification message and builds a `SpentDetail`.
+#[async_trait]
+impl ProcessService for ProcessServiceImpl {
+    /// Splits a raw card notification into trimmed, non-empty lines with brackets removed.
     ///
     /// # Arguments
     ///
-    /// * `split_args_vec` - Tokenized fields extracted from the notification text
-    /// * `user_seq` - Unique identifier of the user
-    /// * `room_seq` - Unique identifier of the Telegram room
-    /// * `user_payment_methods` - Slice of payment methods registered by the user
+    /// * `input_text` - Lowercased notification text received from Telegram
     ///
     /// # Returns
     ///
-    /// Returns `Ok(SpentDetail)` on success.
+    /// Returns the lines that are handed to `modify_by_consume_filter`.
     ///
     /// # Errors
     ///
-    /// Returns an error if required fields are missing or the card alias cannot be matched.
-    fn modify_nh_card(
-        &self,
-        split_args_vec: &[String],
-        user_seq: i64,
-        room_seq: i64,
-        user_payment_methods: &[UserPaymentMethods],
-    ) -> anyhow::Result<SpentDetail> {
-        let split_val: Vec<&str> = vec![",", "원"];
-
-        let card_name: String = split_args_vec
-            .first()
-            .ok_or_else(|| {
-                anyhow!("[ProcessServiceImpl::modify_nh_card] Price field (index 0) not found")
-            })?
-            .replace("승인", "");
-
-        let payment_method_id: i64 = user_payment_methods
-            .iter()
-            .find(|elem| card_name.contains(elem.card_alias().as_str()))
-            .map(|elem| *elem.payment_method_id())
-            .ok_or_else(|| {
-                anyhow!(
-                    "[ProcessServiceImpl::modify_nh_card] No matching payment method found for card_name: {}",
-                    card_name
-                )
-            })?;
-
-        let (spent_money, spent_at, spent_name): (i64, DateTime<FixedOffset>, String) =
-            if split_args_vec.len() > 4 {
-                let price_str: &str = split_args_vec.get(2).ok_or_else(|| {
-                    anyhow!("[ProcessServiceImpl::modify_nh_card] Price field (index 2) not found")
-                })?;
-                let consume_price_vec: Vec<String> =
-                    self.to_string_vector_by_replace(price_str, &split_val)?;
-                let spent_money: i64 = self.find_consume_prodt_money(&consume_price_vec, 0)?;
-
-                let time_str: &str = split_args_vec.get(3).ok_or_else(|| {
-                    anyhow!("[ProcessServiceImpl::modify_nh_card] Time field (index 3) not found")
-                })?;
-                let consume_time_vec: Vec<String> =
-                    time_str.split(" ").map(|s| s.trim().to_string()).collect();
-                let spent_at: DateTime<FixedOffset> =
-                    self.to_consume_datetime_seoul(&consume_time_vec)?;
-
-                let spent_name: String = split_args_vec
-                    .get(4)
-                    .ok_or_else(|| anyhow!("[ProcessServiceImpl::modify_nh_card] Product name field (index 4) not found"))?
-                    .to_string();
-
-                (spent_money, spent_at, spent_name)
-            } else {
-                let price_str: &str = split_args_vec.get(1).ok_or_else(|| {
-                    anyhow!("[ProcessServiceImpl::modify_nh_card] Price field (index 1) not found")
-                })?;
-                let consume_price_vec: Vec<String> =
-                    self.to_string_vector_by_replace(price_str, &split_val)?;
-                let spent_money: i64 = self.find_consume_prodt_money(&consume_price_vec, 1)?;
-                let spent_at: DateTime<FixedOffset> =
-                    Utc::now().with_timezone(&Seoul).fixed_offset();
-                let spent_name: String = split_args_vec
-                    .get(2)
-                    .ok_or_else(|| anyhow!("[ProcessServiceImpl::modify_nh_card] Product name field (index 2) not found"))?
-                    .to_string();
-
-                (spent_money, spent_at, spent_name)
-            };
+    /// Returns an error if the cleanup regex cannot be compiled.
+    fn to_consume_notification_lines(&self, input_text: &str) -> anyhow::Result<Vec<String>> {
+        let bracket_re: Regex = Regex::new(r"\.*?\\n?").map_err(|e| {
+            anyhow!(
+                "[ProcessServiceImpl::to_consume_notification_lines] Bad regex: {:?}",
+                e
+            )
+        })?;
 
-        let spent_detail: SpentDetail = SpentDetail {
-            spent_name,
-            spent_money,
-            spent_at,
-            should_index: 1,
-            user_seq,
-            spent_group_id: 0,
-            consume_keyword_type_id: 0,
-            room_seq,
-            payment_method_id,
-        };
+        let lines: Vec<String> = bracket_re
+            .replace_all(input_text, "")
+            .split('\n')
+            .map(|s| s.replace("[", "").replace("]", "").trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
 
-        Ok(spent_detail)
+        Ok(lines)
     }
 
-    /// Parses a Samsung card payment notification message and builds a `SpentDetail`.
+    /// Dispatches the card payment notification to the first registered card parser that accepts it.
+    ///
+    /// A parser is only tried when the user has at least one payment method whose
+    /// `card_company_nm` equals the parser's `card_company_nm`.
     ///
     /// # Arguments
     ///
     /// * `split_args_vec` - Tokenized fields extracted from the notification text
     /// * `user_seq` - Unique identifier of the user
     /// * `room_seq` - Unique identifier of the Telegram room
-    /// * `user_payment_methods` - Slice of payment methods registered by the user
+    /// * `user_payment_methods` - List of payment methods registered by the user
+    /// * `received_at` - Telegram message date, used to infer the year of the payment
     ///
     /// # Returns
     ///
-    /// Returns `Ok(SpentDetail)` on success.
+    /// Returns `Ok(SpentDetailByCardApproval)` holding the parsed spending and whether the
+    /// notification approves a payment or cancels/refunds an earlier one.
     ///
     /// # Errors
     ///
-    /// Returns an error if required fields are missing or the card alias cannot be matched.
-    fn modify_samsung_card(
+    /// Returns a `CardParseError` (wrapped in `anyhow::Error`) if no parser recognises the
+    /// notification or the matching parser fails.
+    fn modify_by_consume_filter(
         &self,
         split_args_vec: &[String],
         user_seq: i64,
         room_seq: i64,
-        user_payment_methods: &[UserPaymentMethods],
-    ) -> anyhow::Result<SpentDetail> {
-        let split_val: Vec<&str> = vec![",", "원"];
+        user_payment_methods: Vec<UserPaymentMethods>,
+        received_at: DateTime<Utc>,
+    ) -> anyhow::Result<SpentDetailByCardApproval> {
+        let consume_type: &String = split_args_vec
+            .first()
+            .ok_or_else(|| anyhow!("[Parameter Error][process_by_consume_filter] Invalid format of 'text' variable entered as parameter : {:?}", split_args_vec))?;
 
-        let card_name: &str = split_args_vec.first().ok_or_else(|| {
-            anyhow!("[ProcessServiceImpl::process_samsung_card] Price field (index 0) not found")
-        })?;
+        let card_company_nms: HashMap<String, Vec<UserPaymentMethods>> = user_payment_methods
+            .into_iter()
+            .filter_map(|elem| {
+                let nm: String = to_card_company_key(elem.card_company_nm().as_deref()?);
+                Some((nm, elem))
+            })
+            .fold(HashMap::new(), |mut acc, (nm, elem)| {
+                acc.entry(nm).or_default().push(elem);
+                acc
+            });
 
-        let payment_method_id: i64 = user_payment_methods
+        let parser: &dyn CardMessageParser = self
+            .card_message_parser_registry
+            .parsers()
             .iter()
-            .find(|elem| card_name.contains(elem.card_alias().as_str()))
-            .map(|elem| *elem.payment_method_id())
-            .ok_or_else(|| {
-                anyhow!(
-                    "[ProcessServiceImpl::process_samsung_card] No matching payment method found for card_name: {}",
-                    card_name
-                )
-            })?;
-
-        // Extract price and payment type
-        let price_str: &str = split_args_vec.get(1).ok_or_else(|| {
-            anyhow!("[ProcessServiceImpl::process_samsung_card] Price field (index 1) not found")
-        })?;
-        let consume_price_vec: Vec<String> =
-            self.to_string_vector_by_replace(price_str, &split_val)?;
-        let spent_money: i64 = self.find_consume_prodt_money(&consume_price_vec, 0)?;
-
-        // Extract time and product name
-        let time_str: &str = split_args_vec.get(2).ok_or_else(|| {
-            anyhow!("[ProcessServiceImpl::process_samsung_card] Time field (index 2) not found")
-        })?;
-        let consume_time_vec: Vec<String> = time_str.split(" ").map(|s| s.to_string()).collect();
-        let spent_at: DateTime<FixedOffset> = self.to_consume_datetime_seoul(&consume_time_vec)?;
+            .map(|parser| parser.as_ref())
+            .find(|parser| {
+                card_company_nms.contains_key(parser.card_company_nm())
+                    && parser.is_match(split_args_vec)
+            })
+            .ok_or_else(|| CardParseError::UnsupportedFormat(consume_type.to_string()))?;
 
-        let spent_name: String = consume_time_vec
-            .get(2)
-            .ok_or_else(|| anyhow!("[ProcessServiceImpl::process_samsung_card] Product name not found in time field"))?
-            .to_string();
+        let user_payment_methods: &Vec<UserPaymentMethods> = card_company_nms
+            .get(parser.card_company_nm())
+            .ok_or_else(|| anyhow!("[ProcessServiceImpl::modify_by_consume_filter] '{}' does not exist in the HashMap.", parser.card_company_nm()))?;
 
-        let spent_detail: SpentDetail = SpentDetail {
-            spent_name,
-            spent_money,
-            spent_at,
-            should_index: 1,
+        let spent_detail: SpentDetail = parser.parse(
+            split_args_vec,
             user_seq,
-            spent_group_id: 0,
-            consume_keyword_type_id: 0,
             room_seq,
-            payment_method_id,
-        };
-
-        Ok(spent_detail)
+            user_payment_methods,
+            received_at.with_timezone(&Seoul).fixed_offset(),
+        )?;
+
+        Ok(SpentDetailByCardApproval::new(
+            find_card_approval_type(split_args_vec),
+            self.find_installment_from_lines(split_args_vec),
+            spent_detail,
+        ))
     }
-}
 
-#[async_trait]
-impl ProcessService for ProcessServiceImpl {
-    /// Dispatches the card payment notification to the appropriate card-specific parser.
+    /// Checks the payment time inferred from a card notification against its receipt time.
     ///
     /// # Arguments
     ///
-    /// * `split_args_vec` - Tokenized fields extracted from the notification text
-    /// * `user_seq` - Unique identifier of the user
-    /// * `room_seq` - Unique identifier of the Telegram room
-    /// * `user_payment_methods` - List of payment methods registered by the user
+    /// * `spent_at` - Payment time parsed from the notification
+    /// * `received_at` - Telegram message date of the notification
+    /// * `future_tolerance_mins` - Minutes `spent_at` may lie ahead of `received_at` (clock skew)
+    /// * `stale_days` - Days after which a payment is reported as stale
     ///
     /// # Returns
     ///
-    /// Returns `Ok(SpentDetail)` on success.
+    /// Returns `SpentAtStatus::YearAdjusted` when the payment falls in another year than the
+    /// receipt, `SpentAtStatus::Stale` when it is older than `stale_days`, otherwise `Normal`.
     ///
     /// # Errors
     ///
-    /// Returns an error if the card company cannot be identified or parsing fails.
-    // 새로운 버전
-    fn modify_by_consume_filter(
+    /// Returns `CardParseError::FutureSpentAt` if `spent_at` is later than `received_at`
+    /// plus `future_tolerance_mins`.
+    fn find_spent_at_status(
         &self,
-        split_args_vec: &[String],
-        user_seq: i64,
-        room_seq: i64,
-        user_payment_methods: Vec<UserPaymentMethods>,
-    ) -> anyhow::Result<SpentDetail> {
-        let consume_type: &String = split_args_vec
-            .first()
-            .ok_or_else(|| anyhow!("[Parameter Error][process_by_consume_filter] Invalid format of 'text' variable entered as parameter : {:?}", split_args_vec))?;
+        spent_at: DateTime<FixedOffset>,
+        received_at: DateTime<Utc>,
+        future_tolerance_mins: i64,
+        stale_days: i64,
+    ) -> anyhow::Result<SpentAtStatus> {
+        let received_at_seoul: DateTime<FixedOffset> =
+            received_at.with_timezone(&Seoul).fixed_offset();
+
+        if spent_at > received_at_seoul + chrono::Duration::minutes(future_tolerance_mins) {
+            return Err(CardParseError::FutureSpentAt {
+                spent_at: spent_at.format("%Y-%m-%dT%H:%M").to_string(),
+                received_at: received_at_seoul.format("%Y-%m-%dT%H:%M").to_string(),
+            }
+            .into());
+        }
 
-        let card_company_nms: HashMap<String, Vec<UserPaymentMethods>> = user_payment_methods
-            .into_iter()
-            .filter_map(|elem| {
-                let nm: String = elem.card_company_nm().clone()?;
-                Some((nm, elem))
-            })
-            .fold(HashMap::new(), |mut acc, (nm, elem)| {
-                acc.entry(nm).or_default().push(elem);
-                acc
-            });
+        if spent_at.year() != received_at_seoul.year() {
+            Ok(SpentAtStatus::YearAdjusted)
+        } else if received_at_seoul - spent_at > chrono::Duration::days(stale_days) {
+            Ok(SpentAtStatus::Stale)
+        } else {
+            Ok(SpentAtStatus::Normal)
+        }
+    }
+
+    /// Picks the original spending that a cancellation notification refers to.
+    ///
+    /// Candidates are expected to be pre-filtered by user, card, amount and time window;
+    /// this function applies the merchant check and prefers the most recent exact match.
+    ///
+    /// # Arguments
+    ///
+    /// * `cancellation` - Spending parsed from the cancellation notification
+    /// * `candidates` - Active spending rows with the same card and amount, newest first
+    ///
+    /// # Returns
+    ///
+    /// Returns the matched row, or `None` if no candidate has a similar merchant name.
+    fn find_cancellation_target(
+        &self,
+        cancellation: &SpentDetail,
+        candidates: &[SpentDetailWithInfo],
+    ) -> Option<SpentDetailWithInfo> {
+        let cancel_name: String = cancellation.spent_name().replace(" ", "");
+
+        let is_same_merchant = |candidate: &SpentDetailWithInfo| -> bool {
+            let candidate_name: String = candidate.spent_name.replace(" ", "");
+            let max_distance: usize =
+                cmp::max(cancel_name.chars().count(), candidate_name.chars().count()) / 3;
+
+            candidate_name.contains(&cancel_name)
+                || cancel_name.contains(&candidate_name)
+                || levenshtein(&candidate_name, &cancel_name) <= max_distance
+        };
 
-        if card_company_nms.contains_key("nh") && consume_type.contains("nh") {
-            let user_payment_methods: &Vec<UserPaymentMethods> = card_company_nms
-                .get("nh")
-                .ok_or_else(|| anyhow!("[ProcessServiceImpl::modify_by_consume_filter_v1] The word ‘NH’ does not exist in the HashMap."))?;
+        let same_amount: Vec<&SpentDetailWithInfo> = candidates
+            .iter()
+            .filter(|candidate| candidate.spent_money == *cancellation.spent_money())
+            .collect();
 
-            self.modify_nh_card(split_args_vec, user_seq, room_seq, user_payment_methods)
-        } else if card_company_nms.contains_key("삼성") && consume_type.contains("삼성") {
-            let user_payment_methods: &Vec<UserPaymentMethods> = card_company_nms
-                .get("삼성")
-                .ok_or_else(|| anyhow!("[ProcessServiceImpl::modify_by_consume_filter_v1] The word ‘NH’ does not exist in the HashMap."))?;
+        same_amount
+            .iter()
+            .find(|candidate| candidate.spent_name.replace(" ", "") == cancel_name)
+            .or_else(|| {
+                same_amount
+                    .iter()
+                    .find(|candidate| is_same_merchant(candidate))
+            })
+            .map(|candidate| (*candidate).clone())
+    }
 
-            self.modify_samsung_card(split_args_vec, user_seq, room_seq, user_payment_methods)
-        } else {
-            Err(anyhow!("[Error][modify_by_consume_filter_v1] Variable 'consume_type' contains an undefined string: {}", consume_type))
+    /// Rebuilds the original purchases from the monthly rows of installment plans, so a
+    /// cancellation of the whole purchase can be matched with `find_cancellation_target`.
+    ///
+    /// # Arguments
+    ///
+    /// * `installment_details` - Active monthly rows of one or more installment groups
+    ///
+    /// # Returns
+    ///
+    /// Returns one entry per `spent_group_id`, newest purchase first. Each entry is the first
+    /// monthly row with the `-k/N` suffix removed from its name and the amounts of all rows of
+    /// the group as `spent_money`.
+    fn find_installment_purchases(
+        &self,
+        installment_details: &[SpentDetailWithInfo],
+    ) -> Vec<SpentDetailWithInfo> {
+        let mut purchases: HashMap<i64, SpentDetailWithInfo> = HashMap::new();
+
+        for detail in installment_details {
+            match purchases.get_mut(&detail.spent_group_id) {
+                Some(purchase) => {
+                    let total_money: i64 = purchase.spent_money + detail.spent_money;
+
+                    if detail.spent_at < purchase.spent_at {
+                        *purchase = detail.clone();
+                    }
+                    purchase.spent_money = total_money;
+                }
+                None => {
+                    purchases.insert(detail.spent_group_id, detail.clone());
+                }
+            }
         }
+
+        let mut purchases: Vec<SpentDetailWithInfo> = purchases
+            .into_values()
+            .map(|mut purchase| {
+                if let Some((spent_name, _)) =
+                    purchase.spent_name.rsplit_once('-').filter(|(_, suffix)| {
+                        suffix.split_once('/').is_some_and(|(month, months)| {
+                            month.parse::<u32>().is_ok() && months.parse::<u32>().is_ok()
+                        })
+                    })
+                {
+                    purchase.spent_name = spent_name.to_string();
+                }
+                purchase
+            })
+            .collect();
+
+        purchases.sort_by_key(|purchase| cmp::Reverse(purchase.spent_at));
+        purchases
     }
 
     #[doc = "Functions that take into account installment payments"]
+    /// Splits an installment purchase into one row per month on the same day of month.
+    /// The remainder of the division is charged in the first month so that the rows add up to the purchase amount.
+    ///
     /// # Arguments
     /// * `spent_detail_by_installment` - Spent detail with installment information
     ///
@@ -391,26 +369,38 @@ impl ProcessService for ProcessServiceImpl {
         let spent_detail: &SpentDetail = spent_detail_by_installment.spent_detail();
         let mut spent_detail_vec: Vec<SpentDetail> = Vec::new();
 
-        if *spent_detail_by_installment.installment() > 0 {
+        let installment: i64 = *spent_detail_by_installment.installment();
+
+        if installment > 0 {
             let spent_money: i64 = *spent_detail.spent_money();
-            let spent_money_ceil: i64 = (spent_money as f64
-                / *spent_detail_by_installment.installment() as f64)
-                .ceil() as i64;
+            let monthly_money: i64 = spent_money / installment;
+            let first_month_money: i64 = monthly_money + spent_money % installment;
 
-            for idx in 0..*spent_detail_by_installment.installment() {
+            for idx in 0..installment {
                 let mut spent_detail_clone: SpentDetail = spent_detail.clone();
 
                 let spent_at: DateTime<FixedOffset> = *spent_detail_clone.spent_at();
-                let calculate_spent_at: DateTime<FixedOffset> =
-                    spent_at + chrono::Duration::days(30 * idx);
+                let calculate_spent_at: DateTime<FixedOffset> = spent_at
+                    .checked_add_months(Months::new(idx as u32))
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "[ProcessServiceImpl::find_spent_detail_installment_process] Month overflow: {} + {}",
+                            spent_at,
+                            idx
+                        )
+                    })?;
 
                 spent_detail_clone.set_spent_at(calculate_spent_at);
-                spent_detail_clone.set_spent_money(spent_money_ceil);
+                spent_detail_clone.set_spent_money(if idx == 0 {
+                    first_month_money
+                } else {
+                    monthly_money
+                });
                 spent_detail_clone.set_spent_name(format!(
                     "{}-{}/{}",
                     spent_detail.spent_name(),
                     idx + 1,
-                    spent_detail_by_installment.installment()
+                    installment
                 ));
 
                 spent_detail_vec.push(spent_detail_clone);
@@ -422,6 +412,55 @@ impl ProcessService for ProcessServiceImpl {
         Ok(spent_detail_vec)
     }
 
+    #[doc = "Function that aggregates the remaining installment rows per card and per plan"]
+    /// # Arguments
+    /// * `installment_remains` - Future monthly installment rows, ordered by `spent_at`
+    ///
+    /// # Returns
+    /// * Vec<InstallmentBalance> - One entry per card, largest remaining balance first
+    fn find_installment_balance_by_card(
+        &self,
+        installment_remains: &[InstallmentRemain],
+    ) -> Vec<InstallmentBalance> {
+        let mut plans_by_card: HashMap<String, HashMap<i64, InstallmentPlanBalance>> =
+            HashMap::new();
+
+        for remain in installment_remains {
+            /* "쿠팡-2/3" -> "쿠팡" */
+            let plan_name: &str = remain
+                .spent_name
+                .rsplit_once('-')
+                .map_or(remain.spent_name.as_str(), |(name, _)| name);
+
+            plans_by_card
+                .entry(remain.card_alias.clone())
+                .or_default()
+                .entry(remain.spent_group_id)
+                .and_modify(|plan| {
+                    plan.remaining_cnt += 1;
+                    plan.remaining_money += remain.spent_money;
+                })
+                .or_insert_with(|| {
+                    InstallmentPlanBalance::new(plan_name.to_string(), 1, remain.spent_money)
+                });
+        }
+
+        let mut installment_balances: Vec<InstallmentBalance> = plans_by_card
+            .into_iter()
+            .map(|(card_alias, plans)| {
+                let mut plans: Vec<InstallmentPlanBalance> = plans.into_values().collect();
+                plans.sort_by_key(|plan| cmp::Reverse(plan.remaining_money));
+
+                let remaining_money: i64 = plans.iter().map(|plan| plan.remaining_money).sum();
+                InstallmentBalance::new(card_alias, remaining_money, plans)
+            })
+            .collect();
+
+        installment_balances.sort_by_key(|balance| cmp::Reverse(balance.remaining_money));
+
+        installment_balances
+    }
+
     #[doc = "Function that returns the time allotted as a parameter and the time before/after `N` months"]
     /// # Arguments
     /// * `date_start` - Start date
@@ -448,6 +487,317 @@ impl ProcessService for ProcessServiceImpl {
         Ok(per_mon_datetim)
     }
 
+    /// Returns the actual payday of a month for a user whose nominal payday is `payday`.
+    ///
+    /// A payday beyond the end of the month falls on its last day. When that date is a weekend
+    /// or holiday according to `DIM_CALENDAR`, the payday moves to the previous business day.
+    /// Dates missing from the calendar only count as non-business days on Saturdays and Sundays.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - Year of the payday
+    /// * `month` - Month of the payday
+    /// * `payday` - Nominal payday of the user (1-31)
+    /// * `dim_calendars` - Calendar rows covering at least the two weeks before the payday
+    ///
+    /// # Returns
+    ///
+    /// Returns the payday as a KST calendar date (midnight, tagged as UTC).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the date cannot be built or no business day exists within two weeks.
+    fn find_business_payday(
+        &self,
+        year: i32,
+        month: u32,
+        payday: u32,
+        dim_calendars: &[DimCalendar],
+    ) -> anyhow::Result<DateTime<Utc>> {
+        let month_last_day: u32 =
+            (find_add_month_from_naivedate(find_naivedate(year, month, 1)?, 1)?
+                - chrono::Duration::days(1))
+            .day();
+        let mut payday_date: NaiveDate =
+            find_naivedate(year, month, payday.clamp(1, month_last_day))?.date_naive();
+
+        for _ in 0..MAX_PAYDAY_SHIFT_DAYS {
+            let is_business_day: bool = match dim_calendars
+                .iter()
+                .find(|dim_calendar| *dim_calendar.dt() == payday_date)
+            {
+                Some(dim_calendar) => dim_calendar.is_business_day(),
+                None => !matches!(payday_date.weekday(), Weekday::Sat | Weekday::Sun),
+            };
+
+            if is_business_day {
+                return Ok(payday_date.and_time(NaiveTime::MIN).and_utc());
+            }
+
+            payday_date = payday_date.pred_opt().ok_or_else(|| {
+                anyhow!(
+                    "[ProcessServiceImpl::find_business_payday] Date underflow before {:?}",
+                    payday_date
+                )
+            })?;
+        }
+
+        Err(anyhow!(
+            "[ProcessServiceImpl::find_business_payday] No business day within {} days before {}-{:02}-{:02}",
+            MAX_PAYDAY_SHIFT_DAYS,
+            year,
+            month,
+            payday
+        ))
+    }
+
+    /// Returns the salary period that contains `ref_date`, as used by `cs`, `gs` and budget alerts.
+    ///
+    /// Salary periods run from one business payday up to, but not including, the next one.
+    /// Next month's payday can move back into the current month (e.g. payday 1 on a weekend),
+    /// in which case dates on or after it already belong to the following period.
+    ///
+    /// # Arguments
+    ///
+    /// * `ref_date` - KST calendar date (midnight, tagged as UTC) inside the wanted period
+    /// * `payday` - Nominal payday of the user (1-31)
+    /// * `dim_calendars` - Calendar rows covering the month before `ref_date` through two months after it
+    ///
+    /// # Returns
+    ///
+    /// Returns `(period_start, period_end)` as KST calendar dates tagged as UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a payday cannot be computed.
+    fn find_salary_period(
+        &self,
+        ref_date: DateTime<Utc>,
+        payday: u32,
+        dim_calendars: &[DimCalendar],
+    ) -> anyhow::Result<(DateTime<Utc>, DateTime<Utc>)> {
+        let month_start: DateTime<Utc> = find_naivedate(ref_date.year(), ref_date.month(), 1)?;
+        let prev_month: DateTime<Utc> = find_add_month_from_naivedate(month_start, -1)?;
+        let next_month: DateTime<Utc> = find_add_month_from_naivedate(month_start, 1)?;
+
+        let cur_payday: DateTime<Utc> = self.find_business_payday(
+            month_start.year(),
+            month_start.month(),
+            payday,
+            dim_calendars,
+        )?;
+
+        if ref_date < cur_payday {
+            let prev_payday: DateTime<Utc> = self.find_business_payday(
+                prev_month.year(),
+                prev_month.month(),
+                payday,
+                dim_calendars,
+            )?;
+
+            return Ok((prev_payday, cur_payday));
+        }
+
+        let next_payday: DateTime<Utc> = self.find_business_payday(
+            next_month.year(),
+            next_month.month(),
+            payday,
+            dim_calendars,
+        )?;
+
+        if ref_date < next_payday {
+            return Ok((cur_payday, next_payday));
+        }
+
+        /* Next month's payday was shifted back into this month and `ref_date` is already past it. */
+        let following_month: DateTime<Utc> = find_add_month_from_naivedate(month_start, 2)?;
+        let following_payday: DateTime<Utc> = self.find_business_payday(
+            following_month.year(),
+            following_month.month(),
+            payday,
+            dim_calendars,
+        )?;
+
+        Ok((next_payday, following_payday))
+    }
+
+    /// Finds the highest alert threshold that a new spending pushed the budget across.
+    ///
+    /// A threshold counts as crossed when the usage before the spending was below it and the
+    /// usage after the spending reaches it, so each threshold fires once per period.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget_money` - Budget of the period
+    /// * `spent_before` - Period total before the new spending
+    /// * `spent_after` - Period total including the new spending
+    /// * `thresholds` - Alert thresholds in percent of `budget_money`
+    ///
+    /// # Returns
+    ///
+    /// Returns the highest crossed threshold, or `None` when no threshold was crossed.
+    fn find_crossed_budget_threshold(
+        &self,
+        budget_money: i64,
+        spent_before: i64,
+        spent_after: i64,
+        thresholds: &[i64],
+    ) -> Option<i64> {
+        if budget_money <= 0 {
+            return None;
+        }
+
+        thresholds
+            .iter()
+            .copied()
+            .filter(|threshold| {
+                let threshold_money: i128 = i128::from(budget_money) * i128::from(*threshold);
+                i128::from(spent_before) * 100 < threshold_money
+                    && i128::from(spent_after) * 100 >= threshold_money
+            })
+            .max()
+    }
+
+    /// Compares the spending of one scope and period in MySQL with the indexed documents.
+    ///
+    /// # Arguments
+    ///
+    /// * `mysql_summaries` - Indexable `SPENT_DETAIL` rows of the scope and period
+    /// * `es_summaries` - `CONSUME_DETAIL` documents of the same scope and period
+    ///
+    /// # Returns
+    ///
+    /// Returns counts and money sums of both stores with the `spent_idx` values that are missing
+    /// from or extra in Elasticsearch, or whose amounts differ, each in ascending order.
+    fn find_reconcile_result(
+        &self,
+        mysql_summaries: &[SpentDetailSummary],
+        es_summaries: &[SpentDetailSummary],
+    ) -> ReconcileResult {
+        let es_money_by_idx: HashMap<i64, i64> = es_summaries
+            .iter()
+            .map(|summary| (summary.spent_idx, summary.spent_money))
+            .collect();
+        let mysql_money_by_idx: HashMap<i64, i64> = mysql_summaries
+            .iter()
+            .map(|summary| (summary.spent_idx, summary.spent_money))
+            .collect();
+
+        let mut missing_spent_idxs: Vec<i64> = Vec::new();
+        let mut mismatched_spent_idxs: Vec<i64> = Vec::new();
+
+        for summary in mysql_summaries {
+            match es_money_by_idx.get(&summary.spent_idx) {
+                None => missing_spent_idxs.push(summary.spent_idx),
+                Some(es_money) if *es_money != summary.spent_money => {
+                    mismatched_spent_idxs.push(summary.spent_idx)
+                }
+                Some(_) => (),
+            }
+        }
+
+        let mut extra_spent_idxs: Vec<i64> = es_summaries
+            .iter()
+            .map(|summary| summary.spent_idx)
+            .filter(|spent_idx| !mysql_money_by_idx.contains_key(spent_idx))
+            .collect();
+
+        missing_spent_idxs.sort_unstable();
+        mismatched_spent_idxs.sort_unstable();
+        extra_spent_idxs.sort_unstable();
+
+        ReconcileResult::new(
+            mysql_summaries.len(),
+            mysql_summaries
+                .iter()
+                .map(|summary| summary.spent_money)
+                .sum(),
+            es_summaries.len(),
+            es_summaries.iter().map(|summary| summary.spent_money).sum(),
+            missing_spent_idxs,
+            extra_spent_idxs,
+            mismatched_spent_idxs,
+        )
+    }
+
+    /// Builds the monthly net-worth series from the daily asset snapshots of a user.
+    ///
+    /// Each month is represented by its last snapshot day. The rows of that day are summed
+    /// across currencies, with USD amounts converted at `usd_to_krw`. Rows in any other
+    /// currency are logged and left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshots` - Snapshot rows of the user, in any order
+    /// * `usd_to_krw` - Exchange rate applied to the USD rows
+    ///
+    /// # Returns
+    ///
+    /// Returns one point per month that has a snapshot, oldest month first.
+    fn find_net_worth_history(
+        &self,
+        snapshots: &[UserCurrentAssetSnapshot],
+        usd_to_krw: Decimal,
+    ) -> NetWorthHistory {
+        let mut last_day_by_month: BTreeMap<(i32, u32), DateTime<Utc>> = BTreeMap::new();
+
+        for snapshot in snapshots {
+            let aggregated_at: DateTime<Utc> = snapshot.aggregated_at;
+            last_day_by_month
+                .entry((aggregated_at.year(), aggregated_at.month()))
+                .and_modify(|last_day| *last_day = (*last_day).max(aggregated_at))
+                .or_insert(aggregated_at);
+        }
+
+        let points: Vec<NetWorthPoint> = last_day_by_month
+            .into_iter()
+            .map(|((year, month), last_day)| {
+                let mut amounts: [Decimal; 5] = [Decimal::ZERO; 5];
+
+                for snapshot in snapshots.iter().filter(|s| s.aggregated_at == last_day) {
+                    let rate: Decimal = match snapshot.currency_code.as_str() {
+                        "KRW" => Decimal::ONE,
+                        "USD" => usd_to_krw,
+                        currency_code => {
+                            warn!(
+                                "[ProcessServiceImpl::find_net_worth_history] Unsupported currency {} of SNAPSHOT-{} is left out",
+                                currency_code, snapshot.summary_seq
+                            );
+                            continue;
+                        }
+                    };
+
+                    amounts[0] += snapshot.deposit_amount * rate;
+                    amounts[1] += snapshot.saving_amount * rate;
+                    amounts[2] += snapshot.stock_amount * rate;
+                    amounts[3] += snapshot.crypto_amount * rate;
+                    amounts[4] += snapshot.cash_amount * rate;
+                }
+
+                let [deposit_amount, saving_amount, stock_amount, crypto_amount, cash_amount] =
+                    amounts;
+
+                NetWorthPoint::new(
+                    format!("{}.{:02}", year, month),
+                    last_day,
+                    deposit_amount,
+                    saving_amount,
+                    stock_amount,
+                    crypto_amount,
+                    cash_amount,
+                )
+            })
+            .collect();
+
+        NetWorthHistory::new(
+            NET_WORTH_ASSET_TYPES
+                .iter()
+                .map(|asset_type| asset_type.to_string())
+                .collect(),
+            points,
+        )
+    }
+
     #[doc = "Function that converts consumption results by category into Python data"]
     /// # Arguments
     /// * `consume_details` - Consumption details
@@ -541,6 +891,103 @@ impl ProcessService for ProcessServiceImpl {
         Ok(to_python_graph_circle)
     }
 
+    /// Sums the spending per merchant (`spent_name`) and returns the largest ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `spent_details` - Spending of the report period
+    /// * `top_cnt` - Maximum number of merchants returned
+    ///
+    /// # Returns
+    ///
+    /// Returns the merchants ordered by total amount, then by name, largest first.
+    fn find_top_merchants<T: crate::models::to_python_graph_line::SpentDetailSource>(
+        &self,
+        spent_details: &AggResultSet<T>,
+        top_cnt: usize,
+    ) -> Vec<MerchantSpending> {
+        let merchant_map: HashMap<&str, (i64, usize)> =
+            spent_details
+                .source_list()
+                .iter()
+                .fold(HashMap::new(), |mut acc, spent_detail| {
+                    let detail: &T = spent_detail.source();
+                    let (spent_money, spent_cnt) = acc.entry(detail.spent_name()).or_insert((0, 0));
+                    *spent_money += detail.spent_money();
+                    *spent_cnt += 1;
+                    acc
+                });
+
+        let mut merchants: Vec<MerchantSpending> = merchant_map
+            .into_iter()
+            .filter(|(_, (spent_money, _))| *spent_money > 0)
+            .map(|(spent_name, (spent_money, spent_cnt))| {
+                MerchantSpending::new(spent_name.to_string(), spent_money, spent_cnt)
+            })
+            .collect();
+
+        merchants.sort_by(|a, b| {
+            b.spent_money
+                .cmp(&a.spent_money)
+                .then_with(|| a.spent_name.cmp(&b.spent_name))
+        });
+        merchants.truncate(top_cnt);
+
+        merchants
+    }
+
+    /// Returns the KST calendar month before the one containing `run_at`, together with the
+    /// month before it for comparison, as used by the monthly report mails.
+    ///
+    /// # Arguments
+    ///
+    /// * `run_at` - Time the report job runs
+    ///
+    /// # Returns
+    ///
+    /// Returns both months as inclusive UTC ranges from 00:00:00 KST on the first day to
+    /// 23:59:59 KST on the last day.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a month boundary cannot be built.
+    fn find_report_month_period(&self, run_at: DateTime<Utc>) -> anyhow::Result<PerDatetime> {
+        let run_month_first: NaiveDate = run_at
+            .with_timezone(&Seoul)
+            .date_naive()
+            .with_day(1)
+            .ok_or_else(|| {
+                anyhow!(
+                    "[ProcessServiceImpl::find_report_month_period] Invalid run date: {}",
+                    run_at
+                )
+            })?;
+
+        let month_start = |months_back: u32| -> anyhow::Result<DateTime<Utc>> {
+            let month_first: NaiveDate = run_month_first
+                .checked_sub_months(Months::new(months_back))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "[ProcessServiceImpl::find_report_month_period] Month underflow: {}",
+                        run_month_first
+                    )
+                })?;
+
+            Ok(find_kst_datetime(month_first.year(), month_first.month(), 1)?.with_timezone(&Utc))
+        };
+
+        let date_start: DateTime<Utc> = month_start(1)?;
+        let n_date_start: DateTime<Utc> = month_start(2)?;
+        let one_sec: chrono::Duration = chrono::Duration::seconds(1);
+
+        Ok(PerDatetime::new(
+            date_start,
+            month_start(0)? - one_sec,
+            n_date_start,
+            date_start - one_sec,
+        ))
+    }
+
     #[doc = "Function that returns the time allotted as a parameter and the time before/after `N` days"]
     /// # Arguments
     /// * `date_start`
@@ -564,3 +1011,615 @@ impl ProcessService for ProcessServiceImpl {
         Ok(per_day_datetim)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::enums::card_approval_type::*;
+    use crate::models::spent_detail_by_es::*;
+
+    use std::path::{Path, PathBuf};
+
+    #[derive(Debug, Deserialize)]
+    struct CardFixture {
+        card_company_nm: String,
+        card_alias: String,
+        text: String,
+        #[serde(default)]
+        received_at: Option<DateTime<FixedOffset>>,
+        expected: Option<CardFixtureExpected>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct CardFixtureExpected {
+        spent_name: String,
+        spent_money: i64,
+        spent_at: Option<String>,
+        #[serde(default)]
+        spent_year: Option<i32>,
+        #[serde(default)]
+        cancellation: bool,
+        #[serde(default)]
+        installment: i64,
+    }
+
+    const FIXTURE_PAYMENT_METHOD_ID: i64 = 1;
+    const FIXTURE_USER_SEQ: i64 = 10;
+    const FIXTURE_ROOM_SEQ: i64 = 20;
+
+    fn find_fixture_paths(dir: &Path) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .unwrap_or_else(|e| panic!("Failed to read fixture dir {:?}: {:?}", dir, e))
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .flat_map(|path| {
+                if path.is_dir() {
+                    find_fixture_paths(&path)
+                } else if path.extension().is_some_and(|ext| ext == "json") {
+                    vec![path]
+                } else {
+                    vec![]
+                }
+            })
+            .collect();
+
+        paths.sort();
+        paths
+    }
+
+    fn to_fixture_payment_method(fixture: &CardFixture) -> UserPaymentMethods {
+        UserPaymentMethods {
+            payment_method_id: FIXTURE_PAYMENT_METHOD_ID,
+            payment_type_cd: "CARD".to_string(),
+            payment_category_cd: "CREDIT".to_string(),
+            card_id: "fixture".to_string(),
+            card_alias: fixture.card_alias.clone(),
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: None,
+            created_by: "test".to_string(),
+            updated_by: None,
+            is_default: false,
+            user_seq: FIXTURE_USER_SEQ,
+            card_company_nm: Some(fixture.card_company_nm.clone()),
+        }
+    }
+
+    #[test]
+    fn test_card_fixtures() {
+        let fixture_dir: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/card_fixtures");
+        let fixture_paths: Vec<PathBuf> = find_fixture_paths(&fixture_dir);
+        assert!(!fixture_paths.is_empty(), "No card fixtures found");
+
+        let process_service: ProcessServiceImpl =
+            ProcessServiceImpl::new(Arc::new(CardMessageParserRegistry::with_default_parsers()));
+
+        let failures: Vec<String> = fixture_paths
+            .iter()
+            .filter_map(|path| {
+                let raw: String = match std::fs::read_to_string(path) {
+                    Ok(raw) => raw,
+                    Err(e) => return Some(format!("{:?}: unreadable fixture: {}", path, e)),
+                };
+                let fixture: CardFixture = match serde_json::from_str(&raw) {
+                    Ok(fixture) => fixture,
+                    Err(e) => return Some(format!("{:?}: invalid fixture: {}", path, e)),
+                };
+
+                /* TelebotServiceImpl lowercases every incoming message */
+                let lines: Vec<String> = process_service
+                    .to_consume_notification_lines(&fixture.text.to_lowercase())
+                    .unwrap_or_default();
+
+                let result: anyhow::Result<SpentDetailByCardApproval> = process_service
+                    .modify_by_consume_filter(
+                        &lines,
+                        FIXTURE_USER_SEQ,
+                        FIXTURE_ROOM_SEQ,
+                        vec![to_fixture_payment_method(&fixture)],
+                        fixture
+                            .received_at
+                            .map_or_else(Utc::now, |received_at| received_at.with_timezone(&Utc)),
+                    );
+
+                match (&fixture.expected, result) {
+                    (None, Err(_)) => None,
+                    (None, Ok(spent_detail_by_approval)) => Some(format!(
+                        "{:?}: expected rejection, got {:?}",
+                        path, spent_detail_by_approval
+                    )),
+                    (Some(_), Err(e)) => Some(format!("{:?}: {:#}", path, e)),
+                    (Some(expected), Ok(spent_detail_by_approval)) => {
+                        let spent_detail: &SpentDetail = spent_detail_by_approval.spent_detail();
+                        let is_cancellation: bool = *spent_detail_by_approval.approval_type()
+                            == CardApprovalType::Cancellation;
+                        let spent_at: String =
+                            spent_detail.spent_at().format("%m/%d %H:%M").to_string();
+                        let matched: bool = spent_detail.spent_name() == &expected.spent_name
+                            && *spent_detail.spent_money() == expected.spent_money
+                            && expected.spent_at.as_ref().is_none_or(|at| *at == spent_at)
+                            && expected
+                                .spent_year
+                                .is_none_or(|year| year == spent_detail.spent_at().year())
+                            && *spent_detail.payment_method_id() == FIXTURE_PAYMENT_METHOD_ID
+                            && *spent_detail.user_seq() == FIXTURE_USER_SEQ
+                            && *spent_detail.room_seq() == FIXTURE_ROOM_SEQ
+                            && is_cancellation == expected.cancellation
+                            && *spent_detail_by_approval.installment() == expected.installment;
+
+                        (!matched).then(|| {
+                            format!(
+                                "{:?}: expected {:?}, got {:?}",
+                                path, expected, spent_detail_by_approval
+                            )
+                        })
+                    }
+                }
+            })
+            .collect();
+
+        assert!(
+            failures.is_empty(),
+            "{} of {} card fixtures failed:\n{}",
+            failures.len(),
+            fixture_paths.len(),
+            failures.join("\n")
+        );
+    }
+
+    fn to_spent_detail_with_info(
+        spent_idx: i64,
+        spent_name: &str,
+        spent_money: i64,
+    ) -> SpentDetailWithInfo {
+        SpentDetailWithInfo {
+            spent_idx,
+            spent_name: spent_name.to_string(),
+            spent_money,
+            spent_at: Utc::now().naive_utc(),
+            created_at: Utc::now().naive_utc(),
+            user_seq: FIXTURE_USER_SEQ,
+            spent_group_id: 0,
+            consume_keyword_type_id: 1,
+            consume_keyword_type: "etc".to_string(),
+            room_seq: FIXTURE_ROOM_SEQ,
+            user_id: "test".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_cancellation_target() {
+        let process_service: ProcessServiceImpl =
+            ProcessServiceImpl::new(Arc::new(CardMessageParserRegistry::default()));
+
+        let cancellation: SpentDetail = SpentDetail {
+            spent_name: "스타벅스 강남점".to_string(),
+            spent_money: 12000,
+            spent_at: Utc::now().with_timezone(&Seoul).fixed_offset(),
+            should_index: 1,
+            user_seq: FIXTURE_USER_SEQ,
+            spent_group_id: 0,
+            consume_keyword_type_id: 0,
+            room_seq: FIXTURE_ROOM_SEQ,
+            payment_method_id: FIXTURE_PAYMENT_METHOD_ID,
+        };
+
+        let candidates: Vec<SpentDetailWithInfo> = vec![
+            to_spent_detail_with_info(3, "이디야커피", 12000),
+            to_spent_detail_with_info(2, "스타벅스강남", 12000),
+            to_spent_detail_with_info(1, "스타벅스강남점", 12000),
+        ];
+
+        let target: Option<SpentDetailWithInfo> =
+            process_service.find_cancellation_target(&cancellation, &candidates);
+        assert_eq!(target.map(|elem| elem.spent_idx), Some(1));
+
+        let target: Option<SpentDetailWithInfo> =
+            process_service.find_cancellation_target(&cancellation, &candidates[..2]);
+        assert_eq!(target.map(|elem| elem.spent_idx), Some(2));
+
+        let target: Option<SpentDetailWithInfo> =
+            process_service.find_cancellation_target(&cancellation, &candidates[..1]);
+        assert!(target.is_none());
+    }
+
+    #[test]
+    fn test_find_installment_purchases() {
+        let process_service: ProcessServiceImpl =
+            ProcessServiceImpl::new(Arc::new(CardMessageParserRegistry::default()));
+
+        let to_monthly_row = |spent_idx: i64,
+                              spent_group_id: i64,
+                              spent_name: &str,
+                              spent_money: i64,
+                              month: u32| {
+            let mut detail: SpentDetailWithInfo =
+                to_spent_detail_with_info(spent_idx, spent_name, spent_money);
+            detail.spent_group_id = spent_group_id;
+            detail.spent_at = NaiveDate::from_ymd_opt(2026, month, 10)
+                .unwrap()
+                .and_hms_opt(3, 0, 0)
+                .unwrap();
+            detail
+        };
+
+        let installment_details: Vec<SpentDetailWithInfo> = vec![
+            to_monthly_row(11, 7, "쿠팡-2/3", 33333, 5),
+            to_monthly_row(10, 7, "쿠팡-1/3", 33334, 4),
+            to_monthly_row(12, 7, "쿠팡-3/3", 33333, 6),
+            to_monthly_row(20, 8, "애플스토어-1/2", 600000, 5),
+            to_monthly_row(21, 8, "애플스토어-2/2", 600000, 6),
+        ];
+
+        let purchases: Vec<SpentDetailWithInfo> =
+            process_service.find_installment_purchases(&installment_details);
+
+        let summaries: Vec<(i64, i64, &str, i64)> = purchases
+            .iter()
+            .map(|purchase| {
+                (
+                    purchase.spent_group_id,
+                    purchase.spent_idx,
+                    purchase.spent_name.as_str(),
+                    purchase.spent_money,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![(8, 20, "애플스토어", 1200000), (7, 10, "쿠팡", 100000)]
+        );
+    }
+
+    #[test]
+    fn test_find_spent_detail_installment_process() {
+        let process_service: ProcessServiceImpl =
+            ProcessServiceImpl::new(Arc::new(CardMessageParserRegistry::default()));
+
+        let spent_at: DateTime<FixedOffset> = Seoul
+            .with_ymd_and_hms(2026, 1, 31, 12, 30, 0)
+            .unwrap()
+            .fixed_offset();
+
+        let spent_detail: SpentDetail = SpentDetail {
+            spent_name: "쿠팡".to_string(),
+            spent_money: 100000,
+            spent_at,
+            should_index: 1,
+            user_seq: FIXTURE_USER_SEQ,
+            spent_group_id: 0,
+            consume_keyword_type_id: 0,
+            room_seq: FIXTURE_ROOM_SEQ,
+            payment_method_id: FIXTURE_PAYMENT_METHOD_ID,
+        };
+
+        let monthly_details: Vec<SpentDetail> = process_service
+            .find_spent_detail_installment_process(&SpentDetailByInstallment::new(3, spent_detail))
+            .unwrap();
+
+        let spent_moneys: Vec<i64> = monthly_details
+            .iter()
+            .map(|detail| *detail.spent_money())
+            .collect();
+        assert_eq!(spent_moneys, vec![33334, 33333, 33333]);
+
+        let spent_ats: Vec<String> = monthly_details
+            .iter()
+            .map(|detail| detail.spent_at().format("%Y-%m-%d").to_string())
+            .collect();
+        assert_eq!(spent_ats, vec!["2026-01-31", "2026-02-28", "2026-03-31"]);
+
+        assert_eq!(monthly_details[1].spent_name(), "쿠팡-2/3");
+    }
+
+    #[test]
+    fn test_find_spent_at_status() {
+        let process_service: ProcessServiceImpl =
+            ProcessServiceImpl::new(Arc::new(CardMessageParserRegistry::default()));
+
+        let received_at: DateTime<Utc> = Seoul
+            .with_ymd_and_hms(2027, 1, 1, 0, 5, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let to_spent_at = |y: i32, m: u32, d: u32, h: u32, min: u32| -> DateTime<FixedOffset> {
+            Seoul
+                .with_ymd_and_hms(y, m, d, h, min, 0)
+                .unwrap()
+                .fixed_offset()
+        };
+
+        let status = |spent_at: DateTime<FixedOffset>| {
+            process_service.find_spent_at_status(spent_at, received_at, 10, 3)
+        };
+
+        assert_eq!(
+            status(to_spent_at(2027, 1, 1, 0, 1)).unwrap(),
+            SpentAtStatus::Normal
+        );
+        /* Clock skew within the tolerance is accepted. */
+        assert_eq!(
+            status(to_spent_at(2027, 1, 1, 0, 10)).unwrap(),
+            SpentAtStatus::Normal
+        );
+        assert_eq!(
+            status(to_spent_at(2026, 12, 31, 23, 50)).unwrap(),
+            SpentAtStatus::YearAdjusted
+        );
+        assert!(status(to_spent_at(2027, 1, 1, 0, 30)).is_err());
+
+        let received_at: DateTime<Utc> = Seoul
+            .with_ymd_and_hms(2027, 3, 20, 12, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            process_service
+                .find_spent_at_status(to_spent_at(2027, 3, 10, 12, 0), received_at, 10, 3)
+                .unwrap(),
+            SpentAtStatus::Stale
+        );
+    }
+
+    #[test]
+    fn test_find_crossed_budget_threshold() {
+        let service: ProcessServiceImpl =
+            ProcessServiceImpl::new(Arc::new(CardMessageParserRegistry::default()));
+        let thresholds: [i64; 3] = [50, 80, 100];
+
+        assert_eq!(
+            service.find_crossed_budget_threshold(100_000, 40_000, 55_000, &thresholds),
+            Some(50)
+        );
+        /* Jumping over several thresholds only reports the highest one. */
+        assert_eq!(
+            service.find_crossed_budget_threshold(100_000, 40_000, 120_000, &thresholds),
+            Some(100)
+        );
+        /* Reaching a threshold exactly counts; staying above it does not fire again. */
+        assert_eq!(
+            service.find_crossed_budget_threshold(100_000, 70_000, 80_000, &thresholds),
+            Some(80)
+        );
+        assert_eq!(
+            service.find_crossed_budget_threshold(100_000, 80_000, 90_000, &thresholds),
+            None
+        );
+        /* Refunds lower the total and never alert. */
+        assert_eq!(
+            service.find_crossed_budget_threshold(100_000, 90_000, 40_000, &thresholds),
+            None
+        );
+        assert_eq!(
+            service.find_crossed_budget_threshold(0, 0, 10_000, &thresholds),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_salary_period() {
+        let service: ProcessServiceImpl =
+            ProcessServiceImpl::new(Arc::new(CardMessageParserRegistry::default()));
+
+        /* 2026-01-25 is a Sunday and 2026-02-25 a Wednesday; without calendar rows only weekends shift. */
+        let (start, end) = service
+            .find_salary_period(find_naivedate(2026, 2, 10).unwrap(), 25, &[])
+            .unwrap();
+        assert_eq!(start, find_naivedate(2026, 1, 23).unwrap());
+        assert_eq!(end, find_naivedate(2026, 2, 25).unwrap());
+
+        /* The shifted payday already belongs to the new period. */
+        let (start, _) = service
+            .find_salary_period(find_naivedate(2026, 1, 23).unwrap(), 25, &[])
+            .unwrap();
+        assert_eq!(start, find_naivedate(2026, 1, 23).unwrap());
+
+        /* Holidays from DIM_CALENDAR shift as well: 2026-02-25 marked as a holiday. */
+        let dim_calendars: Vec<DimCalendar> = vec![DimCalendar::new(
+            NaiveDate::from_ymd_opt(2026, 2, 25).unwrap(),
+            false,
+            true,
+        )];
+        let (_, end) = service
+            .find_salary_period(find_naivedate(2026, 2, 10).unwrap(), 25, &dim_calendars)
+            .unwrap();
+        assert_eq!(end, find_naivedate(2026, 2, 24).unwrap());
+
+        /* A payday past the end of the month falls on its last business day. */
+        let (_, end) = service
+            .find_salary_period(find_naivedate(2026, 2, 10).unwrap(), 31, &[])
+            .unwrap();
+        assert_eq!(end, find_naivedate(2026, 2, 27).unwrap());
+
+        /* Payday 1: 2026-08-01 is a Saturday, so August's payday moves back to 2026-07-31. */
+        let (start, end) = service
+            .find_salary_period(find_naivedate(2026, 7, 30).unwrap(), 1, &[])
+            .unwrap();
+        assert_eq!(start, find_naivedate(2026, 7, 1).unwrap());
+        assert_eq!(end, find_naivedate(2026, 7, 31).unwrap());
+
+        for ref_date in [
+            find_naivedate(2026, 7, 31).unwrap(),
+            find_naivedate(2026, 8, 1).unwrap(),
+        ] {
+            let (start, end) = service.find_salary_period(ref_date, 1, &[]).unwrap();
+            assert_eq!(start, find_naivedate(2026, 7, 31).unwrap());
+            assert_eq!(end, find_naivedate(2026, 9, 1).unwrap());
+        }
+
+        /* Payday 31 in February: 2026-02-28 is a Saturday, so the period starts on 2026-02-27. */
+        let (start, end) = service
+            .find_salary_period(find_naivedate(2026, 2, 26).unwrap(), 31, &[])
+            .unwrap();
+        assert_eq!(start, find_naivedate(2026, 1, 30).unwrap());
+        assert_eq!(end, find_naivedate(2026, 2, 27).unwrap());
+
+        let (start, end) = service
+            .find_salary_period(find_naivedate(2026, 2, 28).unwrap(), 31, &[])
+            .unwrap();
+        assert_eq!(start, find_naivedate(2026, 2, 27).unwrap());
+        assert_eq!(end, find_naivedate(2026, 3, 31).unwrap());
+    }
+
+    #[test]
+    fn test_find_report_month_period() {
+        let service: ProcessServiceImpl =
+            ProcessServiceImpl::new(Arc::new(CardMessageParserRegistry::default()));
+        let kst = |y: i32, m: u32, d: u32, h: u32, mi: u32, sec: u32| {
+            Seoul
+                .with_ymd_and_hms(y, m, d, h, mi, sec)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+
+        /* 2026-10-01 09:00 KST reports September and compares it with August. */
+        let period: PerDatetime = service
+            .find_report_month_period(kst(2026, 10, 1, 9, 0, 0))
+            .unwrap();
+        assert_eq!(period.date_start, kst(2026, 9, 1, 0, 0, 0));
+        assert_eq!(period.date_end, kst(2026, 9, 30, 23, 59, 59));
+        assert_eq!(period.n_date_start, kst(2026, 8, 1, 0, 0, 0));
+        assert_eq!(period.n_date_end, kst(2026, 8, 31, 23, 59, 59));
+
+        /* The KST date decides the month: 2026-01-01 00:30 KST is still 2025-12-31 in UTC. */
+        let period: PerDatetime = service
+            .find_report_month_period(kst(2026, 1, 1, 0, 30, 0))
+            .unwrap();
+        assert_eq!(period.date_start, kst(2025, 12, 1, 0, 0, 0));
+        assert_eq!(period.n_date_start, kst(2025, 11, 1, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_find_top_merchants() {
+        let service: ProcessServiceImpl =
+            ProcessServiceImpl::new(Arc::new(CardMessageParserRegistry::default()));
+        let spent_detail = |spent_idx: i64, spent_name: &str, spent_money: i64| {
+            DocumentWithId::new(
+                spent_idx.to_string(),
+                0.0,
+                SpentDetailByEs {
+                    spent_idx,
+                    spent_name: spent_name.to_string(),
+                    spent_money,
+                    spent_at: Utc::now(),
+                    created_at: Utc::now(),
+                    user_seq: FIXTURE_USER_SEQ,
+                    consume_keyword_type_id: 1,
+                    consume_keyword_type: "food".to_string(),
+                    room_seq: 1,
+                    produced_at: None,
+                },
+            )
+        };
+        let spent_details: AggResultSet<SpentDetailByEs> = AggResultSet::new(
+            0.0,
+            vec![
+                spent_detail(1, "cafe", 4500),
+                spent_detail(2, "mart", 30000),
+                spent_detail(3, "cafe", 5500),
+                spent_detail(4, "bakery", 10000),
+                spent_detail(5, "refund", -3000),
+            ],
+        );
+
+        assert_eq!(
+            service.find_top_merchants(&spent_details, 2),
+            vec![
+                MerchantSpending::new("mart".to_string(), 30000, 1),
+                MerchantSpending::new("bakery".to_string(), 10000, 1),
+            ]
+        );
+        assert_eq!(service.find_top_merchants(&spent_details, 10).len(), 3);
+    }
+
+    #[test]
+    fn test_find_reconcile_result() {
+        let process_service: ProcessServiceImpl =
+            ProcessServiceImpl::new(Arc::new(CardMessageParserRegistry::default()));
+
+        let mysql_summaries: Vec<SpentDetailSummary> = vec![
+            SpentDetailSummary::new(3, 1000),
+            SpentDetailSummary::new(1, 5000),
+            SpentDetailSummary::new(2, 2000),
+        ];
+        let es_summaries: Vec<SpentDetailSummary> = vec![
+            SpentDetailSummary::new(1, 5000),
+            SpentDetailSummary::new(2, 2500),
+            SpentDetailSummary::new(9, 700),
+        ];
+
+        let result: ReconcileResult =
+            process_service.find_reconcile_result(&mysql_summaries, &es_summaries);
+
+        assert_eq!(*result.mysql_cnt(), 3);
+        assert_eq!(*result.mysql_money(), 8000);
+        assert_eq!(*result.es_cnt(), 3);
+        assert_eq!(*result.es_money(), 8200);
+        assert_eq!(result.missing_spent_idxs(), &vec![3]);
+        assert_eq!(result.extra_spent_idxs(), &vec![9]);
+        assert_eq!(result.mismatched_spent_idxs(), &vec![2]);
+        assert!(!result.is_consistent());
+
+        assert!(process_service
+            .find_reconcile_result(&mysql_summaries, &mysql_summaries)
+            .is_consistent());
+    }
+
+    #[test]
+    fn test_find_net_worth_history() {
+        let process_service: ProcessServiceImpl =
+            ProcessServiceImpl::new(Arc::new(CardMessageParserRegistry::default()));
+        let snapshot = |currency_code: &str, (y, m, d): (i32, u32, u32), stock_amount: i64| {
+            UserCurrentAssetSnapshot::new(
+                0,
+                1,
+                currency_code.to_string(),
+                find_naivedate(y, m, d).unwrap(),
+                Decimal::from(10),
+                Decimal::from(stock_amount),
+                Decimal::ZERO,
+                Decimal::from(1000),
+                Decimal::ZERO,
+                Utc::now(),
+                None,
+                "system".to_string(),
+                None,
+            )
+        };
+
+        /* September keeps only its last day; the JPY row is left out. */
+        let snapshots: Vec<UserCurrentAssetSnapshot> = vec![
+            snapshot("KRW", (2026, 10, 18), 500),
+            snapshot("USD", (2026, 10, 18), 2),
+            snapshot("KRW", (2026, 9, 1), 100),
+            snapshot("KRW", (2026, 9, 30), 300),
+            snapshot("JPY", (2026, 9, 30), 700),
+        ];
+
+        let history: NetWorthHistory =
+            process_service.find_net_worth_history(&snapshots, Decimal::from(1400));
+
+        assert_eq!(history.asset_types().len(), NET_WORTH_ASSET_TYPES.len());
+
+        let periods: Vec<&str> = history
+            .points()
+            .iter()
+            .map(|point| point.period_label.as_str())
+            .collect();
+        assert_eq!(periods, vec!["2026.09", "2026.10"]);
+
+        let september: &NetWorthPoint = &history.points()[0];
+        assert_eq!(
+            *september.aggregated_at(),
+            find_naivedate(2026, 9, 30).unwrap()
+        );
+        assert_eq!(*september.stock_amount_krw(), Decimal::from(300));
+        assert_eq!(*september.deposit_amount_krw(), Decimal::from(1000));
+
+        let october: &NetWorthPoint = &history.points()[1];
+        assert_eq!(*october.stock_amount_krw(), Decimal::from(500 + 2 * 1400));
+        assert_eq!(
+            *october.deposit_amount_krw(),
+            Decimal::from(1000 + 1000 * 1400)
+        );
+        assert_eq!(*october.cash_amount_krw(), Decimal::from(10 + 10 * 1400));
+    }
+}
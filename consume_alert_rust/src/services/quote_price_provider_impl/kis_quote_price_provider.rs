@@ -0,0 +1,458 @@
+use crate::common::*;
+
+use crate::models::{kis_api_token::*, stock_type::*};
+use crate::service_traits::{mysql_query_service::*, quote_price_provider::*};
+
+use crate::AppConfig;
+
+/// Minutes before `token_expired_at` at which the stored access token is renewed.
+const KIS_TOKEN_RENEW_MARGIN_MINS: i64 = 30;
+
+/// Message codes KIS answers with when the access token is expired or invalid.
+const KIS_TOKEN_ERROR_CODES: [&str; 2] = ["EGW00121", "EGW00123"];
+
+#[derive(Debug, Deserialize)]
+struct KisTokenResponse {
+    access_token: String,
+    /// Seconds the token stays valid
+    expires_in: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct KisQuotationResponse<T> {
+    rt_cd: String,
+    #[serde(default)]
+    msg_cd: String,
+    #[serde(default)]
+    msg1: String,
+    output: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KisDomesticPriceOutput {
+    /// Current price
+    stck_prpr: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct KisOverseasPriceOutput {
+    /// Last traded price
+    last: String,
+}
+
+/// Quotes stock prices through the Korea Investment Securities (KIS) Open API.
+///
+/// The access token is shared through `KIS_API_TOKEN`: a stored token is reused until
+/// [`KIS_TOKEN_RENEW_MARGIN_MINS`] before `token_expired_at`, then a new one is issued and
+/// replaces it. A token KIS rejects before then is replaced right away without consulting the
+/// stored row. KIS limits how often tokens may be issued, so renewal is serialized and the
+/// token is also kept in memory.
+pub struct KisQuotePriceProvider<M: MysqlQueryService> {
+    mysql_query_service: Arc<M>,
+    http_client: Client,
+    kis_api_url: Url,
+    app_key: String,
+    app_secret: String,
+    access_token: tokio::sync::Mutex<Option<KisApiToken>>,
+}
+
+impl<M: MysqlQueryService> KisQuotePriceProvider<M> {
+    /// Creates a KIS quote provider from `KIS_API_URL`, `KIS_APP_KEY` and `KIS_APP_SECRET`.
+    ///
+    /// # Arguments
+    ///
+    /// * `mysql_query_service` - Service used to read and store `KIS_API_TOKEN`
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `KisQuotePriceProvider` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the app key or secret is missing or the API URL is invalid.
+    pub fn new(mysql_query_service: Arc<M>) -> anyhow::Result<Self> {
+        let app_config: &AppConfig = AppConfig::get_global();
+
+        let (Some(app_key), Some(app_secret)) =
+            (app_config.kis_app_key(), app_config.kis_app_secret())
+        else {
+            return Err(anyhow!(
+                "[KisQuotePriceProvider::new] 'KIS_APP_KEY' and 'KIS_APP_SECRET' must be set"
+            ));
+        };
+
+        let kis_api_url: Url = Url::parse(app_config.kis_api_url()).map_err(|e| {
+            anyhow!(
+                "[KisQuotePriceProvider::new] Invalid KIS_API_URL '{}': {}",
+                app_config.kis_api_url(),
+                e
+            )
+        })?;
+
+        Ok(Self {
+            mysql_query_service,
+            http_client: Client::new(),
+            kis_api_url,
+            app_key: app_key.clone(),
+            app_secret: app_secret.clone(),
+            access_token: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    /// Returns an access token that stays valid for at least the renewal margin.
+    ///
+    /// The token in memory is used first, then the one stored in `KIS_API_TOKEN`. When neither
+    /// is usable a new token is issued and stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stored token cannot be read or a new token cannot be issued.
+    async fn find_access_token(&self) -> anyhow::Result<String> {
+        let mut access_token = self.access_token.lock().await;
+        let renew_margin: chrono::Duration = chrono::Duration::minutes(KIS_TOKEN_RENEW_MARGIN_MINS);
+
+        if let Some(token) = access_token
+            .as_ref()
+            .filter(|token| token.is_usable(Utc::now(), renew_margin))
+        {
+            return Ok(token.access_token.clone());
+        }
+
+        match self
+            .mysql_query_service
+            .find_kis_api_token()
+            .await?
+            .filter(|token| token.is_usable(Utc::now(), renew_margin))
+        {
+            Some(stored_token) => {
+                let token_value: String = stored_token.access_token.clone();
+                *access_token = Some(stored_token);
+                Ok(token_value)
+            }
+            None => self.renew_access_token(&mut access_token).await,
+        }
+    }
+
+    /// Replaces an access token KIS rejected before it expired.
+    ///
+    /// The stored token is not consulted, since it is usually the rejected one. When another
+    /// request already replaced the rejected token, that token is returned instead of issuing
+    /// another one.
+    ///
+    /// # Arguments
+    ///
+    /// * `rejected_token` - Access token KIS answered with a token error
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a new token cannot be issued.
+    async fn reissue_access_token(&self, rejected_token: &str) -> anyhow::Result<String> {
+        let mut access_token = self.access_token.lock().await;
+
+        if let Some(token) = access_token
+            .as_ref()
+            .filter(|token| token.access_token != rejected_token)
+        {
+            return Ok(token.access_token.clone());
+        }
+
+        warn!("[KisQuotePriceProvider::reissue_access_token] KIS rejected the access token, issuing a new one");
+
+        self.renew_access_token(&mut access_token).await
+    }
+
+    /// Issues a new access token, stores it in `KIS_API_TOKEN` and keeps it in memory.
+    ///
+    /// A failure to store the token is logged and the new token is still used.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The locked in-memory token to replace
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a new token cannot be issued.
+    async fn renew_access_token(
+        &self,
+        access_token: &mut Option<KisApiToken>,
+    ) -> anyhow::Result<String> {
+        let issued_token: KisApiToken = self.issue_access_token().await?;
+
+        if let Err(e) = self
+            .mysql_query_service
+            .replace_kis_api_token_with_transaction(&issued_token)
+            .await
+        {
+            error!(
+                "[KisQuotePriceProvider::renew_access_token] Failed to store the new token: {:#}",
+                e
+            );
+        }
+
+        info!(
+            "[KisQuotePriceProvider::renew_access_token] Issued a KIS token valid until {}",
+            issued_token.token_expired_at
+        );
+
+        let token_value: String = issued_token.access_token.clone();
+        *access_token = Some(issued_token);
+
+        Ok(token_value)
+    }
+
+    /// Issues a new access token with the app key and secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or KIS does not return a token.
+    async fn issue_access_token(&self) -> anyhow::Result<KisApiToken> {
+        let token_uri: Url = self.kis_api_url.join("/oauth2/tokenP")?;
+
+        let res: reqwest::Response = self
+            .http_client
+            .post(token_uri)
+            .json(&json!({
+                "grant_type": "client_credentials",
+                "appkey": self.app_key,
+                "appsecret": self.app_secret,
+            }))
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            let status: reqwest::StatusCode = res.status();
+            let error_body: String = res.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "[KisQuotePriceProvider::issue_access_token] Token request failed. Status: {}, Body: {}",
+                status,
+                error_body
+            ));
+        }
+
+        let token_res: KisTokenResponse = res.json().await?;
+        let issued_at: DateTime<Utc> = Utc::now();
+
+        Ok(KisApiToken::new(
+            token_res.access_token,
+            issued_at + chrono::Duration::seconds(token_res.expires_in),
+            issued_at,
+            None,
+            "system".to_string(),
+            None,
+        ))
+    }
+
+    /// Calls a KIS quotation API and returns its `output`.
+    ///
+    /// When KIS rejects the access token, a new token is issued and the call is repeated once.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - API path
+    /// * `tr_id` - KIS transaction id of the API
+    /// * `query` - Query parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or KIS answers with an error.
+    async fn call_quotation_api<T: DeserializeOwned>(
+        &self,
+        uri: &str,
+        tr_id: &str,
+        query: &[(&str, &str)],
+    ) -> anyhow::Result<T> {
+        let access_token: String = self.find_access_token().await?;
+
+        let (mut status, mut quotation_res) = self
+            .request_quotation::<T>(uri, tr_id, query, &access_token)
+            .await?;
+
+        if KIS_TOKEN_ERROR_CODES.contains(&quotation_res.msg_cd.as_str()) {
+            let access_token: String = self.reissue_access_token(&access_token).await?;
+
+            (status, quotation_res) = self
+                .request_quotation::<T>(uri, tr_id, query, &access_token)
+                .await?;
+        }
+
+        match quotation_res {
+            KisQuotationResponse {
+                rt_cd,
+                output: Some(output),
+                ..
+            } if rt_cd == "0" => Ok(output),
+            KisQuotationResponse { msg_cd, msg1, .. } => Err(anyhow!(
+                "[KisQuotePriceProvider::call_quotation_api] '{}' failed. Status: {}, Code: {}, Message: {}",
+                uri,
+                status,
+                msg_cd,
+                msg1
+            )),
+        }
+    }
+
+    /// Sends one request to a KIS quotation API with the given access token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the body is not a KIS quotation response.
+    async fn request_quotation<T: DeserializeOwned>(
+        &self,
+        uri: &str,
+        tr_id: &str,
+        query: &[(&str, &str)],
+        access_token: &str,
+    ) -> anyhow::Result<(reqwest::StatusCode, KisQuotationResponse<T>)> {
+        let quotation_uri: Url = self.kis_api_url.join(uri)?;
+
+        let res: reqwest::Response = self
+            .http_client
+            .get(quotation_uri)
+            .query(query)
+            .header("authorization", format!("Bearer {}", access_token))
+            .header("appkey", &self.app_key)
+            .header("appsecret", &self.app_secret)
+            .header("tr_id", tr_id)
+            .header("custtype", "P")
+            .send()
+            .await?;
+
+        /* KIS reports errors, including token errors, in the JSON body of any status. */
+        let status: reqwest::StatusCode = res.status();
+        let res_body: String = res.text().await?;
+
+        let quotation_res: KisQuotationResponse<T> =
+            serde_json::from_str(&res_body).map_err(|e| {
+                anyhow!(
+                    "[KisQuotePriceProvider::request_quotation] Unexpected response from '{}'. Status: {}, Body: {}, Error: {}",
+                    uri,
+                    status,
+                    res_body,
+                    e
+                )
+            })?;
+
+        Ok((status, quotation_res))
+    }
+}
+
+/// Returns the KIS overseas exchange code of a `STOCK_TYPE.market`.
+fn to_kis_exchange_code(market: &str) -> Option<&'static str> {
+    match market.to_uppercase().as_str() {
+        "NASDAQ" | "NAS" => Some("NAS"),
+        "NYSE" | "NYS" => Some("NYS"),
+        "AMEX" | "AMS" => Some("AMS"),
+        _ => None,
+    }
+}
+
+/// Parses a KIS price field, rejecting empty and non-positive values (no trade yet).
+fn parse_kis_price(price: &str, api_symbol: &str) -> anyhow::Result<Decimal> {
+    Decimal::from_str(price.trim())
+        .ok()
+        .filter(|price| price.is_sign_positive() && !price.is_zero())
+        .ok_or_else(|| {
+            anyhow!(
+                "[KisQuotePriceProvider] No valid price for '{}': '{}'",
+                api_symbol,
+                price
+            )
+        })
+}
+
+#[async_trait]
+impl<M: MysqlQueryService + Send + Sync> QuotePriceProvider for KisQuotePriceProvider<M> {
+    fn provider_nm(&self) -> &'static str {
+        "kis"
+    }
+
+    /// Quotes the current price of one symbol: the domestic price API for KRW markets, the
+    /// overseas price API for NASDAQ, NYSE and AMEX.
+    ///
+    /// # Arguments
+    ///
+    /// * `stock_type` - Market the symbol is listed on
+    /// * `api_symbol` - `STOCK.api_symbol`, e.g. `005930` or `AAPL`
+    ///
+    /// # Returns
+    ///
+    /// Returns the price in the market's currency.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the market is not supported or KIS returns no price.
+    async fn find_quote_price(
+        &self,
+        stock_type: &StockType,
+        api_symbol: &str,
+    ) -> anyhow::Result<Decimal> {
+        if stock_type.is_domestic() {
+            let output: KisDomesticPriceOutput = self
+                .call_quotation_api(
+                    "/uapi/domestic-stock/v1/quotations/inquire-price",
+                    "FHKST01010100",
+                    &[
+                        ("FID_COND_MRKT_DIV_CODE", "J"),
+                        ("FID_INPUT_ISCD", api_symbol),
+                    ],
+                )
+                .await?;
+
+            return parse_kis_price(&output.stck_prpr, api_symbol);
+        }
+
+        let exchange_code: &str = to_kis_exchange_code(&stock_type.market).ok_or_else(|| {
+            anyhow!(
+                "[KisQuotePriceProvider::find_quote_price] Unsupported market: {}",
+                stock_type.market
+            )
+        })?;
+
+        let output: KisOverseasPriceOutput = self
+            .call_quotation_api(
+                "/uapi/overseas-price/v1/quotations/price",
+                "HHDFS00000300",
+                &[("AUTH", ""), ("EXCD", exchange_code), ("SYMB", api_symbol)],
+            )
+            .await?;
+
+        parse_kis_price(&output.last, api_symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kis_quotation_response() {
+        let quotation_res: KisQuotationResponse<KisOverseasPriceOutput> =
+            serde_json::from_value(json!({
+                "rt_cd": "0",
+                "msg_cd": "MCA00000",
+                "msg1": "정상처리 되었습니다.",
+                "output": { "rsym": "DNASAAPL", "last": "231.4500" }
+            }))
+            .unwrap();
+        let output: KisOverseasPriceOutput = quotation_res.output.unwrap();
+        assert_eq!(
+            parse_kis_price(&output.last, "AAPL").unwrap(),
+            Decimal::new(2314500, 4)
+        );
+
+        let token_error: KisQuotationResponse<KisDomesticPriceOutput> =
+            serde_json::from_value(json!({
+                "rt_cd": "1",
+                "msg_cd": "EGW00123",
+                "msg1": "기간이 만료된 token 입니다."
+            }))
+            .unwrap();
+        assert!(token_error.output.is_none());
+        assert!(KIS_TOKEN_ERROR_CODES.contains(&token_error.msg_cd.as_str()));
+
+        assert!(parse_kis_price("", "AAPL").is_err());
+        assert!(parse_kis_price("0", "005930").is_err());
+        assert_eq!(to_kis_exchange_code("nasdaq"), Some("NAS"));
+        assert_eq!(to_kis_exchange_code("TSE"), None);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::common::*;
+
+use std::sync::Mutex;
+
+use crate::models::stock_type::*;
+use crate::service_traits::quote_price_provider::*;
+
+/// Answers quotes from a fixed price list so the price refresh can be tested without KIS.
+///
+/// Symbols missing from the list fail like an unknown symbol would. Every requested symbol
+/// is recorded in request order.
+#[derive(Debug, new)]
+pub struct MockQuotePriceProvider {
+    quote_prices: HashMap<String, Decimal>,
+    #[new(default)]
+    requested_symbols: Mutex<Vec<String>>,
+}
+
+impl MockQuotePriceProvider {
+    /// Returns the symbols quoted so far, in request order.
+    pub fn requested_symbols(&self) -> Vec<String> {
+        self.requested_symbols
+            .lock()
+            .map(|requested_symbols| requested_symbols.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl QuotePriceProvider for MockQuotePriceProvider {
+    fn provider_nm(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn find_quote_price(
+        &self,
+        stock_type: &StockType,
+        api_symbol: &str,
+    ) -> anyhow::Result<Decimal> {
+        if let Ok(mut requested_symbols) = self.requested_symbols.lock() {
+            requested_symbols.push(api_symbol.to_string());
+        }
+
+        self.quote_prices.get(api_symbol).copied().ok_or_else(|| {
+            anyhow!(
+                "[MockQuotePriceProvider::find_quote_price] No quote for '{}' on {}",
+                api_symbol,
+                stock_type.market
+            )
+        })
+    }
+}
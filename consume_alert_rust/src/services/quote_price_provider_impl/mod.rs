@@ -0,0 +1,7 @@
+mod kis_quote_price_provider;
+#[cfg(test)]
+mod mock_quote_price_provider;
+
+pub use kis_quote_price_provider::*;
+#[cfg(test)]
+pub use mock_quote_price_provider::*;
@@ -1,89 +1,64 @@
 use crate::common::*;
-use crate::config::AppConfig;
+use crate::models::file_info::*;
 use crate::repository::smtp_repository::*;
 use crate::service_traits::smtp_service::*;
 
 use futures::future::join_all;
 
-#[derive(Debug, Getters)]
+#[derive(Debug, Getters, new)]
 #[getset(get = "pub")]
 pub struct SmtpServiceImpl<R: SmtpRepository> {
     smtp_repo: R,
-    receivers: Vec<String>,
-}
-
-impl<R: SmtpRepository> SmtpServiceImpl<R> {
-    /// Builds the service from a repository instance, reading the receiver list from `AppConfig`.
-    pub fn new(smtp_repo: R) -> Self {
-        let cfg: &AppConfig = AppConfig::get_global();
-        let receivers: Vec<String> = cfg
-            .smtp_receivers()
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
-        Self {
-            smtp_repo,
-            receivers,
-        }
-    }
 }
 
 #[async_trait]
 impl<R: SmtpRepository + Send + Sync> SmtpService for SmtpServiceImpl<R> {
-    /// Sends an HTML email to all recipients configured in `SMTP_RECEIVERS`.
+    /// Sends the same HTML email to every given recipient.
     ///
     /// Delivery to each recipient is attempted concurrently. Per-recipient failures are
-    /// logged as errors but do not propagate — the method always returns `Ok(())`.
+    /// logged as errors but do not propagate.
     ///
     /// # Arguments
     ///
+    /// * `receivers` - Recipient email addresses
     /// * `subject` - Email subject line
     /// * `html_body` - HTML content of the email body
+    /// * `inline_images` - PNG images referenced from the body by `cid:<file_name>`
     ///
-    /// # Errors
+    /// # Returns
     ///
-    /// Returns an error only if the receiver list is empty and no send is possible.
-    async fn send_email(&self, subject: &str, html_body: &str) -> anyhow::Result<()> {
-        let tasks = self
-            .receivers
-            .iter()
-            .map(|addr| self.smtp_repo.send_html_email(addr, subject, html_body));
+    /// Returns the number of recipients the email was delivered to.
+    async fn send_email(
+        &self,
+        receivers: &[String],
+        subject: &str,
+        html_body: &str,
+        inline_images: &[FileInfo],
+    ) -> anyhow::Result<usize> {
+        let tasks = receivers.iter().map(|addr| {
+            self.smtp_repo
+                .send_html_email(addr, subject, html_body, inline_images)
+        });
 
         let results: Vec<anyhow::Result<()>> = join_all(tasks).await;
+        let mut sent_cnt: usize = 0;
 
-        for result in results {
+        for (addr, result) in receivers.iter().zip(results) {
             match result {
-                Ok(_) => info!("[SmtpServiceImpl::send_email] Email sent successfully"),
+                Ok(_) => {
+                    sent_cnt += 1;
+                    info!(
+                        "[SmtpServiceImpl::send_email] Email sent successfully to '{}'",
+                        addr
+                    )
+                }
                 Err(e) => error!(
-                    "[SmtpServiceImpl::send_email] Failed to send email: {:#}",
-                    e
+                    "[SmtpServiceImpl::send_email] Failed to send email to '{}': {:#}",
+                    addr, e
                 ),
             }
         }
 
-        Ok(())
-    }
-
-    /// Sends an HTML email to a single specific recipient.
-    ///
-    /// # Arguments
-    ///
-    /// * `address` - Target recipient email address
-    /// * `subject` - Email subject line
-    /// * `html_body` - HTML content of the email body
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the SMTP delivery fails.
-    async fn send_email_to(
-        &self,
-        address: &str,
-        subject: &str,
-        html_body: &str,
-    ) -> anyhow::Result<()> {
-        self.smtp_repo
-            .send_html_email(address, subject, html_body)
-            .await
+        Ok(sent_cnt)
     }
 }
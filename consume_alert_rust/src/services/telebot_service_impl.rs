@@ -2,6 +2,8 @@ use crate::common::*;
 
 use crate::AppConfig;
 
+use crate::configuration::command_registry::*;
+
 use crate::models::{
     consume_result_by_type::*, document_with_id::*, file_info::*, to_python_graph_line::*,
 };
@@ -16,6 +18,7 @@ pub struct TelebotServiceImpl {
     pub chat_id: ChatId,
     pub input_text: String,
     pub user_id: String,
+    pub message_date: DateTime<Utc>,
 }
 
 impl TelebotServiceImpl {
@@ -37,16 +40,20 @@ impl TelebotServiceImpl {
                 ""
             }
         }
-        .to_string()
         .to_lowercase();
+        let input_text: String = normalize_command_text(&input_text);
 
         let chat_id: ChatId = message.chat.id;
 
+        /* A forwarded card alert keeps the time it was originally received. */
+        let message_date: DateTime<Utc> = message.forward_date().unwrap_or(message.date);
+
         Self {
             bot,
             chat_id,
             input_text,
             user_id: user_id.to_string(),
+            message_date,
         }
     }
 
@@ -290,4 +297,9 @@ impl TelebotService for TelebotServiceImpl {
     fn get_telegram_user_id(&self) -> String {
         self.user_id.to_string()
     }
+
+    #[doc = "Function that returns the time the Telegram message (or the forwarded original) was sent."]
+    fn get_message_date(&self) -> DateTime<Utc> {
+        self.message_date
+    }
 }
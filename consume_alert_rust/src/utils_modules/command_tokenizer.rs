@@ -0,0 +1,474 @@
+use crate::common::*;
+
+use crate::enums::command_arg_error::*;
+
+use crate::utils_modules::time_utils::*;
+
+/// Date format accepted by date and date range arguments.
+const DATE_FORMAT: &str = "YYYY.MM.DD";
+/// Format accepted by month arguments.
+const YEAR_MONTH_FORMAT: &str = "YYYY.MM";
+/// Format accepted by year arguments.
+const YEAR_FORMAT: &str = "YYYY";
+
+/// One whitespace-separated argument, with `"`-quoted parts already unquoted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandToken {
+    text: String,
+    /// Byte offset in `text` of the last `:` that was not inside quotes
+    separator_idx: Option<usize>,
+}
+
+impl CommandToken {
+    /// Returns the unquoted argument text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Splits the token at its last unquoted `:` into a `(key, value)` pair.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` when the token has no unquoted `:`.
+    pub fn key_value(&self) -> Option<(&str, &str)> {
+        self.separator_idx
+            .map(|idx| (&self.text[..idx], &self.text[idx + 1..]))
+    }
+}
+
+/// A `<key>:<value>` argument such as `snack:15000`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyValueArg {
+    pub key: String,
+    pub value: String,
+}
+
+impl KeyValueArg {
+    /// Parses the value as a number, ignoring thousands separators (`15,000`).
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - Argument name used in the error message (e.g. `"amount"`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `CommandArgError::InvalidNumber` if the value is not a valid `T`.
+    pub fn parse_value<T: FromStr>(&self, field: &'static str) -> Result<T, CommandArgError> {
+        parse_number(&self.value, field)
+    }
+}
+
+/// A `YYYY.MM` argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YearMonthArg {
+    pub year: i32,
+    pub month: u32,
+}
+
+/// A `YYYY.MM.DD-YYYY.MM.DD` argument whose start is not after its end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRangeArg {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+/// Tokenized Telegram command: the arguments and switches after the command word.
+///
+/// Arguments are split on whitespace; a `"`-quoted part keeps its whitespace and `:` literally,
+/// so `c "tea: large":4500` has the key `tea: large`. Tokens such as `-t` are switches and
+/// are kept apart from the arguments. Negative numbers are arguments, not switches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandLine {
+    args: Vec<CommandToken>,
+    switches: Vec<String>,
+}
+
+impl CommandLine {
+    /// Tokenizes the raw command text.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_text` - The normalized message text, e.g. `cm 2023.07 -t`
+    ///
+    /// # Errors
+    ///
+    /// Returns `CommandArgError::UnterminatedQuote` if a `"` is never closed.
+    pub fn parse(input_text: &str) -> Result<Self, CommandArgError> {
+        /* The first token is the command word, which `COMMAND_REGISTRY` has already resolved. */
+        let tokens: std::iter::Skip<std::vec::IntoIter<CommandToken>> =
+            tokenize(input_text)?.into_iter().skip(1);
+
+        let mut args: Vec<CommandToken> = Vec::new();
+        let mut switches: Vec<String> = Vec::new();
+
+        for token in tokens {
+            if is_switch(&token) {
+                switches.push(token.text);
+            } else {
+                args.push(token);
+            }
+        }
+
+        Ok(Self { args, switches })
+    }
+
+    /// Returns the arguments after the command word, without switches.
+    pub fn args(&self) -> &[CommandToken] {
+        &self.args
+    }
+
+    /// Returns true when `switch` (e.g. `-t`) was given.
+    pub fn has_switch(&self, switch: &str) -> bool {
+        self.switches.iter().any(|given| given == switch)
+    }
+
+    /// Returns the argument texts from `start_idx` on, joined with single spaces.
+    pub fn joined_args_from(&self, start_idx: usize) -> String {
+        self.args
+            .iter()
+            .skip(start_idx)
+            .map(CommandToken::text)
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    /// Accepts a command that takes no arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CommandArgError::UnexpectedArgs` if any argument was given.
+    pub fn no_args(&self) -> Result<(), CommandArgError> {
+        self.expect_at_most(0)
+    }
+
+    /// Parses the arguments as one `<key>:<value>` pair.
+    ///
+    /// An unquoted key may contain spaces (`c 점심 식사:9000`); the separator is the last
+    /// unquoted `:` of the last argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CommandArgError::MissingArg` if no argument was given, or
+    /// `CommandArgError::InvalidKeyValue` if the pair has no separator or an empty side.
+    pub fn key_value_arg(&self) -> Result<KeyValueArg, CommandArgError> {
+        let (last_arg, leading_args) = self
+            .args
+            .split_last()
+            .ok_or(CommandArgError::MissingArg("<name>:<amount>"))?;
+
+        let invalid_key_value = || CommandArgError::InvalidKeyValue(self.joined_args_from(0));
+        let (last_key, value) = last_arg.key_value().ok_or_else(invalid_key_value)?;
+
+        let key: String = leading_args
+            .iter()
+            .map(CommandToken::text)
+            .chain(std::iter::once(last_key))
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .trim()
+            .to_string();
+        let value: String = value.trim().to_string();
+
+        if key.is_empty() || value.is_empty() {
+            return Err(invalid_key_value());
+        }
+
+        Ok(KeyValueArg { key, value })
+    }
+
+    /// Parses an optional `YYYY.MM.DD` argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if more than one argument was given or the date is invalid.
+    pub fn optional_date_arg(&self) -> Result<Option<NaiveDate>, CommandArgError> {
+        self.expect_at_most(1)?;
+        self.args
+            .first()
+            .map(|arg| parse_date(arg.text()))
+            .transpose()
+    }
+
+    /// Parses an optional `YYYY.MM` argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if more than one argument was given or the month is invalid.
+    pub fn optional_year_month_arg(&self) -> Result<Option<YearMonthArg>, CommandArgError> {
+        self.expect_at_most(1)?;
+        self.args
+            .first()
+            .map(|arg| parse_year_month(arg.text()))
+            .transpose()
+    }
+
+    /// Parses an optional `YYYY` argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if more than one argument was given or the year is invalid.
+    pub fn optional_year_arg(&self) -> Result<Option<i32>, CommandArgError> {
+        self.expect_at_most(1)?;
+        self.args
+            .first()
+            .map(|arg| parse_year(arg.text()))
+            .transpose()
+    }
+
+    /// Parses a required `YYYY.MM.DD-YYYY.MM.DD` range; spaces around the `-` are allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range is missing, either date is invalid, or the start date
+    /// lies after the end date.
+    pub fn date_range_arg(&self) -> Result<DateRangeArg, CommandArgError> {
+        let range_text: String = self.args.iter().map(CommandToken::text).collect::<String>();
+
+        if range_text.is_empty() {
+            return Err(CommandArgError::MissingArg("YYYY.MM.DD-YYYY.MM.DD"));
+        }
+
+        let (start_text, end_text) =
+            range_text
+                .split_once('-')
+                .ok_or_else(|| CommandArgError::InvalidDate {
+                    value: range_text.clone(),
+                    format: "YYYY.MM.DD-YYYY.MM.DD",
+                })?;
+
+        let start_date: NaiveDate = parse_date(start_text)?;
+        let end_date: NaiveDate = parse_date(end_text)?;
+
+        if start_date > end_date {
+            return Err(CommandArgError::InvalidDateRange {
+                start_date,
+                end_date,
+            });
+        }
+
+        Ok(DateRangeArg {
+            start_date,
+            end_date,
+        })
+    }
+
+    fn expect_at_most(&self, max_args: usize) -> Result<(), CommandArgError> {
+        if self.args.len() > max_args {
+            return Err(CommandArgError::UnexpectedArgs(
+                self.args[max_args..]
+                    .iter()
+                    .map(|arg| arg.text().to_string())
+                    .collect(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a number argument, ignoring thousands separators (`15,000`).
+///
+/// # Arguments
+///
+/// * `value` - The argument text
+/// * `field` - Argument name used in the error message
+///
+/// # Errors
+///
+/// Returns `CommandArgError::InvalidNumber` if the value is not a valid `T`.
+pub fn parse_number<T: FromStr>(value: &str, field: &'static str) -> Result<T, CommandArgError> {
+    value
+        .replace(',', "")
+        .parse::<T>()
+        .map_err(|_| CommandArgError::InvalidNumber {
+            field,
+            value: value.to_string(),
+        })
+}
+
+/// Splits the text into whitespace-separated tokens, honouring `"` quotes.
+fn tokenize(input_text: &str) -> Result<Vec<CommandToken>, CommandArgError> {
+    let mut tokens: Vec<CommandToken> = Vec::new();
+    let mut current: Option<CommandToken> = None;
+    let mut in_quotes: bool = false;
+
+    for c in input_text.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                /* `""` is an empty argument, so a quote alone starts a token. */
+                current.get_or_insert_with(empty_token);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if let Some(token) = current.take() {
+                    tokens.push(token);
+                }
+            }
+            c => {
+                let token: &mut CommandToken = current.get_or_insert_with(empty_token);
+                if c == ':' && !in_quotes {
+                    token.separator_idx = Some(token.text.len());
+                }
+                token.text.push(c);
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(CommandArgError::UnterminatedQuote);
+    }
+
+    tokens.extend(current);
+    Ok(tokens)
+}
+
+fn empty_token() -> CommandToken {
+    CommandToken {
+        text: String::new(),
+        separator_idx: None,
+    }
+}
+
+/* A switch is `-` followed by letters only, so `-5000` stays an argument. */
+fn is_switch(token: &CommandToken) -> bool {
+    token
+        .text
+        .strip_prefix('-')
+        .is_some_and(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic()))
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, CommandArgError> {
+    let invalid_date = || CommandArgError::InvalidDate {
+        value: value.to_string(),
+        format: DATE_FORMAT,
+    };
+
+    let value: &str = value.trim();
+    if !is_valid_date_format(value, r"^\d{4}\.\d{2}\.\d{2}$").unwrap_or(false) {
+        return Err(invalid_date());
+    }
+
+    NaiveDate::parse_from_str(value, "%Y.%m.%d").map_err(|_| invalid_date())
+}
+
+fn parse_year_month(value: &str) -> Result<YearMonthArg, CommandArgError> {
+    let invalid_month = || CommandArgError::InvalidDate {
+        value: value.to_string(),
+        format: YEAR_MONTH_FORMAT,
+    };
+
+    if !is_valid_date_format(value, r"^\d{4}\.\d{2}$").unwrap_or(false) {
+        return Err(invalid_month());
+    }
+
+    let (year, month) = value.split_once('.').ok_or_else(invalid_month)?;
+    let year: i32 = year.parse().map_err(|_| invalid_month())?;
+    let month: u32 = month.parse().map_err(|_| invalid_month())?;
+
+    if !(1..=12).contains(&month) {
+        return Err(invalid_month());
+    }
+
+    Ok(YearMonthArg { year, month })
+}
+
+fn parse_year(value: &str) -> Result<i32, CommandArgError> {
+    if !is_valid_date_format(value, r"^\d{4}$").unwrap_or(false) {
+        return Err(CommandArgError::InvalidDate {
+            value: value.to_string(),
+            format: YEAR_FORMAT,
+        });
+    }
+
+    parse_number(value, "year")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_separates_command_args_and_switches() {
+        let command_line: CommandLine = CommandLine::parse("ctr 2023.07.07-2023.08.01 -t").unwrap();
+
+        assert_eq!(command_line.args().len(), 1);
+        assert_eq!(command_line.args()[0].text(), "2023.07.07-2023.08.01");
+        assert!(command_line.has_switch("-t"));
+
+        let command_line: CommandLine = CommandLine::parse("ce 2n9c money -5000").unwrap();
+        assert_eq!(command_line.joined_args_from(1), "money -5000");
+    }
+
+    #[test]
+    fn test_key_value_arg_supports_spaces_and_quotes() {
+        let key_value: KeyValueArg = CommandLine::parse("c 점심 식사:9,000")
+            .unwrap()
+            .key_value_arg()
+            .unwrap();
+        assert_eq!(key_value.key, "점심 식사");
+        assert_eq!(key_value.parse_value::<i64>("amount").unwrap(), 9000);
+
+        let key_value: KeyValueArg = CommandLine::parse(r#"c "tea: large":4500"#)
+            .unwrap()
+            .key_value_arg()
+            .unwrap();
+        assert_eq!(key_value.key, "tea: large");
+        assert_eq!(key_value.value, "4500");
+
+        assert_eq!(
+            CommandLine::parse("c snack").unwrap().key_value_arg(),
+            Err(CommandArgError::InvalidKeyValue("snack".to_string()))
+        );
+        assert_eq!(
+            CommandLine::parse(r#"c "snack:1000"#).unwrap_err(),
+            CommandArgError::UnterminatedQuote
+        );
+    }
+
+    #[test]
+    fn test_date_args_are_validated() {
+        assert_eq!(
+            CommandLine::parse("cm 2023.07")
+                .unwrap()
+                .optional_year_month_arg()
+                .unwrap(),
+            Some(YearMonthArg {
+                year: 2023,
+                month: 7
+            })
+        );
+        assert!(CommandLine::parse("cm 2023.13")
+            .unwrap()
+            .optional_year_month_arg()
+            .is_err());
+        assert_eq!(
+            CommandLine::parse("ct").unwrap().optional_date_arg(),
+            Ok(None)
+        );
+        assert!(CommandLine::parse("ct 2023.02.30")
+            .unwrap()
+            .optional_date_arg()
+            .is_err());
+        assert!(matches!(
+            CommandLine::parse("cy 2023 2024")
+                .unwrap()
+                .optional_year_arg(),
+            Err(CommandArgError::UnexpectedArgs(_))
+        ));
+
+        let date_range: DateRangeArg = CommandLine::parse("ctr 2023.07.07 - 2023.08.01")
+            .unwrap()
+            .date_range_arg()
+            .unwrap();
+        assert_eq!(
+            date_range.start_date,
+            NaiveDate::from_ymd_opt(2023, 7, 7).unwrap()
+        );
+        assert!(matches!(
+            CommandLine::parse("ctr 2023.08.01-2023.07.07")
+                .unwrap()
+                .date_range_arg(),
+            Err(CommandArgError::InvalidDateRange { .. })
+        ));
+    }
+}
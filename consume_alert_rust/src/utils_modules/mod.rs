@@ -1,6 +1,7 @@
+pub mod command_tokenizer;
 pub mod common_function;
 pub mod currency_utils;
-pub mod io_utils;
 pub mod logger_utils;
 pub mod numeric_utils;
+pub mod schedule_utils;
 pub mod time_utils;
@@ -16,6 +16,32 @@ pub fn to_numeric(s: &str) -> i64 {
     s.parse::<i64>().unwrap_or(0)
 }
 
+const SHORT_ID_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+#[doc = "Encodes a positive row id as a lowercase base36 string, ex) 123456 -> \"2n9c\""]
+pub fn to_short_id(id: i64) -> String {
+    let mut remain: u64 = id.unsigned_abs();
+    let mut digits: Vec<u8> = Vec::new();
+
+    loop {
+        digits.push(SHORT_ID_DIGITS[(remain % 36) as usize]);
+        remain /= 36;
+
+        if remain == 0 {
+            break;
+        }
+    }
+
+    digits.iter().rev().map(|&digit| digit as char).collect()
+}
+
+#[doc = "Decodes a base36 string created by `to_short_id`; returns None for invalid input"]
+pub fn from_short_id(short_id: &str) -> Option<i64> {
+    i64::from_str_radix(short_id.trim().trim_start_matches('#'), 36)
+        .ok()
+        .filter(|id| *id > 0)
+}
+
 #[doc = "Formats a Decimal with thousand separators on the integer part, keeping N decimal places"]
 pub fn format_decimal_with_commas(value: Decimal, decimals: u32) -> String {
     let sign: &str = if value.is_sign_negative() { "-" } else { "+" };
@@ -56,3 +82,18 @@ where
 
     Ok(*currency.exchange_rate())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_id_round_trip() {
+        assert_eq!(to_short_id(123456), "2n9c");
+        assert_eq!(from_short_id("2n9c"), Some(123456));
+        assert_eq!(from_short_id("#2N9C"), Some(123456));
+        assert_eq!(from_short_id(&to_short_id(1)), Some(1));
+        assert_eq!(from_short_id("not-an-id"), None);
+        assert_eq!(from_short_id("0"), None);
+    }
+}
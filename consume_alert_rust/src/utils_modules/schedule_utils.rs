@@ -0,0 +1,39 @@
+use crate::common::*;
+
+/// Runs `run` on every tick of `schedule` forever, evaluating the schedule in KST.
+///
+/// Each run receives its scheduled time. A failed run is logged and the next tick is awaited
+/// as usual. Returns only when the schedule has no upcoming tick.
+///
+/// # Arguments
+///
+/// * `schedule` - Cron schedule parsed from the environment
+/// * `schedule_nm` - Environment variable of the schedule, used in logs : ex) "STOCK_PRICE_CRON"
+/// * `run` - Issues one scheduled run
+pub async fn run_on_schedule<F, Fut, T>(schedule: &cron::Schedule, schedule_nm: &str, mut run: F)
+where
+    F: FnMut(DateTime<Utc>) -> Fut,
+    Fut: std::future::Future<Output = anyhow::Result<T>>,
+{
+    loop {
+        let Some(next_run_at) = schedule.upcoming(Seoul).next() else {
+            warn!(
+                "[schedule_utils::run_on_schedule] {} has no upcoming run, scheduler stopped",
+                schedule_nm
+            );
+            return;
+        };
+        let next_run_at: DateTime<Utc> = next_run_at.with_timezone(&Utc);
+
+        if let Ok(wait_time) = (next_run_at - Utc::now()).to_std() {
+            tokio::time::sleep(wait_time).await;
+        }
+
+        if let Err(e) = run(next_run_at).await {
+            error!(
+                "[schedule_utils::run_on_schedule] {} run failed: {:#}",
+                schedule_nm, e
+            );
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::common::*;
+
+use crate::models::{consume_result_by_type::*, merchant_spending::*};
+
+#[doc = "Monthly consumption report of one aggregation group, rendered as an HTML mail."]
+#[allow(clippy::too_many_arguments)]
+#[derive(Debug, Getters, new)]
+#[getset(get = "pub")]
+pub struct ConsumeReportMailView {
+    pub agg_group_name: String,
+    pub date_start: DateTime<Utc>,
+    pub date_end: DateTime<Utc>,
+    pub total_money: i64,
+    pub versus_total_money: i64,
+    pub spent_cnt: usize,
+    pub consume_result_by_types: Vec<ConsumeResultByType>,
+    /// Spending per category in the comparison period
+    pub versus_money_by_type: HashMap<String, i64>,
+    pub top_merchants: Vec<MerchantSpending>,
+    /// Content-IDs of the chart images attached to the mail
+    pub chart_cids: Vec<String>,
+}
+
+impl ConsumeReportMailView {
+    /// Returns the mail subject, e.g. `[소비 리포트] 우리집 2026년 09월`.
+    pub fn subject(&self) -> String {
+        format!(
+            "[소비 리포트] {} {}",
+            self.agg_group_name,
+            self.date_start.with_timezone(&Seoul).format("%Y년 %m월")
+        )
+    }
+
+    /// Renders the report as an HTML document.
+    ///
+    /// Charts are referenced as `cid:` images, so the mail must carry them as inline parts
+    /// named after `chart_cids`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the summary, category breakdown, top merchants and charts as one HTML string.
+    pub fn to_html(&self) -> String {
+        let mut html: String =
+            String::from("<html><body style=\"font-family:sans-serif;color:#222;\">");
+
+        html.push_str(&format!(
+            "<h2>{} 소비 리포트</h2><p>{} ~ {}</p>",
+            escape_html(&self.agg_group_name),
+            self.date_start.with_timezone(&Seoul).format("%Y-%m-%d"),
+            self.date_end.with_timezone(&Seoul).format("%Y-%m-%d")
+        ));
+
+        html.push_str("<h3>요약</h3><table cellpadding=\"4\">");
+        html.push_str(&format!(
+            "<tr><td>총 소비</td><td align=\"right\">{} 원</td></tr>\
+             <tr><td>결제 건수</td><td align=\"right\">{} 건</td></tr>\
+             <tr><td>전월 소비</td><td align=\"right\">{} 원</td></tr>\
+             <tr><td>전월 대비</td><td align=\"right\">{}</td></tr>",
+            self.total_money.to_formatted_string(&Locale::en),
+            self.spent_cnt,
+            self.versus_total_money.to_formatted_string(&Locale::en),
+            format_change(self.total_money, self.versus_total_money)
+        ));
+        html.push_str("</table>");
+
+        html.push_str(
+            "<h3>카테고리별 소비</h3><table border=\"1\" cellpadding=\"4\" cellspacing=\"0\">\
+             <tr><th>카테고리</th><th>금액</th><th>비율</th><th>전월</th><th>전월 대비</th></tr>",
+        );
+        for consume_result in &self.consume_result_by_types {
+            let versus_money: i64 = self
+                .versus_money_by_type
+                .get(consume_result.consume_prodt_type())
+                .copied()
+                .unwrap_or(0);
+
+            html.push_str(&format!(
+                "<tr><td>{}</td><td align=\"right\">{} 원</td><td align=\"right\">{:.1}%</td>\
+                 <td align=\"right\">{} 원</td><td align=\"right\">{}</td></tr>",
+                escape_html(consume_result.consume_prodt_type()),
+                consume_result
+                    .consume_prodt_cost()
+                    .to_formatted_string(&Locale::en),
+                consume_result.consume_prodt_per(),
+                versus_money.to_formatted_string(&Locale::en),
+                format_change(*consume_result.consume_prodt_cost(), versus_money)
+            ));
+        }
+        html.push_str("</table>");
+
+        html.push_str(
+            "<h3>상위 사용처</h3><table border=\"1\" cellpadding=\"4\" cellspacing=\"0\">\
+             <tr><th>#</th><th>사용처</th><th>금액</th><th>건수</th></tr>",
+        );
+        for (rank, merchant) in self.top_merchants.iter().enumerate() {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td align=\"right\">{} 원</td><td align=\"right\">{}</td></tr>",
+                rank + 1,
+                escape_html(merchant.spent_name()),
+                merchant.spent_money().to_formatted_string(&Locale::en),
+                merchant.spent_cnt()
+            ));
+        }
+        html.push_str("</table>");
+
+        if !self.chart_cids.is_empty() {
+            html.push_str("<h3>차트</h3>");
+            for chart_cid in &self.chart_cids {
+                html.push_str(&format!(
+                    "<p><img src=\"cid:{}\" alt=\"{}\" style=\"max-width:100%;\"></p>",
+                    chart_cid, chart_cid
+                ));
+            }
+        }
+
+        html.push_str("</body></html>");
+        html
+    }
+}
+
+/// Formats the difference to the comparison period, e.g. `+12,000 원 (+8.3%)`.
+fn format_change(money: i64, versus_money: i64) -> String {
+    let diff: i64 = money - versus_money;
+    let sign: &str = if diff > 0 { "+" } else { "" };
+    let diff_text: String = format!("{}{} 원", sign, diff.to_formatted_string(&Locale::en));
+
+    if versus_money > 0 {
+        format!(
+            "{} ({}{:.1}%)",
+            diff_text,
+            sign,
+            diff as f64 / versus_money as f64 * 100.0
+        )
+    } else {
+        diff_text
+    }
+}
+
+/// Escapes text taken from spending records before it is placed in the HTML body.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consume_report_mail_view() {
+        let date_start: DateTime<Utc> = Seoul
+            .with_ymd_and_hms(2026, 9, 1, 0, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let date_end: DateTime<Utc> = Seoul
+            .with_ymd_and_hms(2026, 9, 30, 23, 59, 59)
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let view: ConsumeReportMailView = ConsumeReportMailView::new(
+            "우리집".to_string(),
+            date_start,
+            date_end,
+            120000,
+            100000,
+            3,
+            vec![ConsumeResultByType::new("food".to_string(), 120000, 100.0)],
+            HashMap::from([("food".to_string(), 100000)]),
+            vec![MerchantSpending::new("<Tom & Jerry>".to_string(), 90000, 2)],
+            vec!["consume_detail".to_string()],
+        );
+
+        assert_eq!(view.subject(), "[소비 리포트] 우리집 2026년 09월");
+
+        let html: String = view.to_html();
+        assert!(html.contains("2026-09-01 ~ 2026-09-30"));
+        assert!(html.contains("+20,000 원 (+20.0%)"));
+        assert!(html.contains("&lt;Tom &amp; Jerry&gt;"));
+        assert!(html.contains("src=\"cid:consume_detail\""));
+    }
+
+    #[test]
+    fn test_format_change() {
+        assert_eq!(format_change(90000, 100000), "-10,000 원 (-10.0%)");
+        assert_eq!(format_change(5000, 0), "+5,000 원");
+        assert_eq!(format_change(0, 0), "0 원");
+    }
+}
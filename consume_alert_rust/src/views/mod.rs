@@ -1 +1,2 @@
+pub mod consume_report_mail_view;
 pub mod spent_detail_view;
@@ -1,6 +1,10 @@
 use crate::common::*;
 use std::fmt;
 
+use crate::enums::spent_at_status::*;
+
+use crate::utils_modules::numeric_utils::*;
+
 #[doc = "Structure containing spent detail information."]
 #[derive(Debug, Serialize, Deserialize, Clone, Getters, Setters, new)]
 #[getset(get = "pub", set = "pub")]
@@ -27,6 +31,39 @@ impl SpentDetailView {
         )
     }
 
+    /// Formats the spending detail together with the short id used by the `ce` edit command.
+    ///
+    /// # Arguments
+    ///
+    /// * `spent_idx` - Primary key of the stored `SPENT_DETAIL` row
+    ///
+    /// # Returns
+    ///
+    /// Returns the regular confirmation message followed by the short id.
+    pub fn to_telegram_string_with_short_id(&self, spent_idx: i64) -> String {
+        format!(
+            "{}\n번호: \"{}\"",
+            self.to_telegram_string(),
+            to_short_id(spent_idx)
+        )
+    }
+
+    /// Formats the spending detail as a Telegram-friendly modification message string.
+    ///
+    /// # Arguments
+    ///
+    /// * `spent_idx` - Primary key of the modified `SPENT_DETAIL` row
+    ///
+    /// # Returns
+    ///
+    /// Returns a formatted string containing the spending after the modification and its short id.
+    pub fn to_telegram_string_to_modify(&self, spent_idx: i64) -> String {
+        format!(
+            "[수정된 결제 정보]\n{}",
+            self.to_telegram_string_with_short_id(spent_idx)
+        )
+    }
+
     /// Formats the spending detail as a Telegram-friendly deletion message string.
     ///
     /// # Returns
@@ -47,6 +84,108 @@ impl SpentDetailView {
             deleted_at,
         )
     }
+
+    /// Formats the spending detail as a Telegram-friendly payment cancellation message string.
+    ///
+    /// # Returns
+    ///
+    /// Returns a formatted string containing the cancelled payment and the current KST cancellation time.
+    pub fn to_telegram_string_to_cancel(&self) -> String {
+        let cancelled_at: String = Utc::now()
+            .with_timezone(&Seoul)
+            .format("%Y-%m-%dT%H:%M")
+            .to_string();
+
+        format!(
+            "[취소된 결제 정보]\n사용처: \"{}\"\n사용한 현금: \"{}\"\n사용시간: \"{}\"\n소비타입: \"{}\"\n취소시각: \"{}\"",
+            self.spent_name,
+            self.spent_money,
+            self.spent_at.format("%Y-%m-%dT%H:%M"),
+            self.consume_keyword_type_nm,
+            cancelled_at,
+        )
+    }
+
+    /// Formats an installment purchase as a Telegram-friendly message string.
+    ///
+    /// # Arguments
+    ///
+    /// * `installment` - Number of monthly installments
+    /// * `monthly_money` - Amount charged in each month after the first
+    /// * `spent_idxs` - Primary keys of the stored monthly `SPENT_DETAIL` rows, first month first
+    ///
+    /// # Returns
+    ///
+    /// Returns the regular confirmation message followed by the installment plan and the short
+    /// id of every monthly row.
+    pub fn to_telegram_string_to_installment(
+        &self,
+        installment: i64,
+        monthly_money: i64,
+        spent_idxs: &[i64],
+    ) -> String {
+        let short_ids: Vec<String> = spent_idxs
+            .iter()
+            .map(|spent_idx| format!("\"{}\"", to_short_id(*spent_idx)))
+            .collect();
+
+        format!(
+            "{}\n할부: \"{}개월\"\n월 납부액: \"{}\"\n번호: {}",
+            self.to_telegram_string(),
+            installment,
+            monthly_money.to_formatted_string(&Locale::en),
+            short_ids.join(", "),
+        )
+    }
+
+    /// Formats a refund that could not be matched to an earlier payment.
+    ///
+    /// # Arguments
+    ///
+    /// * `spent_idx` - Primary key of the stored refund row
+    ///
+    /// # Returns
+    ///
+    /// Returns a formatted string containing the refunded (negative) amount, time, category, and short id.
+    pub fn to_telegram_string_to_refund(&self, spent_idx: i64) -> String {
+        format!(
+            "[환불 정보 - 원거래 없음]\n사용처: \"{}\"\n환불 금액: \"{}\"\n환불시간: \"{}\"\n소비타입: \"{}\"\n번호: \"{}\"",
+            self.spent_name,
+            self.spent_money,
+            self.spent_at.format("%Y-%m-%dT%H:%M"),
+            self.consume_keyword_type_nm,
+            to_short_id(spent_idx),
+        )
+    }
+}
+
+impl SpentDetailView {
+    /// Formats a warning for a payment time that should be checked by the user.
+    ///
+    /// # Arguments
+    ///
+    /// * `spent_at_status` - Plausibility of the payment time inferred from the notification
+    ///
+    /// # Returns
+    ///
+    /// Returns the warning message, or `None` when the payment time looks normal.
+    pub fn to_telegram_string_to_spent_at_warning(
+        &self,
+        spent_at_status: SpentAtStatus,
+    ) -> Option<String> {
+        let reason: &str = match spent_at_status {
+            SpentAtStatus::Normal => return None,
+            SpentAtStatus::YearAdjusted => "알림 수신 연도와 다른 연도로 기록되었습니다.",
+            SpentAtStatus::Stale => "알림 수신 시각보다 오래된 결제입니다.",
+        };
+
+        Some(format!(
+            "[사용시간 확인 필요]\n사용처: \"{}\"\n사용시간: \"{}\"\n{}",
+            self.spent_name,
+            self.spent_at.format("%Y-%m-%dT%H:%M"),
+            reason,
+        ))
+    }
 }
 
 impl fmt::Display for SpentDetailView {